@@ -4,6 +4,7 @@
 //!   anchor search <query>            Find symbols
 //!   anchor read <symbol>             Full context
 //!   anchor context <query>           Search + Read combined
+//!   anchor find-sig '(&str) -> bool' Find by signature shape
 //!
 //! Write:
 //!   anchor write <path> <content>    Create/overwrite file
@@ -15,14 +16,18 @@
 //! System:
 //!   anchor build                     Build graph
 //!   anchor stats                     Show stats
-//!   anchor daemon [start|stop]       Manage daemon
+//!   anchor daemon [start|stop|status] Manage daemon
+//!
+//! search, context and deps use a running daemon's graph when there is
+//! one (--daemon starts it, --no-daemon skips it).
 
+use anchor::cli::daemon::{self as cli_daemon, DaemonMode};
+use anchor::cli::read::{load_or_build_graph, GraphSource};
 use anchor::cli::{self, read as cli_read, Cli, Commands};
-use anchor::graph::{build_graph, CodeGraph};
+use anchor::query::canonical_intent;
 use anchor::updater;
 use anyhow::Result;
 use clap::Parser;
-use std::path::Path;
 
 fn main() {
     let cli = Cli::parse();
@@ -36,6 +41,15 @@ fn main() {
 fn run(cli: Cli) -> Result<()> {
     let root = cli.root.canonicalize().unwrap_or(cli.root);
     let cache_path = root.join(".anchor/graph.bin");
+    let daemon_mode = DaemonMode::from_flags(cli.daemon, cli.no_daemon);
+
+    // Run `f` against the daemon's graph if one should be used, else the cached graph
+    let with_source = |f: &dyn Fn(GraphSource) -> Result<()>| -> Result<()> {
+        match cli_daemon::client(&root, daemon_mode)? {
+            Some(client) => f(GraphSource::Daemon(&client)),
+            None => f(GraphSource::Graph(&load_or_build_graph(&root, &cache_path)?)),
+        }
+    };
 
     // No command = show help
     if cli.command.is_none() {
@@ -45,14 +59,33 @@ fn run(cli: Cli) -> Result<()> {
 
     match cli.command.unwrap() {
         // ─── Query Commands ───────────────────────────────────────
-        Commands::Context { query, limit } => {
-            let graph = load_or_build_graph(&root, &cache_path)?;
-            cli_read::context(&graph, &query, limit)
+        Commands::Context { query, limit, intent, signature, threshold, blame, focus } => {
+            match intent.as_deref().map(canonical_intent) {
+                None | Some("explore") if !blame && !focus => {
+                    with_source(&|source| cli_read::context(source, &query, limit))
+                }
+                intent => cli_read::context_intent(
+                    &load_or_build_graph(&root, &cache_path)?,
+                    &query,
+                    intent.unwrap_or("explore"),
+                    signature.as_deref(),
+                    threshold,
+                    blame,
+                    focus,
+                ),
+            }
+        }
+
+        Commands::Search { query, pattern, limit, r#async, fuzzy, ignore_case } => {
+            with_source(&|source| {
+                let pattern = pattern.as_deref();
+                cli_read::search(source, &query, pattern, limit, r#async, fuzzy, ignore_case)
+            })
         }
 
-        Commands::Search { query, pattern, limit } => {
+        Commands::FindSig { signature, limit } => {
             let graph = load_or_build_graph(&root, &cache_path)?;
-            cli_read::search(&graph, &query, pattern.as_deref(), limit)
+            cli_read::find_sig(&graph, &signature, limit)
         }
 
         // ─── Write Commands (TODO: ACI-based) ─────────────────────
@@ -61,6 +94,7 @@ fn run(cli: Cli) -> Result<()> {
             if let Some(parent) = full_path.parent() {
                 std::fs::create_dir_all(parent)?;
             }
+            anchor::graph::snapshot_before_write(&root, std::path::Path::new(&path))?;
             std::fs::write(&full_path, &content)?;
             println!(r#"{{"status": "created", "path": "{}"}}"#, path);
             Ok(())
@@ -92,6 +126,71 @@ fn run(cli: Cli) -> Result<()> {
             cli_read::overview(&graph)
         }
 
+        Commands::Summary => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::summary(&graph)
+        }
+
+        Commands::Cochange { symbol, limit } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::cochange(&graph, &symbol, limit)
+        }
+
+        Commands::Precommit => {
+            // Diff line numbers are from the working tree: index it fresh
+            let graph = cli_read::build_project_graph(&root);
+            cli_read::precommit(&graph, &root)
+        }
+
+        Commands::Untested { limit, skip_accessors } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::untested(&graph, limit, skip_accessors)
+        }
+
+        Commands::DeadCode => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::dead_code(&graph)
+        }
+
+        Commands::Owners { path } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::owners(&graph, &path)
+        }
+
+        Commands::Blame { symbol } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::blame(&graph, &symbol)
+        }
+
+        Commands::History { symbol } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::history(&graph, &symbol)
+        }
+
+        Commands::MoveFile { from, to, json } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::move_file(&graph, &from, &to, json)
+        }
+
+        Commands::Deps { symbol, json } => {
+            with_source(&|source| cli_read::deps(source, &symbol, json))
+        }
+
+        Commands::Impact { symbol, depth } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::impact(&graph, &symbol, depth)
+        }
+
+        Commands::Path { from, to } => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::call_path(&graph, &from, &to)
+        }
+
+        Commands::ApiCheck => {
+            let graph = load_or_build_graph(&root, &cache_path)?;
+            cli_read::api_check(&graph)
+        }
+
         Commands::Files => {
             let graph = load_or_build_graph(&root, &cache_path)?;
             cli_read::files(&graph)
@@ -143,23 +242,3 @@ fn uninstall() -> Result<()> {
 
     Ok(())
 }
-
-/// Load graph from cache or build if not exists
-fn load_or_build_graph(root: &Path, cache_path: &Path) -> Result<CodeGraph> {
-    if cache_path.exists() {
-        match CodeGraph::load(cache_path) {
-            Ok(graph) => return Ok(graph),
-            Err(e) => {
-                eprintln!("Warning: Failed to load cache, rebuilding: {}", e);
-            }
-        }
-    }
-
-    // Build and cache
-    let graph = build_graph(root);
-    if let Some(parent) = cache_path.parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
-    let _ = graph.save(cache_path);
-    Ok(graph)
-}
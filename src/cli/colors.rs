@@ -0,0 +1,27 @@
+//! ANSI colors for terminal output.
+//!
+//! Kept tiny on purpose: a handful of SGR codes and a `paint` helper that
+//! becomes a no-op when color is disabled (pipes, files, `NO_COLOR`).
+
+use std::io::IsTerminal;
+
+pub const RESET: &str = "\x1b[0m";
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const CYAN: &str = "\x1b[36m";
+
+/// Whether stdout should get colored output.
+pub fn enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+/// Wrap `text` in `color` (and a reset) when `on` is true.
+pub fn paint(text: &str, color: &str, on: bool) -> String {
+    if on {
+        format!("{}{}{}", color, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
@@ -1,9 +1,20 @@
 //! Daemon management: start, stop, status
-//! TODO: Daemon functionality not finalized yet.
+//!
+//! Query commands use a running daemon's in-memory graph instead of
+//! loading `graph.bin`; see [`client`].
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Subcommand;
 use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::daemon::{
+    is_daemon_running, read_token, send_request, start_daemon, Request, Response, Transport,
+};
+
+/// How long `start` waits for the daemon to finish its first build.
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Subcommand)]
 pub enum DaemonAction {
@@ -13,37 +24,152 @@ pub enum DaemonAction {
     Stop,
     /// Check daemon status
     Status,
+    /// Run the daemon in the foreground (what `start` spawns)
+    #[command(hide = true)]
+    Run,
 }
 
-/// Handle daemon management commands (not finalized)
-pub fn handle(_root: &Path, action: Option<&DaemonAction>) -> Result<()> {
-    match action {
-        None => {
-            println!("Daemon functionality not yet finalized.");
-            println!("Use 'anchor build' to index, then query with 'anchor search/context'.");
-            Ok(())
+/// Whether query commands go through the daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonMode {
+    /// Use the daemon if one is running, else the on-disk graph
+    Auto,
+    /// Use the daemon, starting it first if needed
+    Require,
+    /// Always use the on-disk graph
+    Off,
+}
+
+impl DaemonMode {
+    /// Mode for the `--daemon` / `--no-daemon` flags.
+    pub fn from_flags(daemon: bool, no_daemon: bool) -> Self {
+        match (daemon, no_daemon) {
+            (true, _) => DaemonMode::Require,
+            (_, true) => DaemonMode::Off,
+            _ => DaemonMode::Auto,
         }
+    }
+}
+
+/// A connection to the project's daemon over its local socket.
+pub struct DaemonClient {
+    transport: Transport,
+    token: Option<String>,
+}
+
+impl DaemonClient {
+    fn new(root: &Path) -> Self {
+        Self {
+            transport: Transport::local(root),
+            token: read_token(root),
+        }
+    }
+
+    /// Send `request` and return the data of an `Ok` response.
+    pub fn request(&self, request: Request) -> Result<serde_json::Value> {
+        match send_request(&self.transport, self.token.as_deref(), request)? {
+            Response::Ok { data } => Ok(data),
+            Response::Error { message } => bail!("daemon: {}", message),
+            response => bail!("unexpected daemon response: {:?}", response),
+        }
+    }
+
+    /// Does the daemon answer?
+    fn ping(&self) -> bool {
+        matches!(
+            send_request(&self.transport, self.token.as_deref(), Request::Ping),
+            Ok(Response::Pong)
+        )
+    }
+}
+
+/// The daemon query commands should use under `mode`: the running one,
+/// one started now for `Require`, or `None` to read the on-disk graph.
+pub fn client(root: &Path, mode: DaemonMode) -> Result<Option<DaemonClient>> {
+    match mode {
+        DaemonMode::Off => Ok(None),
+        // A stale PID file from a crashed daemon falls back to the disk graph
+        DaemonMode::Auto => Ok(Some(DaemonClient::new(root))
+            .filter(|client| is_daemon_running(root) && client.ping())),
+        DaemonMode::Require => {
+            if !is_daemon_running(root) {
+                start_background(root)?;
+                wait_for_ready(root)?;
+            }
+            Ok(Some(DaemonClient::new(root)))
+        }
+    }
+}
+
+/// Handle daemon management commands
+pub fn handle(root: &Path, action: Option<&DaemonAction>) -> Result<()> {
+    match action {
+        None | Some(DaemonAction::Status) => status(root),
         Some(DaemonAction::Start) => {
-            println!("Daemon functionality not yet finalized.");
+            if is_daemon_running(root) {
+                println!("daemon already running");
+                return Ok(());
+            }
+            start_background(root)?;
+            wait_for_ready(root)?;
+            println!("daemon started");
             Ok(())
         }
         Some(DaemonAction::Stop) => {
-            println!("Daemon functionality not yet finalized.");
-            Ok(())
-        }
-        Some(DaemonAction::Status) => {
-            println!("Daemon functionality not yet finalized.");
+            if !is_daemon_running(root) {
+                println!("daemon not running");
+                return Ok(());
+            }
+            let client = DaemonClient::new(root);
+            send_request(&client.transport, client.token.as_deref(), Request::Shutdown)?;
+            // The accept loop only sees the shutdown flag on its next connection
+            let _ = client.ping();
+            println!("daemon stopped");
             Ok(())
         }
+        Some(DaemonAction::Run) => start_daemon(root),
+    }
+}
+
+/// Print whether the daemon runs and, if so, its health report.
+fn status(root: &Path) -> Result<()> {
+    if !is_daemon_running(root) {
+        println!("daemon not running");
+        return Ok(());
     }
+    let health = DaemonClient::new(root).request(Request::Health)?;
+    println!("daemon running");
+    println!("{}", serde_json::to_string_pretty(&health)?);
+    Ok(())
 }
 
-/// Start daemon in background (not finalized)
-pub fn start_background(_root: &Path) -> Result<()> {
+/// Start daemon in background: this binary re-run as `daemon run`,
+/// detached from the terminal.
+pub fn start_background(root: &Path) -> Result<()> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("--root")
+        .arg(root)
+        .args(["daemon", "run"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Own process group, so closing the terminal doesn't stop it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    command.spawn()?;
     Ok(())
 }
 
-/// Wait for daemon to be ready (not finalized)
-pub fn wait_for_ready(_root: &Path) {
-    // No-op
+/// Wait for the daemon to answer pings, which it does once its first
+/// graph build is done.
+pub fn wait_for_ready(root: &Path) -> Result<()> {
+    let started = Instant::now();
+    while started.elapsed() < READY_TIMEOUT {
+        if DaemonClient::new(root).ping() {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    bail!("daemon did not start within {}s", READY_TIMEOUT.as_secs())
 }
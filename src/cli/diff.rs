@@ -0,0 +1,129 @@
+//! Unified-diff style rendering of suggested edits.
+//!
+//! Turns the `edits` of a `change` context into hunks a human can scan:
+//!
+//! ```text
+//! @@ src/api.rs:12 (handle_request) @@
+//!      10| fn handle_request(req: &Request) {
+//! -    11|     validate(req.body);
+//! +    11|     validate(req.body, <strict>);
+//!      12| }
+//! ```
+
+use super::colors::{paint, BOLD, CYAN, DIM, GREEN, RED};
+use crate::query::Edit;
+
+/// Render one edit as a diff hunk. With `color`, the current line is red,
+/// the suggestion green, and surrounding context dimmed.
+pub fn format_edit_diff(edit: &Edit, color: bool) -> String {
+    let mut out = Vec::new();
+
+    let mut header = format!("@@ {}:{} ({}) @@", edit.file, edit.line, edit.in_symbol);
+    if let (false, Some(expected)) = (edit.arity_ok, edit.expected_args) {
+        header.push_str(&format!(" args {} -> {}", edit.actual_args, expected));
+    }
+    out.push(paint(&header, &format!("{}{}", BOLD, CYAN), color));
+
+    let mut emitted_change = false;
+    for ctx in &edit.context {
+        // Context lines look like " 1234| code", the target line ">1234| code"
+        if let Some(target) = ctx.strip_prefix('>') {
+            push_change(&mut out, edit, target, color);
+            emitted_change = true;
+        } else {
+            out.push(paint(ctx, DIM, color));
+        }
+    }
+
+    // No file context available: show the line from the graph snippet
+    if !emitted_change {
+        let current = format!("{:4}| {}", edit.line, edit.line_content);
+        push_change(&mut out, edit, &current, color);
+    }
+
+    out.join("\n")
+}
+
+/// Render all edits, separated by blank lines.
+pub fn format_edits(edits: &[Edit], color: bool) -> String {
+    edits
+        .iter()
+        .map(|e| format_edit_diff(e, color))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Push the `-` line and, if there is a suggestion, the `+` line.
+///
+/// The `+` line is the current line with the usage swapped for the
+/// suggestion, so indentation and surrounding code are preserved.
+fn push_change(out: &mut Vec<String>, edit: &Edit, current: &str, color: bool) {
+    out.push(paint(&format!("-{}", current), RED, color));
+
+    if let Some(ref suggested) = edit.suggested {
+        let new_line = if current.contains(&edit.usage) {
+            current.replacen(&edit.usage, suggested, 1)
+        } else {
+            format!("{:4}| {}", edit.line, suggested)
+        };
+        out.push(paint(&format!("+{}", new_line), GREEN, color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_edit() -> Edit {
+        Edit {
+            file: "src/api.rs".to_string(),
+            line: 11,
+            in_symbol: "handle_request".to_string(),
+            usage: "validate(req.body)".to_string(),
+            line_content: "validate(req.body);".to_string(),
+            suggested: Some("validate(req.body, <strict>)".to_string()),
+            new_args: vec!["strict: bool".to_string()],
+            removed_args: vec![],
+            context: vec![
+                "   10| fn handle_request(req: &Request) {".to_string(),
+                ">  11|     validate(req.body);".to_string(),
+                "   12| }".to_string(),
+            ],
+            actual_args: 1,
+            expected_args: Some(2),
+            arity_ok: false,
+        }
+    }
+
+    #[test]
+    fn test_format_edit_diff_plain() {
+        let out = format_edit_diff(&sample_edit(), false);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines[0], "@@ src/api.rs:11 (handle_request) @@ args 1 -> 2");
+        assert_eq!(lines[1], "   10| fn handle_request(req: &Request) {");
+        assert_eq!(lines[2], "-  11|     validate(req.body);");
+        assert_eq!(lines[3], "+  11|     validate(req.body, <strict>);");
+        assert_eq!(lines[4], "   12| }");
+    }
+
+    #[test]
+    fn test_format_edit_diff_colored() {
+        let out = format_edit_diff(&sample_edit(), true);
+        assert!(out.contains(&format!("{}-  11|", RED)));
+        assert!(out.contains(&format!("{}+  11|", GREEN)));
+        assert!(out.contains(&format!("{}   10|", DIM)));
+    }
+
+    #[test]
+    fn test_format_edit_diff_without_context() {
+        let mut edit = sample_edit();
+        edit.context.clear();
+        edit.suggested = None;
+
+        let out = format_edit_diff(&edit, false);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], "-  11| validate(req.body);");
+    }
+}
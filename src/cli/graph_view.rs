@@ -0,0 +1,245 @@
+//! Box-drawing dependency graph: dependents above, the symbol in the
+//! middle, dependencies below.
+//!
+//! ```text
+//!  login   signup
+//!    │       │
+//!    └───┬───┘
+//!        ▼
+//!  ┌──────────┐
+//!  │ validate │
+//!  └──────────┘
+//!        │
+//!        ▼
+//!    is_empty
+//! ```
+
+/// Labels shown per row before the rest are folded into "+N more".
+const MAX_ROW_LABELS: usize = 8;
+/// Longer labels are cut to this many characters.
+const MAX_LABEL_CHARS: usize = 24;
+/// Spaces between neighboring labels.
+const LABEL_GAP: usize = 3;
+
+/// Lay out `target` with `dependents` above and `dependencies` below.
+/// Returns one string per line, without trailing spaces.
+pub fn layout_dependencies(target: &str, dependents: &[String], dependencies: &[String]) -> Vec<String> {
+    let above = row_labels(dependents);
+    let below = row_labels(dependencies);
+    let target = truncate(target);
+
+    let box_width = target.chars().count() + 4;
+    let width = [row_width(&above), row_width(&below), box_width]
+        .into_iter()
+        .max()
+        .unwrap_or(box_width);
+    let center = width / 2;
+
+    let mut canvas = Canvas::default();
+
+    if !above.is_empty() {
+        let centers = label_centers(&above, width);
+        canvas.labels(&above, &centers);
+        canvas.marks(&centers, '│');
+        canvas.bus(&centers, &[center]);
+        canvas.marks(&[center], '▼');
+    }
+
+    let start = center - box_width / 2;
+    let line = "─".repeat(box_width - 2);
+    canvas.text(start, &format!("┌{}┐", line));
+    canvas.text(start, &format!("│ {} │", target));
+    canvas.text(start, &format!("└{}┘", line));
+
+    if !below.is_empty() {
+        let centers = label_centers(&below, width);
+        canvas.marks(&[center], '│');
+        canvas.bus(&[center], &centers);
+        canvas.marks(&centers, '▼');
+        canvas.labels(&below, &centers);
+    }
+
+    canvas.lines()
+}
+
+/// Deduplicated, truncated labels, folding the overflow into "+N more".
+fn row_labels(names: &[String]) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for name in names {
+        let label = truncate(name);
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    if labels.len() > MAX_ROW_LABELS {
+        let hidden = labels.len() - (MAX_ROW_LABELS - 1);
+        labels.truncate(MAX_ROW_LABELS - 1);
+        labels.push(format!("+{} more", hidden));
+    }
+    labels
+}
+
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        return label.to_string();
+    }
+    let mut cut: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Every label gets a slot as wide as the widest one.
+fn slot_width(labels: &[String]) -> usize {
+    labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) + LABEL_GAP
+}
+
+fn row_width(labels: &[String]) -> usize {
+    (labels.len() * slot_width(labels)).saturating_sub(LABEL_GAP)
+}
+
+/// Column of each label's center, with the row centered in `width`.
+fn label_centers(labels: &[String], width: usize) -> Vec<usize> {
+    let slot = slot_width(labels);
+    let offset = (width - row_width(labels)) / 2;
+    (0..labels.len())
+        .map(|i| offset + i * slot + (slot - LABEL_GAP) / 2)
+        .collect()
+}
+
+/// Rows of characters, grown as they are drawn.
+#[derive(Default)]
+struct Canvas {
+    rows: Vec<Vec<char>>,
+}
+
+impl Canvas {
+    fn new_row(&mut self) -> &mut Vec<char> {
+        self.rows.push(Vec::new());
+        self.rows.last_mut().expect("row was just pushed")
+    }
+
+    /// Put `text` on a new row starting at column `start`.
+    fn text(&mut self, start: usize, text: &str) {
+        let row = self.new_row();
+        put(row, start, text);
+    }
+
+    /// Each label centered on its column.
+    fn labels(&mut self, labels: &[String], centers: &[usize]) {
+        let row = self.new_row();
+        for (label, &center) in labels.iter().zip(centers) {
+            put(row, center - (label.chars().count() - 1) / 2, label);
+        }
+    }
+
+    /// `mark` at each column.
+    fn marks(&mut self, columns: &[usize], mark: char) {
+        let row = self.new_row();
+        for &column in columns {
+            put(row, column, &mark.to_string());
+        }
+    }
+
+    /// A horizontal line joining lines coming down at the `upper` columns
+    /// to lines leaving at the `lower` ones. Nothing is drawn when they
+    /// all share one column.
+    fn bus(&mut self, upper: &[usize], lower: &[usize]) {
+        let columns = || upper.iter().chain(lower);
+        let (Some(&left), Some(&right)) = (columns().min(), columns().max()) else {
+            return;
+        };
+        if left == right {
+            return;
+        }
+        let row = self.new_row();
+        for column in left..=right {
+            let joint = junction(
+                upper.contains(&column),
+                lower.contains(&column),
+                column > left,
+                column < right,
+            );
+            put(row, column, &joint.to_string());
+        }
+    }
+
+    fn lines(self) -> Vec<String> {
+        self.rows
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+            .collect()
+    }
+}
+
+/// Write `text` into `row` at `start`, padding with spaces.
+fn put(row: &mut Vec<char>, start: usize, text: &str) {
+    for (i, c) in text.chars().enumerate() {
+        let column = start + i;
+        if row.len() <= column {
+            row.resize(column + 1, ' ');
+        }
+        row[column] = c;
+    }
+}
+
+/// The box-drawing character joining lines in the given directions.
+fn junction(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (true, true, true, true) => '┼',
+        (true, true, true, false) => '┤',
+        (true, true, false, true) => '├',
+        (true, false, true, true) => '┴',
+        (true, false, true, false) => '┘',
+        (true, false, false, true) => '└',
+        (false, true, true, true) => '┬',
+        (false, true, true, false) => '┐',
+        (false, true, false, true) => '┌',
+        (false, false, true, true) => '─',
+        _ => ' ',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_layout_dependents_above_dependencies_below() {
+        let lines = layout_dependencies(
+            "validate",
+            &names(&["login", "signup"]),
+            &names(&["is_empty", "trim", "len"]),
+        );
+
+        let expected = vec![
+            "        login    signup",
+            "          │        │",
+            "          └────┬───┘",
+            "               ▼",
+            "         ┌──────────┐",
+            "         │ validate │",
+            "         └──────────┘",
+            "               │",
+            "    ┌──────────┼──────────┐",
+            "    ▼          ▼          ▼",
+            " is_empty     trim       len",
+        ];
+        assert_eq!(lines, expected, "\n{}", lines.join("\n"));
+    }
+
+    #[test]
+    fn test_layout_folds_long_rows() {
+        let many: Vec<String> = (0..12).map(|i| format!("f{}", i)).collect();
+
+        let lines = layout_dependencies("hub", &[], &many);
+
+        assert!(lines[0].trim_start().starts_with('┌'), "no dependents row above the box");
+        let labels = lines.last().unwrap();
+        assert!(labels.contains("f6") && !labels.contains("f7"));
+        assert!(labels.contains("+5 more"));
+    }
+}
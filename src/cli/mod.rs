@@ -6,7 +6,10 @@
 //! - Parallel: plan
 //! - System: build, stats, daemon
 
+pub mod colors;
 pub mod daemon;
+pub mod diff;
+pub mod graph_view;
 pub mod plan;
 pub mod read;
 // pub mod write;  // TODO: Write operations not finalized yet
@@ -23,6 +26,14 @@ pub struct Cli {
     #[arg(short, long, default_value = ".")]
     pub root: PathBuf,
 
+    /// Query through the daemon, starting it if it isn't running
+    #[arg(long, global = true, conflicts_with = "no_daemon")]
+    pub daemon: bool,
+
+    /// Read the on-disk graph even if a daemon is running
+    #[arg(long, global = true)]
+    pub no_daemon: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -40,18 +51,47 @@ Start here:
   build                 Index codebase
   map                   Codebase map (modules + top symbols)
   map <scope>           Zoom into module
+  summary               Languages, entry points, hotspots, deps
 
 Query:
   context <symbol>      Code + callers + callees
+  context <symbol> -i change [-s <new sig>]
+                        Call sites to update, as a diff
+  context <symbol> -i create [--threshold <0-1>]
+                        Similar symbols to follow, best match first
+  context <symbol> --blame
+                        Also report who last changed each symbol (JSON)
+  context <symbol> -i change --focus
+                        List tests, getters and logging callers last
   search <query>        Find symbols
+  search <query> --async
+                        Only async functions and methods
+  find-sig <shape>      Find functions by signature, e.g. '(&str) -> bool'
+  cochange <symbol>     Symbols often committed together with it
+  precommit             Symbols changed in the working tree + impact (JSON)
+  owners <path>         Reviewers for a file or symbol (CODEOWNERS)
+  blame <symbol>        Last commit and author to change a symbol
+  history <symbol>      Versions of a symbol from before each write
+  deps <symbol>         Dependents above, dependencies below (--json for raw)
+  path <from> <to>      Shortest call chain from one symbol to another
+  move-file <from> <to> Imports to update for moving a file, as a diff
+  impact <symbol>       Everything that depends on it, transitively (--depth N)
+  untested              Public functions no test calls, most used first
+  dead-code             Private functions nothing calls (file:line)
   plan <file.json>      Batch read operations
 
 Other:
   overview              Files + symbol counts
+  api-check             Dead API calls + unused endpoints
   stats                 Graph statistics
+  daemon start|stop|status
+                        Keep the graph in memory, updated as files change
 
 Options:
   -r, --root <PATH>     Project root (default: .)
+  --daemon              search/context/deps through the daemon (auto-start)
+  --no-daemon           search/context/deps from graph.bin, even if a
+                        daemon is running
 ";
 
 #[derive(Subcommand)]
@@ -65,6 +105,27 @@ pub enum Commands {
         /// Max results
         #[arg(short, long, default_value = "5")]
         limit: usize,
+
+        /// Intent: explore (default), change, create, tests, overview
+        /// (aliases: understand/find = explore, modify/refactor = change)
+        #[arg(short, long)]
+        intent: Option<String>,
+
+        /// New signature for change intent, e.g. 'validate(s: &str, strict: bool)'
+        #[arg(short, long)]
+        signature: Option<String>,
+
+        /// Minimum similarity (0.0-1.0) for create-intent patterns
+        #[arg(long)]
+        threshold: Option<f64>,
+
+        /// Add the last commit to change each symbol (runs git blame)
+        #[arg(long)]
+        blame: bool,
+
+        /// List boilerplate dependents (tests, getters, logging) last
+        #[arg(long)]
+        focus: bool,
     },
 
     /// Search for symbols (lightweight: names, files, lines)
@@ -79,6 +140,111 @@ pub enum Commands {
         /// Max results
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// Only async functions and methods
+        #[arg(long = "async")]
+        r#async: bool,
+
+        /// Also match misspelled or partial names, ranked by similarity
+        #[arg(long)]
+        fuzzy: bool,
+
+        /// Match names regardless of case
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+    },
+
+    /// Find functions by signature shape, e.g. '(&str) -> bool'
+    FindSig {
+        /// Signature shape: parameter types and optional return type
+        signature: String,
+
+        /// Max results
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Symbols in files that change in the same commits (git history)
+    Cochange {
+        /// Symbol name
+        symbol: String,
+
+        /// Max results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Symbols touched by uncommitted changes and their dependents, as JSON
+    Precommit,
+
+    /// Public functions no test calls, most depended-on first
+    Untested {
+        /// Max results
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Leave out trivial getters and setters
+        #[arg(long)]
+        skip_accessors: bool,
+    },
+
+    /// Private functions and methods nothing calls or references
+    DeadCode,
+
+    /// Reviewers for a file or symbol, from CODEOWNERS
+    Owners {
+        /// File path or symbol name
+        path: String,
+    },
+
+    /// Last commit and author to change a symbol, from git blame
+    Blame {
+        /// Symbol name
+        symbol: String,
+    },
+
+    /// Versions of a symbol from the write history, oldest first
+    History {
+        /// Symbol name
+        symbol: String,
+    },
+
+    /// Dependents and dependencies of a symbol, drawn as a graph
+    Deps {
+        /// Symbol name
+        symbol: String,
+        /// Print the raw dependency lists as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Transitive dependents of a symbol: what a change could break
+    Impact {
+        /// Symbol name
+        symbol: String,
+
+        /// Max hops from the symbol
+        #[arg(short, long, default_value = "3")]
+        depth: usize,
+    },
+
+    /// Shortest chain of calls from one symbol to another
+    Path {
+        /// Calling symbol
+        from: String,
+        /// Called symbol
+        to: String,
+    },
+
+    /// Import rewrites that moving a file needs, shown as a diff
+    MoveFile {
+        /// Current path of the file
+        from: String,
+        /// Where it's moving to
+        to: String,
+        /// Print the edits as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     // ─── Parallel (1 command) ─────────────────────────────────────
@@ -116,6 +282,12 @@ pub enum Commands {
     /// Show codebase overview (files, structure, key symbols)
     Overview,
 
+    /// One-shot project summary (languages, entry points, hotspots, deps)
+    Summary,
+
+    /// Check frontend API calls against backend routes
+    ApiCheck,
+
     // ─── System ───────────────────────────────────────────────────
     /// Build/rebuild the code graph
     Build,
@@ -129,7 +301,6 @@ pub enum Commands {
     Files,
 
     /// Manage the anchor daemon
-    #[command(hide = true)]
     Daemon {
         #[command(subcommand)]
         action: Option<daemon::DaemonAction>,
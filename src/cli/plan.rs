@@ -11,7 +11,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 // use crate::daemon::{send_request, Request, Response};  // TODO: Write operations not finalized
 use crate::graph::CodeGraph;
 // use crate::write::{create_file, insert_after, replace_all, WriteError};  // TODO: Write operations not finalized
-use super::read as cli_read;
+use super::read::{self as cli_read, GraphSource};
 
 /// Execute a plan file sequentially (fallback when no daemon)
 pub fn execute(root: &Path, file: &str) -> Result<()> {
@@ -34,7 +34,7 @@ pub fn execute(root: &Path, file: &str) -> Result<()> {
 
     let graph = if has_reads {
         let cache_path = root.join(".anchor/graph.bin");
-        CodeGraph::load(&cache_path).ok()
+        super::read::load_or_build_graph(root, &cache_path).ok()
     } else {
         None
     };
@@ -81,7 +81,7 @@ fn execute_operation(_root: &Path, op: &PlanOperation, graph: Option<&CodeGraph>
         PlanOperation::Search { query, pattern, limit } => {
             print!("search {} ... ", query);
             if let Some(g) = graph {
-                let _ = cli_read::search(g, query, pattern.as_deref(), limit.unwrap_or(20));
+                let _ = cli_read::search(GraphSource::Graph(g), query, pattern.as_deref(), limit.unwrap_or(20), false, false, false);
             }
             Ok(())
         }
@@ -95,7 +95,7 @@ fn execute_operation(_root: &Path, op: &PlanOperation, graph: Option<&CodeGraph>
         PlanOperation::Context { query, limit } => {
             print!("context {} ... ", query);
             if let Some(g) = graph {
-                let _ = cli_read::context(g, query, limit.unwrap_or(5));
+                let _ = cli_read::context(GraphSource::Graph(g), query, limit.unwrap_or(5));
             }
             Ok(())
         }
@@ -191,7 +191,7 @@ pub fn execute_parallel(root: &Path, file: &str) -> Result<()> {
 
     let graph = if has_reads {
         let cache_path = root.join(".anchor/graph.bin");
-        CodeGraph::load(&cache_path).ok()
+        super::read::load_or_build_graph(root, &cache_path).ok()
     } else {
         None
     };
@@ -271,25 +271,25 @@ fn execute_operation_via_daemon(_root: &Path, op: &PlanOperation, graph: Option<
     match op {
         PlanOperation::Search { query, pattern, limit } => {
             if let Some(g) = graph {
-                let _ = cli_read::search(g, query, pattern.as_deref(), limit.unwrap_or(20));
+                let _ = cli_read::search(GraphSource::Graph(g), query, pattern.as_deref(), limit.unwrap_or(20), false, false, false);
             }
-            return Ok(PlanResponse::Ok { data: serde_json::json!({"op": "search"}) });
+            Ok(PlanResponse::Ok { data: serde_json::json!({"op": "search"}) })
         }
         PlanOperation::Read { symbol } => {
             if let Some(g) = graph {
                 let _ = cli_read::read(g, symbol);
             }
-            return Ok(PlanResponse::Ok { data: serde_json::json!({"op": "read"}) });
+            Ok(PlanResponse::Ok { data: serde_json::json!({"op": "read"}) })
         }
         PlanOperation::Context { query, limit } => {
             if let Some(g) = graph {
-                let _ = cli_read::context(g, query, limit.unwrap_or(5));
+                let _ = cli_read::context(GraphSource::Graph(g), query, limit.unwrap_or(5));
             }
-            return Ok(PlanResponse::Ok { data: serde_json::json!({"op": "context"}) });
+            Ok(PlanResponse::Ok { data: serde_json::json!({"op": "context"}) })
         }
         // Write operations not finalized
         _ => {
-            return Ok(PlanResponse::Error { message: "Write operations not yet finalized".to_string() });
+            Ok(PlanResponse::Error { message: "Write operations not yet finalized".to_string() })
         }
     }
 }
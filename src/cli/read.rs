@@ -7,36 +7,85 @@ use anyhow::Result;
 use std::path::Path;
 use std::sync::Arc;
 
-use crate::graph::CodeGraph;
+use super::daemon::DaemonClient;
+use super::graph_view;
+use crate::daemon::Request;
+use crate::graph::{CodeGraph, DependencyInfo, EdgeKind, NodeKind, SignatureShape};
 use crate::graphql::{build_schema, execute};
+use crate::query::DependencyResponse;
+
+/// Where `search`, `context` and `deps` read the graph from.
+#[derive(Clone, Copy)]
+pub enum GraphSource<'a> {
+    /// A graph loaded in this process
+    Graph(&'a CodeGraph),
+    /// A running daemon's in-memory graph
+    Daemon(&'a DaemonClient),
+}
+
+impl GraphSource<'_> {
+    /// Run a GraphQL query, returning the JSON result.
+    fn graphql(self, query: &str) -> Result<serde_json::Value> {
+        match self {
+            GraphSource::Graph(graph) => {
+                let schema = build_schema(Arc::new(graph.clone()));
+                let result = tokio::runtime::Runtime::new()?.block_on(execute(&schema, query));
+                Ok(serde_json::from_str(&result)?)
+            }
+            GraphSource::Daemon(client) => client.request(Request::Graphql {
+                query: query.to_string(),
+            }),
+        }
+    }
+
+    fn dependencies(self, symbol: &str) -> Result<DependencyResponse> {
+        match self {
+            GraphSource::Graph(graph) => Ok(crate::query::anchor_dependencies(graph, symbol)),
+            GraphSource::Daemon(client) => {
+                let data = client.request(Request::Deps {
+                    symbol: symbol.to_string(),
+                })?;
+                Ok(serde_json::from_value(data)?)
+            }
+        }
+    }
+}
 
 /// Search for symbols by name or pattern.
 ///
 /// Wraps GraphQL `symbol` query with optional regex pattern.
-pub fn search(graph: &CodeGraph, query: &str, pattern: Option<&str>, limit: usize) -> Result<()> {
-    let schema = build_schema(Arc::new(graph.clone()));
-
+pub fn search(
+    source: GraphSource,
+    query: &str,
+    pattern: Option<&str>,
+    limit: usize,
+    async_only: bool,
+    fuzzy: bool,
+    ignore_case: bool,
+) -> Result<()> {
     // Build GraphQL query based on whether pattern is provided
     let gql_query = if let Some(pat) = pattern {
         // Use regex search
         format!(
-            r#"{{ search(pattern: "{}", limit: {}) {{ name kind file line code }} }}"#,
+            r#"{{ search(pattern: "{}", limit: {}, asyncOnly: {}, ignoreCase: {}) {{ name kind file line code }} }}"#,
             escape_graphql(pat),
-            limit
+            limit,
+            async_only,
+            ignore_case
         )
     } else {
-        // Use symbol query with prefix matching
+        // Use symbol query with prefix (or fuzzy) matching
         format!(
-            r#"{{ symbol(name: "{}") {{ name kind file line }} }}"#,
-            escape_graphql(query)
+            r#"{{ symbol(name: "{}", asyncOnly: {}, fuzzy: {}, ignoreCase: {}) {{ name kind file line }} }}"#,
+            escape_graphql(query),
+            async_only,
+            fuzzy,
+            ignore_case
         )
     };
 
     // Execute GraphQL query
-    let result = tokio::runtime::Runtime::new()?.block_on(execute(&schema, &gql_query));
-
-    // Parse and format output
-    let json: serde_json::Value = serde_json::from_str(&result)?;
+    let json = source.graphql(&gql_query)?;
 
     if let Some(errors) = json.get("errors") {
         if let Some(arr) = errors.as_array() {
@@ -79,6 +128,69 @@ pub fn search(graph: &CodeGraph, query: &str, pattern: Option<&str>, limit: usiz
     Ok(())
 }
 
+/// Find functions by signature shape, e.g. `(&str) -> bool`.
+///
+/// Wraps GraphQL `bySignature` query.
+pub fn find_sig(graph: &CodeGraph, signature: &str, limit: usize) -> Result<()> {
+    let shape = match SignatureShape::parse(signature) {
+        Some(s) => s,
+        None => {
+            println!("Invalid signature '{}' (expected e.g. '(&str) -> bool')", signature);
+            return Ok(());
+        }
+    };
+
+    let schema = build_schema(Arc::new(graph.clone()));
+
+    let params: Vec<String> = shape
+        .params
+        .iter()
+        .map(|p| format!(r#""{}""#, escape_graphql(p)))
+        .collect();
+    let returns = shape
+        .return_type
+        .as_ref()
+        .map(|r| format!(r#", returns: "{}""#, escape_graphql(r)))
+        .unwrap_or_default();
+
+    let gql_query = format!(
+        r#"{{ bySignature(params: [{}]{}, limit: {}) {{ name kind file line }} }}"#,
+        params.join(", "),
+        returns,
+        limit
+    );
+
+    let result = tokio::runtime::Runtime::new()?.block_on(execute(&schema, &gql_query));
+    let json: serde_json::Value = serde_json::from_str(&result)?;
+
+    if let Some(errors) = json.get("errors") {
+        if let Some(arr) = errors.as_array() {
+            if !arr.is_empty() {
+                if let Some(msg) = arr[0].get("message") {
+                    println!("Error: {}", msg.as_str().unwrap_or("unknown"));
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    let symbols = json
+        .get("data")
+        .and_then(|d| d.get("bySignature"))
+        .and_then(|s| s.as_array());
+
+    match symbols {
+        Some(s) if !s.is_empty() => {
+            for sym in s {
+                print_symbol_compact(sym);
+            }
+        }
+        _ => println!("No functions match '{}'", signature),
+    }
+
+    Ok(())
+}
+
 /// Read full context for a symbol.
 ///
 /// Wraps GraphQL `symbol` query with callers/callees.
@@ -173,17 +285,14 @@ pub fn read(graph: &CodeGraph, symbol: &str) -> Result<()> {
 /// Context: Search + Read combined.
 ///
 /// Wraps GraphQL `symbol` query with code and relationships.
-pub fn context(graph: &CodeGraph, query: &str, limit: usize) -> Result<()> {
-    let schema = build_schema(Arc::new(graph.clone()));
-
+pub fn context(source: GraphSource, query: &str, limit: usize) -> Result<()> {
     // GraphQL query: symbol search with code, callers, callees
     let gql_query = format!(
         r#"{{ symbol(name: "{}") {{ name kind file line code callers {{ name }} callees {{ name }} }} }}"#,
         escape_graphql(query)
     );
 
-    let result = tokio::runtime::Runtime::new()?.block_on(execute(&schema, &gql_query));
-    let json: serde_json::Value = serde_json::from_str(&result)?;
+    let json = source.graphql(&gql_query)?;
 
     if let Some(errors) = json.get("errors") {
         if let Some(arr) = errors.as_array() {
@@ -265,10 +374,63 @@ pub fn context(graph: &CodeGraph, query: &str, limit: usize) -> Result<()> {
     Ok(())
 }
 
+/// Context with an explicit intent (change, create, ...).
+///
+/// On a terminal, change-intent edits are shown as colored diff hunks;
+/// otherwise the full `ContextResponse` is printed as JSON.
+pub fn context_intent(
+    graph: &CodeGraph,
+    query: &str,
+    intent: &str,
+    new_signature: Option<&str>,
+    threshold: Option<f64>,
+    blame: bool,
+    focus: bool,
+) -> Result<()> {
+    // Query filter defaults from the config of the project the graph indexes
+    let filter = graph
+        .meta()
+        .map(|meta| crate::config::AnchorConfig::for_project(&meta.root).query.symbol_filter())
+        .unwrap_or_default();
+    let mut response = crate::query::get_context_with_threshold(
+        graph,
+        query,
+        intent,
+        new_signature,
+        &filter,
+        threshold.unwrap_or(crate::query::DEFAULT_SIMILARITY_THRESHOLD),
+    );
+    if blame {
+        crate::query::attach_blame(graph, &mut response);
+    }
+    if focus {
+        crate::query::apply_focus(graph, &mut response);
+    }
+
+    if !super::colors::enabled() || response.edits.is_empty() {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+
+    for sym in &response.symbols {
+        println!("{} {} {}:{}", sym.name, sym.kind, sym.file, sym.line);
+    }
+    println!();
+    println!("{}", super::diff::format_edits(&response.edits, true));
+
+    if !response.tests.is_empty() {
+        let tests: Vec<&str> = response.tests.iter().map(|t| t.name.as_str()).collect();
+        println!();
+        println!("tests: {}", tests.join(" "));
+    }
+
+    Ok(())
+}
+
 /// Build/rebuild the code graph
 pub fn build(root: &Path, cache_path: &Path) -> Result<()> {
     println!("Building...");
-    let graph = crate::graph::build_graph(root);
+    let graph = build_project_graph(root);
     std::fs::create_dir_all(cache_path.parent().unwrap())?;
     graph.save(cache_path)?;
 
@@ -277,6 +439,43 @@ pub fn build(root: &Path, cache_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Build the graph with the build settings of `.anchor/config.toml`.
+pub fn build_project_graph(root: &Path) -> CodeGraph {
+    crate::config::AnchorConfig::for_project(root).graph_builder(root).build()
+}
+
+/// Load the cached graph, or build and cache it.
+///
+/// A cache that fails to load (truncated by a crash, or written by an
+/// incompatible version) is deleted and rebuilt with a warning, so a bad
+/// `graph.bin` never blocks a command.
+pub fn load_or_build_graph(root: &Path, cache_path: &Path) -> Result<CodeGraph> {
+    if cache_path.exists() {
+        match CodeGraph::load(cache_path) {
+            Ok(graph) => {
+                if let Some(meta) = graph.meta() {
+                    if meta.commit_mismatch(crate::graph::git_head(root).as_deref()) {
+                        eprintln!("Warning: index was built at a different commit; run `anchor build` to refresh");
+                    }
+                }
+                return Ok(graph);
+            }
+            Err(e) => {
+                eprintln!("Warning: cache {} is unreadable ({}), rebuilding", cache_path.display(), e);
+                let _ = std::fs::remove_file(cache_path);
+            }
+        }
+    }
+
+    // Build and cache
+    let graph = build_project_graph(root);
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = graph.save(cache_path);
+    Ok(graph)
+}
+
 /// Get graph stats via GraphQL
 pub fn stats(graph: &CodeGraph) -> Result<()> {
     let schema = build_schema(Arc::new(graph.clone()));
@@ -292,9 +491,40 @@ pub fn stats(graph: &CodeGraph) -> Result<()> {
         println!("files:{} symbols:{} edges:{}", files, symbols, edges);
     }
 
+    if let Some(meta) = graph.meta() {
+        let commit = meta.git_commit.as_deref().map(short_commit).unwrap_or("-");
+        println!(
+            "built:{} version:{} commit:{}",
+            meta.built_at, meta.anchor_version, commit
+        );
+
+        let head = crate::graph::git_head(&meta.root);
+        if meta.commit_mismatch(head.as_deref()) {
+            println!(
+                "stale: HEAD is {} (run `anchor build`)",
+                head.as_deref().map(short_commit).unwrap_or("-")
+            );
+        }
+
+        // A grammar the runtime rejects makes a whole language vanish
+        for failure in &meta.grammar_failures {
+            println!(
+                "missing:{} files:{} ({})",
+                failure.language.name(),
+                failure.skipped_files,
+                failure.error
+            );
+        }
+    }
+
     Ok(())
 }
 
+/// First 8 characters of a commit hash.
+fn short_commit(commit: &str) -> &str {
+    commit.get(..8).unwrap_or(commit)
+}
+
 /// Print a symbol in compact format: name Kind file:line
 fn print_symbol_compact(sym: &serde_json::Value) {
     let name = sym.get("name").and_then(|v| v.as_str()).unwrap_or("");
@@ -394,7 +624,7 @@ pub fn map(graph: &CodeGraph, scope: Option<&str>) -> Result<()> {
 
             let callers = graph.dependents(&symbol.name).len();
             let callees = graph.dependencies(&symbol.name).len();
-            let short_module = dir.split('/').last().unwrap_or(&dir).to_string();
+            let short_module = dir.split('/').next_back().unwrap_or(&dir).to_string();
 
             modules.entry(dir.clone())
                 .or_default()
@@ -448,7 +678,7 @@ pub fn map(graph: &CodeGraph, scope: Option<&str>) -> Result<()> {
     // Top level view: modules with counts
     let module_line: Vec<String> = modules.iter()
         .map(|(dir, symbols)| {
-            let short_dir = dir.split('/').last().unwrap_or(dir);
+            let short_dir = dir.split('/').next_back().unwrap_or(dir);
             format!("{}({}s)", short_dir, symbols.len())
         })
         .collect();
@@ -471,7 +701,7 @@ pub fn map(graph: &CodeGraph, scope: Option<&str>) -> Result<()> {
 
     // Top connected: symbols with most relationships (deduplicated by name)
     let mut by_connections = all_symbols.clone();
-    by_connections.sort_by(|a, b| (b.2 + b.3).cmp(&(a.2 + a.3)));
+    by_connections.sort_by_key(|b| std::cmp::Reverse(b.2 + b.3));
 
     let mut seen: HashSet<String> = HashSet::new();
     let mut top: Vec<String> = Vec::new();
@@ -495,6 +725,344 @@ pub fn map(graph: &CodeGraph, scope: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// One-shot project summary for first contact.
+///
+/// Format:
+/// lang: Rust(42) Python(3)
+/// entry: cli.rs:main ...
+/// hot: engine.rs:search(12) ...
+/// api: GET /api/users ...
+/// deps: serde tokio ...
+/// tree: src/ (3) src/graph/ (6) ...
+pub fn summary(graph: &CodeGraph) -> Result<()> {
+    let summary = graph.project_summary();
+
+    let short = |file: &Path| {
+        file.file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let langs: Vec<String> = summary
+        .languages
+        .iter()
+        .map(|l| format!("{}({})", l.language, l.files))
+        .collect();
+    println!("lang: {}", langs.join(" "));
+
+    let entries: Vec<String> = summary
+        .entry_points
+        .iter()
+        .map(|e| format!("{}:{}", short(&e.file), e.name))
+        .collect();
+    if !entries.is_empty() {
+        println!("entry: {}", entries.join(" "));
+    }
+
+    let hot: Vec<String> = summary
+        .hotspots
+        .iter()
+        .map(|h| format!("{}:{}({})", short(&h.file), h.name, h.connections))
+        .collect();
+    if !hot.is_empty() {
+        println!("hot: {}", hot.join(" "));
+    }
+
+    if !summary.endpoints.is_empty() {
+        println!("api: {}", summary.endpoints.join(", "));
+    }
+    if !summary.external_dependencies.is_empty() {
+        println!("deps: {}", summary.external_dependencies.join(" "));
+    }
+    if !summary.directories.is_empty() {
+        println!("tree: {}", summary.directories.join(" "));
+    }
+
+    Ok(())
+}
+
+/// Check frontend API calls against backend route definitions.
+///
+/// Format:
+/// DEAD GET /api/missing client.ts:7 (loadMissing)
+/// UNUSED DELETE /api/users/:param routes.js:3
+pub fn api_check(graph: &CodeGraph) -> Result<()> {
+    use crate::graph::MismatchKind;
+
+    let mismatches = graph.endpoint_mismatches();
+    if mismatches.is_empty() {
+        println!("endpoints:{} mismatches:0", graph.endpoints().count());
+        return Ok(());
+    }
+
+    for m in &mismatches {
+        let label = match m.kind {
+            MismatchKind::DeadCall => "DEAD",
+            MismatchKind::UnusedEndpoint => "UNUSED",
+        };
+        let file_name = m
+            .file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| m.file.to_string_lossy().to_string());
+        let scope = m
+            .scope
+            .as_ref()
+            .map(|s| format!(" ({})", s))
+            .unwrap_or_default();
+
+        println!(
+            "{} {} {} {}:{}{}",
+            label,
+            m.method.as_deref().unwrap_or("*"),
+            m.url,
+            file_name,
+            m.line,
+            scope
+        );
+    }
+
+    let dead = mismatches
+        .iter()
+        .filter(|m| m.kind == MismatchKind::DeadCall)
+        .count();
+    println!("dead:{} unused:{}", dead, mismatches.len() - dead);
+
+    Ok(())
+}
+
+/// Symbols coupled to `symbol` through git history.
+pub fn cochange(graph: &CodeGraph, symbol: &str, limit: usize) -> Result<()> {
+    let related = graph.cochange(symbol, limit);
+    if related.is_empty() {
+        println!("no co-change history for {}", symbol);
+        return Ok(());
+    }
+
+    for c in &related {
+        let file_name = c
+            .file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| c.file.to_string_lossy().to_string());
+        println!(
+            "{} {} {}:{} ({} commits)",
+            c.symbol,
+            short_kind(&c.kind.to_string()),
+            file_name,
+            c.line,
+            c.shared_commits
+        );
+    }
+
+    Ok(())
+}
+
+/// Print the symbols touched by uncommitted changes as JSON for hooks
+pub fn precommit(graph: &CodeGraph, root: &Path) -> Result<()> {
+    let changes = graph.uncommitted_symbols(root);
+    println!("{}", serde_json::to_string_pretty(&changes)?);
+    Ok(())
+}
+
+/// Print public functions no test calls, most depended-on first
+pub fn untested(graph: &CodeGraph, limit: usize, skip_accessors: bool) -> Result<()> {
+    let untested = graph.untested_symbols(skip_accessors);
+    if untested.is_empty() {
+        println!("every public function is called by a test");
+        return Ok(());
+    }
+
+    for u in untested.iter().take(limit) {
+        let file_name = u
+            .file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| u.file.to_string_lossy().to_string());
+        println!(
+            "{} {} {}:{} ({} inbound)",
+            u.symbol,
+            short_kind(&u.kind.to_string()),
+            file_name,
+            u.line,
+            u.inbound
+        );
+    }
+    if untested.len() > limit {
+        println!("... {} more", untested.len() - limit);
+    }
+    Ok(())
+}
+
+/// Print unreferenced functions and methods as `file:line name kind`
+pub fn dead_code(graph: &CodeGraph) -> Result<()> {
+    let unreferenced = graph.unreferenced_symbols();
+    if unreferenced.is_empty() {
+        println!("no unreferenced symbols");
+        return Ok(());
+    }
+
+    for u in &unreferenced {
+        println!(
+            "{}:{} {} {}",
+            u.file.display(),
+            u.line,
+            u.symbol,
+            short_kind(&u.kind.to_string())
+        );
+    }
+    Ok(())
+}
+
+/// Print the CODEOWNERS owners of a file or symbol, one per line
+pub fn owners(graph: &CodeGraph, path: &str) -> Result<()> {
+    let owners = graph.owners(path);
+    if owners.is_empty() {
+        println!("no owners for {}", path);
+    }
+    for owner in &owners {
+        println!("{}", owner);
+    }
+    Ok(())
+}
+
+/// Import edits for moving a file, as diff hunks (or JSON)
+pub fn move_file(graph: &CodeGraph, from: &str, to: &str, json: bool) -> Result<()> {
+    let edits = graph.move_file_edits(Path::new(from), Path::new(to));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&edits)?);
+    } else if edits.is_empty() {
+        println!("no imports of {} to update", from);
+    } else {
+        println!("{}", super::diff::format_edits(&edits, super::colors::enabled()));
+    }
+    Ok(())
+}
+
+/// Last commit to change a symbol
+pub fn blame(graph: &CodeGraph, symbol: &str) -> Result<()> {
+    match graph.blame(symbol) {
+        Some(info) => {
+            let date = chrono::DateTime::from_timestamp(info.date, 0)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            let commit = info.commit.get(..8).unwrap_or(&info.commit);
+            println!("{} {} {}", commit, date, info.author);
+        }
+        None => println!("no blame for {} (not found, uncommitted, or not a git repo)", symbol),
+    }
+    Ok(())
+}
+
+/// Every saved version of a symbol, oldest first.
+pub fn history(graph: &CodeGraph, symbol: &str) -> Result<()> {
+    let versions = graph.symbol_history(symbol);
+    if versions.is_empty() {
+        println!("no history for {}: symbol not found", symbol);
+        return Ok(());
+    }
+
+    for version in versions {
+        let time = chrono::DateTime::from_timestamp_millis(version.timestamp)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        println!(
+            "--- {} {}:{}-{}",
+            time,
+            version.file.display(),
+            version.line_start,
+            version.line_end
+        );
+        println!("{}", version.code);
+    }
+    Ok(())
+}
+
+/// Dependents above, the symbol in the middle, dependencies below, drawn
+/// as a box graph. `json` prints the raw dependency lists instead.
+pub fn deps(source: GraphSource, symbol: &str, json: bool) -> Result<()> {
+    let response = source.dependencies(symbol)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+
+    // Structure edges (file contains symbol) are noise in the picture
+    let names = |deps: &[DependencyInfo]| -> Vec<String> {
+        deps.iter()
+            .filter(|d| !matches!(d.relationship, EdgeKind::Defines | EdgeKind::Contains))
+            .filter(|d| d.kind != NodeKind::File)
+            .map(|d| d.symbol.clone())
+            .collect()
+    };
+    let dependents = names(&response.dependents);
+    let dependencies = names(&response.dependencies);
+    if dependents.is_empty() && dependencies.is_empty() {
+        println!("no dependents or dependencies for {}", symbol);
+        return Ok(());
+    }
+
+    for line in graph_view::layout_dependencies(symbol, &dependents, &dependencies) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Blast radius of changing `symbol`: its dependents up to `depth` hops
+/// away, each prefixed with its distance, nearest first.
+pub fn impact(graph: &CodeGraph, symbol: &str, depth: usize) -> Result<()> {
+    let dependents = graph.transitive_dependents(symbol, depth);
+    if dependents.is_empty() {
+        println!("nothing depends on {}", symbol);
+        return Ok(());
+    }
+
+    for (dep, distance) in &dependents {
+        let file_name = dep
+            .file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| dep.file.to_string_lossy().to_string());
+        println!(
+            "{} {} {} {}:{} ({})",
+            distance,
+            dep.symbol,
+            short_kind(&dep.kind.to_string()),
+            file_name,
+            dep.line,
+            dep.relationship
+        );
+    }
+    println!("{} dependents within {} hops", dependents.len(), depth);
+    Ok(())
+}
+
+/// The shortest call chain from `from` to `to`, one hop per line.
+pub fn call_path(graph: &CodeGraph, from: &str, to: &str) -> Result<()> {
+    let Some(path) = graph.call_path(from, to) else {
+        println!("no call path from {} to {}", from, to);
+        return Ok(());
+    };
+
+    for (i, step) in path.iter().enumerate() {
+        let file_name = step
+            .file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| step.file.to_string_lossy().to_string());
+        let arrow = if i == 0 { "  " } else { "→ " };
+        println!(
+            "{}{} {} {}:{}",
+            arrow,
+            step.symbol,
+            short_kind(&step.kind.to_string()),
+            file_name,
+            step.line_start
+        );
+    }
+    Ok(())
+}
+
 /// Short kind abbreviation
 fn short_kind(kind: &str) -> &str {
     match kind {
@@ -510,6 +1078,32 @@ fn short_kind(kind: &str) -> &str {
         "type" => "ty",
         "variable" => "v",
         "impl" => "impl",
+        "component" => "cmp",
+        "macro" => "mac",
+        "field" => "fd",
         _ => kind,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_corrupt_cache_is_rebuilt() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn alpha() {}\n").unwrap();
+        let cache_path = dir.path().join(".anchor/graph.bin");
+        std::fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        std::fs::write(&cache_path, b"not a graph").unwrap();
+        assert!(CodeGraph::load(&cache_path).is_err());
+
+        let graph = load_or_build_graph(dir.path(), &cache_path).unwrap();
+        assert_eq!(graph.search("alpha", 1).len(), 1);
+
+        // The bad file was replaced with a working cache
+        let reloaded = CodeGraph::load(&cache_path).unwrap();
+        assert_eq!(reloaded.stats().symbol_count, graph.stats().symbol_count);
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::graph::{CodeGraphBuilder, NodeKind, SymbolFilter};
+
 /// Top-level Anchor configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnchorConfig {
@@ -12,6 +14,10 @@ pub struct AnchorConfig {
     pub project: ProjectConfig,
     #[serde(default)]
     pub graph: GraphConfig,
+    #[serde(default)]
+    pub query: QueryConfig,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
 }
 
 /// Project-level settings.
@@ -34,6 +40,45 @@ pub struct GraphConfig {
     /// Maximum lines in a code snippet.
     #[serde(default = "default_max_snippet_lines")]
     pub max_snippet_lines: usize,
+    /// Extra paths to index even though .gitignore excludes them
+    /// (e.g. one vendored dependency), relative to the project root.
+    #[serde(default)]
+    pub also_index: Vec<PathBuf>,
+    /// Gitignore-style patterns of generated or vendored files, e.g.
+    /// `["*.pb.rs", "generated/"]`. They are indexed but tagged `generated`.
+    #[serde(default)]
+    pub generated: Vec<String>,
+    /// Symbol kinds to index, e.g. `["function", "method", "struct"]`.
+    /// Empty (the default) indexes every kind.
+    #[serde(default)]
+    pub index_kinds: Vec<NodeKind>,
+}
+
+/// Default filters for search and context queries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryConfig {
+    /// Include test functions and test files in results.
+    #[serde(default)]
+    pub include_tests: bool,
+    /// Include private/crate-visible symbols in results.
+    #[serde(default = "default_include_private")]
+    pub include_private: bool,
+    /// Include generated and vendored code in results.
+    #[serde(default = "default_include_generated")]
+    pub include_generated: bool,
+}
+
+/// Daemon access control.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    /// Write a random token to `.anchor/token` on start and reject
+    /// requests that don't carry it.
+    #[serde(default)]
+    pub require_token: bool,
+    /// With `require_token`, still answer read requests without a token.
+    /// Writes and shutdown always need it.
+    #[serde(default)]
+    pub allow_unauthenticated_reads: bool,
 }
 
 fn default_root() -> String {
@@ -57,6 +102,14 @@ fn default_max_snippet_lines() -> usize {
     10
 }
 
+fn default_include_private() -> bool {
+    true
+}
+
+fn default_include_generated() -> bool {
+    true
+}
+
 impl Default for ProjectConfig {
     fn default() -> Self {
         Self {
@@ -71,6 +124,31 @@ impl Default for GraphConfig {
         Self {
             cache_path: default_cache_path(),
             max_snippet_lines: default_max_snippet_lines(),
+            also_index: Vec::new(),
+            generated: Vec::new(),
+            index_kinds: Vec::new(),
+        }
+    }
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self {
+            include_tests: false,
+            include_private: default_include_private(),
+            include_generated: default_include_generated(),
+        }
+    }
+}
+
+impl QueryConfig {
+    /// The symbol filter queries use unless they override it.
+    pub fn symbol_filter(&self) -> SymbolFilter {
+        SymbolFilter {
+            include_tests: self.include_tests,
+            include_private: self.include_private,
+            include_generated: self.include_generated,
+            async_only: false,
         }
     }
 }
@@ -84,12 +162,32 @@ impl AnchorConfig {
         }
     }
 
+    /// The configuration of the project at `root`: its
+    /// `.anchor/config.toml`, or defaults.
+    pub fn for_project(root: &Path) -> Self {
+        Self::load(&root.join(".anchor/config.toml"))
+    }
+
     /// Resolve the project root relative to the config file's parent directory.
     pub fn resolve_root(&self, anchor_dir: &Path) -> PathBuf {
         let parent = anchor_dir.parent().unwrap_or(anchor_dir);
         parent.join(&self.project.root)
     }
 
+    /// A graph builder for `root` with the configured build settings.
+    pub fn graph_builder(&self, root: &Path) -> CodeGraphBuilder {
+        let builder = self
+            .graph
+            .also_index
+            .iter()
+            .fold(CodeGraphBuilder::new(root), |builder, path| builder.also_index(path))
+            .index_kinds(&self.graph.index_kinds);
+        self.graph
+            .generated
+            .iter()
+            .fold(builder, |builder, pattern| builder.generated(pattern))
+    }
+
     /// Resolve the graph cache path relative to the anchor directory's parent.
     pub fn resolve_cache_path(&self, anchor_dir: &Path) -> PathBuf {
         let parent = anchor_dir.parent().unwrap_or(anchor_dir);
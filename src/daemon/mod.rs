@@ -1,8 +1,9 @@
 //! Daemon module — background process for real-time graph updates.
 //!
 //! The daemon keeps the code graph in memory, watches for file changes,
-//! and serves queries over a Unix socket. This enables instant queries
-//! without loading the graph from disk on every CLI command.
+//! and serves queries over a local socket (a Unix socket, or a named pipe
+//! on Windows), or a TCP address for clients that can't reach one. This enables instant queries without loading the
+//! graph from disk on every CLI command.
 //!
 //! ## Architecture
 //!
@@ -26,6 +27,11 @@
 
 pub mod protocol;
 pub mod server;
+pub mod transport;
 
-pub use protocol::{Request, Response};
-pub use server::{is_daemon_running, send_request, socket_path, start_daemon};
+pub use protocol::{Envelope, Request, Response};
+pub use server::{
+    is_daemon_running, read_token, send_request, send_request_streaming, socket_path,
+    start_daemon, start_daemon_tcp, token_path,
+};
+pub use transport::Transport;
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::query::Query;
+
 /// Request from CLI to daemon.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "command")]
@@ -9,16 +11,52 @@ pub enum Request {
     // ─── Read Operations ───────────────────────────────────────
     /// Search for symbols/files
     #[serde(rename = "search")]
-    Search { query: String, depth: usize },
+    Search {
+        query: String,
+        depth: usize,
+        /// Follow at most this many edges per node
+        #[serde(default)]
+        max_fanout: Option<usize>,
+        /// Treat `query` as a regex over symbol names
+        #[serde(default)]
+        regex: bool,
+    },
+
+    /// Search for several symbols at once; one response per query, in order
+    #[serde(rename = "search_batch")]
+    SearchBatch { queries: Vec<Query> },
 
     /// Get full context for a symbol
     #[serde(rename = "context")]
     Context { query: String, intent: String },
 
+    /// Run a GraphQL query against the in-memory graph
+    #[serde(rename = "graphql")]
+    Graphql { query: String },
+
     /// Get dependencies for a symbol
     #[serde(rename = "deps")]
     Deps { symbol: String },
 
+    /// Get the innermost symbol containing a file position
+    #[serde(rename = "symbol_at")]
+    SymbolAt { path: String, line: usize },
+
+    /// Get circular dependencies between symbols and files
+    #[serde(rename = "cycles")]
+    Cycles,
+
+    /// List detected API route definitions and client calls
+    #[serde(rename = "endpoints")]
+    Endpoints {
+        /// Only this HTTP method
+        #[serde(default)]
+        method: Option<String>,
+        /// Only URLs whose normalized path starts with this
+        #[serde(default)]
+        prefix: Option<String>,
+    },
+
     /// Get graph statistics
     #[serde(rename = "stats")]
     Stats,
@@ -48,6 +86,11 @@ pub enum Request {
         new: String,
     },
 
+    /// Rename a definition, named by qualified name (`Order::save`) or
+    /// `path:line`, at the uses and imports that resolve to it (with locks)
+    #[serde(rename = "rename")]
+    Rename { symbol: String, new_name: String },
+
     // ─── Lock Management ───────────────────────────────────────
     /// Check lock status for a file
     #[serde(rename = "lock_status")]
@@ -62,6 +105,14 @@ pub enum Request {
     #[serde(rename = "rebuild")]
     Rebuild,
 
+    /// Force rebuild, sending `Response::Progress` frames while parsing
+    #[serde(rename = "rebuild_stream")]
+    RebuildStream,
+
+    /// Uptime, graph stats, watcher backlog and lock count
+    #[serde(rename = "health")]
+    Health,
+
     /// Check if daemon is alive
     #[serde(rename = "ping")]
     Ping,
@@ -71,6 +122,53 @@ pub enum Request {
     Shutdown,
 }
 
+impl Request {
+    /// Does this request change files, the graph or stop the daemon?
+    /// These always need the token when the daemon requires one.
+    pub fn is_write(&self) -> bool {
+        match self {
+            Request::Graphql { query } => graphql_mutates(query),
+            request => matches!(
+                request,
+                Request::Create { .. }
+                    | Request::Insert { .. }
+                    | Request::Replace { .. }
+                    | Request::Rename { .. }
+                    | Request::Rebuild
+                    | Request::RebuildStream
+                    | Request::Shutdown
+            ),
+        }
+    }
+}
+
+/// Does a GraphQL document hold a mutation? A document that doesn't
+/// parse counts as one, so it can't slip past the token.
+fn graphql_mutates(query: &str) -> bool {
+    use async_graphql::parser::types::OperationType;
+    match async_graphql::parser::parse_query(query) {
+        Ok(document) => document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation),
+        Err(_) => true,
+    }
+}
+
+/// A request as sent on the wire: the command plus the daemon's token,
+/// if it requires one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Send long arrays in the result as `Response::Chunk` frames ahead of
+    /// the final response instead of in one line
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub chunked: bool,
+    #[serde(flatten)]
+    pub request: Request,
+}
+
 /// Response from daemon to CLI.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "status")]
@@ -83,6 +181,20 @@ pub enum Response {
     #[serde(rename = "error")]
     Error { message: String },
 
+    /// Intermediate progress frame; more frames follow
+    #[serde(rename = "progress")]
+    Progress { parsed: usize, total: usize },
+
+    /// Part of a long array in the final `Ok` data: the top-level array
+    /// itself when `field` is `None`, else that top-level field. More
+    /// frames follow, and the array in the final response holds the rest
+    #[serde(rename = "chunk")]
+    Chunk {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        items: Vec<serde_json::Value>,
+    },
+
     /// Pong response (daemon is alive)
     #[serde(rename = "pong")]
     Pong,
@@ -1,23 +1,33 @@
-//! Daemon server — Unix socket server that handles CLI requests.
+//! Daemon server — local socket or TCP server that handles CLI requests.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use std::io::{BufRead, BufReader, Write};
-use std::os::unix::net::{UnixListener, UnixStream};
+use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
+use std::time::Instant;
 use tracing::{debug, error, info, warn};
 
+use crate::config::AnchorConfig;
 use crate::graph::engine::CodeGraph;
+use crate::graph::{snapshot_before_write, snapshot_if, BuildProgress, ProgressSink, RenameTarget, SymbolFilter};
+use crate::graphql::{build_live_schema, execute, AnchorSchema};
 use crate::lock::{LockManager, LockStatus};
-use crate::watcher::{start_watching, WatcherHandle};
+use crate::query::{anchor_search_batch_filtered, get_context_filtered};
+use crate::watcher::{start_watching, WatcherHandle, WatcherStats};
 use crate::write;
-use crate::{anchor_dependencies, anchor_stats, build_graph, get_context, graph_search};
+use crate::{
+    anchor_dependencies, anchor_stats, graph_search, graph_search_regex, graph_search_with_fanout,
+};
 
-use super::protocol::{Request, Response};
+use super::protocol::{Envelope, Request, Response};
+use super::transport::{process_exists, Connection, Local, LocalSocket, Transport};
 
-/// Default socket path (in project's .anchor directory)
+/// Default socket path (in project's .anchor directory). On Windows the
+/// named pipe is derived from it.
 pub fn socket_path(root: &Path) -> PathBuf {
     root.join(".anchor").join("anchor.sock")
 }
@@ -27,120 +37,410 @@ pub fn pid_path(root: &Path) -> PathBuf {
     root.join(".anchor").join("daemon.pid")
 }
 
-/// Start the daemon server.
-pub fn start_daemon(root: &Path) -> Result<()> {
-    let root = root.canonicalize()?;
-    let sock_path = socket_path(&root);
-    let pid_file = pid_path(&root);
+/// Token file path; present only while a daemon that requires it runs.
+pub fn token_path(root: &Path) -> PathBuf {
+    root.join(".anchor").join("token")
+}
 
-    // Ensure .anchor directory exists
-    std::fs::create_dir_all(sock_path.parent().unwrap())?;
+/// Read the running daemon's token, if it requires one.
+pub fn read_token(root: &Path) -> Option<String> {
+    std::fs::read_to_string(token_path(root))
+        .ok()
+        .map(|token| token.trim().to_string())
+}
 
-    // Remove stale socket if exists
-    if sock_path.exists() {
-        std::fs::remove_file(&sock_path)?;
-    }
+/// Which requests a connection may make without the token.
+struct Auth {
+    token: Option<String>,
+    allow_unauthenticated_reads: bool,
+}
 
-    // Write PID file
-    std::fs::write(&pid_file, std::process::id().to_string())?;
+impl Auth {
+    /// Set up access control from `.anchor/config.toml`, writing a fresh
+    /// token when one is required.
+    fn init(root: &Path) -> Result<Self> {
+        let config = AnchorConfig::for_project(root).daemon;
+        let path = token_path(root);
+        // A token left by a crashed daemon must not outlive it
+        let _ = std::fs::remove_file(&path);
 
-    // Build initial graph
-    info!(root = %root.display(), "building initial graph");
-    let graph = build_graph(&root);
-    let graph = Arc::new(RwLock::new(graph));
+        let token = if config.require_token {
+            let token = uuid::Uuid::new_v4().simple().to_string();
+            write_private(&path, &token)?;
+            info!(path = %path.display(), "daemon requires a token");
+            Some(token)
+        } else {
+            None
+        };
 
-    // Create lock manager
-    let lock_manager = Arc::new(LockManager::new());
-    info!("lock manager initialized");
+        Ok(Self {
+            token,
+            allow_unauthenticated_reads: config.allow_unauthenticated_reads,
+        })
+    }
 
-    // Start file watcher
-    let _watcher: Option<WatcherHandle> = match start_watching(&root, Arc::clone(&graph), 200) {
-        Ok(handle) => {
-            info!("file watcher started");
-            Some(handle)
+    /// Reject a request whose token doesn't match, unless it's a read and
+    /// reads are open.
+    fn check(&self, envelope: &Envelope) -> std::result::Result<(), String> {
+        let Some(expected) = &self.token else {
+            return Ok(());
+        };
+        if self.allow_unauthenticated_reads && !envelope.request.is_write() {
+            return Ok(());
         }
-        Err(e) => {
-            warn!(error = %e, "file watcher failed to start");
-            None
+        match &envelope.token {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+            Some(_) => Err("unauthorized: token mismatch".to_string()),
+            None => Err("unauthorized: this daemon requires a token".to_string()),
         }
-    };
+    }
+}
 
-    // Bind socket
-    let listener = UnixListener::bind(&sock_path)?;
-    info!(socket = %sock_path.display(), "daemon listening");
+/// Compare without returning early, so timing doesn't leak how much of a
+/// guessed token was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Write `contents` to a new file only the owner can read.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// What `Request::Health` reports beyond the graph and locks.
+struct DaemonStatus {
+    started: Instant,
+    last_rebuild: Mutex<DateTime<Utc>>,
+    rebuilds_running: AtomicUsize,
+    watcher: Option<Arc<WatcherStats>>,
+}
 
-    // Shutdown flag
-    let shutdown = Arc::new(AtomicBool::new(false));
+impl DaemonStatus {
+    /// Count a full rebuild as running until the guard drops.
+    fn begin_rebuild(&self) -> RebuildGuard<'_> {
+        self.rebuilds_running.fetch_add(1, Ordering::Relaxed);
+        RebuildGuard(self)
+    }
 
-    // Accept connections
-    for stream in listener.incoming() {
-        if shutdown.load(Ordering::Relaxed) {
-            break;
+    /// Record that the graph was just rebuilt from disk.
+    fn rebuilt(&self) {
+        if let Ok(mut last) = self.last_rebuild.lock() {
+            *last = Utc::now();
         }
+    }
 
-        match stream {
-            Ok(stream) => {
-                let graph = Arc::clone(&graph);
-                let shutdown = Arc::clone(&shutdown);
-                let lock_manager = Arc::clone(&lock_manager);
-                let root = root.clone();
+    fn health(&self, graph: &CodeGraph, lock_manager: &LockManager) -> serde_json::Value {
+        let stats = graph.stats();
+        let last_rebuild = self.last_rebuild.lock().ok().map(|last| *last);
+        let watcher = self.watcher.as_ref();
+        serde_json::json!({
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "files_watched": stats.file_count,
+            "stats": stats,
+            "graph_health": graph.health(),
+            "last_rebuild": last_rebuild,
+            "pending_rebuilds": self.rebuilds_running.load(Ordering::Relaxed),
+            "watching": watcher.is_some(),
+            "watcher_backlog": watcher.map_or(0, |w| w.backlog()),
+            "files_updated": watcher.map_or(0, |w| w.files_updated()),
+            "last_update": watcher.and_then(|w| w.last_update()),
+            "active_locks": lock_manager.active_locks().len()
+        })
+    }
+}
 
-                thread::spawn(move || {
-                    if let Err(e) = handle_client(stream, &graph, &lock_manager, &shutdown, &root) {
-                        debug!(error = %e, "client handler error");
-                    }
-                });
+/// A full rebuild in progress; see `DaemonStatus::begin_rebuild`.
+struct RebuildGuard<'a>(&'a DaemonStatus);
+
+impl Drop for RebuildGuard<'_> {
+    fn drop(&mut self) {
+        self.0.rebuilds_running.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// State shared by every connection the daemon serves.
+struct Shared {
+    root: PathBuf,
+    graph: Arc<RwLock<CodeGraph>>,
+    schema: AnchorSchema,
+    lock_manager: LockManager,
+    auth: Auth,
+    status: DaemonStatus,
+    /// Filters for search and context requests that don't set their own,
+    /// from `[query]` in the project config.
+    query_defaults: SymbolFilter,
+    shutdown: AtomicBool,
+}
+
+/// A running daemon: the shared state plus the watcher keeping it fresh.
+struct Daemon {
+    shared: Arc<Shared>,
+    _watcher: Option<WatcherHandle>,
+}
+
+impl Daemon {
+    /// Write the PID and token files, build the graph and start watching
+    /// `root`.
+    fn start(root: &Path) -> Result<Self> {
+        // Ensure .anchor directory exists
+        std::fs::create_dir_all(root.join(".anchor"))?;
+
+        // Write PID file
+        std::fs::write(pid_path(root), std::process::id().to_string())?;
+        let auth = Auth::init(root)?;
+        let started = Instant::now();
+
+        // Build initial graph
+        info!(root = %root.display(), "building initial graph");
+        let config = AnchorConfig::for_project(root);
+        let graph = config.graph_builder(root).build();
+        let graph = Arc::new(RwLock::new(graph));
+        let last_rebuild = Utc::now();
+
+        // Create lock manager
+        let lock_manager = LockManager::new();
+        info!("lock manager initialized");
+
+        // Start file watcher
+        let watcher = match start_watching(root, Arc::clone(&graph), 200) {
+            Ok(handle) => {
+                info!("file watcher started");
+                Some(handle)
             }
             Err(e) => {
-                error!(error = %e, "accept error");
+                warn!(error = %e, "file watcher failed to start");
+                None
+            }
+        };
+
+        let status = DaemonStatus {
+            started,
+            last_rebuild: Mutex::new(last_rebuild),
+            rebuilds_running: AtomicUsize::new(0),
+            watcher: watcher.as_ref().map(WatcherHandle::stats),
+        };
+
+        let shared = Shared {
+            root: root.to_path_buf(),
+            schema: build_live_schema(Arc::clone(&graph), root.to_path_buf()),
+            graph,
+            lock_manager,
+            auth,
+            status,
+            query_defaults: config.query.symbol_filter(),
+            shutdown: AtomicBool::new(false),
+        };
+        Ok(Self {
+            shared: Arc::new(shared),
+            _watcher: watcher,
+        })
+    }
+
+    /// Accept connections until a client asks the daemon to shut down.
+    fn serve<S: Connection>(&self, incoming: impl Iterator<Item = std::io::Result<S>>) {
+        for stream in incoming {
+            if self.shared.shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+
+            match stream {
+                Ok(stream) => {
+                    let shared = Arc::clone(&self.shared);
+                    thread::spawn(move || {
+                        if let Err(e) = handle_client(stream, &shared) {
+                            debug!(error = %e, "client handler error");
+                        }
+                    });
+                }
+                Err(e) => {
+                    error!(error = %e, "accept error");
+                }
             }
         }
+
+        info!("daemon shutting down");
+        let _ = std::fs::remove_file(pid_path(&self.shared.root));
+        let _ = std::fs::remove_file(token_path(&self.shared.root));
     }
+}
+
+/// Start the daemon server on the project's local socket.
+pub fn start_daemon(root: &Path) -> Result<()> {
+    let root = root.canonicalize()?;
+    let sock_path = socket_path(&root);
+
+    let daemon = Daemon::start(&root)?;
+
+    // Bind socket
+    let listener = <Local as LocalSocket>::bind(&sock_path)?;
+    info!(socket = %sock_path.display(), "daemon listening");
+
+    daemon.serve(std::iter::repeat_with(|| LocalSocket::accept(&listener)));
+    <Local as LocalSocket>::cleanup(&sock_path);
+
+    Ok(())
+}
+
+/// Start the daemon server on a TCP address instead of the Unix socket.
+///
+/// Speaks the same newline-delimited JSON protocol, so it can serve
+/// clients on Windows or in another container. Anyone who can reach the
+/// address can talk to it, so set `require_token` under `[daemon]` in
+/// `.anchor/config.toml`: every request must then carry the token the
+/// daemon writes to `.anchor/token` (reads excepted with
+/// `allow_unauthenticated_reads`).
+pub fn start_daemon_tcp(root: &Path, addr: SocketAddr) -> Result<()> {
+    let root = root.canonicalize()?;
+    let daemon = Daemon::start(&root)?;
+
+    let listener = TcpListener::bind(addr)?;
+    info!(addr = %listener.local_addr()?, "daemon listening");
 
-    // Cleanup
-    info!("daemon shutting down");
-    let _ = std::fs::remove_file(&sock_path);
-    let _ = std::fs::remove_file(&pid_file);
+    daemon.serve(listener.incoming());
 
     Ok(())
 }
 
 /// Handle a single client connection.
-fn handle_client(
-    stream: UnixStream,
-    graph: &Arc<RwLock<CodeGraph>>,
-    lock_manager: &Arc<LockManager>,
-    shutdown: &Arc<AtomicBool>,
-    root: &Path,
-) -> Result<()> {
+fn handle_client<S: Connection>(stream: S, shared: &Shared) -> Result<()> {
     let mut reader = BufReader::new(stream.try_clone()?);
     let mut writer = stream;
 
     let mut line = String::new();
     reader.read_line(&mut line)?;
 
-    let request: Request = serde_json::from_str(&line)?;
-    debug!(?request, "received request");
+    let envelope: Envelope = serde_json::from_str(&line)?;
+    debug!(request = ?envelope.request, "received request");
 
-    let response = process_request(request, graph, lock_manager, shutdown, root);
+    let response = match shared.auth.check(&envelope) {
+        Err(message) => {
+            warn!(%message, "rejected request");
+            Response::error(message)
+        }
+        Ok(()) => match envelope.request {
+            Request::RebuildStream => rebuild_streaming(shared, &writer)?,
+            request => process_request(request, shared),
+        },
+    };
+
+    write_response(&mut writer, response, envelope.chunked)
+}
+
+/// Items per `Response::Chunk` frame.
+const CHUNK_SIZE: usize = 500;
+
+/// Write the final response. For a chunked client, each array in `Ok` data
+/// longer than `CHUNK_SIZE` (the data itself, or a top-level field) is
+/// first sent as `Response::Chunk` frames and left empty in the final line.
+fn write_response(writer: &mut impl Write, response: Response, chunked: bool) -> Result<()> {
+    let response = match response {
+        Response::Ok { mut data } if chunked => {
+            match &mut data {
+                serde_json::Value::Array(items) if items.len() > CHUNK_SIZE => {
+                    write_chunks(writer, None, std::mem::take(items))?;
+                }
+                serde_json::Value::Object(fields) => {
+                    for (field, value) in fields.iter_mut() {
+                        if let serde_json::Value::Array(items) = value {
+                            if items.len() > CHUNK_SIZE {
+                                let items = std::mem::take(items);
+                                write_chunks(writer, Some(field.as_str()), items)?;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            Response::Ok { data }
+        }
+        response => response,
+    };
 
     let response_json = serde_json::to_string(&response)?;
     writeln!(writer, "{}", response_json)?;
-
     Ok(())
 }
 
+/// Send `items` as `Response::Chunk` frames of `CHUNK_SIZE`.
+fn write_chunks(
+    writer: &mut impl Write,
+    field: Option<&str>,
+    items: Vec<serde_json::Value>,
+) -> Result<()> {
+    let mut items = items.into_iter();
+    loop {
+        let chunk: Vec<_> = items.by_ref().take(CHUNK_SIZE).collect();
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        let frame = Response::Chunk {
+            field: field.map(str::to_string),
+            items: chunk,
+        };
+        writeln!(writer, "{}", serde_json::to_string(&frame)?)?;
+    }
+}
+
+/// Rebuild the graph, writing a `Response::Progress` line per progress
+/// update before returning the final response.
+///
+/// Frames use the same newline-delimited JSON as every other response, so
+/// a client reads lines until one isn't a progress frame.
+fn rebuild_streaming<S: Connection>(shared: &Shared, writer: &S) -> Result<Response> {
+    let Shared { graph, status, root, .. } = shared;
+    let frames = std::sync::Mutex::new(writer.try_clone()?);
+    let sink = ProgressSink::new(move |p: BuildProgress| {
+        let frame = Response::Progress {
+            parsed: p.parsed,
+            total: p.total,
+        };
+        if let (Ok(mut out), Ok(json)) = (frames.lock(), serde_json::to_string(&frame)) {
+            // A client that hung up still gets the graph rebuilt
+            let _ = writeln!(out, "{}", json);
+        }
+    });
+
+    let _rebuild = status.begin_rebuild();
+    let new_graph = AnchorConfig::for_project(root).graph_builder(root).on_progress(sink).build();
+    let mut g = match graph.write() {
+        Ok(g) => g,
+        Err(e) => return Ok(Response::error(format!("lock error: {}", e))),
+    };
+    *g = new_graph;
+    status.rebuilt();
+    Ok(Response::ok(serde_json::json!({
+        "message": "graph rebuilt",
+        "stats": g.stats()
+    })))
+}
+
 /// Process a request and return a response.
-fn process_request(
-    request: Request,
-    graph: &Arc<RwLock<CodeGraph>>,
-    lock_manager: &Arc<LockManager>,
-    shutdown: &Arc<AtomicBool>,
-    root: &Path,
-) -> Response {
+fn process_request(request: Request, shared: &Shared) -> Response {
+    let Shared {
+        root,
+        graph,
+        schema,
+        lock_manager,
+        status,
+        query_defaults,
+        shutdown,
+        ..
+    } = shared;
     match request {
         Request::Ping => Response::Pong,
 
+        Request::Health => {
+            let g = match graph.read() {
+                Ok(g) => g,
+                Err(e) => return Response::error(format!("lock error: {}", e)),
+            };
+            Response::ok(status.health(&g, lock_manager))
+        }
+
         Request::Shutdown => {
             shutdown.store(true, Ordering::Relaxed);
             Response::Goodbye
@@ -156,13 +456,26 @@ fn process_request(
             Response::ok(result)
         }
 
-        Request::Search { query, depth } => {
+        Request::Search { query, depth, max_fanout, regex } => {
             let g = match graph.read() {
                 Ok(g) => g,
                 Err(e) => return Response::error(format!("lock error: {}", e)),
             };
-            let result = graph_search(&g, &query, depth);
-            Response::ok(result)
+            if !regex {
+                return Response::ok(graph_search_with_fanout(&g, &query, depth, max_fanout));
+            }
+            match graph_search_regex(&g, &query, depth, max_fanout) {
+                Ok(result) => Response::ok(result),
+                Err(e) => Response::error(e.to_string()),
+            }
+        }
+
+        Request::SearchBatch { queries } => {
+            let g = match graph.read() {
+                Ok(g) => g,
+                Err(e) => return Response::error(format!("lock error: {}", e)),
+            };
+            Response::ok(anchor_search_batch_filtered(&g, queries, query_defaults))
         }
 
         Request::Context { query, intent } => {
@@ -170,7 +483,7 @@ fn process_request(
                 Ok(g) => g,
                 Err(e) => return Response::error(format!("lock error: {}", e)),
             };
-            let result = get_context(&g, &query, &intent);
+            let result = get_context_filtered(&g, &query, &intent, None, query_defaults);
             Response::ok(result)
         }
 
@@ -183,6 +496,44 @@ fn process_request(
             Response::ok(result)
         }
 
+        Request::Graphql { query } => {
+            let result = match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime.block_on(execute(schema, &query)),
+                Err(e) => return Response::error(format!("runtime error: {}", e)),
+            };
+            match serde_json::from_str::<serde_json::Value>(&result) {
+                Ok(json) => Response::ok(json),
+                Err(e) => Response::error(format!("graphql error: {}", e)),
+            }
+        }
+
+        Request::SymbolAt { path, line } => {
+            let g = match graph.read() {
+                Ok(g) => g,
+                Err(e) => return Response::error(format!("lock error: {}", e)),
+            };
+            match g.symbol_at(&root.join(&path), line) {
+                Some(symbol) => Response::ok(symbol),
+                None => Response::error(format!("no symbol at {}:{}", path, line)),
+            }
+        }
+
+        Request::Cycles => {
+            let g = match graph.read() {
+                Ok(g) => g,
+                Err(e) => return Response::error(format!("lock error: {}", e)),
+            };
+            Response::ok(g.find_cycles())
+        }
+
+        Request::Endpoints { method, prefix } => {
+            let g = match graph.read() {
+                Ok(g) => g,
+                Err(e) => return Response::error(format!("lock error: {}", e)),
+            };
+            Response::ok(g.endpoints_matching(method.as_deref(), prefix.as_deref()))
+        }
+
         Request::Overview => {
             let g = match graph.read() {
                 Ok(g) => g,
@@ -224,7 +575,10 @@ fn process_request(
                         let _ = std::fs::create_dir_all(parent);
                     }
 
-                    let result = write::create_file(&file_path, &content);
+                    // Overwriting an existing file keeps its old contents
+                    let result = snapshot_before_write(root, &file_path)
+                        .map_err(write::WriteError::from)
+                        .and_then(|_| write::create_file(&file_path, &content));
                     lock_manager.release(&file_path);
 
                     match result {
@@ -264,7 +618,10 @@ fn process_request(
             match lock_result {
                 crate::lock::LockResult::Acquired { dependents, .. }
                 | crate::lock::LockResult::AcquiredAfterWait { dependents, .. } => {
-                    let result = write::insert_after(&file_path, &pattern, &content);
+                    // Without the pattern the insert fails and leaves no version to keep
+                    let result = snapshot_if(root, &file_path, |contents| contents.contains(&pattern))
+                        .map_err(write::WriteError::from)
+                        .and_then(|_| write::insert_after(&file_path, &pattern, &content));
                     lock_manager.release(&file_path);
 
                     match result {
@@ -304,7 +661,9 @@ fn process_request(
             match lock_result {
                 crate::lock::LockResult::Acquired { dependents, .. }
                 | crate::lock::LockResult::AcquiredAfterWait { dependents, .. } => {
-                    let result = write::replace_all(&file_path, &old, &new);
+                    let result = snapshot_if(root, &file_path, |contents| contents.contains(&old))
+                        .map_err(write::WriteError::from)
+                        .and_then(|_| write::replace_all(&file_path, &old, &new));
                     lock_manager.release(&file_path);
 
                     match result {
@@ -327,6 +686,10 @@ fn process_request(
             }
         }
 
+        Request::Rename { symbol, new_name } => {
+            rename(&symbol, &new_name, graph, lock_manager, root)
+        }
+
         // ─── Lock Management ───────────────────────────────────
         Request::LockStatus { path } => {
             let file_path = root.join(&path);
@@ -365,13 +728,16 @@ fn process_request(
         }
 
         // ─── System ────────────────────────────────────────────
-        Request::Rebuild => {
-            let new_graph = build_graph(root);
+        // Streaming needs the client stream; handle_client routes it
+        Request::RebuildStream | Request::Rebuild => {
+            let _rebuild = status.begin_rebuild();
+            let new_graph = AnchorConfig::for_project(root).graph_builder(root).build();
             let mut g = match graph.write() {
                 Ok(g) => g,
                 Err(e) => return Response::error(format!("lock error: {}", e)),
             };
             *g = new_graph;
+            status.rebuilt();
             let stats = g.stats();
             Response::ok(serde_json::json!({
                 "message": "graph rebuilt",
@@ -381,6 +747,90 @@ fn process_request(
     }
 }
 
+/// Rename the definition `target` names (a qualified name or
+/// `path:line`) to `new_name` at every site that resolves to it, locking
+/// each file in turn while it's rewritten.
+///
+/// A file that stays locked past the timeout is skipped and listed under
+/// `failed`, so the caller can retry it.
+fn rename(
+    target: &str,
+    new_name: &str,
+    graph: &RwLock<CodeGraph>,
+    lock_manager: &LockManager,
+    root: &Path,
+) -> Response {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    if !new_name.chars().all(is_ident) || !new_name.starts_with(|c: char| !c.is_ascii_digit()) {
+        return Response::error(format!("not an identifier: {:?}", new_name));
+    }
+
+    let target = match RenameTarget::parse(target) {
+        RenameTarget::At(path, line) => RenameTarget::At(root.join(path), line),
+        target => target,
+    };
+    let plan = match graph.read() {
+        Ok(g) => g.rename_sites(&target),
+        Err(e) => return Response::error(format!("graph lock error: {}", e)),
+    };
+    let plan = match plan {
+        Ok(plan) => plan,
+        Err(e) => return Response::error(e.to_string()),
+    };
+
+    let mut files = Vec::new();
+    let mut failed = Vec::new();
+    for site in &plan.sites {
+        let file_path = root.join(&site.file);
+        let lock_result = match graph.read() {
+            Ok(g) => lock_manager.acquire_with_wait(
+                &file_path,
+                &g,
+                std::time::Duration::from_secs(30),
+            ),
+            Err(e) => return Response::error(format!("graph lock error: {}", e)),
+        };
+
+        match lock_result {
+            crate::lock::LockResult::Acquired { .. }
+            | crate::lock::LockResult::AcquiredAfterWait { .. } => {
+                let result = snapshot_if(root, &file_path, |contents| contents.contains(plan.name.as_str()))
+                    .map_err(write::WriteError::from)
+                    .and_then(|_| write::replace_word(&file_path, &plan.name, new_name, &site.words));
+                lock_manager.release(&file_path);
+
+                match result {
+                    Ok(wr) => files.push(serde_json::json!({
+                        "path": wr.path,
+                        "lines_changed": wr.lines_written,
+                        "replacements": wr.replacements
+                    })),
+                    // The graph's sites held no use of the name, e.g. a stale index
+                    Err(write::WriteError::PatternNotFound(_)) => {}
+                    Err(e) => failed.push(serde_json::json!({
+                        "path": file_path.display().to_string(),
+                        "error": e.to_string()
+                    })),
+                }
+            }
+            crate::lock::LockResult::Blocked { blocked_by, reason } => {
+                failed.push(serde_json::json!({
+                    "path": file_path.display().to_string(),
+                    "error": format!("Blocked by {}: {}", blocked_by.display(), reason)
+                }))
+            }
+        }
+    }
+
+    Response::ok(serde_json::json!({
+        "success": failed.is_empty(),
+        "symbol": plan.qualified_name,
+        "new_name": new_name,
+        "files": files,
+        "failed": failed
+    }))
+}
+
 /// Check if daemon is running by checking PID file and process.
 pub fn is_daemon_running(root: &Path) -> bool {
     let pid_file = pid_path(root);
@@ -391,29 +841,393 @@ pub fn is_daemon_running(root: &Path) -> bool {
 
     // Read PID and check if process is alive
     if let Ok(pid_str) = std::fs::read_to_string(&pid_file) {
-        if let Ok(pid) = pid_str.trim().parse::<i32>() {
-            // Check if process exists (signal 0 = check existence)
-            unsafe {
-                return libc::kill(pid, 0) == 0;
-            }
+        if let Ok(pid) = pid_str.trim().parse::<u32>() {
+            return process_exists(pid);
         }
     }
 
     false
 }
 
-/// Send a request to the daemon and get a response.
-pub fn send_request(root: &Path, request: Request) -> Result<Response> {
-    let sock_path = socket_path(root);
-    let mut stream = UnixStream::connect(&sock_path)?;
+/// Send a request to the daemon and get a response. Pass the daemon's
+/// token (see [`read_token`]) if it requires one.
+pub fn send_request(
+    transport: &Transport,
+    token: Option<&str>,
+    request: Request,
+) -> Result<Response> {
+    send_request_streaming(transport, token, request, |_| {})
+}
 
+/// Send a request and pass any progress frames to `on_progress` until the
+/// final response arrives.
+pub fn send_request_streaming(
+    transport: &Transport,
+    token: Option<&str>,
+    request: Request,
+    on_progress: impl FnMut(BuildProgress),
+) -> Result<Response> {
+    let request = Envelope {
+        token: token.map(str::to_string),
+        chunked: true,
+        request,
+    };
+    match transport {
+        Transport::Local(path) => exchange(<Local as LocalSocket>::connect(path)?, request, on_progress),
+        Transport::Tcp(addr) => exchange(TcpStream::connect(addr)?, request, on_progress),
+    }
+}
+
+/// Write one request line, then read lines until the final response,
+/// putting any chunked arrays back into its data.
+fn exchange<S: Connection>(
+    mut stream: S,
+    request: Envelope,
+    mut on_progress: impl FnMut(BuildProgress),
+) -> Result<Response> {
     let request_json = serde_json::to_string(&request)?;
     writeln!(stream, "{}", request_json)?;
 
-    let mut reader = BufReader::new(stream);
-    let mut response_line = String::new();
-    reader.read_line(&mut response_line)?;
+    let reader = BufReader::new(stream);
+    let mut chunks = Vec::new();
+    for line in reader.lines() {
+        match serde_json::from_str(&line?)? {
+            Response::Progress { parsed, total } => on_progress(BuildProgress { parsed, total }),
+            Response::Chunk { field, items } => chunks.push((field, items)),
+            Response::Ok { mut data } => {
+                for (field, items) in chunks {
+                    let target = match &field {
+                        Some(field) => data.get_mut(field),
+                        None => Some(&mut data),
+                    };
+                    if let Some(serde_json::Value::Array(array)) = target {
+                        array.extend(items);
+                    }
+                }
+                return Ok(Response::Ok { data });
+            }
+            response => return Ok(response),
+        }
+    }
+    anyhow::bail!("daemon closed the connection before responding")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// A project with one file: `alpha` calling `beta`.
+    fn project() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "pub fn alpha() {\n    beta();\n}\n\npub fn beta() {}\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    /// Require a token, optionally still answering reads without one.
+    fn require_token(root: &Path, allow_unauthenticated_reads: bool) {
+        fs::create_dir_all(root.join(".anchor")).unwrap();
+        fs::write(
+            root.join(".anchor/config.toml"),
+            format!(
+                "[daemon]\nrequire_token = true\nallow_unauthenticated_reads = {}\n",
+                allow_unauthenticated_reads
+            ),
+        )
+        .unwrap();
+    }
+
+    /// Serve `root` on an ephemeral loopback port for the rest of the test.
+    fn serve_tcp(root: &Path) -> Transport {
+        let daemon = Daemon::start(root).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || daemon.serve(listener.incoming()));
+        Transport::Tcp(addr)
+    }
+
+    /// Serve `root` on its local socket for the rest of the test.
+    fn serve_local(root: &Path) -> Transport {
+        let daemon = Daemon::start(root).unwrap();
+        let listener = <Local as LocalSocket>::bind(&socket_path(root)).unwrap();
+        thread::spawn(move || daemon.serve(std::iter::repeat_with(|| LocalSocket::accept(&listener))));
+        Transport::local(root)
+    }
+
+    /// Send `envelope` by hand and return every line the daemon answers with.
+    fn raw_exchange(transport: &Transport, envelope: &Envelope) -> Vec<Response> {
+        let Transport::Tcp(addr) = transport else { unreachable!() };
+        let mut stream = TcpStream::connect(addr).unwrap();
+        writeln!(stream, "{}", serde_json::to_string(envelope).unwrap()).unwrap();
+        BufReader::new(stream)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect()
+    }
 
-    let response: Response = serde_json::from_str(&response_line)?;
-    Ok(response)
+    fn ok_data(response: Response) -> serde_json::Value {
+        match response {
+            Response::Ok { data } => data,
+            other => panic!("expected ok, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tcp_round_trip() {
+        let dir = project();
+        let transport = serve_tcp(dir.path());
+
+        assert!(matches!(send_request(&transport, None, Request::Ping).unwrap(), Response::Pong));
+
+        let request = Request::Search { query: "alpha".to_string(), depth: 1, max_fanout: None, regex: false };
+        let data = ok_data(send_request(&transport, None, request).unwrap());
+        let names: Vec<&str> = data["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|s| s["name"].as_str())
+            .collect();
+        assert!(names.contains(&"alpha"));
+        assert!(names.contains(&"beta"));
+
+        assert!(matches!(send_request(&transport, None, Request::Shutdown).unwrap(), Response::Goodbye));
+    }
+
+    #[test]
+    fn test_local_socket_round_trip() {
+        let dir = project();
+        let transport = serve_local(dir.path());
+
+        assert!(matches!(send_request(&transport, None, Request::Ping).unwrap(), Response::Pong));
+        let data = ok_data(send_request(&transport, None, Request::Deps { symbol: "alpha".to_string() }).unwrap());
+        assert_eq!(data["dependencies"][0]["symbol"], "beta");
+    }
+
+    #[test]
+    fn test_token_mismatch_is_rejected() {
+        let dir = project();
+        require_token(dir.path(), false);
+        let transport = serve_tcp(dir.path());
+        let token = read_token(dir.path()).unwrap();
+
+        for wrong in [None, Some("not-the-token")] {
+            match send_request(&transport, wrong, Request::Stats).unwrap() {
+                Response::Error { message } => assert!(message.starts_with("unauthorized")),
+                other => panic!("expected rejection, got {:?}", other),
+            }
+        }
+        ok_data(send_request(&transport, Some(&token), Request::Stats).unwrap());
+    }
+
+    #[test]
+    fn test_writes_need_token_when_reads_are_open() {
+        let dir = project();
+        require_token(dir.path(), true);
+        let transport = serve_tcp(dir.path());
+        let token = read_token(dir.path()).unwrap();
+
+        ok_data(send_request(&transport, None, Request::Stats).unwrap());
+
+        let create = || Request::Create { path: "src/new.rs".to_string(), content: "pub fn gamma() {}\n".to_string() };
+        match send_request(&transport, None, create()).unwrap() {
+            Response::Error { message } => assert!(message.starts_with("unauthorized")),
+            other => panic!("expected rejection, got {:?}", other),
+        }
+        assert!(!dir.path().join("src/new.rs").exists());
+
+        ok_data(send_request(&transport, Some(&token), create()).unwrap());
+        assert!(dir.path().join("src/new.rs").exists());
+    }
+
+    #[test]
+    fn test_rebuilds_and_disguised_mutations_need_token() {
+        let dir = project();
+        require_token(dir.path(), true);
+        let transport = serve_tcp(dir.path());
+
+        let graphql = |query: &str| Request::Graphql { query: query.to_string() };
+        let writes = [
+            Request::Rebuild,
+            Request::RebuildStream,
+            // A comment or a named operation ahead of the mutation
+            graphql("# refresh\nmutation { rebuild { symbols } }"),
+            graphql("query Q { stats { symbols } }\nmutation M { reindexFile(path: \"src/lib.rs\") { symbols } }"),
+            graphql("mutation {"),
+        ];
+        for request in writes {
+            match send_request(&transport, None, request.clone()).unwrap() {
+                Response::Error { message } => assert!(message.starts_with("unauthorized"), "{:?}: {}", request, message),
+                other => panic!("expected rejection of {:?}, got {:?}", request, other),
+            }
+        }
+
+        // Plain queries are still reads
+        let data = ok_data(send_request(&transport, None, graphql("# count\n{ stats { symbols } }")).unwrap());
+        assert!(data["data"]["stats"]["symbols"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_chunked_response_reassembles() {
+        let dir = project();
+        let transport = serve_tcp(dir.path());
+        let batch = || Request::SearchBatch {
+            queries: (0..CHUNK_SIZE + 100)
+                .map(|i| crate::query::Query::Simple(if i % 2 == 0 { "alpha" } else { "beta" }.to_string()))
+                .collect(),
+        };
+
+        let single = raw_exchange(&transport, &Envelope { token: None, chunked: false, request: batch() });
+        assert_eq!(single.len(), 1);
+        let single = ok_data(single.into_iter().next().unwrap());
+
+        let frames = raw_exchange(&transport, &Envelope { token: None, chunked: true, request: batch() });
+        let chunks = frames.iter().filter(|f| matches!(f, Response::Chunk { .. })).count();
+        assert_eq!(chunks, 2);
+
+        let reassembled = ok_data(send_request(&transport, None, batch()).unwrap());
+        assert_eq!(reassembled, single);
+    }
+
+    #[test]
+    fn test_health_fields() {
+        let dir = project();
+        let transport = serve_tcp(dir.path());
+
+        let health = ok_data(send_request(&transport, None, Request::Health).unwrap());
+        assert!(health["uptime_secs"].is_u64());
+        assert_eq!(health["files_watched"], 1);
+        assert_eq!(health["stats"]["file_count"], 1);
+        assert_eq!(health["stats"]["symbol_count"], 2);
+        assert_eq!(health["graph_health"]["dangling_edges"], 0);
+        assert_eq!(health["pending_rebuilds"], 0);
+        assert_eq!(health["watching"], true);
+        assert_eq!(health["watcher_backlog"], 0);
+        assert_eq!(health["files_updated"], 0);
+        assert!(health["last_update"].is_null());
+        assert_eq!(health["active_locks"], 0);
+
+        let started: DateTime<Utc> = serde_json::from_value(health["last_rebuild"].clone()).unwrap();
+        ok_data(send_request(&transport, None, Request::Rebuild).unwrap());
+        let health = ok_data(send_request(&transport, None, Request::Health).unwrap());
+        let rebuilt: DateTime<Utc> = serde_json::from_value(health["last_rebuild"].clone()).unwrap();
+        assert!(rebuilt > started);
+    }
+
+    #[test]
+    fn test_rename_leaves_same_named_method_alone() {
+        let dir = project();
+        let models = "\
+pub struct Order;
+
+impl Order {
+    pub fn save(&self) {}
+}
+
+pub struct User;
+
+impl User {
+    pub fn save(&self) {}
+}
+
+pub fn checkout(order: &Order, user: &User) {
+    Order::save(order);
+    User::save(user);
+}
+";
+        fs::write(dir.path().join("src/models.rs"), models).unwrap();
+        let transport = serve_tcp(dir.path());
+
+        // A bare name shared by two definitions isn't guessed at
+        let ambiguous = Request::Rename { symbol: "save".to_string(), new_name: "store".to_string() };
+        match send_request(&transport, None, ambiguous).unwrap() {
+            Response::Error { message } => assert!(message.contains("ambiguous")),
+            other => panic!("expected an error, got {:?}", other),
+        }
+
+        let rename = Request::Rename { symbol: "Order::save".to_string(), new_name: "persist".to_string() };
+        let data = ok_data(send_request(&transport, None, rename).unwrap());
+        assert_eq!(data["success"], true);
+        assert_eq!(data["symbol"], "models::Order::save");
+        assert_eq!(data["files"][0]["replacements"], 2);
+
+        let renamed = fs::read_to_string(dir.path().join("src/models.rs")).unwrap();
+        assert_eq!(renamed, models.replacen("fn save", "fn persist", 1).replace("Order::save", "Order::persist"));
+        assert!(renamed.contains("impl User {\n    pub fn save(&self) {}"));
+        assert!(renamed.contains("User::save(user);"));
+    }
+
+    #[test]
+    fn test_writes_snapshot_previous_contents() {
+        let dir = project();
+        let transport = serve_tcp(dir.path());
+        let original = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+
+        let replace = Request::Replace {
+            path: "src/lib.rs".to_string(),
+            old: "beta".to_string(),
+            new: "gamma".to_string(),
+        };
+        ok_data(send_request(&transport, None, replace).unwrap());
+        // Writes whose pattern is missing fail and keep no version
+        let missing = Request::Replace {
+            path: "src/lib.rs".to_string(),
+            old: "nowhere".to_string(),
+            new: "gamma".to_string(),
+        };
+        assert!(matches!(send_request(&transport, None, missing).unwrap(), Response::Error { .. }));
+        let missing = Request::Insert {
+            path: "src/lib.rs".to_string(),
+            pattern: "nowhere".to_string(),
+            content: "fn extra() {}\n".to_string(),
+        };
+        assert!(matches!(send_request(&transport, None, missing).unwrap(), Response::Error { .. }));
+        let create = Request::Create { path: "src/new.rs".to_string(), content: "fn fresh() {}\n".to_string() };
+        ok_data(send_request(&transport, None, create).unwrap());
+
+        let history = |file: &str| -> Vec<String> {
+            let Ok(entries) = fs::read_dir(dir.path().join(crate::graph::HISTORY_DIR).join(file)) else {
+                return Vec::new();
+            };
+            entries.map(|e| fs::read_to_string(e.unwrap().path()).unwrap()).collect()
+        };
+        assert_eq!(history("src/lib.rs"), vec![original]);
+        // A new file had nothing before it
+        assert!(history("src/new.rs").is_empty());
+    }
+
+    #[test]
+    fn test_query_defaults_come_from_config() {
+        let dir = project();
+        fs::write(dir.path().join("src/util.rs"), "fn helper() {}\n").unwrap();
+        fs::create_dir_all(dir.path().join(".anchor")).unwrap();
+        fs::write(dir.path().join(".anchor/config.toml"), "[query]\ninclude_private = false\n").unwrap();
+        let transport = serve_tcp(dir.path());
+
+        let search = |queries: Vec<crate::query::Query>| {
+            ok_data(send_request(&transport, None, Request::SearchBatch { queries }).unwrap())
+        };
+        let data = search(vec![crate::query::Query::Simple("helper".to_string())]);
+        assert_eq!(data[0]["found"], false);
+
+        // A query's own filter still wins
+        let data = search(vec![crate::query::Query::Structured {
+            symbol: "helper".to_string(),
+            kind: None,
+            file: None,
+            include_tests: None,
+            include_private: Some(true),
+            include_generated: None,
+            async_only: None,
+            dedup: None,
+            fuzzy: None,
+            ignore_case: None,
+        }]);
+        assert_eq!(data[0]["found"], true);
+    }
 }
@@ -0,0 +1,175 @@
+//! Daemon transport — the local socket the CLI reaches the daemon over.
+//!
+//! On Unix that's a domain socket at `.anchor/anchor.sock`; on Windows it's
+//! a named pipe whose name is derived from the same path, so both ends agree
+//! on it without extra configuration. TCP works everywhere.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+/// A connected stream that can be split into a reader and a writer half.
+pub(crate) trait Connection: Read + Write + Send + Sized + 'static {
+    fn try_clone(&self) -> io::Result<Self>;
+}
+
+impl Connection for TcpStream {
+    fn try_clone(&self) -> io::Result<Self> {
+        TcpStream::try_clone(self)
+    }
+}
+
+#[cfg(unix)]
+impl Connection for UnixStream {
+    fn try_clone(&self) -> io::Result<Self> {
+        UnixStream::try_clone(self)
+    }
+}
+
+/// The platform's local socket: bound by the daemon, connected to by the CLI.
+pub(crate) trait LocalSocket: Sized {
+    type Stream: Connection;
+
+    /// Listen at `path`, replacing a stale socket left by a dead daemon.
+    fn bind(path: &Path) -> io::Result<Self>;
+
+    /// Wait for the next client.
+    fn accept(&self) -> io::Result<Self::Stream>;
+
+    /// Connect to a daemon listening at `path`.
+    fn connect(path: &Path) -> io::Result<Self::Stream>;
+
+    /// Remove whatever `bind` left behind.
+    fn cleanup(path: &Path);
+}
+
+#[cfg(unix)]
+impl LocalSocket for UnixListener {
+    type Stream = UnixStream;
+
+    fn bind(path: &Path) -> io::Result<Self> {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        UnixListener::bind(path)
+    }
+
+    fn accept(&self) -> io::Result<UnixStream> {
+        UnixListener::accept(self).map(|(stream, _)| stream)
+    }
+
+    fn connect(path: &Path) -> io::Result<UnixStream> {
+        UnixStream::connect(path)
+    }
+
+    fn cleanup(path: &Path) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// The local socket on this platform.
+#[cfg(unix)]
+pub(crate) type Local = UnixListener;
+
+#[cfg(windows)]
+pub(crate) use pipe::PipeListener as Local;
+
+#[cfg(windows)]
+mod pipe {
+    use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions, Name};
+    use interprocess::TryClone;
+    use std::io;
+    use std::path::Path;
+
+    use super::{Connection, LocalSocket};
+
+    /// A named pipe listener standing in for the Unix socket.
+    pub(crate) struct PipeListener(LocalSocketListener);
+
+    impl Connection for LocalSocketStream {
+        fn try_clone(&self) -> io::Result<Self> {
+            TryClone::try_clone(self)
+        }
+    }
+
+    /// Pipe name for a socket path: `anchor-` and an FNV-1a hash of the
+    /// path, which is stable across builds unlike `DefaultHasher`.
+    fn pipe_name(path: &Path) -> io::Result<Name<'static>> {
+        let hash = path
+            .to_string_lossy()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("anchor-{:016x}", hash).to_ns_name::<GenericNamespaced>()
+    }
+
+    impl LocalSocket for PipeListener {
+        type Stream = LocalSocketStream;
+
+        fn bind(path: &Path) -> io::Result<Self> {
+            ListenerOptions::new()
+                .name(pipe_name(path)?)
+                .create_sync()
+                .map(PipeListener)
+        }
+
+        fn accept(&self) -> io::Result<LocalSocketStream> {
+            self.0.accept()
+        }
+
+        fn connect(path: &Path) -> io::Result<LocalSocketStream> {
+            LocalSocketStream::connect(pipe_name(path)?)
+        }
+
+        // Named pipes vanish with the last handle
+        fn cleanup(_path: &Path) {}
+    }
+}
+
+/// Where a client reaches the daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// The platform's local socket for this path: a Unix domain socket, or
+    /// a named pipe on Windows.
+    Local(PathBuf),
+    /// TCP listener at this address.
+    Tcp(SocketAddr),
+}
+
+impl Transport {
+    /// The project's default local socket.
+    pub fn local(root: &Path) -> Self {
+        Transport::Local(super::socket_path(root))
+    }
+}
+
+/// Does a process with this PID exist?
+#[cfg(unix)]
+pub(crate) fn process_exists(pid: u32) -> bool {
+    // Signal 0 checks existence without delivering anything
+    unsafe { libc::kill(pid as i32, 0) == 0 }
+}
+
+/// Does a process with this PID exist?
+#[cfg(windows)]
+pub(crate) fn process_exists(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle == 0 {
+            return false;
+        }
+        let mut code = 0u32;
+        let alive = GetExitCodeProcess(handle, &mut code) != 0 && code == STILL_ACTIVE as u32;
+        CloseHandle(handle);
+        alive
+    }
+}
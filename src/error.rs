@@ -3,6 +3,8 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::parser::SupportedLanguage;
+
 /// Result type for Anchor operations.
 pub type Result<T> = std::result::Result<T, AnchorError>;
 
@@ -53,11 +55,16 @@ pub enum AnchorError {
     #[error("Unsupported language for file: {0}")]
     UnsupportedLanguage(PathBuf),
 
-    /// tree-sitter parser failed to initialize for a language.
-    #[error("Parser init failed for {0}: {1}")]
-    ParserInitError(PathBuf, String),
+    /// A bundled grammar was built for a tree-sitter ABI the runtime
+    /// doesn't support, so no file of that language can be parsed.
+    #[error("{} grammar is incompatible with the tree-sitter runtime: {1}", .0.name())]
+    GrammarAbiMismatch(SupportedLanguage, String),
 
     /// tree-sitter returned None from parse (e.g., timeout or cancellation).
     #[error("tree-sitter parse failed for: {0}")]
     TreeSitterParseFailed(PathBuf),
+
+    /// A requested response field doesn't exist.
+    #[error("Unknown field '{0}'; expected one of: {1}")]
+    UnknownField(String, String),
 }
@@ -0,0 +1,206 @@
+//! Blame — who last changed a symbol, from `git blame`.
+//!
+//! Blame is slow, so it is computed on demand and a file is blamed once
+//! per [`BlameCache`]: looking up several symbols of one file in a query
+//! runs git once.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::engine::CodeGraph;
+use super::meta::run_git;
+use super::types::NodeKind;
+
+/// The most recent commit touching a range of lines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlameInfo {
+    pub author: String,
+    pub commit: String,
+    /// Author time, seconds since the Unix epoch.
+    pub date: i64,
+}
+
+/// Blame of one file: the commit of each line, and commit details.
+#[derive(Debug, Default)]
+struct FileBlame {
+    /// Commit hash per line, index 0 for line 1.
+    lines: Vec<String>,
+    commits: HashMap<String, BlameInfo>,
+}
+
+/// Per-file blame results, so a file is only blamed once.
+#[derive(Debug, Default)]
+pub struct BlameCache {
+    /// `None` when the file couldn't be blamed (not in git, untracked).
+    files: HashMap<PathBuf, Option<FileBlame>>,
+}
+
+impl BlameCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most recently committed change to lines `start..=end` of
+    /// `file`. Uncommitted lines are skipped.
+    pub fn range(&mut self, file: &Path, start: usize, end: usize) -> Option<BlameInfo> {
+        let blame = self
+            .files
+            .entry(file.to_path_buf())
+            .or_insert_with(|| blame_file(file))
+            .as_ref()?;
+
+        blame
+            .lines
+            .iter()
+            .skip(start.saturating_sub(1))
+            .take(end.saturating_sub(start) + 1)
+            .filter_map(|commit| blame.commits.get(commit))
+            .filter(|info| !is_uncommitted(&info.commit))
+            .fold(None, |latest: Option<&BlameInfo>, info| match latest {
+                Some(latest) if latest.date >= info.date => Some(latest),
+                _ => Some(info),
+            })
+            .cloned()
+    }
+}
+
+impl CodeGraph {
+    /// The last commit to change `symbol`'s lines, or `None` outside git or
+    /// for an unknown or uncommitted symbol. Runs `git blame`.
+    pub fn blame(&self, symbol: &str) -> Option<BlameInfo> {
+        self.blame_with(&mut BlameCache::new(), symbol)
+    }
+
+    /// Like [`blame`](Self::blame), reusing file blames from `cache`.
+    pub fn blame_with(&self, cache: &mut BlameCache, symbol: &str) -> Option<BlameInfo> {
+        let graph = self.inner_graph();
+        graph
+            .node_indices()
+            .map(|idx| &graph[idx])
+            .filter(|node| {
+                !node.removed
+                    && node.name == symbol
+                    && !matches!(node.kind, NodeKind::File | NodeKind::Import)
+            })
+            .find_map(|node| cache.range(&node.file_path, node.line_start, node.line_end))
+    }
+}
+
+/// Run `git blame --porcelain` on a file.
+fn blame_file(file: &Path) -> Option<FileBlame> {
+    let dir = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = file.file_name()?.to_str()?;
+    let output = run_git(dir, &["blame", "--porcelain", "--", name])?;
+    Some(parse_porcelain(&output))
+}
+
+/// Parse `git blame --porcelain` output.
+///
+/// Each line starts with `<commit> <original line> <final line>`; the
+/// first line from a commit is followed by its `author`, `author-time`
+/// and other headers, then every line ends with its tab-prefixed content.
+fn parse_porcelain(output: &str) -> FileBlame {
+    let mut blame = FileBlame::default();
+    let mut current = String::new();
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        if let Some(author) = line.strip_prefix("author ") {
+            if let Some(info) = blame.commits.get_mut(&current) {
+                info.author = author.to_string();
+            }
+            continue;
+        }
+        if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some(info) = blame.commits.get_mut(&current) {
+                info.date = time.trim().parse().unwrap_or(0);
+            }
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let (Some(commit), Some(_original), Some(Ok(final_line))) =
+            (parts.next(), parts.next(), parts.next().map(str::parse::<usize>))
+        else {
+            continue;
+        };
+        if commit.len() < 40 || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+            continue;
+        }
+
+        current = commit.to_string();
+        blame.commits.entry(current.clone()).or_insert_with(|| BlameInfo {
+            author: String::new(),
+            commit: current.clone(),
+            date: 0,
+        });
+        if blame.lines.len() < final_line {
+            blame.lines.resize(final_line, String::new());
+        }
+        blame.lines[final_line - 1] = current.clone();
+    }
+
+    blame
+}
+
+/// Git blames edits in the working tree on the all-zero commit.
+fn is_uncommitted(commit: &str) -> bool {
+    commit.chars().all(|c| c == '0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Ada Lovelace", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_blame_reports_commit_author() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("lib.rs"), "fn greet() -> u32 {\n    1\n}\n").unwrap();
+        git(root, &["add", "lib.rs"]);
+        git(root, &["commit", "-q", "-m", "add greet"]);
+        // Uncommitted code has no author yet
+        fs::write(root.join("lib.rs"), "fn greet() -> u32 {\n    1\n}\n\nfn draft() {}\n").unwrap();
+
+        let graph = build_graph(root);
+        let head = run_git(root, &["rev-parse", "HEAD"]).unwrap();
+
+        let blame = graph.blame("greet").expect("committed function has blame");
+        assert_eq!(blame.author, "Ada Lovelace");
+        assert_eq!(blame.commit, head.trim());
+        assert!(blame.date > 0);
+        assert!(graph.blame("draft").is_none());
+    }
+
+    #[test]
+    fn test_blame_outside_git() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn alpha() {}\n").unwrap();
+
+        let graph = build_graph(dir.path());
+
+        assert!(graph.blame("alpha").is_none());
+    }
+}
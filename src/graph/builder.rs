@@ -3,61 +3,633 @@
 //! Walks source files respecting .gitignore, parses each with tree-sitter,
 //! and assembles the complete code graph with all relationships.
 
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
-use std::sync::Mutex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use tracing::warn;
 
-use super::engine::CodeGraph;
-use super::types::FileExtractions;
-use crate::parser::{extract_file, SupportedLanguage};
+use super::engine::{CodeGraph, PendingLinks};
+use super::meta::{GrammarFailure, GraphMeta};
+use super::types::{FileExtractions, NodeKind};
+use crate::error::AnchorError;
+use crate::parser::{extract_file_with, ExtractOptions, SupportedLanguage};
 
 /// Build a code graph from all source files in a directory.
 ///
 /// Respects .gitignore, walks recursively, parses all supported
 /// language files, and returns a fully connected CodeGraph.
 pub fn build_graph(root: &Path) -> CodeGraph {
-    let files: Vec<_> = WalkBuilder::new(root)
-        .hidden(true)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .build()
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
-        .filter(|entry| SupportedLanguage::from_path(entry.path()).is_some())
-        .map(|entry| entry.into_path())
-        .collect();
+    CodeGraphBuilder::new(root).build()
+}
+
+/// How far a build has got, reported while files are parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BuildProgress {
+    /// Files parsed (or skipped as unreadable) so far.
+    pub parsed: usize,
+    /// Files that will be parsed in total.
+    pub total: usize,
+}
+
+/// Receives `BuildProgress` updates from parsing threads.
+#[derive(Clone)]
+pub struct ProgressSink(Arc<dyn Fn(BuildProgress) + Send + Sync>);
+
+impl ProgressSink {
+    pub fn new(f: impl Fn(BuildProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    fn emit(&self, progress: BuildProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressSink")
+    }
+}
+
+/// Configurable graph builder.
+///
+/// ```no_run
+/// use anchor::graph::CodeGraphBuilder;
+/// use anchor::SupportedLanguage;
+///
+/// let graph = CodeGraphBuilder::new(".")
+///     .languages(&[SupportedLanguage::Rust])
+///     .ignore("target/")
+///     .parallelism(4)
+///     .max_file_bytes(512 * 1024)
+///     .call_stopwords(&["log_event"])
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeGraphBuilder {
+    root: PathBuf,
+    languages: Option<Vec<SupportedLanguage>>,
+    ignores: Vec<String>,
+    also_index: Vec<PathBuf>,
+    parallelism: Option<usize>,
+    max_file_bytes: Option<u64>,
+    call_stopwords: Vec<String>,
+    index_kinds: Vec<NodeKind>,
+    generated: Vec<String>,
+    extract_options: ExtractOptions,
+    progress: Option<ProgressSink>,
+    streaming: bool,
+}
+
+/// Files parsed per batch in a streaming build.
+const STREAM_CHUNK_FILES: usize = 256;
+
+impl CodeGraphBuilder {
+    /// Start a builder for the project at `root` with default settings:
+    /// all languages, .gitignore only, all cores, no file size limit,
+    /// only the built-in call stopwords.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            languages: None,
+            ignores: Vec::new(),
+            also_index: Vec::new(),
+            parallelism: None,
+            max_file_bytes: None,
+            call_stopwords: Vec::new(),
+            index_kinds: Vec::new(),
+            generated: Vec::new(),
+            extract_options: ExtractOptions::default(),
+            progress: None,
+            streaming: false,
+        }
+    }
+
+    /// Only parse files in these languages.
+    pub fn languages(mut self, languages: &[SupportedLanguage]) -> Self {
+        self.languages = Some(languages.to_vec());
+        self
+    }
+
+    /// Skip paths matching a gitignore-style glob (e.g. `vendor/`, `*.gen.rs`).
+    /// Can be called repeatedly.
+    pub fn ignore(mut self, pattern: impl Into<String>) -> Self {
+        self.ignores.push(pattern.into());
+        self
+    }
+
+    /// Also index files under `path` (relative to the root, or absolute),
+    /// even if .gitignore or [`ignore`](Self::ignore) patterns skip it.
+    /// For deliberately including a vendored dependency. Can be called
+    /// repeatedly.
+    pub fn also_index(mut self, path: impl Into<PathBuf>) -> Self {
+        self.also_index.push(path.into());
+        self
+    }
+
+    /// Number of threads used for parsing.
+    pub fn parallelism(mut self, threads: usize) -> Self {
+        self.parallelism = Some(threads.max(1));
+        self
+    }
+
+    /// Skip files larger than this many bytes.
+    pub fn max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = Some(bytes);
+        self
+    }
+
+    /// Don't record calls to these names, on top of each language's
+    /// [`call_stopwords`](SupportedLanguage::call_stopwords). Useful for
+    /// project-wide helpers (logging, metrics) that add noise to the graph.
+    pub fn call_stopwords(mut self, names: &[&str]) -> Self {
+        self.call_stopwords.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Tag files matching a gitignore-style glob (e.g. `*.pb.rs`,
+    /// `generated/`) as generated code. They stay in the graph, but queries
+    /// can filter them out and the change intent warns against editing
+    /// them. Files with a `@generated` header are tagged regardless. Can be
+    /// called repeatedly.
+    pub fn generated(mut self, pattern: impl Into<String>) -> Self {
+        self.generated.push(pattern.into());
+        self
+    }
+
+    /// Only index symbols of these kinds, e.g. `[Function, Method, Struct]`
+    /// to keep variables, constants and imports out of the graph. Defaults
+    /// to every kind.
+    pub fn index_kinds(mut self, kinds: &[NodeKind]) -> Self {
+        self.index_kinds = kinds.to_vec();
+        self
+    }
+
+    /// Line appended to snippets cut short by the size limits, or `None`
+    /// to keep snippets free of injected comments. Defaults to
+    /// [`DEFAULT_TRUNCATION_MARKER`](crate::parser::DEFAULT_TRUNCATION_MARKER).
+    pub fn truncation_marker(mut self, marker: Option<&str>) -> Self {
+        self.extract_options.truncation_marker = marker.map(str::to_string);
+        self
+    }
+
+    /// Tag JSX-returning functions as components with `Renders` edges
+    /// between them (on by default).
+    pub fn jsx_components(mut self, enabled: bool) -> Self {
+        self.extract_options.jsx_components = enabled;
+        self
+    }
+
+    /// Report parsing progress to `sink`, about every 1% of files and
+    /// always once all files are parsed. Called from parsing threads.
+    pub fn on_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    /// Feed parsed files to a single graph-building thread as they are
+    /// parsed, instead of holding every extraction until parsing ends.
+    ///
+    /// Peak memory is bounded by the files in flight rather than the whole
+    /// tree, at some cost in speed. Meant for CI runs on very large repos;
+    /// the resulting graph is identical to a batch build.
+    pub fn streaming(mut self, enabled: bool) -> Self {
+        self.streaming = enabled;
+        self
+    }
+
+    /// Scan, parse and assemble the graph.
+    pub fn build(&self) -> CodeGraph {
+        let files = self.collect_files();
+        let generated = self.generated_matcher();
+        let failures = GrammarFailures::default();
+        let build = || {
+            if self.streaming {
+                self.build_streaming(&files, generated.as_ref(), &failures)
+            } else {
+                self.build_batch(&files, generated.as_ref(), &failures)
+            }
+        };
+        let mut graph = match self.thread_pool() {
+            Some(pool) => pool.install(build),
+            None => build(),
+        };
+
+        graph.compute_centrality();
+
+        let mut meta = GraphMeta::capture(&graph, &self.root);
+        meta.grammar_failures = failures.into_inner();
+        meta.extract_options = self.extract_options.clone();
+        meta.generated = self.generated.clone();
+        meta.call_stopwords = self.call_stopwords.clone();
+        meta.index_kinds = self.index_kinds.clone();
+        meta.languages = self.languages.clone();
+        meta.ignores = self.ignores.clone();
+        meta.also_index = self.also_index.clone();
+        meta.max_file_bytes = self.max_file_bytes;
+        graph.set_meta(meta);
+
+        graph
+    }
+
+    /// A builder that selects and extracts files the way the build behind
+    /// `meta` did.
+    fn from_meta(meta: &GraphMeta) -> Self {
+        let mut builder = Self::new(&meta.root);
+        builder.extract_options = meta.extract_options.clone();
+        builder.generated = meta.generated.clone();
+        builder.call_stopwords = meta.call_stopwords.clone();
+        builder.index_kinds = meta.index_kinds.clone();
+        builder.languages = meta.languages.clone();
+        builder.ignores = meta.ignores.clone();
+        builder.also_index = meta.also_index.clone();
+        builder.max_file_bytes = meta.max_file_bytes;
+        builder
+    }
+
+    /// Whether the build indexes `file_path`: a file in a selected language
+    /// within the size limit that no ignore pattern skips, unless it's under
+    /// an `also_index` path. `.gitignore` is left to the caller.
+    fn indexes(&self, file_path: &Path) -> bool {
+        let Ok(metadata) = fs::metadata(file_path) else {
+            return false;
+        };
+        let selected = metadata.is_file()
+            && self.max_file_bytes.is_none_or(|max| metadata.len() <= max)
+            && SupportedLanguage::from_file(file_path).is_some_and(|lang| {
+                self.languages.as_ref().is_none_or(|langs| langs.contains(&lang))
+            });
+        if !selected {
+            return false;
+        }
+        if self.also_index.iter().any(|path| file_path.starts_with(self.root.join(path))) {
+            return true;
+        }
+        self.glob_matcher(&self.ignores, "ignore")
+            .is_none_or(|ignores| !self.matches(&ignores, file_path))
+    }
+
+    /// Read and extract one file with the builder's options, stopwords and
+    /// generated patterns.
+    fn extract(&self, file_path: &Path) -> Result<FileExtractions, Box<dyn std::error::Error + Send + Sync>> {
+        let source = fs::read_to_string(file_path)?;
+        let mut extraction = extract_file_with(file_path, &source, &self.extract_options)?;
+        self.drop_stopword_calls(&mut extraction);
+        self.mark_generated(&mut extraction, self.generated_matcher().as_ref());
+        Ok(extraction)
+    }
+
+    /// Parse every file, then build the graph from all extractions at once.
+    fn build_batch(
+        &self,
+        files: &[PathBuf],
+        generated: Option<&Gitignore>,
+        failures: &GrammarFailures,
+    ) -> CodeGraph {
+        let mut extractions =
+            parse_files(files, &self.extract_options, self.progress.as_ref(), failures);
+        for extraction in &mut extractions {
+            self.drop_stopword_calls(extraction);
+            self.mark_generated(extraction, generated);
+        }
+
+        let mut graph = CodeGraph::new();
+        graph.set_index_kinds(&self.index_kinds);
+        graph.build_from_extractions(extractions);
+        graph
+    }
+
+    /// Parse files in batches of `STREAM_CHUNK_FILES` and send each
+    /// extraction over a bounded channel to a thread that ingests it.
+    ///
+    /// Files are parsed in path order and sent in that order, so nodes and
+    /// edges are added exactly as `build_from_extractions` would add them.
+    fn build_streaming(
+        &self,
+        files: &[PathBuf],
+        generated: Option<&Gitignore>,
+        failures: &GrammarFailures,
+    ) -> CodeGraph {
+        let mut files = files.to_vec();
+        files.sort();
+        let counter = ProgressCounter::new(files.len(), self.progress.as_ref());
+        let (sender, receiver) = mpsc::sync_channel::<FileExtractions>(STREAM_CHUNK_FILES);
+
+        let index_kinds = self.index_kinds.clone();
+        let ingest = thread::spawn(move || {
+            let mut graph = CodeGraph::new();
+            graph.set_index_kinds(&index_kinds);
+            let mut links = PendingLinks::default();
+            for extraction in receiver {
+                graph.ingest_extraction(extraction, &mut links);
+            }
+            graph.resolve_links(links);
+            graph
+        });
+
+        for chunk in files.chunks(STREAM_CHUNK_FILES) {
+            let extractions: Vec<Option<FileExtractions>> = chunk
+                .par_iter()
+                .map(|file_path| {
+                    let extraction = parse_file(file_path, &self.extract_options, failures);
+                    counter.tick();
+                    extraction
+                })
+                .collect();
+            for mut extraction in extractions.into_iter().flatten() {
+                self.drop_stopword_calls(&mut extraction);
+                self.mark_generated(&mut extraction, generated);
+                if sender.send(extraction).is_err() {
+                    break;
+                }
+            }
+        }
+        drop(sender);
+
+        ingest.join().unwrap_or_else(|_| {
+            warn!("streaming graph builder panicked, returning an empty graph");
+            CodeGraph::new()
+        })
+    }
+
+    /// Remove calls to the builder's extra stopwords.
+    fn drop_stopword_calls(&self, extraction: &mut FileExtractions) {
+        if !self.call_stopwords.is_empty() {
+            extraction
+                .calls
+                .retain(|call| !self.call_stopwords.iter().any(|stopword| call.is_stopword(stopword)));
+        }
+    }
+
+    /// Matcher for the generated patterns, if any were given.
+    fn generated_matcher(&self) -> Option<Gitignore> {
+        self.glob_matcher(&self.generated, "generated")
+    }
+
+    /// Gitignore-style matcher for `patterns` (the `kind` named in
+    /// warnings), if any were given.
+    fn glob_matcher(&self, patterns: &[String], kind: &str) -> Option<Gitignore> {
+        if patterns.is_empty() {
+            return None;
+        }
+        let mut builder = GitignoreBuilder::new(&self.root);
+        for pattern in patterns {
+            if let Err(e) = builder.add_line(None, pattern) {
+                warn!(pattern = %pattern, error = %e, "invalid {} pattern", kind);
+            }
+        }
+        match builder.build() {
+            Ok(matcher) => Some(matcher),
+            Err(e) => {
+                warn!(error = %e, "{} patterns not applied", kind);
+                None
+            }
+        }
+    }
+
+    /// Whether `path`, or a directory above it, matches `matcher`. Paths
+    /// outside the root match nothing.
+    fn matches(&self, matcher: &Gitignore, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        !relative.has_root() && matcher.matched_path_or_any_parents(relative, false).is_ignore()
+    }
+
+    /// Tag the extraction as generated if its path matches `generated`.
+    fn mark_generated(&self, extraction: &mut FileExtractions, generated: Option<&Gitignore>) {
+        if generated.is_some_and(|generated| self.matches(generated, &extraction.file_path)) {
+            extraction.generated = true;
+        }
+    }
+
+    /// Walk the root and return the files that pass every filter.
+    fn collect_files(&self) -> Vec<PathBuf> {
+        let mut walker = WalkBuilder::new(&self.root);
+        walker
+            .hidden(true)
+            .git_ignore(true)
+            .git_global(true)
+            .git_exclude(true);
+
+        if !self.ignores.is_empty() {
+            let mut overrides = OverrideBuilder::new(&self.root);
+            for pattern in &self.ignores {
+                if let Err(e) = overrides.add(&format!("!{}", pattern)) {
+                    warn!(pattern = %pattern, error = %e, "invalid ignore pattern");
+                }
+            }
+            match overrides.build() {
+                Ok(overrides) => {
+                    walker.overrides(overrides);
+                }
+                Err(e) => warn!(error = %e, "ignore patterns not applied"),
+            }
+        }
+
+        let mut files: Vec<PathBuf> = walker
+            .build()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| self.is_indexable(entry))
+            .map(|entry| entry.into_path())
+            .collect();
+
+        // Extra paths skip every ignore rule but are filtered like the rest
+        let mut seen: HashSet<PathBuf> = files.iter().cloned().collect();
+        for path in &self.also_index {
+            let path = self.root.join(path);
+            if !path.exists() {
+                warn!(path = %path.display(), "also_index path does not exist");
+                continue;
+            }
+            let extra = WalkBuilder::new(&path)
+                .standard_filters(false)
+                .hidden(true)
+                .build()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| self.is_indexable(entry))
+                .map(|entry| entry.into_path());
+            for file in extra {
+                if seen.insert(file.clone()) {
+                    files.push(file);
+                }
+            }
+        }
+
+        files
+    }
+
+    /// A regular file in a selected language, within the size limit.
+    fn is_indexable(&self, entry: &ignore::DirEntry) -> bool {
+        entry.file_type().is_some_and(|ft| ft.is_file())
+            && self.max_file_bytes.is_none_or(|max| {
+                entry.metadata().is_ok_and(|m| m.len() <= max)
+            })
+            && SupportedLanguage::from_file(entry.path()).is_some_and(|lang| {
+                self.languages.as_ref().is_none_or(|langs| langs.contains(&lang))
+            })
+    }
+
+    /// A dedicated pool when parallelism is set; `None` means the global pool.
+    fn thread_pool(&self) -> Option<rayon::ThreadPool> {
+        let threads = self.parallelism?;
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| warn!(error = %e, "falling back to the global thread pool"))
+            .ok()
+    }
+}
+
+/// Read and parse files in parallel, skipping any that fail.
+fn parse_files(
+    files: &[PathBuf],
+    options: &ExtractOptions,
+    progress: Option<&ProgressSink>,
+    failures: &GrammarFailures,
+) -> Vec<FileExtractions> {
     let extractions: Mutex<Vec<FileExtractions>> = Mutex::new(Vec::with_capacity(files.len()));
+    let counter = ProgressCounter::new(files.len(), progress);
 
     files.par_iter().for_each(|file_path| {
-        if let Ok(source) = fs::read_to_string(file_path) {
-            if let Ok(extraction) = extract_file(file_path, &source) {
-                if let Ok(mut exts) = extractions.lock() {
-                    exts.push(extraction);
-                }
+        if let Some(extraction) = parse_file(file_path, options, failures) {
+            if let Ok(mut exts) = extractions.lock() {
+                exts.push(extraction);
             }
         }
+        counter.tick();
     });
 
-    let extractions = extractions.into_inner().unwrap_or_default();
+    extractions.into_inner().unwrap_or_default()
+}
 
-    let mut graph = CodeGraph::new();
-    graph.build_from_extractions(extractions);
+/// Read and parse one file, or `None` if it can't be read or parsed.
+/// Files skipped for a broken grammar are counted in `failures`.
+fn parse_file(
+    file_path: &Path,
+    options: &ExtractOptions,
+    failures: &GrammarFailures,
+) -> Option<FileExtractions> {
+    let source = fs::read_to_string(file_path).ok()?;
+    match extract_file_with(file_path, &source, options) {
+        Ok(extraction) => Some(extraction),
+        Err(AnchorError::GrammarAbiMismatch(language, error)) => {
+            failures.record(language, &error);
+            None
+        }
+        Err(_) => None,
+    }
+}
 
-    graph
+/// Languages whose grammar failed to load during one build, with the files
+/// each one cost. Shared across the parsing threads.
+#[derive(Debug, Default)]
+struct GrammarFailures {
+    failures: Mutex<Vec<GrammarFailure>>,
 }
 
+impl GrammarFailures {
+    /// Count a file skipped because `language`'s grammar failed. Warns on
+    /// the first file of each language only; returns whether it warned.
+    fn record(&self, language: SupportedLanguage, error: &str) -> bool {
+        let Ok(mut failures) = self.failures.lock() else {
+            return false;
+        };
+        if let Some(failure) = failures.iter_mut().find(|f| f.language == language) {
+            failure.skipped_files += 1;
+            return false;
+        }
+        warn!(
+            language = language.name(),
+            error = %error,
+            "grammar incompatible with the tree-sitter runtime, skipping its files"
+        );
+        failures.push(GrammarFailure {
+            language,
+            error: error.to_string(),
+            skipped_files: 1,
+        });
+        true
+    }
+
+    fn into_inner(self) -> Vec<GrammarFailure> {
+        let mut failures = self.failures.into_inner().unwrap_or_default();
+        failures.sort_by_key(|f| f.language.name());
+        failures
+    }
+}
+
+/// Counts parsed files across threads and reports to a `ProgressSink`.
+struct ProgressCounter<'a> {
+    sink: Option<&'a ProgressSink>,
+    total: usize,
+    step: usize,
+    parsed: AtomicUsize,
+}
+
+impl<'a> ProgressCounter<'a> {
+    fn new(total: usize, sink: Option<&'a ProgressSink>) -> Self {
+        Self {
+            sink,
+            total,
+            step: (total / 100).max(1),
+            parsed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record one more file parsed (or skipped as unreadable).
+    fn tick(&self) {
+        if let Some(sink) = self.sink {
+            let done = self.parsed.fetch_add(1, Ordering::Relaxed) + 1;
+            if done.is_multiple_of(self.step) || done == self.total {
+                sink.emit(BuildProgress { parsed: done, total: self.total });
+            }
+        }
+    }
+}
+
+/// Re-parse a single file and update it in the graph.
+///
+/// The file is extracted with the settings the graph was built with (see
+/// [`GraphMeta`]), so it matches the rest of the graph. Symbols deleted
+/// from the file are removed along with inbound edges from other files;
+/// symbols that survive keep their callers. Importance scores are marked
+/// stale, for [`CodeGraph::refresh_centrality`] to recompute once per
+/// batch of files. A file the build would have skipped (another language, an
+/// ignored path, over the size limit) is left out, or removed if it was
+/// indexed before. If the file can't be read or parsed, it is removed
+/// from the graph and the error returned.
 pub fn rebuild_file(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    graph.remove_file(file_path);
-    let source = fs::read_to_string(file_path)?;
-    let extraction = extract_file(file_path, &source)?;
-    graph.build_from_extractions(vec![extraction]);
-    Ok(())
+    let builder = match graph.meta() {
+        Some(meta) => CodeGraphBuilder::from_meta(meta),
+        None => CodeGraphBuilder::new(file_path.parent().unwrap_or(Path::new(""))),
+    };
+    graph.centrality_stale = true;
+    if file_path.exists() && !builder.indexes(file_path) {
+        graph.remove_file(file_path);
+        return Ok(());
+    }
+    let extraction = builder.extract(file_path);
+
+    match extraction {
+        Ok(extraction) => {
+            graph.replace_file(extraction);
+            Ok(())
+        }
+        Err(e) => {
+            graph.remove_file(file_path);
+            Err(e)
+        }
+    }
 }
 
 /// Get statistics about what files would be parsed in a directory.
@@ -73,7 +645,7 @@ pub fn scan_stats(root: &Path) -> ScanStats {
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_some_and(|ft| ft.is_file()))
     {
-        if let Some(lang) = SupportedLanguage::from_path(entry.path()) {
+        if let Some(lang) = SupportedLanguage::from_file(entry.path()) {
             stats.total_files += 1;
             match lang {
                 SupportedLanguage::Rust => stats.rust_files += 1,
@@ -106,3 +678,410 @@ impl std::fmt::Display for ScanStats {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::EdgeKind;
+    use crate::parser::extract_file;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rebuild_file_drops_deleted_symbols() {
+        let dir = tempdir().unwrap();
+        let main_rs = dir.path().join("main.rs");
+        let auth_rs = dir.path().join("auth.rs");
+        fs::write(&main_rs, "fn main() {\n    login();\n    logout();\n}\n").unwrap();
+        fs::write(&auth_rs, "fn login() {}\n\nfn logout() {}\n").unwrap();
+
+        let mut graph = build_graph(dir.path());
+        let deps: Vec<String> = graph.dependencies("main").into_iter().map(|d| d.symbol).collect();
+        assert!(deps.contains(&"login".to_string()));
+        assert!(deps.contains(&"logout".to_string()));
+
+        // Delete login() from auth.rs, keep logout()
+        fs::write(&auth_rs, "fn logout() {}\n").unwrap();
+        rebuild_file(&mut graph, &auth_rs).unwrap();
+
+        let deps: Vec<String> = graph.dependencies("main").into_iter().map(|d| d.symbol).collect();
+        assert!(!deps.contains(&"login".to_string()));
+        assert!(deps.contains(&"logout".to_string()), "surviving callee keeps its caller");
+        assert!(graph.search("login", 5).is_empty());
+
+        let callers: Vec<String> = graph
+            .dependents("logout")
+            .into_iter()
+            .filter(|d| d.relationship == EdgeKind::Calls)
+            .map(|d| d.symbol)
+            .collect();
+        assert_eq!(callers, vec!["main".to_string()]);
+    }
+
+    #[test]
+    fn test_rebuild_file_repeatedly_stays_bounded() {
+        let dir = tempdir().unwrap();
+        let main_rs = dir.path().join("main.rs");
+        let auth_rs = dir.path().join("auth.rs");
+        fs::write(&main_rs, "mod auth;\n\nfn main() {\n    login();\n    logout();\n}\n").unwrap();
+        fs::write(&auth_rs, "use std::fmt;\n\nfn login() {}\n\nfn logout() {}\n").unwrap();
+
+        let mut graph = build_graph(dir.path());
+        let size = |graph: &CodeGraph| {
+            (graph.inner_graph().node_count(), graph.inner_graph().edge_count())
+        };
+        let before = size(&graph);
+
+        // Alternate between shifted lines (no node matches) and the original
+        for i in 0..100 {
+            let source = if i % 2 == 0 {
+                "use std::fmt;\n\n\nfn login() {\n    logout();\n}\n\nfn logout() {}\n"
+            } else {
+                "use std::fmt;\n\nfn login() {}\n\nfn logout() {}\n"
+            };
+            fs::write(&auth_rs, source).unwrap();
+            rebuild_file(&mut graph, &auth_rs).unwrap();
+        }
+
+        // The last edit restored the original file
+        assert_eq!(size(&graph), before);
+        assert_eq!(graph.health().dangling_edges, 0);
+        assert_eq!(graph.search("login", 5).len(), 1);
+        let deps: Vec<String> = graph.dependencies("main").into_iter().map(|d| d.symbol).collect();
+        assert!(deps.contains(&"login".to_string()) && deps.contains(&"logout".to_string()));
+    }
+
+    #[test]
+    fn test_rebuild_file_defers_centrality_to_refresh() {
+        let dir = tempdir().unwrap();
+        let lib_rs = dir.path().join("lib.rs");
+        fs::write(&lib_rs, "fn a() {}\nfn b() {}\nfn c() {\n    a();\n}\nfn d() {\n    a();\n}\n").unwrap();
+        let mut graph = build_graph(dir.path());
+        let importance = |graph: &CodeGraph, name: &str| graph.find_qualified(&lib_rs, name).unwrap().importance;
+        assert_eq!(importance(&graph, "a"), 1.0);
+
+        fs::write(&lib_rs, "fn a() {}\nfn b() {}\nfn c() {\n    b();\n}\nfn d() {\n    b();\n}\n").unwrap();
+        rebuild_file(&mut graph, &lib_rs).unwrap();
+        assert!(graph.centrality_stale);
+        // Compacting keeps the scores, stale or not
+        graph.compact();
+        assert!(graph.centrality_stale);
+
+        graph.refresh_centrality();
+        assert!(!graph.centrality_stale);
+        assert_eq!(importance(&graph, "b"), 1.0);
+        assert!(importance(&graph, "a") < 1.0);
+    }
+
+    #[test]
+    fn test_build_graph_includes_shebang_scripts() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("deploy"),
+            "#!/usr/bin/env python3\ndef deploy():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes"), "def not_code():\n").unwrap();
+
+        let graph = build_graph(dir.path());
+
+        assert_eq!(graph.search("deploy", 1).len(), 1);
+        assert!(graph.search("not_code", 1).is_empty());
+        assert_eq!(scan_stats(dir.path()).python_files, 1);
+    }
+
+    #[test]
+    fn test_builder_languages_and_ignore() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("generated")).unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn kept() {}\n").unwrap();
+        fs::write(dir.path().join("tool.py"), "def script():\n    pass\n").unwrap();
+        fs::write(dir.path().join("generated/out.rs"), "fn generated() {}\n").unwrap();
+
+        let graph = CodeGraphBuilder::new(dir.path())
+            .languages(&[SupportedLanguage::Rust])
+            .ignore("generated/")
+            .parallelism(2)
+            .build();
+
+        // Same result as parsing the one matching file by hand
+        let lib_rs = dir.path().join("lib.rs");
+        let source = fs::read_to_string(&lib_rs).unwrap();
+        let mut manual = CodeGraph::new();
+        manual.build_from_extractions(vec![extract_file(&lib_rs, &source).unwrap()]);
+
+        assert_eq!(graph.all_files(), vec![lib_rs]);
+        assert_eq!(graph.stats().symbol_count, manual.stats().symbol_count);
+        assert_eq!(graph.stats().total_edges, manual.stats().total_edges);
+        assert!(graph.search("script", 1).is_empty());
+        assert!(graph.search("generated", 1).is_empty());
+
+        // Defaults pick up everything
+        let all = build_graph(dir.path());
+        assert_eq!(all.all_files().len(), 3);
+    }
+
+    #[test]
+    fn test_builder_also_index_vendored_path() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        fs::create_dir_all(root.join("vendor/lib")).unwrap();
+        fs::create_dir_all(root.join("vendor/other")).unwrap();
+        fs::write(root.join(".gitignore"), "vendor/\n").unwrap();
+        fs::write(root.join("main.rs"), "fn main() {\n    parse();\n}\n").unwrap();
+        fs::write(root.join("vendor/lib/parse.rs"), "pub fn parse() {}\n").unwrap();
+        fs::write(root.join("vendor/other/skip.rs"), "pub fn skipped() {}\n").unwrap();
+
+        assert!(build_graph(root).search("parse", 1).is_empty());
+
+        let graph = CodeGraphBuilder::new(root).also_index("vendor/lib").build();
+
+        assert_eq!(graph.search("parse", 1).len(), 1);
+        assert!(graph.search("skipped", 1).is_empty());
+        let callees: Vec<String> = graph.dependencies("main").into_iter().map(|d| d.symbol).collect();
+        assert_eq!(callees, vec!["parse".to_string()]);
+    }
+
+    #[test]
+    fn test_builder_max_file_bytes() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("small.rs"), "fn small() {}\n").unwrap();
+        fs::write(dir.path().join("big.rs"), format!("fn big() {{}}\n{}", "// pad\n".repeat(100))).unwrap();
+
+        let graph = CodeGraphBuilder::new(dir.path()).max_file_bytes(100).build();
+
+        assert_eq!(graph.search("small", 1).len(), 1);
+        assert!(graph.search("big", 1).is_empty());
+    }
+
+    #[test]
+    fn test_builder_call_stopwords() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "fn trace() {}\nfn work() {}\nfn run() {\n    trace();\n    work();\n}\n",
+        )
+        .unwrap();
+
+        let graph = CodeGraphBuilder::new(dir.path()).call_stopwords(&["trace"]).build();
+
+        let callees: Vec<String> = graph.dependencies("run").into_iter().map(|d| d.symbol).collect();
+        assert_eq!(callees, vec!["work".to_string()]);
+    }
+
+    #[test]
+    fn test_rebuild_file_keeps_build_settings() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("gen")).unwrap();
+        fs::write(root.join("lib.rs"), "fn trace() {}\nfn work() {}\nfn run() {\n    trace();\n}\n").unwrap();
+        fs::write(root.join("gen/api.rs"), "pub fn api() {}\n").unwrap();
+
+        let mut graph = CodeGraphBuilder::new(root)
+            .call_stopwords(&["trace"])
+            .generated("gen/")
+            .truncation_marker(None)
+            .build();
+        let meta = graph.meta().unwrap();
+        assert_eq!(meta.call_stopwords, vec!["trace".to_string()]);
+        assert_eq!(meta.generated, vec!["gen/".to_string()]);
+        assert_eq!(meta.extract_options.truncation_marker, None);
+
+        fs::write(root.join("lib.rs"), "fn trace() {}\nfn work() {}\nfn run() {\n    trace();\n    work();\n}\n").unwrap();
+        fs::write(root.join("gen/api.rs"), "pub fn api() {}\npub fn api2() {}\n").unwrap();
+        rebuild_file(&mut graph, &root.join("lib.rs")).unwrap();
+        rebuild_file(&mut graph, &root.join("gen/api.rs")).unwrap();
+
+        let callees: Vec<String> = graph.dependencies("run").into_iter().map(|d| d.symbol).collect();
+        assert_eq!(callees, vec!["work".to_string()]);
+        assert!(graph.find_qualified(&root.join("gen/api.rs"), "api2").unwrap().generated);
+    }
+
+    #[test]
+    fn test_rebuild_file_skips_what_the_build_skipped() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("vendor/keep")).unwrap();
+        fs::write(root.join("lib.rs"), "fn small() {}\n").unwrap();
+
+        let built = CodeGraphBuilder::new(root)
+            .languages(&[SupportedLanguage::Rust])
+            .ignore("vendor/")
+            .also_index("vendor/keep")
+            .max_file_bytes(64)
+            .build();
+        let meta = built.meta().unwrap();
+        assert_eq!(meta.languages, Some(vec![SupportedLanguage::Rust]));
+        assert_eq!(meta.ignores, vec!["vendor/".to_string()]);
+        assert_eq!(meta.also_index, vec![PathBuf::from("vendor/keep")]);
+        assert_eq!(meta.max_file_bytes, Some(64));
+
+        // The settings come back with a saved graph
+        let cache = root.join("graph.bin");
+        built.save(&cache).unwrap();
+        let mut graph = CodeGraph::load(&cache).unwrap();
+
+        fs::write(root.join("tool.py"), "def tool():\n    pass\n").unwrap();
+        fs::write(root.join("vendor/dep.rs"), "fn dep() {}\n").unwrap();
+        fs::write(root.join("vendor/keep/kept.rs"), "fn kept() {}\n").unwrap();
+        fs::write(root.join("lib.rs"), format!("fn small() {{}}\n// {}\n", "x".repeat(64))).unwrap();
+        for file in ["tool.py", "vendor/dep.rs", "vendor/keep/kept.rs", "lib.rs"] {
+            rebuild_file(&mut graph, &root.join(file)).unwrap();
+        }
+
+        assert!(graph.search("tool", 1).is_empty());
+        assert!(graph.search("dep", 1).is_empty());
+        assert_eq!(graph.search("kept", 1).len(), 1);
+        // Grown past the size limit, so it leaves the graph
+        assert!(graph.search("small", 1).is_empty());
+    }
+
+    #[test]
+    fn test_index_kinds_survive_save_and_rebuild() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("lib.rs"), "pub struct Config;
+pub fn load() {}
+").unwrap();
+
+        let graph = CodeGraphBuilder::new(root).index_kinds(&[NodeKind::Function]).build();
+        assert_eq!(graph.meta().unwrap().index_kinds, vec![NodeKind::Function]);
+        let cache = root.join("graph.bin");
+        graph.save(&cache).unwrap();
+
+        let mut loaded = CodeGraph::load(&cache).unwrap();
+        fs::write(root.join("lib.rs"), "pub struct Config;
+pub struct Extra;
+pub fn load() {}
+pub fn save() {}
+").unwrap();
+        rebuild_file(&mut loaded, &root.join("lib.rs")).unwrap();
+
+        assert!(!loaded.search("save", 1).is_empty());
+        assert!(loaded.search("Extra", 1).is_empty());
+        assert!(loaded.search("Config", 1).is_empty());
+    }
+
+    #[test]
+    fn test_builder_reports_progress() {
+        let dir = tempdir().unwrap();
+        for i in 0..5 {
+            fs::write(dir.path().join(format!("f{}.rs", i)), format!("fn f{}() {{}}\n", i)).unwrap();
+        }
+
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let sink_frames = Arc::clone(&frames);
+        let graph = CodeGraphBuilder::new(dir.path())
+            .on_progress(ProgressSink::new(move |p| sink_frames.lock().unwrap().push(p)))
+            .build();
+
+        let frames = frames.lock().unwrap();
+        assert!(!frames.is_empty());
+        assert!(frames.iter().all(|p| p.total == 5 && p.parsed <= 5));
+        assert!(frames.iter().any(|p| p.parsed == 5));
+        assert_eq!(graph.stats().file_count, 5);
+    }
+
+    #[test]
+    fn test_grammar_failures_aggregate_per_language() {
+        let failures = GrammarFailures::default();
+        let error = AnchorError::GrammarAbiMismatch(
+            SupportedLanguage::Go,
+            "Incompatible language version 99. Expected minimum 13, maximum 15".to_string(),
+        );
+        assert!(error.to_string().starts_with("Go grammar is incompatible"));
+
+        // Every Go file fails, from whichever thread parses it
+        let warned: usize = (0..40)
+            .into_par_iter()
+            .map(|i| {
+                let language = if i % 10 == 0 { SupportedLanguage::Java } else { SupportedLanguage::Go };
+                failures.record(language, "Incompatible language version 99") as usize
+            })
+            .sum();
+
+        assert_eq!(warned, 2, "one warning per language");
+        let failures = failures.into_inner();
+        let summary: Vec<(&str, usize)> = failures
+            .iter()
+            .map(|f| (f.language.name(), f.skipped_files))
+            .collect();
+        assert_eq!(summary, vec![("Go", 36), ("Java", 4)]);
+    }
+
+    #[test]
+    fn test_failing_grammar_is_reported_after_build() {
+        let dir = tempdir().unwrap();
+        for name in ["a.go", "b.go", "c.go"] {
+            fs::write(dir.path().join(name), "package main\n\nfunc run() {}\n").unwrap();
+        }
+        fs::write(dir.path().join("lib.rs"), "pub fn kept() {}\n").unwrap();
+        crate::parser::extractor::broken_grammars::break_under(dir.path(), SupportedLanguage::Go);
+
+        for streaming in [false, true] {
+            let graph = CodeGraphBuilder::new(dir.path()).streaming(streaming).build();
+
+            let failures = &graph.meta().unwrap().grammar_failures;
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].language, SupportedLanguage::Go);
+            assert_eq!(failures[0].skipped_files, 3);
+            assert!(failures[0].error.contains("Incompatible language version"));
+
+            // Other languages still build, and stats say what went missing
+            assert!(!graph.search("kept", 1).is_empty());
+            assert!(graph.search("run", 1).is_empty());
+            let stats = serde_json::to_value(crate::query::anchor_stats(&graph)).unwrap();
+            assert_eq!(stats["grammar_failures"][0]["skipped_files"], 3);
+        }
+    }
+
+    #[test]
+    fn test_streaming_build_matches_batch() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("auth")).unwrap();
+        fs::write(
+            dir.path().join("main.rs"),
+            "mod auth;\n\nfn main() {\n    let s = Session::new();\n    login(&s);\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("auth/mod.rs"),
+            "pub struct Session {}\n\nimpl Session {\n    pub fn new() -> Self {\n        Session {}\n    }\n}\n\npub fn login(s: &Session) {\n    check(s);\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("auth/check.rs"), "pub fn check(s: &Session) {}\n").unwrap();
+        fs::write(
+            dir.path().join("app.py"),
+            "class App:\n    def run(self):\n        start()\n\ndef start():\n    pass\n",
+        )
+        .unwrap();
+
+        let batch = CodeGraphBuilder::new(dir.path()).build();
+        let streamed = CodeGraphBuilder::new(dir.path()).streaming(true).build();
+
+        fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+            serde_json::to_value(value).unwrap()
+        }
+        assert_eq!(json(&batch.stats()), json(&streamed.stats()));
+        for name in ["main", "login", "check", "Session", "new", "App", "run", "start"] {
+            assert_eq!(
+                json(&batch.search(name, 10)),
+                json(&streamed.search(name, 10)),
+                "search results differ for {}",
+                name
+            );
+            assert_eq!(
+                json(&batch.dependents(name)),
+                json(&streamed.dependents(name)),
+                "dependents differ for {}",
+                name
+            );
+        }
+        assert!(!streamed.dependents("check").is_empty());
+    }
+}
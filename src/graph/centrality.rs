@@ -0,0 +1,140 @@
+//! Symbol importance — PageRank over the call graph.
+//!
+//! Every call passes a share of the caller's rank to the callee, so a
+//! symbol called from many places, or from other important symbols, ends
+//! up with a high score. Searches use it to list the match that matters
+//! most first.
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::HashMap;
+
+use super::engine::CodeGraph;
+use super::types::{EdgeKind, NodeKind};
+
+/// Share of rank that follows calls; the rest is spread evenly.
+const DAMPING: f64 = 0.85;
+
+/// Upper bound on power iterations.
+const MAX_ITERATIONS: usize = 50;
+
+/// Stop once no score moves more than this between iterations.
+const TOLERANCE: f64 = 1e-9;
+
+impl CodeGraph {
+    /// Score every live symbol by PageRank over `Calls` edges and store it
+    /// as the node's `importance`, scaled so the highest score is 1.0.
+    ///
+    /// Scores describe the graph when this runs: `build` computes them,
+    /// and callers patching the graph file by file should call it again,
+    /// or [`refresh_centrality`](Self::refresh_centrality) once a batch of
+    /// files is in.
+    pub fn compute_centrality(&mut self) {
+        self.centrality_stale = false;
+        let graph = self.inner_graph();
+        let nodes: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !node.removed && !matches!(node.kind, NodeKind::File | NodeKind::Import)
+            })
+            .collect();
+        if nodes.is_empty() {
+            return;
+        }
+        let position: HashMap<NodeIndex, usize> =
+            nodes.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+
+        // Callees of each node, as positions in `nodes`
+        let callees: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|&idx| {
+                graph
+                    .edges_directed(idx, Direction::Outgoing)
+                    .filter(|e| e.weight().kind == EdgeKind::Calls)
+                    .filter_map(|e| position.get(&e.target()).copied())
+                    .collect()
+            })
+            .collect();
+
+        let n = nodes.len() as f64;
+        let mut rank = vec![1.0 / n; nodes.len()];
+        for _ in 0..MAX_ITERATIONS {
+            // Symbols that call nothing spread their rank over everyone
+            let dangling: f64 = callees
+                .iter()
+                .zip(&rank)
+                .filter(|(out, _)| out.is_empty())
+                .map(|(_, r)| r)
+                .sum();
+            let base = (1.0 - DAMPING) / n + DAMPING * dangling / n;
+
+            let mut next = vec![base; nodes.len()];
+            for (from, out) in callees.iter().enumerate() {
+                let share = DAMPING * rank[from] / out.len().max(1) as f64;
+                for &to in out {
+                    next[to] += share;
+                }
+            }
+
+            let delta = next
+                .iter()
+                .zip(&rank)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            rank = next;
+            if delta < TOLERANCE {
+                break;
+            }
+        }
+
+        let max = rank.iter().copied().fold(0.0, f64::max);
+        let graph = self.inner_graph_mut();
+        for idx in graph.node_indices() {
+            graph[idx].importance = 0.0;
+        }
+        for (&idx, score) in nodes.iter().zip(rank) {
+            graph[idx].importance = if max > 0.0 { score / max } else { 0.0 };
+        }
+    }
+
+    /// Recompute importance scores if [`rebuild_file`](super::rebuild_file)
+    /// has changed files since they were computed. Rebuilding only marks
+    /// the scores stale, so a batch of changed files pays for one pass.
+    pub fn refresh_centrality(&mut self) {
+        if self.centrality_stale {
+            self.compute_centrality();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_compute_centrality_ranks_callees() {
+        let source = "fn log() {}\n\nfn save() {\n    log();\n}\n\nfn load() {\n    log();\n}\n\nfn main() {\n    save();\n    load();\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/main.rs"), source).unwrap(),
+        ]);
+        graph.compute_centrality();
+
+        let importance = |name: &str| graph.search(name, 1)[0].importance;
+        assert_eq!(importance("log"), 1.0);
+        assert!(importance("save") > importance("main"));
+        assert_eq!(importance("save"), importance("load"));
+        assert!(importance("main") > 0.0);
+
+        // An uncalled `log` indexed earlier still ranks below the called one
+        graph.replace_file(parser::extract_file(&PathBuf::from("src/a.rs"), "fn log() {}\n").unwrap());
+        graph.compute_centrality();
+        let results = graph.search("log", 2);
+        assert_eq!(results[0].file, PathBuf::from("src/main.rs"));
+        assert!(results[1].importance < results[0].importance);
+    }
+}
@@ -0,0 +1,195 @@
+//! Co-change coupling from git history.
+//!
+//! Files that keep changing in the same commits are coupled even when no
+//! call or import connects them (a handler and its template, a schema and
+//! its migration). `CodeGraph::cochange` ranks those files for a symbol
+//! and maps them back to the symbols they define.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::engine::CodeGraph;
+use super::meta::run_git;
+use super::types::NodeKind;
+
+/// How many recent commits to scan.
+const MAX_COMMITS: &str = "1000";
+
+/// Marks the start of a commit in `git log` output.
+const COMMIT_MARKER: &str = "@@commit";
+
+/// A symbol in a file that changes together with the queried symbol's file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoChange {
+    pub symbol: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Commits that touched both files.
+    pub shared_commits: usize,
+}
+
+impl CodeGraph {
+    /// Symbols whose files are most often committed together with the file
+    /// defining `symbol`, strongest coupling first.
+    ///
+    /// Reads the last 1000 commits of the enclosing git repository. Returns
+    /// an empty list if the symbol is unknown or the code isn't under git.
+    pub fn cochange(&self, symbol: &str, limit: usize) -> Vec<CoChange> {
+        let Some(target) = self.search(symbol, 1).into_iter().next() else {
+            return Vec::new();
+        };
+        let Some(target_file) = canonical(&target.file) else {
+            return Vec::new();
+        };
+        let Some(repo_root) = target_file
+            .parent()
+            .and_then(|dir| run_git(dir, &["rev-parse", "--show-toplevel"]))
+            .map(|out| PathBuf::from(out.trim()))
+        else {
+            return Vec::new();
+        };
+        let format = format!("--pretty=tformat:{}", COMMIT_MARKER);
+        let Some(log) = run_git(
+            &repo_root,
+            &["log", "-n", MAX_COMMITS, "--name-only", &format],
+        ) else {
+            return Vec::new();
+        };
+
+        // Graph files by canonical path, so git's repo-relative paths map back
+        let graph_files: HashMap<PathBuf, PathBuf> = self
+            .all_files()
+            .into_iter()
+            .filter_map(|f| canonical(&f).map(|c| (c, f)))
+            .collect();
+
+        let mut counts: HashMap<&PathBuf, usize> = HashMap::new();
+        for commit in parse_commits(&log) {
+            let files: HashSet<PathBuf> = commit.iter().map(|f| repo_root.join(f)).collect();
+            if !files.contains(&target_file) {
+                continue;
+            }
+            for file in &files {
+                if *file == target_file {
+                    continue;
+                }
+                if let Some(graph_file) = graph_files.get(file) {
+                    *counts.entry(graph_file).or_default() += 1;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(&PathBuf, usize)> = counts.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        ranked
+            .into_iter()
+            .flat_map(|(file, shared_commits)| {
+                let mut symbols = self.symbols_in_file(file);
+                symbols.sort_by_key(|n| n.line_start);
+                symbols
+                    .into_iter()
+                    .filter(|n| n.kind != NodeKind::Import)
+                    .map(move |n| CoChange {
+                        symbol: n.name.clone(),
+                        kind: n.kind,
+                        file: n.file_path.clone(),
+                        line: n.line_start,
+                        shared_commits,
+                    })
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Split `git log --name-only` output into the file lists of each commit.
+fn parse_commits(log: &str) -> Vec<Vec<&str>> {
+    log.split(COMMIT_MARKER)
+        .map(|chunk| {
+            chunk
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|files| !files.is_empty())
+        .collect()
+}
+
+fn canonical(path: &Path) -> Option<PathBuf> {
+    std::fs::canonicalize(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_parse_commits() {
+        let log = "@@commit\n\nsrc/a.rs\nsrc/b.rs\n@@commit\n\nsrc/c.rs\n";
+        assert_eq!(
+            parse_commits(log),
+            vec![vec!["src/a.rs", "src/b.rs"], vec!["src/c.rs"]]
+        );
+    }
+
+    #[test]
+    fn test_cochange_in_git_repo() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+
+        fs::write(root.join("a.rs"), "fn alpha() {}\n").unwrap();
+        fs::write(root.join("b.rs"), "fn beta() {}\n").unwrap();
+        fs::write(root.join("c.rs"), "fn gamma() {}\n").unwrap();
+        git(root, &["add", "a.rs", "b.rs"]);
+        git(root, &["commit", "-q", "-m", "a and b"]);
+        git(root, &["add", "c.rs"]);
+        git(root, &["commit", "-q", "-m", "c alone"]);
+
+        fs::write(root.join("a.rs"), "fn alpha() { beta(); }\n").unwrap();
+        fs::write(root.join("b.rs"), "fn beta() { }\n").unwrap();
+        git(root, &["commit", "-q", "-am", "a and b again"]);
+
+        let graph = build_graph(root);
+
+        let related = graph.cochange("alpha", 10);
+        assert_eq!(related.len(), 1, "{:?}", related);
+        assert_eq!(related[0].symbol, "beta");
+        assert_eq!(related[0].shared_commits, 2);
+
+        // Coupling is mutual
+        let related = graph.cochange("beta", 10);
+        assert_eq!(related[0].symbol, "alpha");
+
+        assert!(graph.cochange("gamma", 10).is_empty());
+    }
+
+    #[test]
+    fn test_cochange_outside_git() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn alpha() {}\n").unwrap();
+
+        let graph = build_graph(dir.path());
+
+        assert!(graph.cochange("alpha", 10).is_empty());
+    }
+}
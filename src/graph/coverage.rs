@@ -0,0 +1,213 @@
+//! Test linkage — which tests exercise a symbol, and which symbols none do.
+//!
+//! A test covers a symbol when it calls it, directly or through test
+//! helpers. This is call-graph linkage, not line coverage: it answers
+//! "where should the next test go?" without running anything.
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+
+use super::engine::{CodeGraph, DependencyInfo};
+use super::types::{EdgeKind, NodeData, NodeKind, Visibility};
+
+/// A public symbol that no test calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UntestedSymbol {
+    pub symbol: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Inbound calls and references: how much code relies on it.
+    pub inbound: usize,
+}
+
+impl CodeGraph {
+    /// Tests that exercise `symbol`: test functions calling it directly or
+    /// through test helpers, nearest first.
+    pub fn tests_for(&self, symbol: &str) -> Vec<DependencyInfo> {
+        let graph = self.inner_graph();
+        let targets: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !node.removed && node.name == symbol && node.kind != NodeKind::File
+            })
+            .collect();
+
+        let mut visited: HashSet<NodeIndex> = targets.iter().copied().collect();
+        let mut queue: VecDeque<NodeIndex> = targets.into_iter().collect();
+        let mut tests = Vec::new();
+
+        while let Some(idx) = queue.pop_front() {
+            for edge in graph.edges_directed(idx, Direction::Incoming) {
+                let caller = edge.source();
+                let node = &graph[caller];
+                if edge.weight().kind != EdgeKind::Calls || node.removed || !node.is_test {
+                    continue;
+                }
+                if visited.insert(caller) {
+                    tests.push(DependencyInfo {
+                        symbol: node.name.clone(),
+                        kind: node.kind,
+                        file: node.file_path.clone(),
+                        line: node.line_start,
+                        relationship: EdgeKind::Calls,
+                        count: edge.weight().count,
+                    });
+                    queue.push_back(caller);
+                }
+            }
+        }
+
+        tests
+    }
+
+    /// Public functions and methods that no test calls, most depended-on
+    /// first.
+    ///
+    /// With `skip_accessors`, trivial getters and setters (short methods
+    /// named `get_*`/`set_*`/`is_*` or that just return a field) are left
+    /// out.
+    pub fn untested_symbols(&self, skip_accessors: bool) -> Vec<UntestedSymbol> {
+        let graph = self.inner_graph();
+
+        // Everything test code calls is covered; test helpers are test code
+        // too, so a test reaching a symbol through a helper still counts
+        let covered: HashSet<NodeIndex> = graph
+            .edge_indices()
+            .filter_map(|e| {
+                let (from, to) = graph.edge_endpoints(e)?;
+                let caller = &graph[from];
+                (graph[e].kind == EdgeKind::Calls && caller.is_test && !caller.removed)
+                    .then_some(to)
+            })
+            .collect();
+
+        let mut untested: Vec<UntestedSymbol> = graph
+            .node_indices()
+            .filter(|idx| !covered.contains(idx))
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !node.removed
+                    && !node.is_test
+                    && node.visibility == Visibility::Public
+                    && matches!(node.kind, NodeKind::Function | NodeKind::Method)
+                    && !(skip_accessors && is_accessor(node))
+            })
+            .map(|idx| {
+                let node = &graph[idx];
+                let inbound = graph
+                    .edges_directed(idx, Direction::Incoming)
+                    .filter(|e| !matches!(e.weight().kind, EdgeKind::Defines | EdgeKind::Contains))
+                    .filter(|e| !graph[e.source()].removed)
+                    .count();
+                UntestedSymbol {
+                    symbol: node.name.clone(),
+                    kind: node.kind,
+                    file: node.file_path.clone(),
+                    line: node.line_start,
+                    inbound,
+                }
+            })
+            .collect();
+
+        untested.sort_by(|a, b| {
+            b.inbound
+                .cmp(&a.inbound)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        untested
+    }
+}
+
+/// Is this a trivial getter or setter: a method of at most three lines
+/// named like an accessor, or whose body only returns a field?
+fn is_accessor(node: &NodeData) -> bool {
+    if node.kind != NodeKind::Method || node.line_end.saturating_sub(node.line_start) > 2 {
+        return false;
+    }
+
+    let name = node.name.trim_start_matches('_');
+    let prefixed = ["get", "set", "is", "has"].iter().any(|prefix| {
+        name.strip_prefix(prefix).is_some_and(|rest| {
+            rest.starts_with('_') || rest.starts_with(|c: char| c.is_uppercase())
+        })
+    });
+    if prefixed {
+        return true;
+    }
+
+    // Body after the signature: `{ &self.name }`, `return self._name`
+    let code = node.code_snippet.trim();
+    let body = match code.split_once('{') {
+        Some((_, rest)) => rest.trim_end().trim_end_matches('}'),
+        None => code.rsplit_once(':').map_or("", |(_, rest)| rest),
+    };
+    let body = body.trim().trim_end_matches(';').trim();
+    let body = body.strip_prefix("return ").unwrap_or(body).trim_start_matches('&');
+    ["self.", "this."].iter().any(|receiver| {
+        body.strip_prefix(receiver).is_some_and(|field| {
+            !field.is_empty() && field.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn build(files: &[(&str, &str)]) -> CodeGraph {
+        let extractions = files
+            .iter()
+            .map(|(path, source)| parser::extract_file(&PathBuf::from(path), source).unwrap())
+            .collect();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(extractions);
+        graph
+    }
+
+    #[test]
+    fn test_untested_symbols_excludes_tested() {
+        let graph = build(&[
+            (
+                "src/lib.rs",
+                "pub fn parse(s: &str) -> u32 {\n    s.len() as u32\n}\n\npub fn render(n: u32) -> String {\n    n.to_string()\n}\n\npub fn run() {\n    render(parse(\"x\"));\n}\n\nfn private_helper() {}\n",
+            ),
+            (
+                "tests/parse.rs",
+                "#[test]\nfn parses() {\n    check(\"1\");\n}\n\nfn check(s: &str) {\n    parse(s);\n}\n",
+            ),
+        ]);
+
+        let tests: Vec<String> = graph.tests_for("parse").into_iter().map(|t| t.symbol).collect();
+        assert_eq!(tests, vec!["check", "parses"]);
+
+        let untested: Vec<(String, usize)> = graph
+            .untested_symbols(false)
+            .into_iter()
+            .map(|u| (u.symbol, u.inbound))
+            .collect();
+        // render is called by run, so it ranks above the uncalled run
+        assert_eq!(untested, vec![("render".to_string(), 1), ("run".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_untested_symbols_skip_accessors() {
+        let graph = build(&[(
+            "src/user.rs",
+            "pub struct User { name: String }\n\nimpl User {\n    pub fn name(&self) -> &str {\n        &self.name\n    }\n\n    pub fn greet(&self) -> String {\n        format!(\"hi {}\", self.name)\n    }\n}\n",
+        )]);
+
+        let names = |skip| -> Vec<String> {
+            graph.untested_symbols(skip).into_iter().map(|u| u.symbol).collect()
+        };
+        assert_eq!(names(false), vec!["name", "greet"]);
+        assert_eq!(names(true), vec!["greet"]);
+    }
+}
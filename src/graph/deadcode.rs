@@ -0,0 +1,112 @@
+//! Dead code — symbols nothing in the project uses.
+//!
+//! Only callables are checked: calls and renders are the uses the graph
+//! records, while a private struct or constant used by name has no edge
+//! pointing at it and would always look dead.
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::engine::CodeGraph;
+use super::types::{EdgeKind, NodeKind, Visibility};
+
+/// A function, method or component that nothing references.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreferencedSymbol {
+    pub symbol: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl CodeGraph {
+    /// Non-public functions, methods and components with no inbound calls,
+    /// imports or references, in file and line order.
+    ///
+    /// Entry points (`main`), exported symbols (public, `#[no_mangle]`),
+    /// test code, and trait and interface methods, which may be reached
+    /// through dynamic dispatch, are never reported.
+    pub fn unreferenced_symbols(&self) -> Vec<UnreferencedSymbol> {
+        let graph = self.inner_graph();
+
+        let mut unreferenced: Vec<UnreferencedSymbol> = graph
+            .node_indices()
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !node.removed
+                    && !node.is_test
+                    && node.visibility != Visibility::Public
+                    && node.name != "main"
+                    && matches!(node.kind, NodeKind::Function | NodeKind::Method | NodeKind::Component)
+            })
+            .filter(|&idx| !self.is_referenced(idx) && !self.is_trait_method(idx))
+            .map(|idx| {
+                let node = &graph[idx];
+                UnreferencedSymbol {
+                    symbol: node.name.clone(),
+                    kind: node.kind,
+                    file: node.file_path.clone(),
+                    line: node.line_start,
+                }
+            })
+            .collect();
+
+        unreferenced.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+        unreferenced
+    }
+
+    /// Does any live node use this one? Structure edges (a file defining
+    /// it, a class containing it) don't count.
+    fn is_referenced(&self, idx: NodeIndex) -> bool {
+        let graph = self.inner_graph();
+        graph
+            .edges_directed(idx, Direction::Incoming)
+            .filter(|e| !matches!(e.weight().kind, EdgeKind::Defines | EdgeKind::Contains))
+            .any(|e| !graph[e.source()].removed)
+    }
+
+    /// Is this a method declared by a trait or interface, or defined in a
+    /// type that implements one?
+    fn is_trait_method(&self, idx: NodeIndex) -> bool {
+        let graph = self.inner_graph();
+        if graph[idx].kind != NodeKind::Method {
+            return false;
+        }
+
+        graph
+            .edges_directed(idx, Direction::Incoming)
+            .filter(|e| e.weight().kind == EdgeKind::Contains)
+            .map(|e| e.source())
+            .any(|container| {
+                matches!(graph[container].kind, NodeKind::Trait | NodeKind::Interface)
+                    || graph
+                        .edges_directed(container, Direction::Outgoing)
+                        .any(|e| e.weight().kind == EdgeKind::Implements)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    #[test]
+    fn test_unreferenced_symbols() {
+        let source = "pub trait Shape {\n    fn area(&self) -> u32;\n}\n\npub struct Square(u32);\n\nimpl Shape for Square {\n    fn area(&self) -> u32 {\n        self.0 * self.0\n    }\n}\n\nimpl Square {\n    fn side(&self) -> u32 {\n        self.0\n    }\n}\n\nfn helper() -> u32 {\n    2\n}\n\nfn unused() {}\n\n#[no_mangle]\nfn exported() {}\n\npub fn api() -> u32 {\n    helper()\n}\n\nfn main() {}\n\n#[test]\nfn checks() {}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/lib.rs"), source).unwrap(),
+        ]);
+
+        let dead: Vec<(String, usize)> = graph
+            .unreferenced_symbols()
+            .into_iter()
+            .map(|u| (u.symbol, u.line))
+            .collect();
+        assert_eq!(dead, vec![("side".to_string(), 14), ("unused".to_string(), 23)]);
+    }
+}
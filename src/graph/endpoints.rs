@@ -0,0 +1,230 @@
+//! Cross-language API consistency checks.
+//!
+//! Matches client calls (`fetch("/api/users")`) against backend route
+//! definitions (`@app.get("/api/users")`) to find integration drift:
+//! calls to routes nobody serves, and routes nobody calls.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::engine::CodeGraph;
+use super::types::{ApiEndpoint, ApiEndpointKind};
+
+/// What kind of drift a mismatch represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    /// A client call with no matching backend route.
+    DeadCall,
+    /// A backend route with no client caller.
+    UnusedEndpoint,
+}
+
+/// A single endpoint without a counterpart on the other side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mismatch {
+    pub kind: MismatchKind,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl Mismatch {
+    fn new(kind: MismatchKind, ep: &ApiEndpoint) -> Self {
+        Self {
+            kind,
+            url: ep.url.clone(),
+            method: ep.method.clone(),
+            file: ep.file.clone(),
+            line: ep.line,
+            scope: ep.scope.clone(),
+        }
+    }
+}
+
+impl CodeGraph {
+    /// Report client calls with no backend route and routes with no caller.
+    ///
+    /// Endpoints are matched by normalized path (host and query string
+    /// stripped, `:param` segments match anything) and HTTP method (an
+    /// unknown method matches any method).
+    pub fn endpoint_mismatches(&self) -> Vec<Mismatch> {
+        let (defined, consumed): (Vec<&ApiEndpoint>, Vec<&ApiEndpoint>) = self
+            .endpoints()
+            .partition(|ep| ep.kind == ApiEndpointKind::Defines);
+
+        let mut mismatches = Vec::new();
+
+        for call in &consumed {
+            if !defined.iter().any(|def| endpoints_match(def, call)) {
+                mismatches.push(Mismatch::new(MismatchKind::DeadCall, call));
+            }
+        }
+
+        for def in &defined {
+            if !consumed.iter().any(|call| endpoints_match(def, call)) {
+                mismatches.push(Mismatch::new(MismatchKind::UnusedEndpoint, def));
+            }
+        }
+
+        mismatches.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        mismatches
+    }
+
+    /// Endpoints in file and line order, optionally only those with this
+    /// HTTP method (any case) or whose path starts with `prefix`.
+    ///
+    /// The prefix is compared against the normalized path, so
+    /// `/api/users` also finds `fetch("https://host/API/users/42")`.
+    pub fn endpoints_matching(&self, method: Option<&str>, prefix: Option<&str>) -> Vec<&ApiEndpoint> {
+        let prefix = prefix.map(str::to_lowercase);
+        let mut endpoints: Vec<&ApiEndpoint> = self
+            .endpoints()
+            .filter(|ep| {
+                method.is_none_or(|m| ep.method.as_deref().is_some_and(|em| em.eq_ignore_ascii_case(m)))
+            })
+            .filter(|ep| {
+                prefix
+                    .as_deref()
+                    .is_none_or(|p| normalize_path(&ep.url).starts_with(p))
+            })
+            .collect();
+        endpoints.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        endpoints
+    }
+}
+
+/// Do a route definition and a client call refer to the same endpoint?
+fn endpoints_match(def: &ApiEndpoint, call: &ApiEndpoint) -> bool {
+    let methods_ok = match (&def.method, &call.method) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => true,
+    };
+    methods_ok && paths_match(&normalize_path(&def.url), &normalize_path(&call.url))
+}
+
+/// Strip scheme, host, query string and trailing slash from a URL.
+fn normalize_path(url: &str) -> String {
+    let path = match url.find("://") {
+        Some(i) => {
+            let rest = &url[i + 3..];
+            rest.find('/').map(|j| &rest[j..]).unwrap_or("/")
+        }
+        None => url,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let path = path.trim_end_matches('/');
+
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path.to_lowercase()
+    }
+}
+
+/// Compare paths segment by segment; `:param` matches any segment.
+fn paths_match(a: &str, b: &str) -> bool {
+    let a: Vec<&str> = a.split('/').collect();
+    let b: Vec<&str> = b.split('/').collect();
+
+    a.len() == b.len()
+        && a
+            .iter()
+            .zip(&b)
+            .all(|(x, y)| x == y || x.starts_with(':') || y.starts_with(':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("https://api.example.com/api/users/?page=2"), "/api/users");
+        assert_eq!(normalize_path("/API/Users"), "/api/users");
+        assert_eq!(normalize_path("/"), "/");
+    }
+
+    #[test]
+    fn test_paths_match_params() {
+        assert!(paths_match("/api/users/:param", "/api/users/42"));
+        assert!(!paths_match("/api/users/:param", "/api/users"));
+    }
+
+    #[test]
+    fn test_endpoint_mismatches() {
+        let server = r#"
+app.get('/api/users', (req, res) => res.json([]));
+app.delete('/api/users/:id', (req, res) => res.sendStatus(204));
+"#;
+        let client = r#"
+async function loadUsers() {
+    return fetch('/api/users');
+}
+
+async function loadMissing() {
+    return fetch('/api/missing');
+}
+"#;
+        let extractions = vec![
+            parser::extract_file(&PathBuf::from("src/server/routes.js"), server).unwrap(),
+            parser::extract_file(&PathBuf::from("src/web/client.ts"), client).unwrap(),
+        ];
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(extractions);
+
+        let mismatches = graph.endpoint_mismatches();
+        assert_eq!(mismatches.len(), 2, "{:?}", mismatches);
+
+        let dead = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::DeadCall)
+            .unwrap();
+        assert_eq!(dead.url, "/api/missing");
+        assert_eq!(dead.scope.as_deref(), Some("loadMissing"));
+
+        let unused = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::UnusedEndpoint)
+            .unwrap();
+        assert_eq!(unused.url, "/api/users/:param");
+        assert_eq!(unused.method.as_deref(), Some("DELETE"));
+    }
+
+    #[test]
+    fn test_endpoints_matching() {
+        let server = r#"
+app.get('/api/users', (req, res) => res.json([]));
+app.delete('/api/users/:id', (req, res) => res.sendStatus(204));
+app.get('/health', (req, res) => res.sendStatus(200));
+"#;
+        let client = "async function loadUsers() {\n    return fetch('https://example.com/API/users');\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/server/routes.js"), server).unwrap(),
+            parser::extract_file(&PathBuf::from("src/web/client.ts"), client).unwrap(),
+        ]);
+
+        assert_eq!(graph.endpoints_matching(None, None).len(), 4);
+
+        let deletes = graph.endpoints_matching(Some("delete"), None);
+        assert_eq!(deletes.len(), 1);
+        assert_eq!(deletes[0].url, "/api/users/:param");
+        assert_eq!(deletes[0].line, 3);
+
+        let users: Vec<&str> = graph
+            .endpoints_matching(None, Some("/api/users"))
+            .iter()
+            .map(|ep| ep.file.to_str().unwrap())
+            .collect();
+        assert_eq!(users, vec!["src/server/routes.js", "src/server/routes.js", "src/web/client.ts"]);
+
+        assert!(graph.endpoints_matching(Some("POST"), Some("/api")).is_empty());
+    }
+}
@@ -3,15 +3,24 @@
 //! Uses petgraph to store code relationships and provides
 //! query methods for searching and traversing the graph.
 
+use petgraph::algo::tarjan_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeFiltered;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing::{debug, info};
 
+use super::imports::ImportResolver;
+use super::meta::GraphMeta;
+use super::owners::CodeOwners;
+use super::signature::SignatureShape;
 use super::types::*;
+use crate::parser::SupportedLanguage;
 
 /// The main code graph — holds all nodes, edges, and indexes for fast lookup.
 #[derive(Clone)]
@@ -20,10 +29,68 @@ pub struct CodeGraph {
     graph: DiGraph<NodeData, EdgeData>,
     /// Index: file path -> node index (for File nodes).
     file_index: HashMap<PathBuf, NodeIndex>,
+    /// Index: file name -> File nodes with that name, sorted by path.
+    files_by_name: HashMap<OsString, Vec<NodeIndex>>,
     /// Index: symbol name -> list of node indexes (for quick name lookup).
     symbol_index: HashMap<String, Vec<NodeIndex>>,
     /// Index: (file_path, symbol_name) -> node index (for unique symbol resolution).
     qualified_index: HashMap<(PathBuf, String), NodeIndex>,
+    /// Index: endpoint URL -> API routes defined or called at it.
+    endpoints: HashMap<String, Vec<ApiEndpoint>>,
+    /// Index: `__init__.py` -> names its `__all__` re-exports.
+    pub(crate) reexports: HashMap<PathBuf, Vec<String>>,
+    /// How and when the graph was built (set by `build_graph`).
+    pub(crate) meta: Option<GraphMeta>,
+    /// Symbol kinds kept when ingesting extractions; empty keeps every kind.
+    index_kinds: Vec<NodeKind>,
+    /// `CODEOWNERS` rules of the root, read on first use.
+    pub(crate) codeowners: OnceLock<Option<CodeOwners>>,
+    /// Whether files changed since importance scores were computed.
+    pub(crate) centrality_stale: bool,
+}
+
+/// Lookups memoized across queries, for passes that resolve many names
+/// at once (see [`get_file_contexts`](crate::query::get_file_contexts)).
+/// Only valid while the graph is unchanged.
+#[derive(Debug, Default)]
+pub(crate) struct LookupCache {
+    /// Import names per file.
+    imports: HashMap<PathBuf, Vec<String>>,
+    /// Lookups computed rather than answered from the cache.
+    pub(crate) computed: usize,
+}
+
+/// References queued by `CodeGraph::ingest_extraction`, in ingestion order.
+#[derive(Debug, Default)]
+pub(crate) struct PendingLinks {
+    /// (file, call) pairs to resolve into `Calls` edges.
+    calls: Vec<(PathBuf, ExtractedCall)>,
+    /// (file, render) pairs to resolve into `Renders` edges.
+    renders: Vec<(PathBuf, ExtractedCall)>,
+    /// (file, call) pairs to resolve into `DynamicCall` edges.
+    dynamic_calls: Vec<(PathBuf, ExtractedCall)>,
+    /// (file, supertype) pairs to resolve into `Implements`/`Extends` edges.
+    supertypes: Vec<(PathBuf, ExtractedSupertype)>,
+    /// (file, parent, child) names to resolve into `Contains` edges.
+    parents: Vec<(PathBuf, String, String)>,
+    /// (file, import) pairs to resolve into file-to-file `Imports` edges.
+    imports: Vec<(PathBuf, ExtractedImport)>,
+    /// `__init__.py` files whose re-exported symbols are public: those of
+    /// each ingested Python file's packages.
+    reexports: Vec<PathBuf>,
+    /// Retired nodes of a replaced file, for its new symbols to move into.
+    reuse: NodeReuse,
+}
+
+/// Retired nodes of a file being replaced. A new symbol takes the node of
+/// the old symbol with the same name, kind and line, else any spare node,
+/// so editing a file over and over doesn't keep growing the graph.
+#[derive(Debug, Default)]
+pub(crate) struct NodeReuse {
+    /// Old nodes by (name, kind, line) that a new symbol matches.
+    matching: HashMap<(String, NodeKind, usize), NodeIndex>,
+    /// Old nodes no new symbol matches.
+    spare: Vec<NodeIndex>,
 }
 
 impl CodeGraph {
@@ -32,11 +99,34 @@ impl CodeGraph {
         Self {
             graph: DiGraph::new(),
             file_index: HashMap::new(),
+            files_by_name: HashMap::new(),
             symbol_index: HashMap::new(),
             qualified_index: HashMap::new(),
+            endpoints: HashMap::new(),
+            reexports: HashMap::new(),
+            meta: None,
+            index_kinds: Vec::new(),
+            codeowners: OnceLock::new(),
+            centrality_stale: false,
+        }
+    }
+
+    /// Only add symbols of these kinds when ingesting extractions, e.g.
+    /// `[Function, Method, Struct]` to leave out variables, constants and
+    /// imports. An empty slice (the default) keeps every kind. Symbols
+    /// already in the graph are unaffected.
+    pub fn set_index_kinds(&mut self, kinds: &[NodeKind]) {
+        self.index_kinds = kinds.to_vec();
+        if let Some(meta) = &mut self.meta {
+            meta.index_kinds = self.index_kinds.clone();
         }
     }
 
+    /// Does the graph keep symbols of this kind?
+    fn indexes_kind(&self, kind: NodeKind) -> bool {
+        self.index_kinds.is_empty() || self.index_kinds.contains(&kind)
+    }
+
     /// Access the underlying petgraph (for serialization).
     pub(crate) fn inner_graph(&self) -> &DiGraph<NodeData, EdgeData> {
         &self.graph
@@ -61,6 +151,11 @@ impl CodeGraph {
         }
         let data = NodeData::new_file(path.clone());
         let idx = self.graph.add_node(data);
+        if let Some(file_name) = path.file_name() {
+            let files = self.files_by_name.entry(file_name.to_os_string()).or_default();
+            let at = files.partition_point(|&other| self.graph[other].file_path < path);
+            files.insert(at, idx);
+        }
         self.file_index.insert(path, idx);
         idx
     }
@@ -75,21 +170,50 @@ impl CodeGraph {
         line_end: usize,
         code_snippet: String,
     ) -> NodeIndex {
-        let data = NodeData::new_symbol(
-            name.clone(),
-            kind,
-            file_path.clone(),
-            line_start,
-            line_end,
-            code_snippet,
-        );
+        let data = NodeData::new_symbol(name, kind, file_path, line_start, line_end, code_snippet);
+        self.add_symbol_data(data)
+    }
+
+    /// Add a fully populated symbol node (keeps visibility and test flags).
+    pub fn add_symbol_data(&mut self, data: NodeData) -> NodeIndex {
         let idx = self.graph.add_node(data);
+        self.index_symbol(idx);
+        idx
+    }
+
+    /// Add a symbol, moving it into a retired node from `reuse` if one is
+    /// left.
+    fn place_symbol(&mut self, data: NodeData, reuse: &mut NodeReuse) -> NodeIndex {
+        let key = (data.name.clone(), data.kind, data.line_start);
+        match reuse.matching.remove(&key).or_else(|| reuse.spare.pop()) {
+            Some(idx) => {
+                self.graph[idx] = data;
+                self.index_symbol(idx);
+                idx
+            }
+            None => self.add_symbol_data(data),
+        }
+    }
 
-        // Update indexes
-        self.symbol_index.entry(name.clone()).or_default().push(idx);
-        self.qualified_index.insert((file_path, name), idx);
+    /// Add a symbol node to the name and qualified-name indexes.
+    fn index_symbol(&mut self, idx: NodeIndex) {
+        let node = &self.graph[idx];
+        let key = (node.file_path.clone(), node.name.clone());
+        self.symbol_index.entry(node.name.clone()).or_default().push(idx);
+        self.qualified_index.insert(key, idx);
+    }
 
-        idx
+    /// Record an API endpoint (route definition or client call).
+    pub fn add_endpoint(&mut self, endpoint: ApiEndpoint) {
+        self.endpoints.entry(endpoint.url.clone()).or_default().push(endpoint);
+    }
+
+    /// Drop the endpoints recorded for a file.
+    fn remove_endpoints(&mut self, path: &Path) {
+        self.endpoints.retain(|_, eps| {
+            eps.retain(|ep| ep.file != path);
+            !eps.is_empty()
+        });
     }
 
     // ─── Edge Operations ────────────────────────────────────────
@@ -99,17 +223,117 @@ impl CodeGraph {
         self.graph.add_edge(from, to, EdgeData::new(kind));
     }
 
+    /// Add an edge, or count one more occurrence on an existing edge of the
+    /// same kind between the same nodes.
+    fn add_or_count_edge(&mut self, from: NodeIndex, to: NodeIndex, kind: EdgeKind) {
+        let existing = self
+            .graph
+            .edges_connecting(from, to)
+            .find(|edge| edge.weight().kind == kind)
+            .map(|edge| edge.id());
+        match existing {
+            Some(edge) => self.graph[edge].count += 1,
+            None => self.add_edge(from, to, kind),
+        }
+    }
+
     // ─── Query Operations ───────────────────────────────────────
 
     /// Search for symbols by name. Returns up to `limit` results.
     pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        self.search_filtered(query, limit, &SymbolFilter::all())
+    }
+
+    /// Search for symbols by name, skipping symbols the filter rejects
+    /// (tests, private helpers). Returns up to `limit` results.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SymbolFilter,
+    ) -> Vec<SearchResult> {
+        self.search_filtered_with(query, limit, filter, &mut LookupCache::default())
+    }
+
+    /// [`search_filtered`](Self::search_filtered), ignoring case: `codegraph`
+    /// finds `CodeGraph`.
+    pub fn search_ignore_case(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SymbolFilter,
+    ) -> Vec<SearchResult> {
+        self.search_matching(query, limit, filter, true, &mut LookupCache::default())
+    }
+
+    /// [`search_filtered`](Self::search_filtered), reusing lookups from `cache`.
+    pub(crate) fn search_filtered_with(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SymbolFilter,
+        cache: &mut LookupCache,
+    ) -> Vec<SearchResult> {
+        self.search_matching(query, limit, filter, false, cache)
+    }
+
+    /// Name search shared by the public variants.
+    fn search_matching(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SymbolFilter,
+        ignore_case: bool,
+        cache: &mut LookupCache,
+    ) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
         let mut results = Vec::new();
 
-        // Exact match first
-        if let Some(indexes) = self.symbol_index.get(query) {
-            for &idx in indexes.iter().take(limit) {
-                if let Some(result) = self.build_search_result(idx) {
+        // Nodes named exactly `name`, or equal but for case
+        let named = |name: &str| -> Option<Vec<NodeIndex>> {
+            if !ignore_case {
+                return self.symbol_index.get(name).cloned();
+            }
+            let name = name.to_lowercase();
+            let indexes: Vec<NodeIndex> = self
+                .symbol_index
+                .iter()
+                .filter(|(candidate, _)| candidate.to_lowercase() == name)
+                .flat_map(|(_, indexes)| indexes.iter().copied())
+                .collect();
+            (!indexes.is_empty()).then_some(indexes)
+        };
+
+        // Exact match first, most important first. A qualified query
+        // (`Config::new`, `User.save`) matches by its last part and keeps
+        // the symbols whose qualified name ends with it.
+        let qualified = query.contains("::") || query.contains('.');
+        let exact = match named(query) {
+            Some(indexes) => Some((indexes, false)),
+            None if qualified => query
+                .rsplit(['.', ':'])
+                .next()
+                .and_then(named)
+                .map(|indexes| (indexes, true)),
+            None => None,
+        };
+        if let Some((indexes, by_qualified)) = exact {
+            let mut indexes: Vec<NodeIndex> = indexes
+                .into_iter()
+                .filter(|&idx| filter.allows(&self.graph[idx]))
+                .filter(|&idx| {
+                    let qualified_name = &self.graph[idx].qualified_name;
+                    !by_qualified
+                        || if ignore_case {
+                            matches_qualified(&qualified_name.to_lowercase(), &query_lower)
+                        } else {
+                            matches_qualified(qualified_name, query)
+                        }
+                })
+                .collect();
+            indexes.sort_by(|&a, &b| self.by_importance(a, b));
+            for idx in indexes.into_iter().take(limit) {
+                if let Some(result) = self.build_search_result_with(idx, cache) {
                     results.push(result);
                 }
             }
@@ -124,7 +348,7 @@ impl CodeGraph {
                 .flat_map(|(_, indexes)| {
                     indexes.iter().filter_map(|&idx| {
                         let node = &self.graph[idx];
-                        if node.removed {
+                        if node.removed || !filter.allows(node) {
                             return None;
                         }
                         // Score: exact > starts_with > contains
@@ -140,10 +364,12 @@ impl CodeGraph {
                 })
                 .collect();
 
-            scored.sort_by_key(|(score, _)| *score);
+            scored.sort_by(|(score_a, a), (score_b, b)| {
+                score_a.cmp(score_b).then_with(|| self.by_importance(*a, *b))
+            });
 
             for (_, idx) in scored.into_iter().take(limit) {
-                if let Some(result) = self.build_search_result(idx) {
+                if let Some(result) = self.build_search_result_with(idx, cache) {
                     results.push(result);
                 }
             }
@@ -152,6 +378,103 @@ impl CodeGraph {
         results
     }
 
+    /// Search for symbols whose names are close to `query`, for names that
+    /// are misremembered or misspelled (`UserServ`, `UserSevrice`).
+    ///
+    /// Exact matches come first, then names starting with the query, then
+    /// names within [`FUZZY_THRESHOLD`] Jaro-Winkler similarity. Each
+    /// result carries its score in `similarity`.
+    pub fn fuzzy_search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        self.fuzzy_search_filtered(query, limit, &SymbolFilter::all())
+    }
+
+    /// [`fuzzy_search`](Self::fuzzy_search), skipping symbols the filter rejects.
+    pub fn fuzzy_search_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SymbolFilter,
+    ) -> Vec<SearchResult> {
+        let query_lower = query.to_lowercase();
+
+        // Tier: exact < prefix < fuzzy
+        let mut scored: Vec<(u8, f64, NodeIndex)> = self
+            .symbol_index
+            .iter()
+            .filter_map(|(name, indexes)| {
+                let name_lower = name.to_lowercase();
+                let similarity = jaro_winkler(&name_lower, &query_lower);
+                let tier = if name_lower == query_lower {
+                    0
+                } else if name_lower.starts_with(&query_lower) {
+                    1
+                } else if similarity >= FUZZY_THRESHOLD {
+                    2
+                } else {
+                    return None;
+                };
+                Some(indexes.iter().map(move |&idx| (tier, similarity, idx)))
+            })
+            .flatten()
+            .filter(|&(_, _, idx)| {
+                let node = &self.graph[idx];
+                !node.removed && filter.allows(node)
+            })
+            .collect();
+
+        scored.sort_by(|(tier_a, score_a, a), (tier_b, score_b, b)| {
+            tier_a
+                .cmp(tier_b)
+                .then_with(|| score_b.total_cmp(score_a))
+                .then_with(|| self.by_importance(*a, *b))
+        });
+
+        let mut cache = LookupCache::default();
+        scored
+            .into_iter()
+            .take(limit)
+            .filter_map(|(_, similarity, idx)| {
+                let mut result = self.build_search_result_with(idx, &mut cache)?;
+                result.similarity = Some(similarity);
+                Some(result)
+            })
+            .collect()
+    }
+
+    /// Find functions and methods whose signature has the given shape.
+    ///
+    /// `params` are parameter types in order (receivers like `self` are not
+    /// counted); `ret` is the return type, or `None` to accept any. Types are
+    /// compared loosely — see [`SignatureShape`] — and `_` matches anything.
+    ///
+    /// Example: `graph.find_by_signature(&["&str"], Some("bool"))`
+    pub fn find_by_signature(&self, params: &[&str], ret: Option<&str>) -> Vec<SearchResult> {
+        let mut matches: Vec<NodeIndex> = self
+            .symbol_index
+            .values()
+            .flatten()
+            .copied()
+            .filter(|&idx| {
+                let node = &self.graph[idx];
+                !node.removed
+                    && matches!(node.kind, NodeKind::Function | NodeKind::Method)
+                    && SignatureShape::from_code(&node.code_snippet, &node.file_path)
+                        .is_some_and(|shape| shape.matches(params, ret))
+            })
+            .collect();
+
+        // Stable output: by file, then line
+        matches.sort_by(|&a, &b| {
+            let (a, b) = (&self.graph[a], &self.graph[b]);
+            (&a.file_path, a.line_start).cmp(&(&b.file_path, b.line_start))
+        });
+
+        matches
+            .into_iter()
+            .filter_map(|idx| self.build_search_result(idx))
+            .collect()
+    }
+
     /// Get all symbols in the graph (for regex filtering).
     ///
     /// Returns all non-removed symbols as SearchResults.
@@ -164,11 +487,44 @@ impl CodeGraph {
             .collect()
     }
 
+    /// Get all API endpoints (definitions and calls) in live files, in no
+    /// particular order.
+    pub fn endpoints(&self) -> impl Iterator<Item = &ApiEndpoint> {
+        self.endpoints.values().flatten()
+    }
+
+    /// Get the API endpoints at exactly this normalized URL
+    /// (`/api/users/:param`).
+    pub fn endpoints_at(&self, url: &str) -> &[ApiEndpoint] {
+        self.endpoints.get(url).map(Vec::as_slice).unwrap_or(&[])
+    }
+
     /// Get all indexed file paths.
     pub fn all_files(&self) -> Vec<std::path::PathBuf> {
         self.file_index.keys().cloned().collect()
     }
 
+    /// The path of an indexed file as the graph stores it. Paths that don't
+    /// match exactly are compared after [`normalize_path`], so `src\auth.rs`
+    /// finds `src/auth.rs`.
+    pub fn resolve_file(&self, path: &Path) -> Option<&Path> {
+        let idx = self.file_node(path)?;
+        Some(self.graph[idx].file_path.as_path())
+    }
+
+    /// The node of the file at `path`, see [`resolve_file`](Self::resolve_file).
+    fn file_node(&self, path: &Path) -> Option<NodeIndex> {
+        if let Some(&idx) = self.file_index.get(path) {
+            return Some(idx);
+        }
+        let wanted = normalize_path(path);
+        self.file_index
+            .iter()
+            .filter(|(file, _)| normalize_path(file) == wanted)
+            .min_by_key(|(file, _)| *file)
+            .map(|(_, &idx)| idx)
+    }
+
     /// Graph-aware search: finds by file path OR symbol name, then traverses connections.
     ///
     /// This is the PROPER search that uses the graph:
@@ -178,12 +534,34 @@ impl CodeGraph {
     ///
     /// Limits: max 10 initial matches, max 50 symbols, max 100 connections
     pub fn search_graph(&self, query: &str, depth: usize) -> GraphSearchResult {
-        const MAX_INITIAL_MATCHES: usize = 10;
-        const MAX_SYMBOLS: usize = 50;
-        const MAX_CONNECTIONS: usize = 100;
+        self.search_graph_with_fanout(query, depth, None)
+    }
+
+    /// Graph-aware search that follows at most `max_fanout` edges from any
+    /// one node, so a hub with thousands of callers can't flood the
+    /// connections. When a node has more, its best-connected neighbors are
+    /// kept and the result is marked truncated.
+    pub fn search_graph_with_fanout(
+        &self,
+        query: &str,
+        depth: usize,
+        max_fanout: Option<usize>,
+    ) -> GraphSearchResult {
+        let mut result = self.traverse_from_matches(query, depth, max_fanout);
+        result.total = result.symbols.len();
+        result
+    }
 
+    /// The matching and traversal behind [`search_graph_with_fanout`](Self::search_graph_with_fanout).
+    fn traverse_from_matches(
+        &self,
+        query: &str,
+        depth: usize,
+        max_fanout: Option<usize>,
+    ) -> GraphSearchResult {
         let query_lower = query.to_lowercase();
         let mut result = GraphSearchResult::default();
+        let mut fanout_capped = false;
 
         // 1. Try file path match first (limited)
         let file_matches: Vec<_> = self
@@ -213,6 +591,19 @@ impl CodeGraph {
                 }
                 result.matched_files.push(path.to_path_buf());
 
+                // Files importing this one
+                let file_node = &self.graph[file_idx];
+                for edge in self.graph.edges_directed(file_idx, Direction::Incoming) {
+                    let importer = &self.graph[edge.source()];
+                    if edge.weight().kind == EdgeKind::Imports
+                        && importer.kind == NodeKind::File
+                        && self.is_live(edge.source())
+                        && result.connections.len() < MAX_CONNECTIONS
+                    {
+                        result.connections.push(connection(file_node, importer, EdgeKind::Imports, Direction::Incoming));
+                    }
+                }
+
                 // Get all symbols defined in this file (traverse Defines edges)
                 for edge in self.graph.edges_directed(file_idx, Direction::Outgoing) {
                     if result.symbols.len() >= MAX_SYMBOLS {
@@ -242,48 +633,28 @@ impl CodeGraph {
                     }
                     let node = &self.graph[idx];
 
-                    // Outgoing edges (what this symbol uses/calls)
-                    for edge in self.graph.edges_directed(idx, Direction::Outgoing) {
-                        if result.connections.len() >= MAX_CONNECTIONS {
-                            break;
-                        }
-                        let target = edge.target();
-                        if self.is_live(target) && !visited.contains(&target) {
-                            visited.insert(target);
-                            let target_node = &self.graph[target];
-                            if target_node.kind != NodeKind::File {
-                                result.connections.push(ConnectionInfo {
-                                    from: node.name.clone(),
-                                    to: target_node.name.clone(),
-                                    relationship: edge.weight().kind,
-                                });
-                            }
-                        }
-                    }
-
-                    // Incoming edges (what calls/uses this symbol)
-                    for edge in self.graph.edges_directed(idx, Direction::Incoming) {
+                    // Outgoing edges (what this symbol uses/calls), then
+                    // incoming edges (what calls/uses this symbol)
+                    let (edges, capped) = self.fanout(idx, &visited, max_fanout);
+                    fanout_capped |= capped;
+                    for (neighbor, kind, direction) in edges {
                         if result.connections.len() >= MAX_CONNECTIONS {
                             break;
                         }
-                        let source = edge.source();
-                        if self.is_live(source) && !visited.contains(&source) {
-                            visited.insert(source);
-                            let source_node = &self.graph[source];
-                            if source_node.kind != NodeKind::File {
-                                result.connections.push(ConnectionInfo {
-                                    from: source_node.name.clone(),
-                                    to: node.name.clone(),
-                                    relationship: edge.weight().kind,
-                                });
-                            }
+                        visited.insert(neighbor);
+                        let neighbor_node = &self.graph[neighbor];
+                        if neighbor_node.kind != NodeKind::File {
+                            result.connections.push(connection(node, neighbor_node, kind, direction));
                         }
                     }
                 }
             }
 
             // Mark as truncated if we hit limits
-            if result.symbols.len() >= MAX_SYMBOLS || result.connections.len() >= MAX_CONNECTIONS {
+            if result.symbols.len() >= MAX_SYMBOLS
+                || result.connections.len() >= MAX_CONNECTIONS
+                || fanout_capped
+            {
                 result.truncated = true;
             }
 
@@ -291,14 +662,45 @@ impl CodeGraph {
         }
 
         // 2. Try symbol name match (limited) - exact or prefix only, no fuzzy substring
-        let symbol_matches: Vec<NodeIndex> = self
-            .symbol_index
-            .iter()
-            .filter(|(name, _)| {
+        self.traverse_from_symbols(
+            |name| {
                 let name_lower = name.to_lowercase();
                 // Exact match or prefix match only - no arbitrary substring
                 name_lower == query_lower || name_lower.starts_with(&query_lower)
-            })
+            },
+            depth,
+            max_fanout,
+        )
+    }
+
+    /// Graph-aware search from the symbols whose names satisfy `is_match`,
+    /// such as a regex. File paths are not matched. Same limits as
+    /// [`search_graph_with_fanout`](Self::search_graph_with_fanout).
+    pub fn search_graph_matching(
+        &self,
+        is_match: impl FnMut(&str) -> bool,
+        depth: usize,
+        max_fanout: Option<usize>,
+    ) -> GraphSearchResult {
+        let mut result = self.traverse_from_symbols(is_match, depth, max_fanout);
+        result.total = result.symbols.len();
+        result
+    }
+
+    /// BFS from the symbols whose names satisfy `is_match`.
+    fn traverse_from_symbols(
+        &self,
+        mut is_match: impl FnMut(&str) -> bool,
+        depth: usize,
+        max_fanout: Option<usize>,
+    ) -> GraphSearchResult {
+        let mut result = GraphSearchResult::default();
+        let mut fanout_capped = false;
+
+        let symbol_matches: Vec<NodeIndex> = self
+            .symbol_index
+            .iter()
+            .filter(|(name, _)| is_match(name))
             .flat_map(|(_, indexes)| indexes.iter().copied())
             .filter(|&idx| self.is_live(idx))
             .take(MAX_INITIAL_MATCHES)
@@ -340,112 +742,161 @@ impl CodeGraph {
 
             // Continue BFS if within depth limit and connection limit
             if current_depth < depth && result.connections.len() < MAX_CONNECTIONS {
-                // Outgoing edges (what this symbol uses)
-                for edge in self.graph.edges_directed(idx, Direction::Outgoing) {
+                // Outgoing edges (what this symbol uses), then incoming
+                // edges (what uses this symbol)
+                let (edges, capped) = self.fanout(idx, &visited, max_fanout);
+                fanout_capped |= capped;
+                for (neighbor, kind, direction) in edges {
                     if result.connections.len() >= MAX_CONNECTIONS {
                         break;
                     }
-                    let target = edge.target();
-                    if self.is_live(target) && !visited.contains(&target) {
-                        visited.insert(target);
-                        queue.push_back((target, current_depth + 1));
-
-                        let target_node = &self.graph[target];
-                        if target_node.kind != NodeKind::File {
-                            result.connections.push(ConnectionInfo {
-                                from: node.name.clone(),
-                                to: target_node.name.clone(),
-                                relationship: edge.weight().kind,
-                            });
-                        }
-                    }
-                }
+                    visited.insert(neighbor);
+                    queue.push_back((neighbor, current_depth + 1));
 
-                // Incoming edges (what uses this symbol)
-                for edge in self.graph.edges_directed(idx, Direction::Incoming) {
-                    if result.connections.len() >= MAX_CONNECTIONS {
-                        break;
-                    }
-                    let source = edge.source();
-                    if self.is_live(source) && !visited.contains(&source) {
-                        visited.insert(source);
-                        queue.push_back((source, current_depth + 1));
-
-                        let source_node = &self.graph[source];
-                        if source_node.kind != NodeKind::File {
-                            result.connections.push(ConnectionInfo {
-                                from: source_node.name.clone(),
-                                to: node.name.clone(),
-                                relationship: edge.weight().kind,
-                            });
-                        }
+                    let neighbor_node = &self.graph[neighbor];
+                    if neighbor_node.kind != NodeKind::File {
+                        result.connections.push(connection(node, neighbor_node, kind, direction));
                     }
                 }
             }
         }
 
         // Mark as truncated if we hit limits
-        if result.symbols.len() >= MAX_SYMBOLS || result.connections.len() >= MAX_CONNECTIONS {
+        if result.symbols.len() >= MAX_SYMBOLS
+            || result.connections.len() >= MAX_CONNECTIONS
+            || fanout_capped
+        {
             result.truncated = true;
         }
 
         result
     }
 
-    /// Find what depends on a given symbol (who calls it, who references it).
+    // Every edge points from the user to the used: caller -> callee,
+    // file -> import, container -> member, function -> referenced type.
+    // So for any edge A -> B, B is a dependency of A and A a dependent of B,
+    // whatever the edge kind.
+
+    /// Find what depends on a given symbol: the sources of its inbound edges
+    /// (callers, importing files, referencing symbols, its defining file).
     pub fn dependents(&self, symbol_name: &str) -> Vec<DependencyInfo> {
-        let mut deps = Vec::new();
+        self.neighbors(symbol_name, TraversalDirection::Inbound, &[], &[])
+    }
 
-        if let Some(indexes) = self.symbol_index.get(symbol_name) {
-            for &idx in indexes {
-                if !self.is_live(idx) {
-                    continue;
-                }
+    /// Find what a given symbol depends on: the targets of its outbound edges
+    /// (callees, imports, referenced types, contained members).
+    pub fn dependencies(&self, symbol_name: &str) -> Vec<DependencyInfo> {
+        self.neighbors(symbol_name, TraversalDirection::Outbound, &[], &[])
+    }
+
+    /// Everything that depends on a symbol, directly or through other
+    /// dependents, up to `max_depth` hops away: each with its distance,
+    /// nearest first. Structure edges (file defines, class contains) are
+    /// not followed.
+    ///
+    /// Every node is reported once, at its shortest distance, so cycles
+    /// end the walk; it also stops after 500 dependents.
+    pub fn transitive_dependents(&self, symbol: &str, max_depth: usize) -> Vec<(DependencyInfo, usize)> {
+        const MAX_DEPENDENTS: usize = 500;
+
+        let mut frontier: Vec<NodeIndex> = self
+            .nodes_named(symbol)
+            .into_iter()
+            .filter(|&idx| self.is_live(idx))
+            .collect();
+        let mut visited: HashSet<NodeIndex> = frontier.iter().copied().collect();
+        let mut dependents = Vec::new();
+
+        for depth in 1..=max_depth {
+            let mut next = Vec::new();
+            for idx in frontier {
                 for edge in self.graph.edges_directed(idx, Direction::Incoming) {
-                    let source_idx = edge.source();
-                    if !self.is_live(source_idx) {
+                    let kind = edge.weight().kind;
+                    let source = edge.source();
+                    if matches!(kind, EdgeKind::Defines | EdgeKind::Contains)
+                        || !self.is_live(source)
+                        || !visited.insert(source)
+                    {
                         continue;
                     }
-                    let source = &self.graph[source_idx];
-                    let edge_data = edge.weight();
+                    if dependents.len() >= MAX_DEPENDENTS {
+                        return dependents;
+                    }
 
-                    deps.push(DependencyInfo {
-                        symbol: source.name.clone(),
-                        kind: source.kind,
-                        file: source.file_path.clone(),
-                        line: source.line_start,
-                        relationship: edge_data.kind,
-                    });
+                    let node = &self.graph[source];
+                    dependents.push((
+                        DependencyInfo {
+                            symbol: node.name.clone(),
+                            kind: node.kind,
+                            file: node.file_path.clone(),
+                            line: node.line_start,
+                            relationship: kind,
+                            count: edge.weight().count,
+                        },
+                        depth,
+                    ));
+                    next.push(source);
                 }
             }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
         }
 
-        deps
+        dependents
     }
 
-    /// Find what a given symbol depends on (what it calls, what it references).
-    pub fn dependencies(&self, symbol_name: &str) -> Vec<DependencyInfo> {
+    /// Nodes adjacent to a symbol, filtered by node kind and edge kind.
+    ///
+    /// `symbol_name` may also be a file's name or path, which is how import
+    /// edges (file -> import) are reached. An empty `node_kinds` or `edge_kinds` slice matches every kind, so
+    /// `neighbors(name, Outbound, &[], &[])` is the same as `dependencies`.
+    /// `Both` lists outbound neighbors before inbound ones.
+    pub fn neighbors(
+        &self,
+        symbol_name: &str,
+        direction: TraversalDirection,
+        node_kinds: &[NodeKind],
+        edge_kinds: &[EdgeKind],
+    ) -> Vec<DependencyInfo> {
+        let directions: &[Direction] = match direction {
+            TraversalDirection::Outbound => &[Direction::Outgoing],
+            TraversalDirection::Inbound => &[Direction::Incoming],
+            TraversalDirection::Both => &[Direction::Outgoing, Direction::Incoming],
+        };
         let mut deps = Vec::new();
 
-        if let Some(indexes) = self.symbol_index.get(symbol_name) {
-            for &idx in indexes {
+        let indexes = self.nodes_named(symbol_name);
+        for &dir in directions {
+            for &idx in &indexes {
                 if !self.is_live(idx) {
                     continue;
                 }
-                for edge in self.graph.edges_directed(idx, Direction::Outgoing) {
-                    let target_idx = edge.target();
-                    if !self.is_live(target_idx) {
+                for edge in self.graph.edges_directed(idx, dir) {
+                    let edge_data = edge.weight();
+                    if !edge_kinds.is_empty() && !edge_kinds.contains(&edge_data.kind) {
+                        continue;
+                    }
+                    let other_idx = match dir {
+                        Direction::Outgoing => edge.target(),
+                        Direction::Incoming => edge.source(),
+                    };
+                    if !self.is_live(other_idx) {
+                        continue;
+                    }
+                    let other = &self.graph[other_idx];
+                    if !node_kinds.is_empty() && !node_kinds.contains(&other.kind) {
                         continue;
                     }
-                    let target = &self.graph[target_idx];
-                    let edge_data = edge.weight();
 
                     deps.push(DependencyInfo {
-                        symbol: target.name.clone(),
-                        kind: target.kind,
-                        file: target.file_path.clone(),
-                        line: target.line_start,
+                        symbol: other.name.clone(),
+                        kind: other.kind,
+                        file: other.file_path.clone(),
+                        line: other.line_start,
                         relationship: edge_data.kind,
+                        count: edge_data.count,
                     });
                 }
             }
@@ -454,9 +905,64 @@ impl CodeGraph {
         deps
     }
 
+    /// Shortest chain of calls from `from` to `to`, both ends included.
+    ///
+    /// When several symbols share a name, every one named `from` is a
+    /// starting point and any one named `to` ends the search, so the result
+    /// is the shortest path over all of them. `None` if `to` is unreachable.
+    pub fn call_path(&self, from: &str, to: &str) -> Option<Vec<SearchResult>> {
+        let live = |name: &str| -> Vec<NodeIndex> {
+            self.symbol_index
+                .get(name)
+                .map(|indexes| indexes.iter().copied().filter(|&idx| self.is_live(idx)).collect())
+                .unwrap_or_default()
+        };
+        let targets: HashSet<NodeIndex> = live(to).into_iter().collect();
+        if targets.is_empty() {
+            return None;
+        }
+
+        // Multi-source BFS: the first target reached is the closest to any source
+        let mut previous: HashMap<NodeIndex, Option<NodeIndex>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for idx in live(from) {
+            if previous.insert(idx, None).is_none() {
+                queue.push_back(idx);
+            }
+        }
+
+        while let Some(idx) = queue.pop_front() {
+            if targets.contains(&idx) {
+                let mut chain = vec![idx];
+                let mut current = idx;
+                while let Some(&Some(prev)) = previous.get(&current) {
+                    chain.push(prev);
+                    current = prev;
+                }
+                return chain
+                    .into_iter()
+                    .rev()
+                    .map(|idx| self.build_search_result(idx))
+                    .collect();
+            }
+            for edge in self.graph.edges_directed(idx, Direction::Outgoing) {
+                let next = edge.target();
+                if edge.weight().kind == EdgeKind::Calls
+                    && self.is_live(next)
+                    && !previous.contains_key(&next)
+                {
+                    previous.insert(next, Some(idx));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Get all symbols defined in a specific file.
     pub fn symbols_in_file(&self, path: &Path) -> Vec<&NodeData> {
-        if let Some(&file_idx) = self.file_index.get(path) {
+        if let Some(file_idx) = self.file_node(path) {
             if !self.is_live(file_idx) {
                 return Vec::new();
             }
@@ -472,6 +978,56 @@ impl CodeGraph {
         }
     }
 
+    /// The innermost symbol whose lines contain `line` in `path`.
+    ///
+    /// Nested definitions win over their containers: a line inside a method
+    /// resolves to the method, not the impl or class around it.
+    pub fn symbol_at(&self, path: &Path, line: usize) -> Option<SearchResult> {
+        let idx = self.node_at(path, line)?;
+        self.build_search_result(idx)
+    }
+
+    /// Node of the innermost symbol at `line` of `path`; see [`Self::symbol_at`].
+    pub(crate) fn node_at(&self, path: &Path, line: usize) -> Option<NodeIndex> {
+        let file_idx = self.file_node(path)?;
+        if !self.is_live(file_idx) {
+            return None;
+        }
+
+        self.graph
+            .edges_directed(file_idx, Direction::Outgoing)
+            .filter(|edge| edge.weight().kind == EdgeKind::Defines && self.is_live(edge.target()))
+            .map(|edge| edge.target())
+            .filter(|&idx| {
+                let node = &self.graph[idx];
+                node.kind != NodeKind::Import && (node.line_start..=node.line_end).contains(&line)
+            })
+            // Smallest span first; on a tie the later start is the inner one
+            .min_by_key(|&idx| {
+                let node = &self.graph[idx];
+                (node.line_end - node.line_start, std::cmp::Reverse(node.line_start))
+            })
+    }
+
+    /// Live symbols whose qualified name ends with the parts of `query`
+    /// (`save`, `Order::save`, `models::Order.save`). Files, imports and
+    /// impl blocks aren't included.
+    pub(crate) fn nodes_qualified(&self, query: &str) -> Vec<NodeIndex> {
+        let name = query.rsplit(['.', ':']).next().unwrap_or(query);
+        self.symbol_index
+            .get(name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&idx| {
+                let node = &self.graph[idx];
+                !node.removed
+                    && !matches!(node.kind, NodeKind::File | NodeKind::Import | NodeKind::Impl)
+                    && matches_qualified(&node.qualified_name, query)
+            })
+            .collect()
+    }
+
     /// Find a symbol by its qualified name (file + symbol name).
     pub fn find_qualified(&self, file_path: &Path, name: &str) -> Option<&NodeData> {
         self.qualified_index
@@ -512,8 +1068,155 @@ impl CodeGraph {
         }
     }
 
+    /// Circular dependencies: each strongly connected component of the
+    /// `Calls` and `Imports` edges with more than one member, as the names
+    /// of its symbols and files. Members are listed in source order.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let edges = EdgeFiltered::from_fn(&self.graph, |edge| {
+            matches!(edge.weight().kind, EdgeKind::Calls | EdgeKind::Imports)
+                && self.is_live(edge.source())
+                && self.is_live(edge.target())
+        });
+
+        let mut cycles: Vec<Vec<String>> = tarjan_scc(&edges)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|mut component| {
+                component.sort_by_key(|&idx| {
+                    let node = &self.graph[idx];
+                    (node.file_path.clone(), node.line_start)
+                });
+                component
+                    .into_iter()
+                    .map(|idx| self.graph[idx].name.clone())
+                    .collect()
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Structural and integrity signals: symbols nothing connects to,
+    /// edges left behind by removed files, and how connected the graph is.
+    pub fn health(&self) -> GraphHealth {
+        let mut degree = vec![0usize; self.graph.node_count()];
+        let mut related = vec![false; self.graph.node_count()];
+        let mut live_edges = 0;
+        let mut dangling_edges = 0;
+
+        for edge in self.graph.edge_references() {
+            let (from, to) = (edge.source(), edge.target());
+            if !self.is_live(from) || !self.is_live(to) {
+                dangling_edges += 1;
+                continue;
+            }
+            live_edges += 1;
+            degree[from.index()] += 1;
+            degree[to.index()] += 1;
+            // Every symbol is defined by its file; only other edges relate it
+            if !matches!(edge.weight().kind, EdgeKind::Defines | EdgeKind::Contains) {
+                related[from.index()] = true;
+                related[to.index()] = true;
+            }
+        }
+
+        let mut live_nodes = 0;
+        let mut orphan_symbols = 0;
+        let mut hub: Option<NodeIndex> = None;
+        for idx in self.graph.node_indices() {
+            if !self.is_live(idx) {
+                continue;
+            }
+            live_nodes += 1;
+            if self.graph[idx].kind == NodeKind::File {
+                continue;
+            }
+            if !related[idx.index()] {
+                orphan_symbols += 1;
+            }
+            if hub.is_none_or(|h| degree[idx.index()] > degree[h.index()]) {
+                hub = Some(idx);
+            }
+        }
+
+        GraphHealth {
+            orphan_symbols,
+            dangling_edges,
+            avg_degree: if live_nodes == 0 {
+                0.0
+            } else {
+                2.0 * live_edges as f64 / live_nodes as f64
+            },
+            max_degree: hub.map_or(0, |h| degree[h.index()]),
+            hub_symbol: hub.map(|h| self.graph[h].name.clone()),
+        }
+    }
+
     // ─── Internal Helpers ───────────────────────────────────────
 
+    /// Live, unvisited neighbors of `idx` for a graph search: outgoing
+    /// edges first, then incoming. With `max_fanout`, only that many are
+    /// kept, preferring neighbors with the most edges; the flag reports
+    /// whether any were dropped.
+    fn fanout(
+        &self,
+        idx: NodeIndex,
+        visited: &HashSet<NodeIndex>,
+        max_fanout: Option<usize>,
+    ) -> (Vec<(NodeIndex, EdgeKind, Direction)>, bool) {
+        let mut seen: HashSet<NodeIndex> = HashSet::new();
+        let mut edges: Vec<(NodeIndex, EdgeKind, Direction)> = Vec::new();
+        for direction in [Direction::Outgoing, Direction::Incoming] {
+            for edge in self.graph.edges_directed(idx, direction) {
+                let neighbor = match direction {
+                    Direction::Outgoing => edge.target(),
+                    Direction::Incoming => edge.source(),
+                };
+                if self.is_live(neighbor) && !visited.contains(&neighbor) && seen.insert(neighbor) {
+                    edges.push((neighbor, edge.weight().kind, direction));
+                }
+            }
+        }
+
+        match max_fanout {
+            Some(max) if edges.len() > max => {
+                // Stable sort: equally central neighbors keep edge order
+                edges.sort_by_key(|&(neighbor, _, _)| std::cmp::Reverse(self.degree(neighbor)));
+                edges.truncate(max);
+                (edges, true)
+            }
+            _ => (edges, false),
+        }
+    }
+
+    /// Number of edges touching a node, in either direction.
+    fn degree(&self, idx: NodeIndex) -> usize {
+        self.graph.edges_directed(idx, Direction::Outgoing).count()
+            + self.graph.edges_directed(idx, Direction::Incoming).count()
+    }
+
+    /// Symbols with this name, plus files whose name or path is `name`.
+    fn nodes_named(&self, name: &str) -> Vec<NodeIndex> {
+        let mut indexes = self.symbol_index.get(name).cloned().unwrap_or_default();
+        let path = Path::new(name);
+        if let Some(&idx) = self.file_index.get(path) {
+            indexes.push(idx);
+        } else if path.components().count() == 1 {
+            if let Some(files) = self.files_by_name.get(path.as_os_str()) {
+                indexes.extend(files);
+            }
+        }
+        indexes
+    }
+
+    /// Order nodes by descending importance, then by position in the graph.
+    fn by_importance(&self, a: NodeIndex, b: NodeIndex) -> std::cmp::Ordering {
+        self.graph[b]
+            .importance
+            .total_cmp(&self.graph[a].importance)
+            .then_with(|| a.cmp(&b))
+    }
+
     /// Check if a node is live (not removed).
     fn is_live(&self, idx: NodeIndex) -> bool {
         self.graph.node_weight(idx).is_some_and(|n| !n.removed)
@@ -521,6 +1224,12 @@ impl CodeGraph {
 
     /// Build a SearchResult from a node index, including connections.
     fn build_search_result(&self, idx: NodeIndex) -> Option<SearchResult> {
+        self.build_search_result_with(idx, &mut LookupCache::default())
+    }
+
+    /// [`build_search_result`](Self::build_search_result), reusing the
+    /// file's imports from `cache`.
+    fn build_search_result_with(&self, idx: NodeIndex, cache: &mut LookupCache) -> Option<SearchResult> {
         let node = &self.graph[idx];
 
         // Don't return File nodes or removed nodes
@@ -559,170 +1268,536 @@ impl CodeGraph {
             .collect();
 
         // Collect imports related to the file this symbol is in, skip removed
-        let imports: Vec<String> = if let Some(&file_idx) = self.file_index.get(&node.file_path) {
-            self.graph
-                .edges_directed(file_idx, Direction::Outgoing)
-                .filter(|e| e.weight().kind == EdgeKind::Imports && self.is_live(e.target()))
-                .map(|e| {
-                    let target = &self.graph[e.target()];
-                    target.name.clone()
-                })
-                .collect()
+        let imports = cache
+            .imports
+            .entry(node.file_path.clone())
+            .or_insert_with(|| {
+                cache.computed += 1;
+                match self.file_index.get(&node.file_path) {
+                    Some(&file_idx) => self
+                        .graph
+                        .edges_directed(file_idx, Direction::Outgoing)
+                        .filter(|e| e.weight().kind == EdgeKind::Imports && self.is_live(e.target()))
+                        .filter(|e| self.graph[e.target()].kind == NodeKind::Import)
+                        .map(|e| {
+                            let target = &self.graph[e.target()];
+                            target.name.clone()
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                }
+            })
+            .clone();
+
+        Some(SearchResult {
+            symbol: node.name.clone(),
+            kind: node.kind,
+            file: node.file_path.clone(),
+            line_start: node.line_start,
+            line_end: node.line_end,
+            code: node.code_snippet.clone(),
+            calls,
+            called_by,
+            imports,
+            visibility: node.visibility,
+            is_test: node.is_test,
+            truncated: node.truncated,
+            value: node.value.clone(),
+            deprecated: node.deprecated,
+            is_async: node.is_async,
+            generated: node.generated,
+            importance: node.importance,
+            qualified_name: node.qualified_name.clone(),
+            similarity: None,
+            aliases: Vec::new(),
+        })
+    }
+
+    // ─── Graph Building from Extractions ────────────────────────
+
+    /// Build the graph from a set of file extractions.
+    /// This is the main entry point for populating the graph.
+    ///
+    /// Extractions are ingested in file path order, so the same files give
+    /// the same node layout no matter what order parallel parsing produced.
+    pub fn build_from_extractions(&mut self, mut extractions: Vec<FileExtractions>) {
+        extractions.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        debug!(
+            file_count = extractions.len(),
+            "ingesting extractions into graph"
+        );
+        let mut links = PendingLinks::default();
+        for extraction in extractions {
+            self.ingest_extraction(extraction, &mut links);
+        }
+        self.resolve_links(links);
+    }
+
+    /// Phase 1 for one file: add its file node, symbols, imports and
+    /// endpoints. Calls, renders and parent links can point at files not
+    /// ingested yet, so they are queued in `links` for `resolve_links`.
+    ///
+    /// Feeding files one at a time lets a streaming build drop each
+    /// extraction's snippets and sources as soon as it is ingested.
+    pub(crate) fn ingest_extraction(
+        &mut self,
+        extraction: FileExtractions,
+        links: &mut PendingLinks,
+    ) {
+        let file_path = extraction.file_path;
+        let file_idx = self.add_file(file_path.clone());
+        self.graph[file_idx].generated = extraction.generated;
+
+        if file_path.file_name().is_some_and(|name| name == "__init__.py") {
+            if extraction.exports.is_empty() {
+                self.reexports.remove(&file_path);
+            } else {
+                self.reexports.insert(file_path.clone(), extraction.exports);
+            }
+        }
+        // A re-ingested module's symbols start out with their own
+        // visibility; its packages' `__all__` has to be applied again
+        if file_path.extension().is_some_and(|ext| ext == "py") {
+            for package in file_path.ancestors().skip(1) {
+                let init = package.join("__init__.py");
+                if self.reexports.contains_key(&init) {
+                    links.reexports.push(init);
+                }
+            }
+        }
+
+        let qualified_names = qualified_names(&file_path, &extraction.symbols);
+        for (symbol, qualified_name) in extraction.symbols.into_iter().zip(qualified_names) {
+            if !self.indexes_kind(symbol.kind) {
+                continue;
+            }
+            let mut data = NodeData::new_symbol(
+                symbol.name.clone(),
+                symbol.kind,
+                file_path.clone(),
+                symbol.line_start,
+                symbol.line_end,
+                symbol.code_snippet,
+            );
+            data.visibility = symbol.visibility;
+            data.is_test = symbol.is_test;
+            data.truncated = symbol.truncated;
+            data.value = symbol.value;
+            data.deprecated = symbol.deprecated;
+            data.decorators = symbol.decorators;
+            data.is_async = symbol.is_async;
+            data.generated = extraction.generated;
+            data.qualified_name = qualified_name;
+            let sym_idx = self.place_symbol(data, &mut links.reuse);
+
+            // File DEFINES Symbol
+            self.add_edge(file_idx, sym_idx, EdgeKind::Defines);
+
+            if let Some(parent) = symbol.parent {
+                links.parents.push((file_path.clone(), parent, symbol.name));
+            }
+        }
+
+        // Add import nodes
+        let imports = if self.indexes_kind(NodeKind::Import) {
+            extraction.imports
         } else {
             Vec::new()
         };
+        for import in imports {
+            links.imports.push((file_path.clone(), import.clone()));
+            let data = NodeData::new_symbol(
+                import.path,
+                NodeKind::Import,
+                file_path.clone(),
+                import.line,
+                import.line,
+                String::new(),
+            );
+            let import_idx = self.place_symbol(data, &mut links.reuse);
+            self.add_edge(file_idx, import_idx, EdgeKind::Imports);
+        }
 
-        Some(SearchResult {
-            symbol: node.name.clone(),
-            kind: node.kind,
-            file: node.file_path.clone(),
-            line_start: node.line_start,
-            line_end: node.line_end,
-            code: node.code_snippet.clone(),
-            calls,
-            called_by,
-            imports,
-        })
+        for endpoint in &extraction.endpoints {
+            self.add_endpoint(ApiEndpoint::from_extracted(endpoint, file_path.clone()));
+        }
+
+        links
+            .calls
+            .extend(extraction.calls.into_iter().map(|call| (file_path.clone(), call)));
+        links
+            .renders
+            .extend(extraction.renders.into_iter().map(|render| (file_path.clone(), render)));
+        links
+            .dynamic_calls
+            .extend(extraction.dynamic_calls.into_iter().map(|call| (file_path.clone(), call)));
+        links
+            .supertypes
+            .extend(extraction.supertypes.into_iter().map(|supertype| (file_path.clone(), supertype)));
     }
 
-    // ─── Graph Building from Extractions ────────────────────────
+    /// Phases 2 and 3: add the edges queued while ingesting files, once
+    /// every file's symbols are in the graph.
+    pub(crate) fn resolve_links(&mut self, links: PendingLinks) {
+        // Phase 2: Resolve contains relationships (parent -> child). Calls
+        // on `self` need to know their caller's type, so these go first.
+        for (file_path, parent_name, child_name) in links.parents {
+            let child_key = (file_path.clone(), child_name);
+            let parent_key = (file_path, parent_name);
+
+            if let (Some(&parent_idx), Some(&child_idx)) = (
+                self.qualified_index.get(&parent_key),
+                self.qualified_index.get(&child_key),
+            ) {
+                self.add_edge(parent_idx, child_idx, EdgeKind::Contains);
+            }
+        }
 
-    /// Build the graph from a set of file extractions.
-    /// This is the main entry point for populating the graph.
-    pub fn build_from_extractions(&mut self, extractions: Vec<FileExtractions>) {
-        debug!(
-            file_count = extractions.len(),
-            "ingesting extractions into graph"
-        );
-        // Phase 1: Add all file nodes and symbol nodes
-        for extraction in &extractions {
-            let file_idx = self.add_file(extraction.file_path.clone());
-
-            for symbol in &extraction.symbols {
-                let sym_idx = self.add_symbol(
-                    symbol.name.clone(),
-                    symbol.kind,
-                    extraction.file_path.clone(),
-                    symbol.line_start,
-                    symbol.line_end,
-                    symbol.code_snippet.clone(),
-                );
+        // Phase 3: Resolve cross-references (calls)
+        for (file_path, call) in links.calls {
+            if let Some((caller_idx, callee_idx)) = self.resolve_call(&file_path, &call) {
+                self.add_or_count_edge(caller_idx, callee_idx, EdgeKind::Calls);
+            }
+        }
 
-                // File DEFINES Symbol
-                self.add_edge(file_idx, sym_idx, EdgeKind::Defines);
+        // Phase 3b: Resolve JSX renders to known components
+        for (file_path, render) in links.renders {
+            let caller_key = (file_path, render.caller);
+            let Some(&caller_idx) = self.qualified_index.get(&caller_key) else {
+                continue;
+            };
+            let component = self.symbol_index.get(&render.callee).and_then(|indexes| {
+                indexes
+                    .iter()
+                    .copied()
+                    .find(|&idx| self.graph[idx].kind == NodeKind::Component)
+            });
+            if let Some(component_idx) = component {
+                self.add_edge(caller_idx, component_idx, EdgeKind::Renders);
             }
+        }
 
-            // Add import nodes
-            for import in &extraction.imports {
-                let import_idx = self.add_symbol(
-                    import.path.clone(),
-                    NodeKind::Import,
-                    extraction.file_path.clone(),
-                    import.line,
-                    import.line,
-                    String::new(),
-                );
-                self.add_edge(file_idx, import_idx, EdgeKind::Imports);
+        // Phase 3c: Resolve string-named calls, only to symbols that exist
+        for (file_path, call) in links.dynamic_calls {
+            let caller_key = (file_path, call.caller);
+            let Some(&caller_idx) = self.qualified_index.get(&caller_key) else {
+                continue;
+            };
+            let callee = self
+                .symbol_index
+                .get(&call.callee)
+                .and_then(|indexes| indexes.first().copied());
+            if let Some(callee_idx) = callee {
+                self.add_edge(caller_idx, callee_idx, EdgeKind::DynamicCall);
             }
         }
 
-        // Phase 2: Resolve cross-references (calls)
-        for extraction in &extractions {
-            for call in &extraction.calls {
-                // Find the caller node
-                let caller_key = (extraction.file_path.clone(), call.caller.clone());
-                let callee_nodes = self.symbol_index.get(&call.callee).cloned();
-
-                if let Some(&caller_idx) = self.qualified_index.get(&caller_key) {
-                    if let Some(callee_indexes) = callee_nodes {
-                        // Connect to the first matching callee
-                        // (in v0, we take the first match — later versions can be smarter)
-                        if let Some(&callee_idx) = callee_indexes.first() {
-                            self.add_edge(caller_idx, callee_idx, EdgeKind::Calls);
-                        }
-                    }
-                }
+        // Phase 3d: Connect types to the traits they implement and the
+        // classes they extend, when those are in the project
+        for (file_path, supertype) in links.supertypes {
+            let Some(source_idx) = self.declared_at(&file_path, &supertype.name, supertype.line) else {
+                continue;
+            };
+            let Some(candidates) = self.symbol_index.get(&supertype.supertype) else {
+                continue;
+            };
+            let target = candidates
+                .iter()
+                .copied()
+                .filter(|&idx| {
+                    matches!(
+                        self.graph[idx].kind,
+                        NodeKind::Trait | NodeKind::Interface | NodeKind::Class
+                    )
+                })
+                .min_by_key(|&idx| self.graph[idx].file_path != file_path);
+            if let Some(target_idx) = target {
+                self.add_edge(source_idx, target_idx, supertype.kind);
+            }
+        }
+
+        // Phase 3e: Link files to the project files they import. External
+        // imports resolve to nothing and stay `Import` nodes only
+        let resolver = ImportResolver::new(self.file_index.keys());
+        let mut import_edges: Vec<(NodeIndex, NodeIndex)> = Vec::new();
+        for (file_path, import) in &links.imports {
+            let Some(&from) = self.file_index.get(file_path) else {
+                continue;
+            };
+            for target in resolver.resolve(file_path, import) {
+                import_edges.push((from, self.file_index[target]));
             }
         }
+        import_edges.sort();
+        import_edges.dedup();
+        for (from, to) in import_edges {
+            self.add_edge(from, to, EdgeKind::Imports);
+        }
 
-        // Phase 3: Resolve contains relationships (parent -> child)
-        for extraction in &extractions {
-            for symbol in &extraction.symbols {
-                if let Some(ref parent_name) = symbol.parent {
-                    let child_key = (extraction.file_path.clone(), symbol.name.clone());
-                    let parent_key = (extraction.file_path.clone(), parent_name.clone());
-
-                    if let (Some(&parent_idx), Some(&child_idx)) = (
-                        self.qualified_index.get(&parent_key),
-                        self.qualified_index.get(&child_key),
-                    ) {
-                        self.add_edge(parent_idx, child_idx, EdgeKind::Contains);
+        // Phase 4: A package's `__all__` re-exports names from its modules,
+        // making them public API even when the module itself hides them
+        let mut inits = links.reexports;
+        inits.sort();
+        inits.dedup();
+        for init_path in inits {
+            let Some(names) = self.reexports.get(&init_path).cloned() else {
+                continue;
+            };
+            let package = init_path.parent().unwrap_or(Path::new("")).to_path_buf();
+            for name in names {
+                if self.qualified_index.contains_key(&(init_path.clone(), name.clone())) {
+                    continue;
+                }
+                let Some(indexes) = self.symbol_index.get(&name) else {
+                    continue;
+                };
+                for &idx in indexes {
+                    let node = &mut self.graph[idx];
+                    if !node.removed
+                        && node.file_path.starts_with(&package)
+                        && !matches!(node.kind, NodeKind::Method | NodeKind::Import)
+                    {
+                        node.visibility = Visibility::Public;
                     }
                 }
             }
         }
     }
 
+    /// The caller and callee a call in `file` links, if both are in the
+    /// graph.
+    pub(crate) fn resolve_call(&self, file: &Path, call: &ExtractedCall) -> Option<(NodeIndex, NodeIndex)> {
+        let caller_key = (file.to_path_buf(), call.caller.clone());
+        let &caller_idx = self.qualified_index.get(&caller_key)?;
+        let callee_indexes = self.symbol_index.get(&call.callee)?;
+        let callee_idx = self.resolve_callee(caller_idx, callee_indexes, call.receiver.as_deref())?;
+        Some((caller_idx, callee_idx))
+    }
+
+    /// The symbol named `name` that starts at `line` of `file`. An impl
+    /// shares its type's name, so the line tells them apart.
+    fn declared_at(&self, file: &Path, name: &str, line: usize) -> Option<NodeIndex> {
+        self.symbol_index
+            .get(name)?
+            .iter()
+            .copied()
+            .find(|&idx| {
+                let node = &self.graph[idx];
+                node.file_path == file && node.line_start == line
+            })
+    }
+
+    /// Pick the callee a call most likely means among the symbols sharing
+    /// its name. One whose type or module matches the call's receiver wins
+    /// (`Config::new` -> a `new` in `impl Config`, `config::load` -> a `load`
+    /// in `config.rs`); `self` stands for the caller's own type. Otherwise
+    /// the first symbol with that name.
+    fn resolve_callee(
+        &self,
+        caller_idx: NodeIndex,
+        candidates: &[NodeIndex],
+        receiver: Option<&str>,
+    ) -> Option<NodeIndex> {
+        let first = candidates.first().copied();
+        let Some(receiver) = receiver else {
+            return first;
+        };
+        let last = receiver.rsplit(['.', ':']).next().unwrap_or(receiver);
+        let qualifier = match last {
+            "self" | "Self" | "this" | "cls" => match self.parent_name(caller_idx) {
+                Some(parent) => parent,
+                None => return first,
+            },
+            name => name,
+        };
+
+        candidates
+            .iter()
+            .copied()
+            .find(|&idx| {
+                self.parent_name(idx) == Some(qualifier)
+                    || module_name(&self.graph[idx].file_path) == Some(qualifier)
+            })
+            .or(first)
+    }
+
+    /// Name of the type or impl containing a symbol, without generic
+    /// arguments (`Cache<K, V>` -> `Cache`).
+    pub(crate) fn parent_name(&self, idx: NodeIndex) -> Option<&str> {
+        self.graph
+            .edges_directed(idx, Direction::Incoming)
+            .find(|e| e.weight().kind == EdgeKind::Contains)
+            .map(|e| {
+                let name = self.graph[e.source()].name.as_str();
+                name.split('<').next().unwrap_or(name).trim()
+            })
+    }
+
     /// Soft-delete all nodes and edges originating from a specific file.
     /// Marks nodes as removed so queries skip them. Use `compact()` to
     /// physically reclaim memory.
     pub fn remove_file(&mut self, path: &Path) {
         if let Some(&file_idx) = self.file_index.get(path) {
             debug!(file = %path.display(), "removing file from graph");
-            // Collect ALL child nodes (DEFINES + IMPORTS edges from file)
-            let child_nodes: Vec<NodeIndex> = self
-                .graph
-                .edges_directed(file_idx, Direction::Outgoing)
-                .map(|e| e.target())
-                .collect();
+            // Collect ALL child nodes (DEFINES + IMPORTS edges from file),
+            // but not the files it imports
+            let child_nodes: Vec<NodeIndex> = self.file_children(file_idx);
+            self.soft_delete(&child_nodes);
 
-            // Soft-delete each child node and clean indexes
-            for &node_idx in &child_nodes {
-                if let Some(node) = self.graph.node_weight_mut(node_idx) {
-                    let name = node.name.clone();
-                    let file = node.file_path.clone();
-                    node.removed = true;
-
-                    // Remove from symbol_index
-                    if let Some(indexes) = self.symbol_index.get_mut(&name) {
-                        indexes.retain(|&idx| idx != node_idx);
-                        if indexes.is_empty() {
-                            self.symbol_index.remove(&name);
-                        }
+            // Soft-delete the file node itself
+            if let Some(file_node) = self.graph.node_weight_mut(file_idx) {
+                file_node.removed = true;
+            }
+            self.file_index.remove(path);
+            self.reexports.remove(path);
+            if let Some(file_name) = path.file_name() {
+                if let Some(files) = self.files_by_name.get_mut(file_name) {
+                    files.retain(|&idx| idx != file_idx);
+                    if files.is_empty() {
+                        self.files_by_name.remove(file_name);
                     }
+                }
+            }
+            self.remove_endpoints(path);
+        }
+    }
 
-                    // Remove from qualified_index
-                    self.qualified_index.remove(&(file, name));
+    /// Mark nodes removed and drop them from the name indexes. Their edges
+    /// stay until `compact`.
+    fn soft_delete(&mut self, nodes: &[NodeIndex]) {
+        for &node_idx in nodes {
+            if let Some(node) = self.graph.node_weight_mut(node_idx) {
+                let name = node.name.clone();
+                let file = node.file_path.clone();
+                node.removed = true;
+
+                // Remove from symbol_index
+                if let Some(indexes) = self.symbol_index.get_mut(&name) {
+                    indexes.retain(|&idx| idx != node_idx);
+                    if indexes.is_empty() {
+                        self.symbol_index.remove(&name);
+                    }
                 }
+
+                // Remove from qualified_index
+                self.qualified_index.remove(&(file, name));
             }
+        }
+    }
 
-            // Soft-delete the file node itself
-            if let Some(file_node) = self.graph.node_weight_mut(file_idx) {
-                file_node.removed = true;
+    /// Replace a file's contents in the graph with a fresh extraction.
+    ///
+    /// Unlike `remove_file` + `build_from_extractions`, this keeps the file
+    /// node and the files importing it, keeps inbound edges from other files
+    /// pointing at symbols that still exist, and drops edges into symbols
+    /// that were deleted from the file so callers no longer list them as
+    /// dependencies. New symbols move into the old symbols' nodes, so the
+    /// graph doesn't grow with every edit.
+    pub fn replace_file(&mut self, extraction: FileExtractions) {
+        let path = extraction.file_path.clone();
+        let Some(&file_idx) = self.file_index.get(&path) else {
+            self.build_from_extractions(vec![extraction]);
+            return;
+        };
+
+        // Remember who points into this file from elsewhere, by qualified
+        // name: a file may define `Order.save` and `User.save`
+        let old_nodes = self.file_children(file_idx);
+        let mut inbound: Vec<(NodeIndex, String, EdgeData)> = Vec::new();
+        for &node_idx in &old_nodes {
+            let target = relink_key(&self.graph[node_idx]);
+            for edge in self.graph.edges_directed(node_idx, Direction::Incoming) {
+                let source = &self.graph[edge.source()];
+                if source.file_path != path && edge.weight().kind != EdgeKind::Defines {
+                    inbound.push((edge.source(), target.to_string(), edge.weight().clone()));
+                }
+            }
+        }
+
+        // Old nodes the new symbols can move into; a symbol that kept its
+        // name, kind and line gets its old node back
+        let new_keys: HashSet<(&str, NodeKind, usize)> = extraction
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.line_start))
+            .chain(extraction.imports.iter().map(|i| (i.path.as_str(), NodeKind::Import, i.line)))
+            .collect();
+        let mut reuse = NodeReuse::default();
+        for &idx in &old_nodes {
+            let node = &self.graph[idx];
+            if new_keys.contains(&(node.name.as_str(), node.kind, node.line_start)) {
+                reuse.matching.insert((node.name.clone(), node.kind, node.line_start), idx);
+            } else {
+                reuse.spare.push(idx);
+            }
+        }
+        // Pop spares in node order
+        reuse.spare.reverse();
+
+        // Drop every edge of the old nodes and the file's outbound edges;
+        // they are re-resolved from the new extraction
+        self.soft_delete(&old_nodes);
+        let retired: HashSet<NodeIndex> = old_nodes.iter().copied().collect();
+        self.graph.retain_edges(|g, e| {
+            g.edge_endpoints(e).is_none_or(|(s, t)| {
+                s != file_idx && !retired.contains(&s) && !retired.contains(&t)
+            })
+        });
+        self.remove_endpoints(&path);
+
+        let mut links = PendingLinks {
+            reuse,
+            ..PendingLinks::default()
+        };
+        self.ingest_extraction(extraction, &mut links);
+        self.resolve_links(links);
+
+        // Re-link callers to symbols that survived the edit
+        let mut survivors: HashMap<String, NodeIndex> = HashMap::new();
+        for idx in self.file_children(file_idx) {
+            if self.is_live(idx) {
+                survivors.entry(relink_key(&self.graph[idx]).to_string()).or_insert(idx);
+            }
+        }
+        for (source_idx, target, edge_data) in inbound {
+            if !self.is_live(source_idx) {
+                continue;
+            }
+            if let Some(&new_idx) = survivors.get(&target) {
+                self.graph.add_edge(source_idx, new_idx, edge_data);
             }
-            self.file_index.remove(path);
         }
     }
 
+    /// Nodes a file owns: its symbols and `Import` nodes, not the files it
+    /// imports.
+    fn file_children(&self, file_idx: NodeIndex) -> Vec<NodeIndex> {
+        self.graph
+            .edges_directed(file_idx, Direction::Outgoing)
+            .map(|e| e.target())
+            .filter(|&idx| self.graph[idx].kind != NodeKind::File)
+            .collect()
+    }
+
     /// Rebuild the graph from scratch, removing all soft-deleted nodes.
     /// Call this periodically or after many incremental updates to reclaim memory.
     pub fn compact(&mut self) {
         info!("compacting graph — rebuilding without soft-deleted nodes");
-        // Collect all live extractions
-        let mut live_files: HashMap<PathBuf, Vec<NodeIndex>> = HashMap::new();
-        for idx in self.graph.node_indices() {
-            let node = &self.graph[idx];
-            if !node.removed && node.kind == NodeKind::File {
-                live_files.insert(node.file_path.clone(), Vec::new());
-            }
-        }
+        // Collect live files in node order so the compacted layout is stable
+        let live_files: Vec<PathBuf> = self
+            .graph
+            .node_indices()
+            .map(|idx| &self.graph[idx])
+            .filter(|node| !node.removed && node.kind == NodeKind::File)
+            .map(|node| node.file_path.clone())
+            .collect();
 
         // Build a new graph with only live nodes
         let mut new_graph = CodeGraph::new();
 
         // Re-add all live file nodes
-        for path in live_files.keys() {
-            new_graph.add_file(path.clone());
+        for path in live_files {
+            new_graph.add_file(path);
         }
 
         // Re-add all live symbol nodes and their edges
@@ -737,14 +1812,7 @@ impl CodeGraph {
                     old_to_new.insert(idx, new_idx);
                 }
             } else {
-                let new_idx = new_graph.add_symbol(
-                    node.name.clone(),
-                    node.kind,
-                    node.file_path.clone(),
-                    node.line_start,
-                    node.line_end,
-                    node.code_snippet.clone(),
-                );
+                let new_idx = new_graph.add_symbol_data(node.clone());
                 old_to_new.insert(idx, new_idx);
             }
         }
@@ -755,12 +1823,18 @@ impl CodeGraph {
                 if let (Some(&new_src), Some(&new_tgt)) =
                     (old_to_new.get(&src), old_to_new.get(&tgt))
                 {
-                    let edge_data = &self.graph[edge];
-                    new_graph.add_edge(new_src, new_tgt, edge_data.kind);
+                    new_graph.graph.add_edge(new_src, new_tgt, self.graph[edge].clone());
                 }
             }
         }
 
+        new_graph.endpoints = std::mem::take(&mut self.endpoints);
+        new_graph.reexports = std::mem::take(&mut self.reexports);
+        new_graph.meta = self.meta.take();
+        new_graph.index_kinds = std::mem::take(&mut self.index_kinds);
+        new_graph.codeowners = std::mem::take(&mut self.codeowners);
+        new_graph.centrality_stale = self.centrality_stale;
+
         // Replace self with the compacted graph
         *self = new_graph;
 
@@ -780,8 +1854,169 @@ impl Default for CodeGraph {
     }
 }
 
+/// The module a file defines: its stem, or its directory's name for
+/// `mod.rs`, `__init__.py` and `index.js`-style files.
+pub(super) fn module_name(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    if matches!(stem, "mod" | "__init__" | "index") {
+        path.parent()?.file_name()?.to_str()
+    } else {
+        Some(stem)
+    }
+}
+
+/// What identifies a node within its file when `replace_file` re-links
+/// edges into it: the qualified name, or the name for nodes without one
+/// (imports).
+fn relink_key(node: &NodeData) -> &str {
+    if node.qualified_name.is_empty() {
+        &node.name
+    } else {
+        &node.qualified_name
+    }
+}
+
+/// Qualified names for a file's symbols, in order: the file's module, then
+/// each enclosing type from the `parent` chain, then the name. Rust joins
+/// every part with `::`; other languages join the types and name with `.`
+/// (`models::User.save`).
+fn qualified_names(path: &Path, symbols: &[ExtractedSymbol]) -> Vec<String> {
+    const MAX_NESTING: usize = 8;
+
+    let rust = SupportedLanguage::from_path(path) == Some(SupportedLanguage::Rust);
+    let separator = if rust { "::" } else { "." };
+
+    // Parent of each named type; impl blocks share their type's name
+    let mut parents: HashMap<&str, Option<&str>> = HashMap::new();
+    for symbol in symbols.iter().filter(|s| s.kind != NodeKind::Impl) {
+        parents.entry(symbol.name.as_str()).or_insert(symbol.parent.as_deref());
+    }
+
+    symbols
+        .iter()
+        .map(|symbol| {
+            let mut parts = vec![symbol.name.as_str()];
+            let mut parent = symbol.parent.as_deref();
+            while let Some(name) = parent.filter(|_| parts.len() <= MAX_NESTING) {
+                parts.push(name);
+                parent = parents.get(name).copied().flatten();
+            }
+            parts.reverse();
+
+            let local = parts.join(separator);
+            match module_name(path) {
+                Some(module) => format!("{}::{}", module, local),
+                None => local,
+            }
+        })
+        .collect()
+}
+
+/// Does `qualified_name` end with the parts of `query`? Parts are split on
+/// `::` and `.`, so `Config::new` matches `config::Config::new` and
+/// `models::Config.new`.
+fn matches_qualified(qualified_name: &str, query: &str) -> bool {
+    let parts = |name: &str| -> Vec<String> {
+        name.replace("::", ".").split('.').map(str::to_string).collect()
+    };
+    let (name, query) = (parts(qualified_name), parts(query));
+    name.ends_with(&query)
+}
+
+/// Graph search limits: nodes matched before traversal, symbols and
+/// connections returned.
+const MAX_INITIAL_MATCHES: usize = 10;
+const MAX_SYMBOLS: usize = 50;
+const MAX_CONNECTIONS: usize = 100;
+
+/// Lowest Jaro-Winkler similarity a name needs to be a fuzzy match.
+pub const FUZZY_THRESHOLD: f64 = 0.8;
+
+/// Jaro-Winkler similarity of two strings, from 0.0 (nothing in common)
+/// to 1.0 (equal). Strings sharing a prefix score higher, which suits
+/// identifiers: `UserServ` is closer to `UserService` than to `ServUser`.
+fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Characters match when equal and no further apart than the window
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Matched characters that appear in a different order
+    let a_order = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_order = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_order.zip(b_order).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// A path in a form that compares equal across platforms: `\` and `/`
+/// both become `/`, `.` segments and repeated separators are dropped, and
+/// case is folded where filesystems are case-insensitive (Windows, macOS).
+pub fn normalize_path(path: &Path) -> String {
+    let text = path.to_string_lossy().replace('\\', "/");
+    let absolute = text.starts_with('/');
+    let parts: Vec<&str> = text
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+    let normalized = format!("{}{}", if absolute { "/" } else { "" }, parts.join("/"));
+    if cfg!(any(windows, target_os = "macos")) {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
 // ─── Query Result Types ─────────────────────────────────────────
 
+/// The connection for an edge between a traversed node and its neighbor,
+/// oriented along the edge.
+fn connection(
+    node: &NodeData,
+    neighbor: &NodeData,
+    kind: EdgeKind,
+    direction: Direction,
+) -> ConnectionInfo {
+    let (from, to) = match direction {
+        Direction::Outgoing => (node, neighbor),
+        Direction::Incoming => (neighbor, node),
+    };
+    ConnectionInfo {
+        from: from.name.clone(),
+        to: to.name.clone(),
+        relationship: kind,
+    }
+}
+
 /// A search result returned by `CodeGraph::search`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -803,6 +2038,41 @@ pub struct SearchResult {
     pub called_by: Vec<SymbolRef>,
     /// Imports in the same file.
     pub imports: Vec<String>,
+    /// Access level of the symbol.
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Whether this is test code.
+    #[serde(default)]
+    pub is_test: bool,
+    /// Whether `code` was cut short of the full definition.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Initializer of a constant (`200` for `const X: u64 = 200;`).
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Whether the symbol is marked deprecated.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Whether the function is `async`.
+    #[serde(default)]
+    pub is_async: bool,
+    /// Whether the symbol is in generated or vendored code.
+    #[serde(default)]
+    pub generated: bool,
+    /// Call-graph centrality from 0.0 to 1.0: how much the rest of the
+    /// code depends on it, relative to the most depended-on symbol.
+    #[serde(default)]
+    pub importance: f64,
+    /// Name qualified by module and enclosing types (`config::Config::new`).
+    #[serde(default)]
+    pub qualified_name: String,
+    /// Similarity to the query from 0.0 to 1.0, set by fuzzy search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f64>,
+    /// Other names the same definition matched under, when duplicate
+    /// results were merged (`signIn` for `const signIn = function login`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 }
 
 /// A reference to a symbol (lightweight, for connections).
@@ -829,6 +2099,13 @@ pub struct DependencyInfo {
     pub line: usize,
     /// How it's related.
     pub relationship: EdgeKind,
+    /// How many times: calls from the caller to the callee.
+    #[serde(default = "default_count")]
+    pub count: u32,
+}
+
+fn default_count() -> u32 {
+    1
 }
 
 /// Statistics about the graph.
@@ -841,6 +2118,23 @@ pub struct GraphStats {
     pub unique_symbol_names: usize,
 }
 
+/// Health signals about the graph, from `CodeGraph::health`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphHealth {
+    /// Symbols with no edges besides their file's `Defines` and their
+    /// container's `Contains`: nothing calls, imports or references them,
+    /// and they use nothing.
+    pub orphan_symbols: usize,
+    /// Edges touching a removed node, waiting for `compact()`.
+    pub dangling_edges: usize,
+    /// Mean number of edges per live node.
+    pub avg_degree: f64,
+    /// Edges on the most connected symbol.
+    pub max_degree: usize,
+    /// Name of the most connected symbol.
+    pub hub_symbol: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -853,6 +2147,71 @@ mod tests {
         assert_eq!(stats.total_edges, 0);
     }
 
+    #[test]
+    fn test_health_orphans_hub_and_dangling() {
+        let source = "fn hub() {}\nfn one() { hub(); }\nfn two() { hub(); }\nfn three() { hub(); }\nfn lonely() {}\n";
+        let extractions = vec![
+            crate::parser::extract_file(&PathBuf::from("a.rs"), source).unwrap(),
+            crate::parser::extract_file(&PathBuf::from("b.rs"), "fn gone() { hub(); }\n").unwrap(),
+        ];
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(extractions);
+        graph.remove_file(Path::new("b.rs"));
+
+        let health = graph.health();
+        assert_eq!(health.orphan_symbols, 1);
+        // b.rs's Defines edge and gone's call to hub
+        assert_eq!(health.dangling_edges, 2);
+        assert_eq!(health.hub_symbol.as_deref(), Some("hub"));
+        assert_eq!(health.max_degree, 4);
+        // 5 Defines + 3 Calls over a.rs and its 5 symbols
+        assert!((health.avg_degree - 16.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_index_kinds_drops_other_symbols() {
+        let source = "use std::fmt;\n\nconst LIMIT: u32 = 3;\nstatic mut COUNT: u32 = 0;\n\npub struct Cart {}\n\nimpl Cart {\n    pub fn total(&self) -> u32 {\n        LIMIT\n    }\n}\n\npub fn checkout(cart: &Cart) -> u32 {\n    cart.total()\n}\n";
+        let extraction = crate::parser::extract_file(&PathBuf::from("cart.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.set_index_kinds(&[NodeKind::Function, NodeKind::Method, NodeKind::Struct]);
+        graph.build_from_extractions(vec![extraction]);
+
+        let kinds: HashSet<NodeKind> = graph
+            .graph
+            .node_weights()
+            .filter(|node| node.kind != NodeKind::File)
+            .map(|node| node.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            HashSet::from([NodeKind::Function, NodeKind::Method, NodeKind::Struct])
+        );
+        assert!(graph.search("LIMIT", 5).is_empty());
+        assert!(graph.search("COUNT", 5).is_empty());
+        // Edges between kept symbols survive
+        assert!(graph.dependencies("checkout").iter().any(|d| d.symbol == "total"));
+    }
+
+    #[test]
+    fn test_file_queries_normalize_paths() {
+        let extraction =
+            crate::parser::extract_file(&PathBuf::from("src/auth.rs"), "fn login() {}\n").unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        for query in ["src\\auth.rs", "./src//auth.rs", "src/auth.rs"] {
+            let names: Vec<&str> = graph
+                .symbols_in_file(Path::new(query))
+                .iter()
+                .map(|node| node.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["login"], "{}", query);
+            assert_eq!(graph.resolve_file(Path::new(query)), Some(Path::new("src/auth.rs")));
+        }
+        assert!(graph.symbol_at(Path::new("src\\auth.rs"), 1).is_some());
+        assert!(graph.resolve_file(Path::new("src/auth.py")).is_none());
+    }
+
     #[test]
     fn test_add_file_and_symbol() {
         let mut graph = CodeGraph::new();
@@ -972,6 +2331,13 @@ mod tests {
                     line_end: 3,
                     code_snippet: "fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 },
                 ExtractedSymbol {
                     name: "multiply".to_string(),
@@ -980,14 +2346,28 @@ mod tests {
                     line_end: 7,
                     code_snippet: "fn multiply(a: i32, b: i32) -> i32 { a * b }".to_string(),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 },
             ],
             imports: vec![],
             calls: vec![ExtractedCall {
                 caller: "multiply".to_string(),
                 callee: "add".to_string(),
+                receiver: None,
                 line: 6,
             }],
+            renders: vec![],
+            dynamic_calls: vec![],
+            supertypes: vec![],
+            exports: vec![],
+            generated: false,
+            endpoints: vec![],
         }];
 
         let mut graph = CodeGraph::new();
@@ -1336,6 +2716,48 @@ mod tests {
         assert!(!dependents.is_empty());
     }
 
+    #[test]
+    fn test_transitive_dependents_by_distance() {
+        // handler -> service -> repo -> db, and db -> repo closes a cycle
+        let mut graph = CodeGraph::new();
+        let file_idx = graph.add_file(PathBuf::from("src/app.rs"));
+        let names = ["db", "repo", "service", "handler"];
+        let idx: Vec<NodeIndex> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let node = graph.add_symbol(
+                    name.to_string(),
+                    NodeKind::Function,
+                    PathBuf::from("src/app.rs"),
+                    i * 3 + 1,
+                    i * 3 + 2,
+                    format!("fn {}() {{}}", name),
+                );
+                graph.add_edge(file_idx, node, EdgeKind::Defines);
+                node
+            })
+            .collect();
+        graph.add_edge(idx[1], idx[0], EdgeKind::Calls);
+        graph.add_edge(idx[2], idx[1], EdgeKind::Calls);
+        graph.add_edge(idx[3], idx[2], EdgeKind::Calls);
+        graph.add_edge(idx[0], idx[1], EdgeKind::Calls);
+
+        let reached = |depth| -> Vec<(String, usize)> {
+            graph
+                .transitive_dependents("db", depth)
+                .into_iter()
+                .map(|(dep, distance)| (dep.symbol, distance))
+                .collect()
+        };
+        assert_eq!(
+            reached(5),
+            vec![("repo".to_string(), 1), ("service".to_string(), 2), ("handler".to_string(), 3)]
+        );
+        assert_eq!(reached(1), vec![("repo".to_string(), 1)]);
+        assert!(reached(0).is_empty());
+    }
+
     #[test]
     fn test_duplicate_symbol_names_across_files() {
         let mut graph = CodeGraph::new();
@@ -1376,6 +2798,46 @@ mod tests {
         assert!(qb.unwrap().code_snippet.contains("/* b */"));
     }
 
+    #[test]
+    fn test_replace_file_relinks_same_named_methods() {
+        let models = PathBuf::from("src/models.py");
+        let source = |padding: &str| {
+            format!(
+                "{}class Order:\n    def save(self):\n        pass\n\nclass User:\n    def save(self):\n        pass\n",
+                padding
+            )
+        };
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            crate::parser::extract_file(&models, &source("")).unwrap(),
+            crate::parser::extract_file(Path::new("src/app.py"), "def run():\n    pass\n\ndef load():\n    pass\n").unwrap(),
+        ]);
+        let node = |graph: &CodeGraph, qualified: &str| {
+            graph
+                .graph
+                .node_indices()
+                .find(|&idx| graph.is_live(idx) && graph.graph[idx].qualified_name == qualified)
+                .unwrap()
+        };
+        let (run, load) = (node(&graph, "app::run"), node(&graph, "app::load"));
+        graph.add_edge(run, node(&graph, "models::User.save"), EdgeKind::Calls);
+        graph.add_edge(load, node(&graph, "models::Order.save"), EdgeKind::Calls);
+
+        // Shifted lines, so neither method keeps its old node
+        graph.replace_file(crate::parser::extract_file(&models, &source("\n\n")).unwrap());
+
+        let callees = |caller: NodeIndex| -> Vec<&str> {
+            graph
+                .graph
+                .edges_directed(caller, Direction::Outgoing)
+                .filter(|e| e.weight().kind == EdgeKind::Calls)
+                .map(|e| graph.graph[e.target()].qualified_name.as_str())
+                .collect()
+        };
+        assert_eq!(callees(run), vec!["models::User.save"]);
+        assert_eq!(callees(load), vec!["models::Order.save"]);
+    }
+
     #[test]
     fn test_remove_nonexistent_file() {
         let mut graph = CodeGraph::new();
@@ -1422,4 +2884,177 @@ mod tests {
 
         assert_eq!(graph.stats().total_edges, 2);
     }
+
+    #[test]
+    fn test_find_by_signature() {
+        let source = r#"
+pub fn process(input: &str) -> String {
+    input.to_uppercase()
+}
+
+fn validate(s: &str) -> bool {
+    !s.is_empty()
+}
+"#;
+        let path = PathBuf::from("src/lib.rs");
+        let extraction = crate::parser::extract_file(&path, source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let results = graph.find_by_signature(&["&str"], Some("bool"));
+        let names: Vec<&str> = results.iter().map(|r| r.symbol.as_str()).collect();
+        assert_eq!(names, vec!["validate"]);
+
+        // Reference-insensitive, any return type
+        let results = graph.find_by_signature(&["str"], None);
+        assert_eq!(results.len(), 2);
+
+        // Arity must match
+        assert!(graph.find_by_signature(&["&str", "bool"], None).is_empty());
+    }
+
+    #[test]
+    fn test_search_graph_max_fanout() {
+        let mut graph = CodeGraph::new();
+        let file = PathBuf::from("src/hub.rs");
+        let hub = graph.add_symbol("dispatch".to_string(), NodeKind::Function, file.clone(), 1, 200, String::new());
+        let mut handlers = Vec::new();
+        for i in 0..100 {
+            let handler = graph.add_symbol(format!("handler_{}", i), NodeKind::Function, file.clone(), 300 + i, 300 + i, String::new());
+            graph.add_edge(hub, handler, EdgeKind::Calls);
+            handlers.push(handler);
+        }
+        // handler_99 is also called elsewhere: the most central neighbor
+        let other = graph.add_symbol("retry".to_string(), NodeKind::Function, file.clone(), 500, 510, String::new());
+        graph.add_edge(other, handlers[99], EdgeKind::Calls);
+
+        let capped = graph.search_graph_with_fanout("dispatch", 1, Some(10));
+        assert_eq!(capped.connections.len(), 10);
+        assert!(capped.truncated);
+        assert_eq!(capped.connections[0].to, "handler_99");
+
+        // Under the cap nothing is dropped or flagged
+        let small = graph.search_graph_with_fanout("retry", 1, Some(10));
+        assert_eq!(small.connections.len(), 1);
+        assert!(!small.truncated);
+    }
+
+    #[test]
+    fn test_neighbors_by_node_and_edge_kind() {
+        let mut graph = CodeGraph::new();
+        let file = PathBuf::from("src/auth.rs");
+
+        let file_idx = graph.add_file(file.clone());
+        let login = graph.add_symbol(
+            "login".to_string(),
+            NodeKind::Function,
+            file.clone(),
+            1,
+            5,
+            "fn login(creds: &Credentials, cfg: Config) -> Session { check(creds) }".to_string(),
+        );
+        let creds = graph.add_symbol("Credentials".to_string(), NodeKind::Struct, file.clone(), 7, 10, String::new());
+        let config = graph.add_symbol("Config".to_string(), NodeKind::Trait, file.clone(), 12, 14, String::new());
+        let session = graph.add_symbol("Session".to_string(), NodeKind::Struct, file.clone(), 16, 18, String::new());
+        let check = graph.add_symbol("check".to_string(), NodeKind::Function, file.clone(), 20, 22, String::new());
+
+        graph.add_edge(file_idx, login, EdgeKind::Defines);
+        graph.add_edge(login, creds, EdgeKind::References);
+        graph.add_edge(login, config, EdgeKind::References);
+        graph.add_edge(login, session, EdgeKind::Returns);
+        graph.add_edge(login, check, EdgeKind::Calls);
+
+        // Structs the function references — not the trait, not the return type
+        let structs = graph.neighbors(
+            "login",
+            TraversalDirection::Outbound,
+            &[NodeKind::Struct],
+            &[EdgeKind::References],
+        );
+        assert_eq!(structs.len(), 1);
+        assert_eq!(structs[0].symbol, "Credentials");
+        assert_eq!(structs[0].relationship, EdgeKind::References);
+
+        // Empty filters match everything, like dependencies()
+        let all = graph.neighbors("login", TraversalDirection::Outbound, &[], &[]);
+        assert_eq!(all.len(), graph.dependencies("login").len());
+        assert_eq!(all.len(), 4);
+
+        // Inbound from the struct's side
+        let users = graph.neighbors(
+            "Credentials",
+            TraversalDirection::Inbound,
+            &[NodeKind::Function],
+            &[],
+        );
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].symbol, "login");
+
+        let both = graph.neighbors("login", TraversalDirection::Both, &[], &[]);
+        assert_eq!(both.len(), 5);
+        assert_eq!(both.last().unwrap().kind, NodeKind::File);
+    }
+
+    #[test]
+    fn test_file_name_lookup_follows_added_and_removed_files() {
+        let mut graph = CodeGraph::new();
+        let define = |graph: &mut CodeGraph, path: &str, name: &str| {
+            let file = graph.add_file(PathBuf::from(path));
+            let symbol = graph.add_symbol(name.to_string(), NodeKind::Function, PathBuf::from(path), 1, 1, String::new());
+            graph.add_edge(file, symbol, EdgeKind::Defines);
+        };
+        let defined_by = |graph: &CodeGraph| -> Vec<String> {
+            graph
+                .neighbors("mod.rs", TraversalDirection::Outbound, &[], &[])
+                .into_iter()
+                .map(|dep| dep.symbol)
+                .collect()
+        };
+
+        define(&mut graph, "src/net/mod.rs", "net");
+        define(&mut graph, "src/auth/mod.rs", "auth");
+        // Files of the same name come back in path order, whatever order they arrived in
+        assert_eq!(defined_by(&graph), vec!["auth", "net"]);
+
+        graph.remove_file(Path::new("src/auth/mod.rs"));
+        assert_eq!(defined_by(&graph), vec!["net"]);
+
+        define(&mut graph, "src/db/mod.rs", "db");
+        assert_eq!(defined_by(&graph), vec!["db", "net"]);
+    }
+
+    #[test]
+    fn test_dependency_direction_is_uniform_across_edge_kinds() {
+        let mut graph = CodeGraph::new();
+        let main_rs = PathBuf::from("src/main.rs");
+
+        let file_idx = graph.add_file(main_rs.clone());
+        let caller = graph.add_symbol("caller".to_string(), NodeKind::Function, main_rs.clone(), 3, 5, String::new());
+        let callee = graph.add_symbol("callee".to_string(), NodeKind::Function, main_rs.clone(), 7, 9, String::new());
+        let config = graph.add_symbol("Config".to_string(), NodeKind::Struct, main_rs.clone(), 11, 13, String::new());
+        let import = graph.add_symbol("crate::db".to_string(), NodeKind::Import, main_rs.clone(), 1, 1, String::new());
+
+        graph.add_edge(caller, callee, EdgeKind::Calls);
+        graph.add_edge(file_idx, import, EdgeKind::Imports);
+        graph.add_edge(caller, config, EdgeKind::References);
+
+        let has = |deps: Vec<DependencyInfo>, name: &str, kind: EdgeKind| {
+            deps.iter().any(|d| d.symbol == name && d.relationship == kind)
+        };
+
+        // Calls: caller -> callee
+        assert!(has(graph.dependencies("caller"), "callee", EdgeKind::Calls));
+        assert!(has(graph.dependents("callee"), "caller", EdgeKind::Calls));
+        assert!(!has(graph.dependencies("callee"), "caller", EdgeKind::Calls));
+
+        // Imports: main.rs -> crate::db, by file name or full path
+        assert!(has(graph.dependencies("main.rs"), "crate::db", EdgeKind::Imports));
+        assert!(has(graph.dependencies("src/main.rs"), "crate::db", EdgeKind::Imports));
+        assert!(has(graph.dependents("crate::db"), "main.rs", EdgeKind::Imports));
+
+        // References: caller -> Config
+        assert!(has(graph.dependencies("caller"), "Config", EdgeKind::References));
+        assert!(has(graph.dependents("Config"), "caller", EdgeKind::References));
+        assert!(graph.dependencies("Config").is_empty());
+    }
 }
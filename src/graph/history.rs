@@ -0,0 +1,293 @@
+//! Write history — versions of a file saved as it is written, and the
+//! timeline of one symbol across them.
+//!
+//! Before each write the file's current contents are stored under
+//! `.anchor/history/<path>/<millis>.snap`, so an agent can see how a
+//! function evolved during a session and go back to the version before its
+//! last change. Only the newest [`MAX_SNAPSHOTS`] of a file are kept.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::engine::CodeGraph;
+use super::types::NodeKind;
+use crate::parser::{extract_file_with, ExtractOptions};
+
+/// Directory of the snapshots, relative to the project root.
+pub const HISTORY_DIR: &str = ".anchor/history";
+
+/// Snapshots kept per file; older ones are dropped as new ones are taken.
+pub const MAX_SNAPSHOTS: usize = 50;
+
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+/// A symbol as it was in one snapshot of its file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolVersion {
+    /// When the snapshot was taken, milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub file: PathBuf,
+    pub line_start: usize,
+    pub line_end: usize,
+    /// The symbol's full source at that point.
+    pub code: String,
+}
+
+/// Save `contents` as the newest snapshot of `file` (relative to `root`,
+/// or absolute inside it). Returns the snapshot's path.
+pub fn record_snapshot(root: &Path, file: &Path, contents: &str) -> io::Result<PathBuf> {
+    let dir = snapshot_dir(root, file).ok_or_else(|| outside_root(file))?;
+    fs::create_dir_all(&dir)?;
+
+    // Writes within the same millisecond still get distinct, ordered names
+    let mut timestamp = chrono::Utc::now().timestamp_millis();
+    if let Some(&(latest, _)) = snapshots(&dir).last() {
+        timestamp = timestamp.max(latest + 1);
+    }
+    let path = dir.join(format!("{}.{}", timestamp, SNAPSHOT_EXTENSION));
+    fs::write(&path, contents)?;
+
+    let existing = snapshots(&dir);
+    for (_, old) in existing.iter().take(existing.len().saturating_sub(MAX_SNAPSHOTS)) {
+        fs::remove_file(old)?;
+    }
+    Ok(path)
+}
+
+/// Snapshot `file` as it is before a write replaces it. A file that
+/// doesn't exist yet has nothing to keep, and records nothing.
+pub fn snapshot_before_write(root: &Path, file: &Path) -> io::Result<Option<PathBuf>> {
+    snapshot_if(root, file, |_| true)
+}
+
+/// [`snapshot_before_write`] for a write that only goes ahead when
+/// `proceeds` accepts the file's current contents, such as a replacement
+/// whose pattern has to be found. A write that fails keeps no version.
+pub fn snapshot_if(root: &Path, file: &Path, proceeds: impl FnOnce(&str) -> bool) -> io::Result<Option<PathBuf>> {
+    if snapshot_dir(root, file).is_none() {
+        return Err(outside_root(file));
+    }
+    match fs::read_to_string(root.join(file)) {
+        Ok(contents) if proceeds(&contents) => record_snapshot(root, file, &contents).map(Some),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+impl CodeGraph {
+    /// Every snapshotted version of `symbol`, oldest first, then the
+    /// version on disk now. Files are found through the graph and
+    /// snapshots under the root's [`HISTORY_DIR`]; versions where the
+    /// symbol doesn't exist are skipped.
+    pub fn symbol_history(&self, symbol: &str) -> Vec<SymbolVersion> {
+        let root = self
+            .meta()
+            .map(|meta| meta.root.clone())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let mut files: Vec<PathBuf> = self
+            .inner_graph()
+            .node_weights()
+            .filter(|node| {
+                !node.removed
+                    && node.name == symbol
+                    && !matches!(node.kind, NodeKind::File | NodeKind::Import)
+            })
+            .map(|node| node.file_path.clone())
+            .collect();
+        files.sort();
+        files.dedup();
+
+        let mut versions: Vec<SymbolVersion> = files
+            .iter()
+            .flat_map(|file| {
+                let mut versions = snapshot_dir(&root, file).map(|dir| snapshots(&dir)).unwrap_or_default();
+                let latest = versions.last().map(|&(timestamp, _)| timestamp);
+                versions.extend(current(file, latest));
+                versions
+                    .into_iter()
+                    .filter_map(move |(timestamp, path)| {
+                        let contents = fs::read_to_string(path).ok()?;
+                        symbol_version(file, &contents, symbol, timestamp)
+                    })
+            })
+            .collect();
+        versions.sort_by_key(|version| version.timestamp);
+        versions
+    }
+}
+
+/// `root/.anchor/history/<file relative to root>`, or `None` for a file
+/// outside the root: an absolute path elsewhere, or one whose `..` climbs
+/// out of it.
+fn snapshot_dir(root: &Path, file: &Path) -> Option<PathBuf> {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let mut dir = root.join(HISTORY_DIR);
+    let mut depth = 0;
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => {
+                dir.push(part);
+                depth += 1;
+            }
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => {
+                dir.pop();
+                depth -= 1;
+            }
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (depth > 0).then_some(dir)
+}
+
+fn outside_root(file: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} is outside the project root", file.display()),
+    )
+}
+
+/// The snapshots in `dir` with their timestamps, oldest first.
+fn snapshots(dir: &Path) -> Vec<(i64, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<(i64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == SNAPSHOT_EXTENSION))
+        .filter_map(|path| {
+            let timestamp = path.file_stem()?.to_str()?.parse().ok()?;
+            Some((timestamp, path))
+        })
+        .collect();
+    snapshots.sort();
+    snapshots
+}
+
+/// `file` on disk, stamped with its modification time. It's newer than
+/// any snapshot taken before a write, even on a coarse-grained clock.
+fn current(file: &Path, latest_snapshot: Option<i64>) -> Option<(i64, PathBuf)> {
+    let modified = fs::metadata(file).ok()?.modified().ok()?;
+    let mut timestamp = chrono::DateTime::<chrono::Utc>::from(modified).timestamp_millis();
+    if let Some(latest) = latest_snapshot {
+        timestamp = timestamp.max(latest + 1);
+    }
+    Some((timestamp, file.to_path_buf()))
+}
+
+/// Parse one snapshot of `file` and cut out `symbol`'s full source.
+fn symbol_version(file: &Path, contents: &str, symbol: &str, timestamp: i64) -> Option<SymbolVersion> {
+    let options = ExtractOptions {
+        truncation_marker: None,
+        ..ExtractOptions::default()
+    };
+    let extraction = extract_file_with(file, contents, &options).ok()?;
+    let found = extraction
+        .symbols
+        .iter()
+        .find(|s| s.name == symbol && s.kind != NodeKind::Import)?;
+
+    // Snippets are bounded; the history keeps the whole body
+    let code = contents
+        .lines()
+        .skip(found.line_start.saturating_sub(1))
+        .take(found.line_end + 1 - found.line_start)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(SymbolVersion {
+        timestamp,
+        file: file.to_path_buf(),
+        line_start: found.line_start,
+        line_end: found.line_end,
+        code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_symbol_history_returns_each_written_body() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let first = "fn greet() -> &'static str {\n    \"hi\"\n}\n";
+        let second = "// greetings\n\nfn greet() -> &'static str {\n    \"hello\"\n}\n\nfn wave() {}\n";
+        for contents in [first, second] {
+            snapshot_before_write(root, Path::new("lib.rs")).unwrap();
+            fs::write(root.join("lib.rs"), contents).unwrap();
+        }
+
+        let graph = build_graph(root);
+        let history = graph.symbol_history("greet");
+
+        let bodies: Vec<&str> = history.iter().map(|v| v.code.as_str()).collect();
+        assert_eq!(
+            bodies,
+            vec![
+                "fn greet() -> &'static str {\n    \"hi\"\n}",
+                "fn greet() -> &'static str {\n    \"hello\"\n}",
+            ]
+        );
+        assert!(history[0].timestamp < history[1].timestamp);
+        assert_eq!(history[1].line_start, 3);
+        // wave only exists in the file as written now
+        assert_eq!(graph.symbol_history("wave").len(), 1);
+        assert!(graph.symbol_history("missing").is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_keep_pre_write_contents_and_are_capped() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        assert_eq!(snapshot_before_write(root, Path::new("lib.rs")).unwrap(), None);
+
+        for i in 0..MAX_SNAPSHOTS + 5 {
+            fs::write(root.join("lib.rs"), format!("fn v{}() {{}}\n", i)).unwrap();
+            snapshot_before_write(root, Path::new("lib.rs")).unwrap();
+        }
+
+        let kept = snapshots(&snapshot_dir(root, Path::new("lib.rs")).unwrap());
+        assert_eq!(kept.len(), MAX_SNAPSHOTS);
+        let oldest = fs::read_to_string(&kept[0].1).unwrap();
+        assert_eq!(oldest, "fn v5() {}\n");
+    }
+
+    #[test]
+    fn test_snapshots_stay_inside_the_history_dir() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("project");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(dir.path().join("outside.rs"), "fn outside() {}\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "fn inside() {}\n").unwrap();
+
+        for escaping in ["../outside.rs", "src/../../outside.rs", ".anchor/../../outside.rs"] {
+            assert!(snapshot_before_write(&root, Path::new(escaping)).is_err(), "{}", escaping);
+        }
+        assert!(snapshot_before_write(&root, &dir.path().join("outside.rs")).is_err());
+        assert!(!root.join(HISTORY_DIR).join("..").join("outside.rs").exists());
+
+        // `..` that stays inside the root is fine
+        let kept = snapshot_before_write(&root, Path::new("src/../src/lib.rs")).unwrap().unwrap();
+        assert!(kept.starts_with(root.join(HISTORY_DIR).join("src/lib.rs")));
+        assert!(snapshot_before_write(&root, &root.join("src/lib.rs")).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_snapshot_if_skips_writes_that_do_not_go_ahead() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("lib.rs"), "fn a() {}\n").unwrap();
+
+        assert_eq!(snapshot_if(root, Path::new("lib.rs"), |contents| contents.contains("fn b")).unwrap(), None);
+        assert!(snapshot_if(root, Path::new("lib.rs"), |contents| contents.contains("fn a")).unwrap().is_some());
+        assert_eq!(snapshots(&snapshot_dir(root, Path::new("lib.rs")).unwrap()).len(), 1);
+    }
+}
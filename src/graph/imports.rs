@@ -0,0 +1,250 @@
+//! Import resolution — the project files an import statement points at.
+//!
+//! Resolution is lexical, against the files in the graph: relative JS/TS
+//! specifiers, Python dotted modules (absolute and relative) and Rust
+//! `crate::`/`super::`/`self::` paths. Imports of anything outside the
+//! project (`axios`, `os`, `std::fs`) resolve to nothing and only keep
+//! their `Import` node.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::refactor::{components, import_family, normalize, python_module, rust_module};
+use super::types::ExtractedImport;
+use crate::parser::SupportedLanguage;
+
+/// Extensions tried for an extensionless JS/TS specifier (`./utils`).
+const JS_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts"];
+
+/// Resolves import strings to the graph's files.
+pub(crate) struct ImportResolver<'a> {
+    /// Files by their lexically normalized path.
+    files: HashMap<PathBuf, &'a Path>,
+}
+
+impl<'a> ImportResolver<'a> {
+    pub(crate) fn new(files: impl IntoIterator<Item = &'a PathBuf>) -> Self {
+        Self {
+            files: files.into_iter().map(|file| (normalize(file), file.as_path())).collect(),
+        }
+    }
+
+    /// The files `import`, found in `importer`, resolves to. Usually one;
+    /// `from . import a, b` names a module per symbol.
+    pub(crate) fn resolve(&self, importer: &Path, import: &ExtractedImport) -> Vec<&'a Path> {
+        let importer = normalize(importer);
+        let targets = match SupportedLanguage::from_path(&importer).map(import_family) {
+            Some(SupportedLanguage::JavaScript) => self.resolve_js(&importer, &import.path).into_iter().collect(),
+            Some(SupportedLanguage::Python) => self.resolve_python(&importer, &import.path, &import.symbols),
+            Some(SupportedLanguage::Rust) => self.resolve_rust(&importer, &import.path).into_iter().collect(),
+            _ => Vec::new(),
+        };
+        let mut targets: Vec<&'a Path> = targets
+            .into_iter()
+            .filter(|target| *target != importer)
+            .filter_map(|target| self.files.get(&target).copied())
+            .collect();
+        targets.dedup();
+        targets
+    }
+
+    fn file(&self, path: PathBuf) -> Option<PathBuf> {
+        self.files.contains_key(&path).then_some(path)
+    }
+
+    /// `./utils` may be `utils.ts`, `utils/index.js`, ...; TypeScript also
+    /// imports `./utils.js` meaning `utils.ts`.
+    fn resolve_js(&self, importer: &Path, spec: &str) -> Option<PathBuf> {
+        if !spec.starts_with('.') {
+            return None;
+        }
+        let target = normalize(&importer.parent()?.join(spec));
+        let mut candidates = vec![target.clone()];
+        for ext in JS_EXTENSIONS {
+            candidates.push(PathBuf::from(format!("{}.{}", target.display(), ext)));
+        }
+        for ext in JS_EXTENSIONS {
+            candidates.push(target.join(format!("index.{}", ext)));
+        }
+        if target.extension().is_some_and(|ext| matches!(ext.to_str(), Some("js" | "jsx" | "mjs"))) {
+            candidates.push(target.with_extension("ts"));
+            candidates.push(target.with_extension("tsx"));
+        }
+        candidates.into_iter().find_map(|candidate| self.file(candidate))
+    }
+
+    /// `from .models import User`, `from . import views`, `import pkg.util`.
+    /// Absolute modules are looked up below each package enclosing the
+    /// importer, nearest first, standing in for `sys.path`.
+    fn resolve_python(&self, importer: &Path, spec: &str, symbols: &[String]) -> Vec<PathBuf> {
+        let Some(package) = importer.parent().map(components) else {
+            return Vec::new();
+        };
+        let mut targets = Vec::new();
+        // `import a, b as c` lists several modules
+        for module in spec.split(',').map(|part| part.split(" as ").next().unwrap_or("").trim()) {
+            let dots = module.chars().take_while(|&c| c == '.').count();
+            let rest: Vec<String> = module[dots..]
+                .split('.')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+
+            let bases: Vec<Vec<String>> = if dots > 0 {
+                let Some(keep) = package.len().checked_sub(dots - 1) else {
+                    continue;
+                };
+                vec![package[..keep].to_vec()]
+            } else if rest.is_empty() {
+                continue;
+            } else {
+                (0..=package.len()).rev().map(|keep| package[..keep].to_vec()).collect()
+            };
+
+            for base in bases {
+                let mut module = base;
+                module.extend(rest.iter().cloned());
+                // Imported names may be submodules of the package
+                let submodules: Vec<PathBuf> = symbols
+                    .iter()
+                    .filter_map(|symbol| {
+                        let mut submodule = module.clone();
+                        submodule.push(symbol.clone());
+                        self.python_file(&submodule)
+                    })
+                    .collect();
+                if !submodules.is_empty() {
+                    targets.extend(submodules);
+                    break;
+                }
+                if let Some(file) = self.python_file(&module) {
+                    targets.push(file);
+                    break;
+                }
+            }
+        }
+        targets
+    }
+
+    /// `a/b.py` or the package `a/b/__init__.py`.
+    fn python_file(&self, module: &[String]) -> Option<PathBuf> {
+        if module.is_empty() {
+            return None;
+        }
+        let path: PathBuf = module.iter().collect();
+        self.file(path.with_extension("py"))
+            .or_else(|| self.file(path.join("__init__.py")))
+            .filter(|file| python_module(file) == module)
+    }
+
+    /// The deepest module file on a `use` path: `crate::graph::engine::CodeGraph`
+    /// is `src/graph/engine.rs` (or `engine/mod.rs`), falling back to
+    /// `src/graph.rs` when `engine` is an item, and to `lib.rs`/`main.rs`
+    /// for `use crate::Item`.
+    fn resolve_rust(&self, importer: &Path, spec: &str) -> Option<PathBuf> {
+        // `pub use`, `pub(crate) use`
+        let path = if spec.starts_with("pub") {
+            spec.split_once("use ")?.1.trim()
+        } else {
+            spec
+        };
+        let segments: Vec<&str> = path
+            .split("::")
+            .take_while(|s| !s.contains(['{', '*']))
+            .map(|s| s.split(" as ").next().unwrap_or(s).trim())
+            .collect();
+
+        let anchors = segments
+            .iter()
+            .take_while(|&&s| matches!(s, "crate" | "super" | "self"))
+            .count();
+        let mut module = match segments.first().copied() {
+            Some("crate") if anchors == 1 => Vec::new(),
+            Some("self" | "super") => {
+                let mut base = rust_module(importer)?;
+                // A file's own module is `self`; each `super` goes up one
+                for _ in segments[..anchors].iter().filter(|&&s| s == "super") {
+                    base.pop()?;
+                }
+                base
+            }
+            _ => return None,
+        };
+        module.extend(segments[anchors..].iter().map(|s| s.to_string()));
+
+        let parts = components(importer);
+        let src = parts.iter().rposition(|part| part == "src")?;
+        let src_dir: PathBuf = parts[..=src].iter().collect();
+        (0..=module.len()).rev().find_map(|len| {
+            if len == 0 {
+                return self
+                    .file(src_dir.join("lib.rs"))
+                    .or_else(|| self.file(src_dir.join("main.rs")));
+            }
+            let path: PathBuf = src_dir.join(module[..len].iter().collect::<PathBuf>());
+            self.file(path.with_extension("rs"))
+                .or_else(|| self.file(path.join("mod.rs")))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(path: &str, symbols: &[&str]) -> ExtractedImport {
+        ExtractedImport {
+            path: path.to_string(),
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn test_resolve_imports_per_language() {
+        let files: Vec<PathBuf> = [
+            "web/src/app.ts",
+            "web/src/utils.ts",
+            "web/src/lib/model/index.js",
+            "web/src/api.ts",
+            "pkg/app.py",
+            "pkg/models.py",
+            "pkg/views.py",
+            "pkg/util/__init__.py",
+            "core/db.py",
+            "src/main.rs",
+            "src/db.rs",
+            "src/graph/mod.rs",
+            "src/graph/engine.rs",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        let resolver = ImportResolver::new(&files);
+        let resolve = |importer: &str, path: &str, symbols: &[&str]| -> Vec<String> {
+            resolver
+                .resolve(Path::new(importer), &import(path, symbols))
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect()
+        };
+
+        assert_eq!(resolve("web/src/app.ts", "./utils", &["a"]), vec!["web/src/utils.ts"]);
+        assert_eq!(resolve("web/src/app.ts", "./lib/model", &[]), vec!["web/src/lib/model/index.js"]);
+        assert_eq!(resolve("web/src/app.ts", "./api.js", &[]), vec!["web/src/api.ts"]);
+        assert!(resolve("web/src/app.ts", "axios", &[]).is_empty());
+
+        assert_eq!(resolve("pkg/app.py", ".models", &["User"]), vec!["pkg/models.py"]);
+        assert_eq!(resolve("pkg/app.py", ".", &["views", "helper"]), vec!["pkg/views.py"]);
+        assert_eq!(resolve("pkg/app.py", "pkg.util", &[]), vec!["pkg/util/__init__.py"]);
+        assert_eq!(resolve("pkg/app.py", "core.db", &["conn"]), vec!["core/db.py"]);
+        assert!(resolve("pkg/app.py", "os", &[]).is_empty());
+
+        assert_eq!(resolve("src/main.rs", "crate::db::connect", &[]), vec!["src/db.rs"]);
+        assert_eq!(resolve("src/main.rs", "crate::graph::{A, B}", &[]), vec!["src/graph/mod.rs"]);
+        assert_eq!(resolve("src/graph/mod.rs", "self::engine::CodeGraph", &[]), vec!["src/graph/engine.rs"]);
+        assert_eq!(resolve("src/graph/engine.rs", "super::super::db", &[]), vec!["src/db.rs"]);
+        assert_eq!(resolve("src/db.rs", "crate::Config", &[]), vec!["src/main.rs"]);
+        assert!(resolve("src/main.rs", "std::fs", &[]).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Graph metadata — how and when a graph was built.
+//!
+//! Saved with `graph.bin` so a loaded graph can be checked against the
+//! current checkout: if the commit it was built at differs from `HEAD`,
+//! the index is stale.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::engine::CodeGraph;
+use super::summary::{language_breakdown, LanguageCount};
+use super::types::NodeKind;
+use crate::parser::{ExtractOptions, SupportedLanguage};
+
+/// Build information persisted alongside the graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphMeta {
+    /// Version of Anchor that built the graph.
+    pub anchor_version: String,
+    /// Build time, seconds since the Unix epoch.
+    pub built_at: u64,
+    /// Project root the graph was built from.
+    pub root: PathBuf,
+    /// `HEAD` commit at build time, if the root is in a git repository.
+    pub git_commit: Option<String>,
+    /// Files per language, most common first.
+    pub language_counts: Vec<LanguageCount>,
+    /// Languages left out of the build because their grammar couldn't be
+    /// loaded.
+    #[serde(default)]
+    pub grammar_failures: Vec<GrammarFailure>,
+    /// How the build extracted files; re-parsing one file uses the same.
+    #[serde(default)]
+    pub extract_options: ExtractOptions,
+    /// Patterns the build tagged as generated code.
+    #[serde(default)]
+    pub generated: Vec<String>,
+    /// Call stopwords the build was given on top of each language's.
+    #[serde(default)]
+    pub call_stopwords: Vec<String>,
+    /// Symbol kinds the build kept; empty keeps every kind.
+    #[serde(default)]
+    pub index_kinds: Vec<NodeKind>,
+    /// Languages the build parsed; `None` parses every language.
+    #[serde(default)]
+    pub languages: Option<Vec<SupportedLanguage>>,
+    /// Ignore patterns the build skipped, on top of `.gitignore`.
+    #[serde(default)]
+    pub ignores: Vec<String>,
+    /// Paths indexed despite the ignore rules.
+    #[serde(default)]
+    pub also_index: Vec<PathBuf>,
+    /// Size above which the build skipped a file.
+    #[serde(default)]
+    pub max_file_bytes: Option<u64>,
+}
+
+/// A language whose grammar failed to load, and what it cost.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrammarFailure {
+    pub language: SupportedLanguage,
+    /// Why the grammar was rejected.
+    pub error: String,
+    /// Files of the language that were skipped.
+    pub skipped_files: usize,
+}
+
+impl GraphMeta {
+    /// Capture metadata for a graph just built from `root`.
+    ///
+    /// The build time honors `SOURCE_DATE_EPOCH`, so reproducible builds of
+    /// the same tree can produce identical `graph.bin` files.
+    pub fn capture(graph: &CodeGraph, root: &Path) -> Self {
+        let built_at = std::env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|epoch| epoch.trim().parse().ok())
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+            });
+
+        Self {
+            anchor_version: env!("CARGO_PKG_VERSION").to_string(),
+            built_at,
+            root: root.to_path_buf(),
+            git_commit: git_head(root),
+            language_counts: language_breakdown(&graph.all_files()),
+            grammar_failures: Vec::new(),
+            extract_options: ExtractOptions::default(),
+            generated: Vec::new(),
+            call_stopwords: Vec::new(),
+            index_kinds: Vec::new(),
+            languages: None,
+            ignores: Vec::new(),
+            also_index: Vec::new(),
+            max_file_bytes: None,
+        }
+    }
+
+    /// Check whether the graph was built at a different commit than `head`.
+    ///
+    /// Returns `false` when either commit is unknown (not a git checkout).
+    pub fn commit_mismatch(&self, head: Option<&str>) -> bool {
+        match (&self.git_commit, head) {
+            (Some(built), Some(head)) => built != head,
+            _ => false,
+        }
+    }
+}
+
+/// The current `HEAD` commit of the repository containing `root`.
+pub fn git_head(root: &Path) -> Option<String> {
+    let commit = run_git(root, &["rev-parse", "HEAD"])?.trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Run `git -C dir <args>` and return stdout, or `None` if git is missing
+/// or the command fails (e.g. `dir` isn't in a repository).
+pub(crate) fn run_git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+impl CodeGraph {
+    /// Metadata recorded when the graph was built, if any.
+    pub fn meta(&self) -> Option<&GraphMeta> {
+        self.meta.as_ref()
+    }
+
+    /// Attach build metadata to the graph. The graph keeps indexing the
+    /// symbol kinds the metadata records.
+    pub fn set_meta(&mut self, meta: GraphMeta) {
+        self.set_index_kinds(&meta.index_kinds);
+        self.meta = Some(meta);
+        // The root may have moved
+        self.codeowners = Default::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_meta_roundtrip() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn one() {}\n").unwrap();
+
+        let graph = build_graph(dir.path());
+        let meta = graph.meta().expect("build_graph should record metadata").clone();
+        assert_eq!(meta.root, dir.path());
+        assert_eq!(meta.anchor_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(meta.language_counts[0].language, "Rust");
+
+        let cache = dir.path().join("graph.bin");
+        graph.save(&cache).unwrap();
+        let loaded = CodeGraph::load(&cache).unwrap();
+        assert_eq!(loaded.meta(), Some(&meta));
+    }
+
+    #[test]
+    fn test_commit_mismatch() {
+        let meta = GraphMeta {
+            anchor_version: "0.0.0".to_string(),
+            built_at: 0,
+            root: PathBuf::from("."),
+            git_commit: Some("abc123".to_string()),
+            language_counts: Vec::new(),
+            grammar_failures: Vec::new(),
+            extract_options: ExtractOptions::default(),
+            generated: Vec::new(),
+            call_stopwords: Vec::new(),
+            index_kinds: Vec::new(),
+            languages: None,
+            ignores: Vec::new(),
+            also_index: Vec::new(),
+            max_file_bytes: None,
+        };
+
+        assert!(!meta.commit_mismatch(Some("abc123")));
+        assert!(meta.commit_mismatch(Some("def456")));
+        assert!(!meta.commit_mismatch(None));
+    }
+}
@@ -3,14 +3,48 @@
 //! Provides the graph data model, engine, query capabilities,
 //! and directory scanning/building for the code graph.
 
+pub mod blame;
 pub mod builder;
+pub mod centrality;
+pub mod cochange;
+pub mod coverage;
+pub mod deadcode;
+pub mod endpoints;
 pub mod engine;
+pub mod history;
+pub mod imports;
+pub mod meta;
+pub mod owners;
+pub mod package;
 pub mod persistence;
+pub mod refactor;
+pub mod rename;
+pub mod signature;
+pub mod summary;
 pub mod types;
+pub mod worktree;
 
-pub use builder::{build_graph, rebuild_file, scan_stats, ScanStats};
-pub use engine::{CodeGraph, DependencyInfo, GraphStats, SearchResult, SymbolRef};
+pub use blame::{BlameCache, BlameInfo};
+pub use builder::{
+    build_graph, rebuild_file, scan_stats, BuildProgress, CodeGraphBuilder, ProgressSink, ScanStats,
+};
+pub use cochange::CoChange;
+pub use coverage::UntestedSymbol;
+pub use deadcode::UnreferencedSymbol;
+pub use endpoints::{Mismatch, MismatchKind};
+pub use engine::{
+    normalize_path, CodeGraph, DependencyInfo, GraphHealth, GraphStats, SearchResult, SymbolRef,
+};
+pub use history::{record_snapshot, snapshot_before_write, snapshot_if, SymbolVersion, HISTORY_DIR, MAX_SNAPSHOTS};
+pub use meta::{git_head, GrammarFailure, GraphMeta};
+pub use owners::CodeOwners;
+pub use package::PACKAGE_MANIFESTS;
+pub use persistence::{BincodeFileStore, GraphSnapshot, GraphStore};
+pub use rename::{Rename, RenameError, RenameSite, RenameTarget};
+pub use signature::SignatureShape;
+pub use summary::{LanguageCount, ProjectSummary, SummarySymbol};
+pub use worktree::{ChangedSymbol, UncommittedChanges};
 pub use types::{
-    ConnectionInfo, EdgeData, EdgeKind, ExtractedCall, ExtractedImport, ExtractedSymbol,
-    FileExtractions, GraphSearchResult, NodeData, NodeKind, SymbolInfo,
+    ApiEndpoint, ApiEndpointKind, ConnectionInfo, DependencyScope, EdgeData, ExtractedApiEndpoint, EdgeKind, ExtractedCall, ExtractedImport,
+    ExtractedSupertype, ExtractedSymbol, FileExtractions, GraphSearchResult, NodeData, NodeKind, SymbolFilter, SymbolInfo, TraversalDirection, Visibility,
 };
@@ -0,0 +1,180 @@
+//! Code owners — who reviews changes to a file, from `CODEOWNERS`.
+//!
+//! Patterns follow gitignore rules (`*` stays within a directory, a
+//! leading `/` or inner `/` anchors to the repo root, a trailing `/`
+//! matches everything below a directory) and the last matching line wins,
+//! as on GitHub and GitLab.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::warn;
+
+use super::engine::CodeGraph;
+
+/// Where a `CODEOWNERS` file is looked for, relative to the root.
+pub const CODEOWNERS_LOCATIONS: &[&str] = &["CODEOWNERS", ".github/CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Parsed `CODEOWNERS` rules, in file order.
+#[derive(Debug, Clone)]
+pub struct CodeOwners {
+    rules: Vec<(Gitignore, Vec<String>)>,
+}
+
+impl CodeOwners {
+    /// Read the first `CODEOWNERS` file found under `root`.
+    pub fn load(root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .find_map(|location| fs::read_to_string(root.join(location)).ok())
+            .map(|content| Self::parse(&content))
+    }
+
+    /// Parse `CODEOWNERS` content. Invalid patterns are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            // Owners end where an inline comment starts
+            let owners: Vec<String> = parts
+                .take_while(|part| !part.starts_with('#'))
+                .map(str::to_string)
+                .collect();
+
+            let mut builder = GitignoreBuilder::new("");
+            let matcher = builder.add_line(None, pattern).and_then(|b| b.build());
+            match matcher {
+                Ok(matcher) => rules.push((matcher, owners)),
+                Err(e) => warn!(pattern = %pattern, error = %e, "invalid CODEOWNERS pattern"),
+            }
+        }
+        Self { rules }
+    }
+
+    /// Owners of a root-relative path. The last matching rule wins; a rule
+    /// without owners leaves the path unowned.
+    pub fn owners_of(&self, path: &Path) -> Vec<String> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(matcher, _)| matcher.matched_path_or_any_parents(path, false).is_ignore())
+            .map(|(_, owners)| owners.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl CodeGraph {
+    /// Owners of a file, or of the file defining a symbol, according to
+    /// the `CODEOWNERS` file of the project root.
+    ///
+    /// Paths that aren't in the graph are still matched, relative to the
+    /// root. Returns an empty list if there is no `CODEOWNERS` file, no
+    /// rule matches, or the path is outside the root. The `CODEOWNERS`
+    /// file is read once per graph.
+    pub fn owners(&self, symbol_or_file: &str) -> Vec<String> {
+        let root = self
+            .meta()
+            .map(|meta| meta.root.clone())
+            .unwrap_or_else(|| PathBuf::from("."));
+        let Some(codeowners) = self.codeowners.get_or_init(|| CodeOwners::load(&root)) else {
+            return Vec::new();
+        };
+
+        let path = Path::new(symbol_or_file);
+        let file = if self.all_files().iter().any(|f| f == path) || root.join(path).exists() {
+            path.to_path_buf()
+        } else {
+            match self.search(symbol_or_file, 1).into_iter().next() {
+                Some(result) => result.file,
+                None => path.to_path_buf(),
+            }
+        };
+
+        match relative_to(&file, &root) {
+            Some(relative) => codeowners.owners_of(&relative),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// `file` relative to `root`, without a leading `./`, or `None` if it's
+/// outside the root.
+fn relative_to(file: &Path, root: &Path) -> Option<PathBuf> {
+    let relative = match file.strip_prefix(root) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) if file.is_absolute() => file.strip_prefix(root.canonicalize().ok()?).ok()?.to_path_buf(),
+        Err(_) => file.to_path_buf(),
+    };
+    let relative = relative.strip_prefix(".").unwrap_or(&relative);
+    let escapes = relative.components().any(|c| matches!(c, Component::ParentDir));
+    (!escapes).then(|| relative.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse(
+            "# Default owners\n* @org/everyone\nsrc/*.rs @org/rust-team # core\n/src/legacy.rs @alice @bob\ndocs/\n",
+        );
+
+        assert_eq!(owners.owners_of(Path::new("src/lib.rs")), vec!["@org/rust-team"]);
+        assert_eq!(owners.owners_of(Path::new("src/legacy.rs")), vec!["@alice", "@bob"]);
+        // `*` doesn't cross directories
+        assert_eq!(owners.owners_of(Path::new("src/deep/mod.rs")), vec!["@org/everyone"]);
+        // A rule without owners unassigns
+        assert!(owners.owners_of(Path::new("docs/guide/intro.md")).is_empty());
+    }
+
+    #[test]
+    fn test_graph_owners_for_file_and_symbol() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".github")).unwrap();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join(".github/CODEOWNERS"), "src/*.rs @org/rust-team\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn alpha() {}\n").unwrap();
+        fs::write(root.join("tool.py"), "def beta():\n    pass\n").unwrap();
+
+        let graph = build_graph(root);
+
+        let file = root.join("src/lib.rs");
+        assert_eq!(graph.owners(&file.to_string_lossy()), vec!["@org/rust-team"]);
+        assert_eq!(graph.owners("src/lib.rs"), vec!["@org/rust-team"]);
+        assert_eq!(graph.owners("alpha"), vec!["@org/rust-team"]);
+        assert!(graph.owners("beta").is_empty());
+    }
+
+    #[test]
+    fn test_owners_read_once_and_only_inside_root() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("project");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("CODEOWNERS"), "* @org/everyone\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn alpha() {}\n").unwrap();
+        fs::write(dir.path().join("outside.rs"), "fn gamma() {}\n").unwrap();
+
+        let graph = build_graph(&root);
+        assert_eq!(graph.owners("src/lib.rs"), vec!["@org/everyone"]);
+
+        // `*` would match anything, but these aren't project files
+        assert!(graph.owners(&dir.path().join("outside.rs").to_string_lossy()).is_empty());
+        assert!(graph.owners("../outside.rs").is_empty());
+        assert!(graph.owners("/etc/hostname").is_empty());
+
+        // Rules were loaded with the graph's first lookup
+        fs::write(root.join("CODEOWNERS"), "* @someone-else\n").unwrap();
+        assert_eq!(graph.owners("src/lib.rs"), vec!["@org/everyone"]);
+    }
+}
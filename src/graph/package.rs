@@ -0,0 +1,176 @@
+//! Package boundaries — the nearest directory above a file holding a
+//! package manifest.
+//!
+//! In a workspace of several packages, an edit to an internal function
+//! usually only concerns callers in its own package; scoping dependents to
+//! the package boundary drops the rest.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::engine::{CodeGraph, DependencyInfo};
+use super::types::{DependencyScope, NodeKind};
+
+/// Files that make their directory a package root.
+pub const PACKAGE_MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "go.mod"];
+
+/// Package root of each directory looked up so far, searching no higher
+/// than the project root.
+#[derive(Debug)]
+struct PackageRoots {
+    /// The project root. Relative paths are resolved against it, and a
+    /// manifest above it (say `~/Cargo.toml`) doesn't count.
+    project: PathBuf,
+    /// `None` when no directory up to the project root has a manifest.
+    dirs: HashMap<PathBuf, Option<PathBuf>>,
+}
+
+impl PackageRoots {
+    fn new(project: &Path) -> Self {
+        Self {
+            project: project.to_path_buf(),
+            dirs: HashMap::new(),
+        }
+    }
+
+    /// The package `file` belongs to.
+    fn of(&mut self, file: &Path) -> Option<PathBuf> {
+        let file = self.project.join(file);
+        self.dir(file.parent()?)
+    }
+
+    fn dir(&mut self, dir: &Path) -> Option<PathBuf> {
+        if !dir.starts_with(&self.project) {
+            return None;
+        }
+        if let Some(root) = self.dirs.get(dir) {
+            return root.clone();
+        }
+        let root = if PACKAGE_MANIFESTS.iter().any(|manifest| dir.join(manifest).is_file()) {
+            Some(dir.to_path_buf())
+        } else if dir == self.project {
+            None
+        } else {
+            dir.parent().and_then(|parent| self.dir(parent))
+        };
+        self.dirs.insert(dir.to_path_buf(), root.clone());
+        root
+    }
+}
+
+impl CodeGraph {
+    /// [`dependents`](Self::dependents) of `symbol` that are within `scope`
+    /// of one of its definitions. Package boundaries are read from the
+    /// filesystem under the graph's root, so its file paths must still
+    /// resolve.
+    pub fn dependents_in(&self, symbol: &str, scope: DependencyScope) -> Vec<DependencyInfo> {
+        let mut dependents = self.dependents(symbol);
+        if scope == DependencyScope::Workspace {
+            return dependents;
+        }
+
+        let mut files: Vec<&Path> = self
+            .inner_graph()
+            .node_weights()
+            .filter(|node| {
+                !node.removed
+                    && node.name == symbol
+                    && !matches!(node.kind, NodeKind::File | NodeKind::Import)
+            })
+            .map(|node| node.file_path.as_path())
+            .collect();
+        files.sort();
+        files.dedup();
+        if files.is_empty() {
+            return dependents;
+        }
+
+        match scope {
+            DependencyScope::Workspace => {}
+            DependencyScope::File => dependents.retain(|dep| files.contains(&dep.file.as_path())),
+            DependencyScope::Package => {
+                let project = self.meta().map(|meta| meta.root.as_path()).unwrap_or(Path::new("."));
+                let mut roots = PackageRoots::new(project);
+                let packages: Vec<Option<PathBuf>> = files.iter().map(|file| roots.of(file)).collect();
+                dependents.retain(|dep| packages.contains(&roots.of(&dep.file)));
+            }
+        }
+        dependents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_package_roots_use_nearest_manifest() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("crates/core/src/nested")).unwrap();
+        fs::create_dir_all(root.join("web/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+        fs::write(root.join("crates/core/Cargo.toml"), "[package]\n").unwrap();
+        fs::write(root.join("web/package.json"), "{}\n").unwrap();
+
+        let mut roots = PackageRoots::new(root);
+        assert_eq!(roots.of(&root.join("crates/core/src/nested/a.rs")), Some(root.join("crates/core")));
+        assert_eq!(roots.of(&root.join("web/src/app.ts")), Some(root.join("web")));
+        assert_eq!(roots.of(&root.join("build.rs")), Some(root.to_path_buf()));
+        // Relative paths are under the project root, not the working directory
+        assert_eq!(roots.of(Path::new("web/src/app.ts")), Some(root.join("web")));
+    }
+
+    #[test]
+    fn test_package_roots_stop_at_project_root() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("project");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("tool")).unwrap();
+        // A manifest above the project, like a stray ~/Cargo.toml
+        fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(project.join("tool/go.mod"), "module tool\n").unwrap();
+
+        let mut roots = PackageRoots::new(&project);
+        assert_eq!(roots.of(&project.join("src/lib.rs")), None);
+        assert_eq!(roots.of(Path::new("src/lib.rs")), None);
+        assert_eq!(roots.of(&project.join("tool/main.go")), Some(project.join("tool")));
+        assert_eq!(roots.of(&dir.path().join("outside.rs")), None);
+    }
+
+    #[test]
+    fn test_package_scope_drops_cross_package_callers() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        for package in ["core", "app"] {
+            fs::create_dir_all(root.join(package).join("src")).unwrap();
+            fs::write(root.join(package).join("Cargo.toml"), "[package]\n").unwrap();
+        }
+        fs::write(
+            root.join("core/src/lib.rs"),
+            "pub fn normalize(s: &str) -> String {\n    s.trim().to_string()\n}\n\npub fn parse(s: &str) -> String {\n    normalize(s)\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("core/src/util.rs"), "fn clean(s: &str) -> String {\n    normalize(s)\n}\n").unwrap();
+        fs::write(root.join("app/src/main.rs"), "fn main() {\n    normalize(\" x \");\n}\n").unwrap();
+
+        let graph = build_graph(root);
+        let callers = |scope| {
+            let mut names: Vec<String> = graph
+                .dependents_in("normalize", scope)
+                .into_iter()
+                .filter(|dep| dep.kind != NodeKind::File)
+                .map(|dep| dep.symbol)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(callers(DependencyScope::Workspace), vec!["clean", "main", "parse"]);
+        assert_eq!(callers(DependencyScope::Package), vec!["clean", "parse"]);
+        assert_eq!(callers(DependencyScope::File), vec!["parse"]);
+    }
+}
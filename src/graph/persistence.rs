@@ -1,58 +1,100 @@
-//! Graph persistence — save and load CodeGraph to/from disk.
+//! Graph persistence — save and load CodeGraph through a `GraphStore`.
 //!
-//! Uses bincode for compact binary serialization. Atomic writes
-//! (write to .tmp, then rename) prevent corruption from crashes.
+//! The default store writes bincode to a single file. Atomic writes
+//! (write to .tmp, then rename) prevent corruption from crashes. Other
+//! backends (sqlite, redis, ...) implement `GraphStore` on top of
+//! `GraphSnapshot`, the graph's plain-data form.
 
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
 use super::engine::CodeGraph;
-use super::types::{EdgeData, NodeData, NodeKind};
+use super::meta::GraphMeta;
+use super::types::{ApiEndpoint, EdgeData, NodeData, NodeKind};
 use crate::error::{AnchorError, Result};
 
 /// Serializable representation of the graph.
 /// Nodes are stored as a flat vec; edges reference nodes by index position.
-#[derive(Serialize, Deserialize)]
-struct SerializableGraph {
-    nodes: Vec<NodeData>,
-    edges: Vec<(u32, u32, EdgeData)>,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    pub nodes: Vec<NodeData>,
+    pub edges: Vec<(u32, u32, EdgeData)>,
+    pub endpoints: Vec<ApiEndpoint>,
+    /// `__init__.py` files and the names their `__all__` re-exports.
+    #[serde(default)]
+    pub reexports: Vec<(PathBuf, Vec<String>)>,
+    pub meta: Option<GraphMeta>,
 }
 
-impl CodeGraph {
-    /// Save the graph to a binary file.
+/// Where a graph is persisted.
+///
+/// Implementations typically store `graph.snapshot()` and rebuild with
+/// `CodeGraph::from_snapshot`.
+pub trait GraphStore {
+    /// Persist the whole graph, replacing whatever was stored.
+    fn save(&self, graph: &CodeGraph) -> Result<()>;
+
+    /// Load the stored graph.
+    fn load(&self) -> Result<CodeGraph>;
+
+    /// Persist the graph after `file` alone was rebuilt.
     ///
+    /// Backends that store files separately can write just that file's
+    /// nodes; the default saves the whole graph.
+    fn save_file_segment(&self, graph: &CodeGraph, file: &Path) -> Result<()> {
+        debug!(file = %file.display(), "saving whole graph for file segment");
+        self.save(graph)
+    }
+}
+
+/// The default store: one bincode file, written atomically.
+#[derive(Debug, Clone)]
+pub struct BincodeFileStore {
+    path: PathBuf,
+}
+
+impl BincodeFileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file the graph is stored in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl GraphStore for BincodeFileStore {
     /// Uses atomic write: writes to a `.tmp` file first, then renames.
     /// This prevents corruption if the process is interrupted mid-write.
-    pub fn save(&self, path: &Path) -> Result<()> {
-        info!(path = %path.display(), "saving graph");
+    fn save(&self, graph: &CodeGraph) -> Result<()> {
+        info!(path = %self.path.display(), "saving graph");
 
-        let sg = self.to_serializable();
-        let bytes =
-            bincode::serialize(&sg).map_err(|e| AnchorError::SerializeError(e.to_string()))?;
+        let bytes = bincode::serialize(&graph.snapshot())
+            .map_err(|e| AnchorError::SerializeError(e.to_string()))?;
 
         // Atomic write: write to .tmp, then rename
-        let tmp_path = path.with_extension("tmp");
+        let tmp_path = self.path.with_extension("tmp");
         let mut file = fs::File::create(&tmp_path)?;
         file.write_all(&bytes)?;
         file.sync_all()?;
-        fs::rename(&tmp_path, path)?;
+        fs::rename(&tmp_path, &self.path)?;
 
         debug!(bytes = bytes.len(), "graph saved");
         Ok(())
     }
 
-    /// Load a graph from a binary file.
-    pub fn load(path: &Path) -> Result<Self> {
-        info!(path = %path.display(), "loading graph");
+    fn load(&self) -> Result<CodeGraph> {
+        info!(path = %self.path.display(), "loading graph");
 
-        let bytes = fs::read(path)?;
-        let sg: SerializableGraph = bincode::deserialize(&bytes)
+        let bytes = fs::read(&self.path)?;
+        let snapshot: GraphSnapshot = bincode::deserialize(&bytes)
             .map_err(|e| AnchorError::ParseError(format!("bincode: {}", e)))?;
 
-        let graph = Self::from_serializable(sg);
+        let graph = CodeGraph::from_snapshot(snapshot);
 
         let stats = graph.stats();
         debug!(
@@ -64,9 +106,31 @@ impl CodeGraph {
 
         Ok(graph)
     }
+}
+
+impl CodeGraph {
+    /// Save the graph to a binary file (see [`BincodeFileStore`]).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        BincodeFileStore::new(path).save(self)
+    }
+
+    /// Load a graph from a binary file (see [`BincodeFileStore`]).
+    pub fn load(path: &Path) -> Result<Self> {
+        BincodeFileStore::new(path).load()
+    }
+
+    /// Save the graph to any store.
+    pub fn save_to(&self, store: &dyn GraphStore) -> Result<()> {
+        store.save(self)
+    }
+
+    /// Load a graph from any store.
+    pub fn load_from(store: &dyn GraphStore) -> Result<Self> {
+        store.load()
+    }
 
     /// Convert to a serializable representation.
-    fn to_serializable(&self) -> SerializableGraph {
+    pub fn snapshot(&self) -> GraphSnapshot {
         let graph = self.inner_graph();
 
         // Collect nodes in index order
@@ -82,11 +146,25 @@ impl CodeGraph {
             })
             .collect();
 
-        SerializableGraph { nodes, edges }
+        // The URL index is unordered; sort so equal graphs save equal bytes
+        let mut endpoints: Vec<ApiEndpoint> = self.endpoints().cloned().collect();
+        endpoints.sort_by(|a, b| (&a.file, a.line, &a.url, &a.method).cmp(&(&b.file, b.line, &b.url, &b.method)));
+
+        let mut reexports: Vec<(PathBuf, Vec<String>)> =
+            self.reexports.iter().map(|(init, names)| (init.clone(), names.clone())).collect();
+        reexports.sort();
+
+        GraphSnapshot {
+            nodes,
+            edges,
+            endpoints,
+            reexports,
+            meta: self.meta().cloned(),
+        }
     }
 
     /// Reconstruct from a serializable representation.
-    fn from_serializable(sg: SerializableGraph) -> Self {
+    pub fn from_snapshot(sg: GraphSnapshot) -> Self {
         use petgraph::graph::NodeIndex;
 
         let mut graph = Self::new();
@@ -97,14 +175,7 @@ impl CodeGraph {
             let idx = if node.kind == NodeKind::File {
                 graph.add_file(node.file_path.clone())
             } else {
-                graph.add_symbol(
-                    node.name.clone(),
-                    node.kind,
-                    node.file_path.clone(),
-                    node.line_start,
-                    node.line_end,
-                    node.code_snippet.clone(),
-                )
+                graph.add_symbol_data(node.clone())
             };
 
             // If the original node was removed, mark it
@@ -121,7 +192,15 @@ impl CodeGraph {
         for (src, tgt, data) in sg.edges {
             let src_idx = index_map[src as usize];
             let tgt_idx = index_map[tgt as usize];
-            graph.add_edge(src_idx, tgt_idx, data.kind);
+            graph.inner_graph_mut().add_edge(src_idx, tgt_idx, data);
+        }
+
+        for endpoint in sg.endpoints {
+            graph.add_endpoint(endpoint);
+        }
+        graph.reexports = sg.reexports.into_iter().collect();
+        if let Some(meta) = sg.meta {
+            graph.set_meta(meta);
         }
 
         graph
@@ -160,6 +239,8 @@ mod tests {
         );
         graph.add_edge(file_idx, helper_idx, EdgeKind::Defines);
         graph.add_edge(fn_idx, helper_idx, EdgeKind::Calls);
+        let call = graph.inner_graph().find_edge(fn_idx, helper_idx).unwrap();
+        graph.inner_graph_mut()[call].count = 3;
 
         let dir = tempdir().unwrap();
         let save_path = dir.path().join("graph.bin");
@@ -184,6 +265,7 @@ mod tests {
         assert_eq!(results[0].symbol, "main");
         assert_eq!(results[0].calls.len(), 1);
         assert_eq!(results[0].calls[0].name, "helper");
+        assert_eq!(loaded.dependencies("main")[0].count, 3);
     }
 
     #[test]
@@ -217,9 +299,124 @@ mod tests {
         assert_eq!(loaded.search("old_fn", 3).len(), 0);
     }
 
+    /// A backend that keeps the graph as JSON in memory.
+    #[derive(Default)]
+    struct MemoryStore {
+        json: std::sync::Mutex<Option<String>>,
+    }
+
+    impl GraphStore for MemoryStore {
+        fn save(&self, graph: &CodeGraph) -> Result<()> {
+            let json = serde_json::to_string(&graph.snapshot())?;
+            *self.json.lock().unwrap() = Some(json);
+            Ok(())
+        }
+
+        fn load(&self) -> Result<CodeGraph> {
+            let json = self.json.lock().unwrap().clone();
+            let json = json.ok_or_else(|| AnchorError::ParseError("nothing stored".to_string()))?;
+            Ok(CodeGraph::from_snapshot(serde_json::from_str(&json)?))
+        }
+    }
+
+    #[test]
+    fn test_custom_store_roundtrip() {
+        let source = "pub fn login() {\n    check();\n}\n\nfn check() {}\n";
+        let extraction = crate::parser::extract_file(&PathBuf::from("src/auth.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let store = MemoryStore::default();
+        assert!(CodeGraph::load_from(&store).is_err());
+        graph.save_to(&store).unwrap();
+        store.save_file_segment(&graph, Path::new("src/auth.rs")).unwrap();
+
+        let loaded = CodeGraph::load_from(&store).unwrap();
+        assert_eq!(loaded.stats().symbol_count, graph.stats().symbol_count);
+        assert_eq!(loaded.stats().total_edges, graph.stats().total_edges);
+        let callees: Vec<String> = loaded.dependencies("login").into_iter().map(|d| d.symbol).collect();
+        assert_eq!(callees, vec!["check"]);
+    }
+
+    #[test]
+    fn test_save_load_preserves_endpoints() {
+        let routes = "app.get('/api/users/:id', (req, res) => res.json({}));\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            crate::parser::extract_file(&PathBuf::from("src/server/routes.js"), routes).unwrap(),
+        ]);
+        assert_eq!(graph.endpoints_at("/api/users/:param").len(), 1);
+
+        let dir = tempdir().unwrap();
+        let save_path = dir.path().join("graph.bin");
+        graph.save(&save_path).unwrap();
+        let mut loaded = CodeGraph::load(&save_path).unwrap();
+
+        let endpoint = &loaded.endpoints_at("/api/users/:param")[0];
+        assert_eq!(endpoint.method.as_deref(), Some("GET"));
+        assert_eq!(endpoint.file, PathBuf::from("src/server/routes.js"));
+        assert_eq!(endpoint.line, 1);
+        assert!(loaded.endpoints_at("/api/users").is_empty());
+
+        loaded.remove_file(Path::new("src/server/routes.js"));
+        assert_eq!(loaded.endpoints().count(), 0);
+    }
+
     #[test]
     fn test_load_nonexistent_file() {
         let result = CodeGraph::load(Path::new("/nonexistent/graph.bin"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_builds_are_byte_identical() {
+        use crate::graph::CodeGraphBuilder;
+
+        let project = tempdir().unwrap();
+        for i in 0..12 {
+            let next = (i + 1) % 12;
+            fs::write(
+                project.path().join(format!("mod_{}.rs", i)),
+                format!("pub fn f{}() {{ f{}(); shared(); }}\npub fn shared() {{}}\n", i, next),
+            )
+            .unwrap();
+        }
+
+        let out = tempdir().unwrap();
+        let mut saved = Vec::new();
+        for run in 0..2 {
+            let mut graph = CodeGraphBuilder::new(project.path()).parallelism(4).build();
+            // The build timestamp is the one field meant to differ between runs
+            let mut meta = graph.meta().unwrap().clone();
+            meta.built_at = 0;
+            graph.set_meta(meta);
+
+            let path = out.path().join(format!("graph{}.bin", run));
+            graph.save(&path).unwrap();
+            saved.push(fs::read(&path).unwrap());
+        }
+
+        assert_eq!(saved[0], saved[1]);
+
+        // Parse order doesn't matter: reversed extractions give the same bytes
+        let mut extractions: Vec<_> = (0..12)
+            .map(|i| {
+                let path = project.path().join(format!("mod_{}.rs", i));
+                let source = fs::read_to_string(&path).unwrap();
+                crate::parser::extract_file(&path, &source).unwrap()
+            })
+            .collect();
+        let mut forward = CodeGraph::new();
+        forward.build_from_extractions(extractions.clone());
+        extractions.reverse();
+        let mut reversed = CodeGraph::new();
+        reversed.build_from_extractions(extractions);
+
+        forward.save(&out.path().join("forward.bin")).unwrap();
+        reversed.save(&out.path().join("reversed.bin")).unwrap();
+        assert_eq!(
+            fs::read(out.path().join("forward.bin")).unwrap(),
+            fs::read(out.path().join("reversed.bin")).unwrap()
+        );
+    }
 }
@@ -0,0 +1,587 @@
+//! File moves — the import rewrites that keep a moved file reachable.
+//!
+//! A rewrite needs the text of each import, not just the file it points
+//! at, so each import string is resolved here against the file it appears
+//! in, language by language: relative JS/TS specifiers, Python dotted
+//! modules (absolute and relative), and Rust `crate::`/`super::`/`self::`
+//! paths and `mod` declarations.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::engine::CodeGraph;
+use super::types::NodeKind;
+use crate::parser::SupportedLanguage;
+use crate::query::Edit;
+
+impl CodeGraph {
+    /// Edits that update the imports of `old_path` for a move to
+    /// `new_path`: on each, the first `usage` on `line` becomes
+    /// `suggested`.
+    ///
+    /// Relative imports inside the moved file are rewritten too; their
+    /// edits name `old_path`, since the file hasn't moved yet. Only import
+    /// statements are covered: qualified uses in code (`utils.math.add()`)
+    /// and a Rust `mod` declaration that has to move to another parent
+    /// module are left to the caller.
+    pub fn move_file_edits(&self, old_path: &Path, new_path: &Path) -> Vec<Edit> {
+        let (old, new) = self.graph_paths(old_path, new_path);
+        let Some(moved) = SupportedLanguage::from_path(&old).map(import_family) else {
+            return Vec::new();
+        };
+        let root = self.meta().map(|meta| normalize(&meta.root)).unwrap_or_default();
+        let graph = self.inner_graph();
+
+        let mut edits = Vec::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            if node.removed || node.kind != NodeKind::Import {
+                continue;
+            }
+            let importer = normalize(&node.file_path);
+            // A Python file can't import a Rust one, however the paths line up
+            let language = SupportedLanguage::from_path(&importer).map(import_family);
+            if language != Some(moved) {
+                continue;
+            }
+            let rewrite = match moved {
+                SupportedLanguage::JavaScript => rewrite_js(&node.name, &importer, &old, &new),
+                SupportedLanguage::Python => {
+                    let names = || python_from_names(&node.file_path, node.line_start);
+                    rewrite_python(&node.name, names, &importer, &root, &old, &new)
+                }
+                SupportedLanguage::Rust => rewrite_rust(&node.name, &importer, &old, &new),
+                _ => None,
+            };
+            if let Some((old_import, new_import)) = rewrite {
+                edits.push(import_edit(&node.file_path, node.line_start, &node.name, old_import, new_import));
+            }
+        }
+
+        edits.extend(self.rust_mod_edits(&old, &new));
+        edits.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        edits.dedup_by(|a, b| (&a.file, a.line, &a.usage, &a.suggested) == (&b.file, b.line, &b.usage, &b.suggested));
+        edits
+    }
+
+    /// `old_path` and `new_path` as the graph spells paths: joined to the
+    /// project root when the graph holds `old_path` under it.
+    fn graph_paths(&self, old_path: &Path, new_path: &Path) -> (PathBuf, PathBuf) {
+        let files: Vec<PathBuf> = self.all_files().iter().map(|f| normalize(f)).collect();
+        let old = normalize(old_path);
+        if files.contains(&old) {
+            return (old, normalize(new_path));
+        }
+        match self.meta().map(|meta| &meta.root) {
+            Some(root) if files.contains(&normalize(&root.join(old_path))) => (
+                normalize(&root.join(old_path)),
+                normalize(&root.join(new_path)),
+            ),
+            _ => (old, normalize(new_path)),
+        }
+    }
+
+    /// Rename `mod old;` in the parent module when a Rust file is renamed
+    /// within its directory.
+    fn rust_mod_edits(&self, old: &Path, new: &Path) -> Vec<Edit> {
+        let (Some(old_mod), Some(new_mod)) = (rust_module(old), rust_module(new)) else {
+            return Vec::new();
+        };
+        let (Some((old_name, old_parent)), Some((new_name, new_parent))) =
+            (old_mod.split_last(), new_mod.split_last())
+        else {
+            return Vec::new();
+        };
+        if old_parent != new_parent || old_name == new_name {
+            return Vec::new();
+        }
+
+        self.all_files()
+            .into_iter()
+            .filter(|file| is_rust_mod_file(file) && rust_module(file).as_deref() == Some(old_parent))
+            .flat_map(|file| {
+                self.symbols_in_file(&file)
+                    .into_iter()
+                    .filter(|s| s.kind == NodeKind::Module && &s.name == old_name)
+                    .filter(|s| s.code_snippet.trim_end().ends_with(';'))
+                    .map(|s| {
+                        let (old_mod, new_mod) = (format!("mod {}", old_name), format!("mod {}", new_name));
+                        import_edit(&s.file_path, s.line_start, &s.name, old_mod, new_mod)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// An edit replacing `old_import` with `new_import` on `line` of `file`,
+/// with the surrounding lines when the file can be read.
+fn import_edit(file: &Path, line: usize, import: &str, old_import: String, new_import: String) -> Edit {
+    let context = crate::query::context::get_context_lines(file, line, 2);
+    let line_content = context
+        .iter()
+        .find_map(|ctx| ctx.strip_prefix('>'))
+        .and_then(|ctx| ctx.split_once("| "))
+        .map(|(_, code)| code.to_string())
+        .unwrap_or_else(|| old_import.clone());
+    Edit {
+        file: file.to_string_lossy().to_string(),
+        line,
+        in_symbol: import.to_string(),
+        usage: old_import,
+        line_content,
+        suggested: Some(new_import),
+        new_args: vec![],
+        removed_args: vec![],
+        context,
+        actual_args: 0,
+        expected_args: None,
+        arity_ok: true,
+    }
+}
+
+/// Languages that import each other's files: JS, TS and TSX share
+/// module resolution.
+pub(super) fn import_family(lang: SupportedLanguage) -> SupportedLanguage {
+    match lang {
+        SupportedLanguage::TypeScript | SupportedLanguage::Tsx => SupportedLanguage::JavaScript,
+        other => other,
+    }
+}
+
+// ─── JavaScript / TypeScript ──────────────────────────────────────
+
+/// Rewrite a relative specifier (`./utils/math`) that points at the moved
+/// file, or one inside the moved file whose base directory changes.
+fn rewrite_js(spec: &str, importer: &Path, old: &Path, new: &Path) -> Option<(String, String)> {
+    if !spec.starts_with('.') {
+        return None;
+    }
+    let importer_dir = importer.parent()?;
+    let target = normalize(&importer_dir.join(spec));
+
+    let rewritten = if importer == old {
+        relative_spec(new.parent()?, &target)
+    } else if target == old {
+        relative_spec(importer_dir, new)
+    } else if target == old.with_extension("") {
+        relative_spec(importer_dir, &new.with_extension(""))
+    } else if old.file_stem().is_some_and(|stem| stem == "index") && old.parent() == Some(&target) {
+        // `./utils` resolving to `./utils/index.js`
+        if new.file_stem().is_some_and(|stem| stem == "index") {
+            relative_spec(importer_dir, new.parent()?)
+        } else {
+            relative_spec(importer_dir, &new.with_extension(""))
+        }
+    } else {
+        return None;
+    };
+
+    (rewritten != spec).then(|| (spec.to_string(), rewritten))
+}
+
+/// `to` relative to the directory `from`, as a JS specifier (`./x`, `../x`).
+fn relative_spec(from: &Path, to: &Path) -> String {
+    let (ups, rest) = relative_parts(&components(from), &components(to));
+    let mut parts: Vec<String> = if ups == 0 {
+        vec![".".to_string()]
+    } else {
+        vec!["..".to_string(); ups]
+    };
+    parts.extend(rest);
+    parts.join("/")
+}
+
+// ─── Python ───────────────────────────────────────────────────────
+
+/// Rewrite a dotted module (`utils.math`, `.math`) that names the moved
+/// file, or a relative one inside the moved file. `names` reads what a
+/// `from` import brings in, for `from utils import math`; one importing
+/// other names besides the moved module is left to the caller.
+///
+/// Absolute modules are looked up as `ImportResolver` does, below each
+/// directory enclosing the importer, but only within `root`, and must
+/// name the moved file exactly: `import math` is the standard library
+/// unless a `math.py` sits in one of those directories.
+fn rewrite_python(
+    spec: &str,
+    names: impl Fn() -> Vec<String>,
+    importer: &Path,
+    root: &Path,
+    old: &Path,
+    new: &Path,
+) -> Option<(String, String)> {
+    let old_module = python_module(old);
+    let new_module = python_module(new);
+    let importer_dir = importer.parent()?;
+    let importer_package = components(importer_dir);
+    let root_module = components(root);
+
+    // `import a, b as c` lists several modules
+    'modules: for module in spec.split(',').map(|part| part.split(" as ").next().unwrap_or("").trim()) {
+        let dots = module.chars().take_while(|&c| c == '.').count();
+        let rest: Vec<String> = module[dots..]
+            .split('.')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        let package = match dots {
+            0 => None,
+            _ => Some(importer_package[..importer_package.len().checked_sub(dots - 1)?].to_vec()),
+        };
+
+        if importer == old {
+            // Only the moved file's relative imports change with it
+            let Some(mut target) = package else {
+                continue;
+            };
+            target.extend(rest);
+            let rewritten = relative_module(&components(new.parent()?), &target);
+            if rewritten != module {
+                return Some((module.to_string(), rewritten));
+            }
+            continue;
+        }
+
+        let bases: Vec<Vec<String>> = match package {
+            Some(package) => vec![package],
+            None if rest.is_empty() => continue,
+            None => importer_dir
+                .ancestors()
+                .filter(|dir| dir.starts_with(root))
+                .map(components)
+                .collect(),
+        };
+        for base in &bases {
+            // How the importer spells a module found below `base`
+            let spell = |target: &[String]| -> Option<String> {
+                if dots > 0 {
+                    return Some(relative_module(&importer_package, target));
+                }
+                let below = target.strip_prefix(base.as_slice()).or_else(|| target.strip_prefix(root_module.as_slice()))?;
+                Some(below.join("."))
+            };
+            let mut target = base.clone();
+            target.extend(rest.iter().cloned());
+
+            if target == old_module {
+                let rewritten = spell(&new_module).filter(|rewritten| !rewritten.is_empty())?;
+                if rewritten == module {
+                    continue 'modules;
+                }
+                return Some((module.to_string(), rewritten));
+            }
+            // `from utils import math` imports the moved module by name
+            if old_module.strip_prefix(target.as_slice()).is_some_and(|name| name.len() == 1) {
+                let names = names();
+                let [name] = names.as_slice() else {
+                    return None;
+                };
+                let (new_name, new_package) = new_module.split_last()?;
+                if old_module.last() != Some(name) {
+                    return None;
+                }
+                let new_from = spell(new_package)?;
+                let old_import = format!("from {} import {}", module, name);
+                let new_import = if new_from.is_empty() {
+                    format!("import {}", new_name)
+                } else {
+                    format!("from {} import {}", new_from, new_name)
+                };
+                return (old_import != new_import).then_some((old_import, new_import));
+            }
+        }
+    }
+    None
+}
+
+/// The names a Python `from ... import` on `line` of `file` brings in,
+/// without aliases. The graph keeps only the module, so they're read from
+/// disk; an unreadable file or another kind of import has none.
+fn python_from_names(file: &Path, line: usize) -> Vec<String> {
+    let Ok(source) = std::fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let statement = line.checked_sub(1).and_then(|index| source.lines().nth(index)).unwrap_or("");
+    let Some((_, names)) = statement.trim_start().strip_prefix("from ").and_then(|rest| rest.split_once(" import ")) else {
+        return Vec::new();
+    };
+    names
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|name| name.split(" as ").next().unwrap_or("").trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Module path of a Python file: its path components, without `.py` and
+/// with `__init__` naming the package.
+pub(super) fn python_module(path: &Path) -> Vec<String> {
+    let mut module = components(&path.with_extension(""));
+    if module.last().is_some_and(|last| last == "__init__") {
+        module.pop();
+    }
+    module
+}
+
+/// `to` relative to the package `from`, as a relative import (`.x`, `..x`).
+fn relative_module(from: &[String], to: &[String]) -> String {
+    let (ups, rest) = relative_parts(from, to);
+    format!("{}{}", ".".repeat(ups + 1), rest.join("."))
+}
+
+// ─── Rust ─────────────────────────────────────────────────────────
+
+/// Rewrite a `use` path (`crate::utils::math::add`) that goes through the
+/// moved module. Relative paths are rewritten as `crate::` paths.
+fn rewrite_rust(spec: &str, importer: &Path, old: &Path, new: &Path) -> Option<(String, String)> {
+    let old_module = rust_module(old)?;
+    let new_module = rust_module(new)?;
+    if old_module.is_empty() || importer == old {
+        return None;
+    }
+
+    // `pub use`, `pub(crate) use`
+    let path = if spec.starts_with("pub") {
+        spec.split_once("use ")?.1.trim()
+    } else {
+        spec
+    };
+    let segments: Vec<&str> = path
+        .split("::")
+        .take_while(|s| !s.contains(['{', '*']))
+        .map(str::trim)
+        .collect();
+
+    let anchors = segments
+        .iter()
+        .take_while(|&&s| matches!(s, "crate" | "super" | "self"))
+        .count();
+    let base = match segments.first().copied() {
+        Some("crate") if anchors == 1 => Vec::new(),
+        Some("self" | "super") => {
+            let mut base = rust_module(importer)?;
+            // A file's own module is `self`; each `super` goes up one
+            for _ in segments[..anchors].iter().filter(|&&s| s == "super") {
+                base.pop()?;
+            }
+            base
+        }
+        _ => return None,
+    };
+
+    let mut resolved = base.clone();
+    resolved.extend(segments[anchors..].iter().map(|s| s.to_string()));
+    if base.len() > old_module.len() || !resolved.starts_with(&old_module) {
+        return None;
+    }
+
+    let consumed = anchors + old_module.len() - base.len();
+    let old_import = segments[..consumed].join("::");
+    let new_import = std::iter::once("crate".to_string())
+        .chain(new_module)
+        .collect::<Vec<_>>()
+        .join("::");
+    (old_import != new_import).then_some((old_import, new_import))
+}
+
+/// Module path of a Rust file below `src/`: `src/utils/math.rs` is
+/// `utils::math`, `mod.rs` names its directory and `lib.rs`/`main.rs`
+/// the crate root.
+pub(super) fn rust_module(path: &Path) -> Option<Vec<String>> {
+    let parts = components(&path.with_extension(""));
+    let src = parts.iter().rposition(|part| part == "src")?;
+    let mut module = parts[src + 1..].to_vec();
+    match module.last().map(String::as_str) {
+        Some("mod") => {
+            module.pop();
+        }
+        Some("lib" | "main") if module.len() == 1 => module.clear(),
+        _ => {}
+    }
+    Some(module)
+}
+
+/// Files that declare child modules of their own module path.
+fn is_rust_mod_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| matches!(name, "mod.rs" | "lib.rs" | "main.rs"))
+}
+
+// ─── Paths ────────────────────────────────────────────────────────
+
+/// Resolve `.` and `..` lexically, without touching the file system.
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+pub(super) fn components(path: &Path) -> Vec<String> {
+    normalize(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect()
+}
+
+/// How many levels to go up from `from`, then the components down to `to`.
+fn relative_parts(from: &[String], to: &[String]) -> (usize, Vec<String>) {
+    let common = from.iter().zip(to).take_while(|(a, b)| a == b).count();
+    (from.len() - common, to[common..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn build(files: &[(&str, &str)]) -> CodeGraph {
+        let extractions = files
+            .iter()
+            .map(|(path, source)| parser::extract_file(&PathBuf::from(path), source).unwrap())
+            .collect();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(extractions);
+        graph
+    }
+
+    fn edit(file: &str, line: usize, old: &str, new: &str) -> (String, usize, String, Option<String>) {
+        (file.to_string(), line, old.to_string(), Some(new.to_string()))
+    }
+
+    fn summary(edits: Vec<Edit>) -> Vec<(String, usize, String, Option<String>)> {
+        edits.into_iter().map(|e| (e.file, e.line, e.usage, e.suggested)).collect()
+    }
+
+    #[test]
+    fn test_move_js_module_rewrites_relative_imports() {
+        let graph = build(&[
+            (
+                "src/app/main.js",
+                "import { add } from './utils/math';\nimport { log } from './log';\n",
+            ),
+            ("src/app/utils/format.js", "import { add } from './math.js';\n"),
+            (
+                "src/app/utils/math.js",
+                "import { round } from './round';\nexport function add(a, b) { return round(a + b); }\n",
+            ),
+            ("src/app/utils/round.js", "export function round(x) { return x; }\n"),
+        ]);
+
+        let edits = summary(graph.move_file_edits(Path::new("src/app/utils/math.js"), Path::new("src/lib/math.js")));
+
+        assert_eq!(
+            edits,
+            vec![
+                edit("src/app/main.js", 1, "./utils/math", "../lib/math"),
+                edit("src/app/utils/format.js", 1, "./math.js", "../../lib/math.js"),
+                // The moved file's own import now reaches back
+                edit("src/app/utils/math.js", 1, "./round", "../app/utils/round"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_edits_render_as_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/utils")).unwrap();
+        std::fs::write(root.join("src/main.js"), "// entry\nimport { add } from './utils/math';\n\nadd(1, 2);\n").unwrap();
+        std::fs::write(root.join("src/utils/math.js"), "export function add(a, b) { return a + b; }\n").unwrap();
+        let graph = crate::graph::build_graph(root);
+
+        let edits = graph.move_file_edits(Path::new("src/utils/math.js"), Path::new("src/math.js"));
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].line_content, "import { add } from './utils/math';");
+
+        let diff = crate::cli::diff::format_edits(&edits, false);
+        assert!(diff.contains("-   2| import { add } from './utils/math';"), "{}", diff);
+        assert!(diff.contains("+   2| import { add } from './math';"), "{}", diff);
+    }
+
+    #[test]
+    fn test_move_python_module_needs_exact_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("pkg/utils")).unwrap();
+        std::fs::write(root.join("pkg/__init__.py"), "").unwrap();
+        std::fs::write(root.join("pkg/utils/__init__.py"), "").unwrap();
+        std::fs::write(root.join("pkg/utils/math.py"), "def add(a, b):\n    return a + b\n").unwrap();
+        std::fs::write(
+            root.join("pkg/app.py"),
+            "import math\nfrom pkg.utils import math as m\nimport pkg.utils.math\nfrom pkg.utils import math, other\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("main.py"), "import math\nfrom math import sqrt\n").unwrap();
+        let graph = crate::graph::build_graph(root);
+
+        let edits = graph.move_file_edits(Path::new("pkg/utils/math.py"), Path::new("pkg/helpers/calc.py"));
+        let edits: Vec<_> = edits
+            .into_iter()
+            .map(|e| (Path::new(&e.file).strip_prefix(root).unwrap().to_path_buf(), e.line, e.usage, e.suggested))
+            .collect();
+
+        // The standard library's `math` stays; so does the import of two names
+        assert_eq!(
+            edits,
+            vec![
+                (
+                    PathBuf::from("pkg/app.py"),
+                    2,
+                    "from pkg.utils import math".to_string(),
+                    Some("from pkg.helpers import calc".to_string())
+                ),
+                (
+                    PathBuf::from("pkg/app.py"),
+                    3,
+                    "pkg.utils.math".to_string(),
+                    Some("pkg.helpers.calc".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_python_and_rust_modules() {
+        let graph = build(&[
+            ("pkg/app.py", "from utils.math import add\nimport utils.math as m\n"),
+            ("pkg/utils/stats.py", "from .math import add\n"),
+            ("pkg/utils/math.py", "def add(a, b):\n    return a + b\n"),
+            ("src/lib.rs", "mod utils;\nuse crate::utils::math::add;\n"),
+            ("src/utils/mod.rs", "pub mod math;\nuse self::math::{add, sub};\n"),
+            ("src/utils/math.rs", "pub fn add() {}\npub fn sub() {}\n"),
+        ]);
+
+        let python = summary(graph.move_file_edits(Path::new("pkg/utils/math.py"), Path::new("pkg/core/math.py")));
+        assert_eq!(
+            python,
+            vec![
+                edit("pkg/app.py", 1, "utils.math", "core.math"),
+                edit("pkg/app.py", 2, "utils.math", "core.math"),
+                edit("pkg/utils/stats.py", 1, ".math", "..core.math"),
+            ]
+        );
+
+        let rust = summary(graph.move_file_edits(Path::new("src/utils/math.rs"), Path::new("src/utils/arith.rs")));
+        assert_eq!(
+            rust,
+            vec![
+                edit("src/lib.rs", 2, "crate::utils::math", "crate::utils::arith"),
+                edit("src/utils/mod.rs", 1, "mod math", "mod arith"),
+                edit("src/utils/mod.rs", 2, "self::math", "crate::utils::arith"),
+            ]
+        );
+    }
+}
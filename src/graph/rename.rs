@@ -0,0 +1,324 @@
+//! Symbol renames — the identifiers that have to change with a definition's
+//! name.
+//!
+//! A rename names one definition, by qualified name or by `path:line`. The
+//! graph doesn't record where in a caller a call is made, so each calling
+//! file is extracted again and its calls re-resolved: a call is rewritten
+//! only when it resolves to that definition, not to another symbol that
+//! shares its name.
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::engine::{module_name, CodeGraph};
+use super::types::{EdgeKind, NodeKind};
+use crate::write::{word_occurrences, WordSite};
+
+/// Why a rename target didn't name exactly one definition.
+#[derive(Debug, thiserror::Error)]
+pub enum RenameError {
+    #[error("symbol not found: {0}")]
+    NotFound(String),
+
+    #[error("{0} is ambiguous, qualify it: {}", .1.join(", "))]
+    Ambiguous(String, Vec<String>),
+}
+
+/// The definition a rename names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// A symbol by its qualified name or a tail of it: `Order::save`,
+    /// `models::Order.save`, or `save` when only one symbol has that name.
+    Qualified(String),
+    /// The innermost symbol at a line of a file.
+    At(PathBuf, usize),
+}
+
+impl RenameTarget {
+    /// `path:line` is a position; anything else a qualified name.
+    pub fn parse(target: &str) -> Self {
+        match target.rsplit_once(':') {
+            Some((path, line)) if !path.is_empty() && !path.ends_with(':') => match line.parse() {
+                Ok(line) => RenameTarget::At(PathBuf::from(path), line),
+                Err(_) => RenameTarget::Qualified(target.to_string()),
+            },
+            _ => RenameTarget::Qualified(target.to_string()),
+        }
+    }
+}
+
+/// A resolved rename: the definition and where its name appears.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename {
+    /// The name being replaced.
+    pub name: String,
+    /// The definition's qualified name.
+    pub qualified_name: String,
+    pub sites: Vec<RenameSite>,
+}
+
+/// Where to rewrite the name in one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenameSite {
+    pub file: PathBuf,
+    pub words: Vec<WordSite>,
+}
+
+impl CodeGraph {
+    /// Where renaming the definition `target` names rewrites its name, by
+    /// file: the definition and its type's impl blocks, calls that resolve
+    /// to it, imports of it, and the spans of symbols that use it some
+    /// other way (a type reference, a dynamic call).
+    ///
+    /// Files are read from disk for call sites, so the graph should be
+    /// current. Uses from a file's top level, with no enclosing symbol,
+    /// aren't covered.
+    pub fn rename_sites(&self, target: &RenameTarget) -> Result<Rename, RenameError> {
+        let target_idx = self.rename_target(target)?;
+        let graph = self.inner_graph();
+        let node = &graph[target_idx];
+        let name = node.name.as_str();
+        let mut words: BTreeMap<PathBuf, BTreeSet<WordSite>> = BTreeMap::new();
+
+        // The definition, and the impl blocks of a type
+        let impls = graph.node_indices().filter(|&idx| {
+            let other = &graph[idx];
+            other.kind == NodeKind::Impl && !other.removed && other.qualified_name == node.qualified_name
+        });
+        for idx in std::iter::once(target_idx).chain(impls) {
+            let definition = &graph[idx];
+            if let Some(line) = name_line(&definition.file_path, name, (definition.line_start, definition.line_end)) {
+                words.entry(definition.file_path.clone()).or_default().insert(WordSite {
+                    lines: (line, line),
+                    receiver: None,
+                });
+            }
+        }
+
+        let mut calling_files = BTreeSet::new();
+        for edge in graph.edges_directed(target_idx, Direction::Incoming) {
+            let user = &graph[edge.source()];
+            let structural = matches!(
+                edge.weight().kind,
+                EdgeKind::Defines | EdgeKind::Contains | EdgeKind::Exports
+            );
+            if structural || user.removed || user.kind == NodeKind::File {
+                continue;
+            }
+            if edge.weight().kind == EdgeKind::Calls {
+                calling_files.insert(user.file_path.clone());
+            } else {
+                words.entry(user.file_path.clone()).or_default().insert(WordSite {
+                    lines: (user.line_start, user.line_end),
+                    receiver: None,
+                });
+            }
+        }
+
+        for file in calling_files {
+            let Ok(source) = std::fs::read_to_string(&file) else { continue };
+            let Ok(extraction) = crate::parser::extract_file(&file, &source) else { continue };
+            for call in extraction.calls.iter().filter(|call| call.callee == name) {
+                if self.resolve_call(&file, call).map(|(_, callee)| callee) == Some(target_idx) {
+                    words.entry(file.clone()).or_default().insert(WordSite {
+                        lines: (call.line, call.line),
+                        receiver: call.receiver.clone(),
+                    });
+                }
+            }
+        }
+
+        // Imports ending in the name, through its module or type
+        let owners = [module_name(&node.file_path), self.parent_name(target_idx)];
+        for idx in graph.node_indices() {
+            let import = &graph[idx];
+            if import.kind != NodeKind::Import || import.removed {
+                continue;
+            }
+            let Some((path, last)) = split_import(&import.name) else { continue };
+            let through = path.rsplit([':', '.', '/']).next().unwrap_or(path);
+            if last == name && owners.contains(&Some(through)) {
+                words.entry(import.file_path.clone()).or_default().insert(WordSite {
+                    lines: (import.line_start, import.line_end),
+                    receiver: Some(through.to_string()),
+                });
+            }
+        }
+
+        Ok(Rename {
+            name: name.to_string(),
+            qualified_name: node.qualified_name.clone(),
+            sites: words
+                .into_iter()
+                .map(|(file, words)| RenameSite {
+                    file,
+                    words: words.into_iter().collect(),
+                })
+                .collect(),
+        })
+    }
+
+    /// The one definition `target` names.
+    fn rename_target(&self, target: &RenameTarget) -> Result<NodeIndex, RenameError> {
+        match target {
+            RenameTarget::At(path, line) => self
+                .node_at(path, *line)
+                .ok_or_else(|| RenameError::NotFound(format!("{}:{}", path.display(), line))),
+            RenameTarget::Qualified(query) => {
+                let graph = self.inner_graph();
+                let found = self.nodes_qualified(query);
+                // An exact qualified name beats the others ending with it
+                let exact: Vec<NodeIndex> = found
+                    .iter()
+                    .copied()
+                    .filter(|&idx| graph[idx].qualified_name == *query)
+                    .collect();
+                match (found.as_slice(), exact.as_slice()) {
+                    ([], _) => Err(RenameError::NotFound(query.clone())),
+                    ([idx], _) | (_, [idx]) => Ok(*idx),
+                    _ => {
+                        let mut names: Vec<String> =
+                            found.iter().map(|&idx| graph[idx].qualified_name.clone()).collect();
+                        names.sort();
+                        Err(RenameError::Ambiguous(query.clone(), names))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// First line of `lines` in `file` with `name` standing on its own, as in
+/// its declaration.
+fn name_line(file: &Path, name: &str, lines: (usize, usize)) -> Option<usize> {
+    let source = std::fs::read_to_string(file).ok()?;
+    source
+        .lines()
+        .enumerate()
+        .skip(lines.0.saturating_sub(1))
+        .take(lines.1 + 1 - lines.0.max(1))
+        .find(|(_, line)| !word_occurrences(line, name, None).is_empty())
+        .map(|(i, _)| i + 1)
+}
+
+/// An import path split before its last part: `crate::auth` and
+/// `validate` for `crate::auth::validate`.
+fn split_import(path: &str) -> Option<(&str, &str)> {
+    let at = path.rfind([':', '.', '/'])?;
+    let last = &path[at + 1..];
+    let rest = path[..at].trim_end_matches([':', '.', '/']);
+    (!rest.is_empty()).then_some((rest, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const MODELS: &str = "\
+pub struct Order;
+
+impl Order {
+    pub fn save(&self) {}
+
+    pub fn submit(&self) {
+        self.save();
+    }
+}
+
+pub struct User;
+
+impl User {
+    pub fn save(&self) {}
+}
+
+pub fn checkout(order: &Order, user: &User) {
+    Order::save(order); User::save(user);
+}
+";
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/models.rs"), MODELS).unwrap();
+        fs::write(
+            dir.path().join("src/main.rs"),
+            "use crate::models::checkout;\n\nfn main() {\n    models::checkout(&Order, &User);\n}\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn words(rename: &Rename, file: &str) -> Vec<(usize, Option<String>)> {
+        rename
+            .sites
+            .iter()
+            .filter(|site| site.file.ends_with(file))
+            .flat_map(|site| site.words.iter().map(|w| (w.lines.0, w.receiver.clone())))
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_sites_follow_one_definition() {
+        let dir = project();
+        let graph = build_graph(dir.path());
+
+        let rename = graph.rename_sites(&RenameTarget::Qualified("Order::save".to_string())).unwrap();
+        assert_eq!(rename.qualified_name, "models::Order::save");
+        assert_eq!(
+            words(&rename, "src/models.rs"),
+            vec![
+                (4, None),
+                (7, Some("self".to_string())),
+                (18, Some("Order".to_string())),
+            ]
+        );
+        assert!(words(&rename, "src/main.rs").is_empty());
+
+        // The same definition by position
+        let at = graph.rename_sites(&RenameTarget::At(dir.path().join("src/models.rs"), 4)).unwrap();
+        assert_eq!(at, rename);
+    }
+
+    #[test]
+    fn test_rename_sites_include_imports() {
+        let dir = project();
+        let graph = build_graph(dir.path());
+
+        let rename = graph.rename_sites(&RenameTarget::parse("checkout")).unwrap();
+        assert_eq!(
+            words(&rename, "src/main.rs"),
+            vec![(1, Some("models".to_string())), (4, Some("models".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_rename_target_must_be_unique() {
+        let dir = project();
+        let graph = build_graph(dir.path());
+
+        match graph.rename_sites(&RenameTarget::parse("save")) {
+            Err(RenameError::Ambiguous(_, names)) => {
+                assert_eq!(names, vec!["models::Order::save", "models::User::save"])
+            }
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+        assert!(matches!(
+            graph.rename_sites(&RenameTarget::parse("missing")),
+            Err(RenameError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(RenameTarget::parse("src/models.rs:4"), RenameTarget::At(PathBuf::from("src/models.rs"), 4));
+        assert_eq!(RenameTarget::parse("Order::save"), RenameTarget::Qualified("Order::save".to_string()));
+        assert_eq!(RenameTarget::parse("save"), RenameTarget::Qualified("save".to_string()));
+    }
+}
@@ -0,0 +1,383 @@
+//! Signature shapes — parameter and return types without names.
+//!
+//! Used by `CodeGraph::find_by_signature` to answer "which functions take
+//! a `&str` and return a `bool`?". Types are compared loosely: references,
+//! lifetimes, `mut` and path qualifiers are ignored, and untyped parameters
+//! (Python, JavaScript) match any requested type.
+
+use std::path::Path;
+
+use crate::parser::SupportedLanguage;
+
+/// The shape of a function signature: parameter types and return type.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SignatureShape {
+    /// Parameter types in order (empty string = untyped).
+    pub params: Vec<String>,
+    /// Return type, if declared.
+    pub return_type: Option<String>,
+}
+
+impl SignatureShape {
+    /// Parse a shape query like `(&str, i32) -> bool`.
+    ///
+    /// Anything before the opening paren (e.g. `fn`) is ignored.
+    pub fn parse(shape: &str) -> Option<Self> {
+        let open = shape.find('(')?;
+        let close = matching_paren(shape, open)?;
+
+        let params = split_top_level(&shape[open + 1..close])
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        let rest = shape[close + 1..].trim();
+        let return_type = rest
+            .strip_prefix("->")
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+
+        Some(Self {
+            params,
+            return_type,
+        })
+    }
+
+    /// Extract the shape from a function's source snippet.
+    ///
+    /// The language (from the file extension) decides where parameter and
+    /// return types live. Receivers (`self`, `&mut self`, `cls`) are skipped.
+    pub fn from_code(code: &str, file_path: &Path) -> Option<Self> {
+        let lang = SupportedLanguage::from_path(file_path);
+        let start = params_search_start(code, lang);
+        let mut open = start + find_params_open(&code[start..])?;
+        let mut close = matching_paren(code, open)?;
+
+        // Go methods: `func (r *T) Name(...)` — skip the receiver list
+        if lang == Some(SupportedLanguage::Go) && code[..open].trim() == "func" {
+            open = close + 1 + find_params_open(&code[close + 1..])?;
+            close = matching_paren(code, open)?;
+        }
+
+        let params = split_top_level(&code[open + 1..close])
+            .into_iter()
+            .filter(|p| !is_receiver(p))
+            .map(|p| param_type(p, lang))
+            .collect();
+
+        let return_type = match lang {
+            Some(SupportedLanguage::Java | SupportedLanguage::CSharp | SupportedLanguage::Cpp) => {
+                prefix_return_type(&code[..open])
+            }
+            _ => suffix_return_type(&code[close + 1..], lang),
+        };
+
+        Some(Self {
+            params,
+            return_type,
+        })
+    }
+
+    /// Check whether this (declared) shape satisfies a query shape.
+    ///
+    /// `_` in the query matches any type. A missing return type in the query
+    /// matches any return type.
+    pub fn matches(&self, params: &[&str], ret: Option<&str>) -> bool {
+        if self.params.len() != params.len() {
+            return false;
+        }
+
+        let params_ok = self
+            .params
+            .iter()
+            .zip(params)
+            .all(|(actual, wanted)| actual.is_empty() || types_match(actual, wanted));
+        if !params_ok {
+            return false;
+        }
+
+        match (ret, &self.return_type) {
+            (None, _) => true,
+            (Some(wanted), Some(actual)) => types_match(actual, wanted),
+            (Some(wanted), None) => {
+                matches!(normalize_type(wanted).as_str(), "()" | "void" | "none" | "_")
+            }
+        }
+    }
+}
+
+/// Compare two types after normalization (`_` is a wildcard).
+pub fn types_match(a: &str, b: &str) -> bool {
+    let a = normalize_type(a);
+    let b = normalize_type(b);
+    a == "_" || b == "_" || a == b
+}
+
+/// Normalize a type for loose comparison.
+///
+/// `&'a mut std::string::String` becomes `string`: references, lifetimes,
+/// `mut`/`const`/`dyn`/`impl`, path qualifiers and whitespace are dropped
+/// and the result is lowercased.
+pub fn normalize_type(typ: &str) -> String {
+    let mut out = String::new();
+    let mut word = String::new();
+    let mut chars = typ.trim().chars().peekable();
+
+    let flush = |word: &mut String, out: &mut String| {
+        if !matches!(word.as_str(), "mut" | "const" | "dyn" | "impl" | "final" | "readonly") {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '&' | '*' if out.is_empty() && word.is_empty() => {}
+            '\'' => {
+                // Lifetime: skip the identifier that follows
+                while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+                    chars.next();
+                }
+            }
+            ':' if chars.peek() == Some(&':') => {
+                // Path qualifier: drop the segment before `::`
+                chars.next();
+                word.clear();
+            }
+            c if c.is_alphanumeric() || c == '_' => word.push(c),
+            c if c.is_whitespace() => flush(&mut word, &mut out),
+            c => {
+                flush(&mut word, &mut out);
+                if c != '&' {
+                    out.push(c);
+                }
+            }
+        }
+    }
+    flush(&mut word, &mut out);
+
+    out.to_lowercase()
+}
+
+// ─── Parsing Helpers ────────────────────────────────────────────
+
+/// Where to look for the parameter list: after the `fn` keyword in Rust
+/// and `def` in Python, so the parens of `pub(crate)`, `#[cfg(test)]` or
+/// `@app.route("/x")` aren't taken for it.
+fn params_search_start(code: &str, lang: Option<SupportedLanguage>) -> usize {
+    let keyword = match lang {
+        Some(SupportedLanguage::Rust) => "fn",
+        Some(SupportedLanguage::Python) => "def",
+        _ => return 0,
+    };
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        // Comments, attributes and decorators may mention the keyword
+        if !trimmed.starts_with("//") && !trimmed.starts_with(['#', '@']) {
+            let word = line.match_indices(keyword).find(|&(i, _)| {
+                let before = line[..i].chars().next_back();
+                let after = line[i + keyword.len()..].chars().next();
+                !before.is_some_and(|c| c.is_alphanumeric() || c == '_') && after.is_some_and(char::is_whitespace)
+            });
+            if let Some((i, _)) = word {
+                return offset + i + keyword.len();
+            }
+        }
+        offset += line.len();
+    }
+    0
+}
+
+/// Find the opening paren of the parameter list, skipping generics.
+fn find_params_open(code: &str) -> Option<usize> {
+    let mut angle = 0usize;
+    for (i, c) in code.char_indices() {
+        match c {
+            '<' => angle += 1,
+            '>' => angle = angle.saturating_sub(1),
+            '(' if angle == 0 => return Some(i),
+            '{' | ';' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find the paren matching the one at `open`.
+fn matching_paren(s: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas that aren't nested inside brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut prev = ' ';
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '<' | '{' => depth += 1,
+            // `->` inside a closure type isn't a closing bracket
+            '>' if prev == '-' => {}
+            ')' | ']' | '>' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(s[start..].trim());
+
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+/// Is this parameter a method receiver?
+fn is_receiver(param: &str) -> bool {
+    let p = param.trim_start_matches('&').trim();
+    let before_colon = p.split(':').next().unwrap_or(p);
+    // `&'a self`, `&mut self`, `self: Box<Self>`
+    let name = before_colon.split_whitespace().last().unwrap_or("");
+    matches!(name, "self" | "cls" | "this")
+}
+
+/// Extract the type of a single parameter declaration.
+fn param_type(param: &str, lang: Option<SupportedLanguage>) -> String {
+    // Drop default values: `x: int = 3`
+    let param = param.split('=').next().unwrap_or(param).trim();
+
+    if let Some(colon) = param.find(':') {
+        return param[colon + 1..].trim().to_string();
+    }
+
+    let tokens: Vec<&str> = param.split_whitespace().collect();
+    if tokens.len() < 2 {
+        return String::new();
+    }
+
+    match lang {
+        // Go: `name Type`
+        Some(SupportedLanguage::Go) => tokens[1..].join(" "),
+        // C-family: `Type name`
+        Some(SupportedLanguage::Java | SupportedLanguage::CSharp | SupportedLanguage::Cpp) => {
+            tokens[..tokens.len() - 1].join(" ")
+        }
+        _ => String::new(),
+    }
+}
+
+/// Return type written after the parameter list (`-> T`, `: T`, Go `T`).
+fn suffix_return_type(rest: &str, lang: Option<SupportedLanguage>) -> Option<String> {
+    let line = rest.lines().next().unwrap_or("");
+    let line = line.split('{').next().unwrap_or(line);
+    let line = line.split(" where").next().unwrap_or(line).trim();
+    let line = line.trim_end_matches(';').trim();
+
+    let typ = if let Some(r) = line.strip_prefix("->") {
+        r.trim_end_matches(':').trim()
+    } else if line.starts_with("=>") {
+        ""
+    } else if let Some(r) = line.strip_prefix(':') {
+        r.split("=>").next().unwrap_or(r).trim()
+    } else if lang == Some(SupportedLanguage::Go) {
+        line
+    } else {
+        ""
+    };
+
+    if typ.is_empty() {
+        None
+    } else {
+        Some(typ.to_string())
+    }
+}
+
+/// Return type written before the name (`public boolean validate(`).
+fn prefix_return_type(head: &str) -> Option<String> {
+    let tokens: Vec<&str> = head.split_whitespace().collect();
+    if tokens.len() < 2 {
+        return None;
+    }
+    Some(tokens[tokens.len() - 2].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_shape() {
+        let shape = SignatureShape::parse("(&str, HashMap<K, V>) -> bool").unwrap();
+        assert_eq!(shape.params, vec!["&str", "HashMap<K, V>"]);
+        assert_eq!(shape.return_type.as_deref(), Some("bool"));
+
+        let shape = SignatureShape::parse("fn()").unwrap();
+        assert!(shape.params.is_empty());
+        assert!(shape.return_type.is_none());
+    }
+
+    #[test]
+    fn test_normalize_type() {
+        assert_eq!(normalize_type("&'a mut str"), "str");
+        assert_eq!(normalize_type("std::string::String"), "string");
+        assert_eq!(normalize_type("Vec< &str >"), "vec<str>");
+        assert_eq!(normalize_type("&dyn Fn(u8)"), "fn(u8)");
+    }
+
+    #[test]
+    fn test_shape_from_rust_method() {
+        let code = "pub fn check<'a>(&self, input: &'a str, n: usize) -> Option<bool> {\n    None\n}";
+        let shape = SignatureShape::from_code(code, Path::new("lib.rs")).unwrap();
+        assert_eq!(shape.params, vec!["&'a str", "usize"]);
+        assert_eq!(shape.return_type.as_deref(), Some("Option<bool>"));
+        assert!(shape.matches(&["&str", "usize"], Some("Option<bool>")));
+    }
+
+    #[test]
+    fn test_shape_skips_visibility_and_attributes() {
+        let code = "#[cfg(test)]\npub(crate) fn check(input: &str) -> bool {\n    true\n}";
+        let shape = SignatureShape::from_code(code, Path::new("lib.rs")).unwrap();
+        assert_eq!(shape.params, vec!["&str"]);
+        assert_eq!(shape.return_type.as_deref(), Some("bool"));
+
+        let shape = SignatureShape::from_code("pub(in crate::graph) fn id() -> u32 {}", Path::new("lib.rs")).unwrap();
+        assert!(shape.params.is_empty());
+        assert_eq!(shape.return_type.as_deref(), Some("u32"));
+    }
+
+    #[test]
+    fn test_shape_from_other_languages() {
+        let py = SignatureShape::from_code("def valid(self, s: str) -> bool:\n    pass", Path::new("a.py")).unwrap();
+        assert!(py.matches(&["str"], Some("bool")));
+
+        let decorated = "@app.route(\"/users/<id>\", methods=[\"GET\"])\n@login_required\nasync def show(id: int) -> dict:\n    pass";
+        let py = SignatureShape::from_code(decorated, Path::new("a.py")).unwrap();
+        assert_eq!(py.params, vec!["int"]);
+        assert_eq!(py.return_type.as_deref(), Some("dict"));
+
+        let go = SignatureShape::from_code("func Valid(s string) bool {\n}", Path::new("a.go")).unwrap();
+        assert!(go.matches(&["string"], Some("bool")));
+
+        let java = SignatureShape::from_code("public boolean valid(String s) {\n}", Path::new("A.java")).unwrap();
+        assert!(java.matches(&["String"], Some("boolean")));
+
+        let ts = SignatureShape::from_code("function valid(s: string): boolean {\n}", Path::new("a.ts")).unwrap();
+        assert!(ts.matches(&["string"], Some("boolean")));
+    }
+}
@@ -0,0 +1,387 @@
+//! Project summary — a one-shot orientation for a new agent session.
+//!
+//! Bundles the language breakdown, entry points, hotspots, API endpoints,
+//! external dependencies and directory layout into one response that fits
+//! a token budget.
+
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::engine::CodeGraph;
+use super::types::{ApiEndpointKind, EdgeKind, NodeData, NodeKind};
+use crate::parser::SupportedLanguage;
+
+/// Default token budget for `project_summary`.
+pub const DEFAULT_SUMMARY_TOKENS: usize = 2000;
+
+/// Rough chars-per-token ratio used for budgeting.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Per-list caps before budgeting kicks in.
+const MAX_ENTRY_POINTS: usize = 10;
+const MAX_HOTSPOTS: usize = 10;
+const MAX_ENDPOINTS: usize = 20;
+const MAX_DEPENDENCIES: usize = 20;
+const MAX_DIRECTORIES: usize = 30;
+
+/// Path prefixes that are never external dependencies.
+const LOCAL_PREFIXES: &[&str] = &["crate", "self", "super", "std", "core", "alloc"];
+
+/// Compact first-contact overview of a codebase.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    /// Files per language, most common first.
+    pub languages: Vec<LanguageCount>,
+    /// Likely entry points (`main`, uncalled functions that do work).
+    pub entry_points: Vec<SummarySymbol>,
+    /// Most connected symbols.
+    pub hotspots: Vec<SummarySymbol>,
+    /// Detected API routes, e.g. `GET /api/users`.
+    pub endpoints: Vec<String>,
+    /// Imported packages that aren't part of the project, most used first.
+    pub external_dependencies: Vec<String>,
+    /// Directories (relative to the project root) with their file counts.
+    pub directories: Vec<String>,
+    /// Approximate size of this summary in tokens.
+    pub estimated_tokens: usize,
+    /// True if lists were cut to fit the budget.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Number of files in one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageCount {
+    pub language: String,
+    pub files: usize,
+}
+
+/// A symbol reference in the summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummarySymbol {
+    pub name: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Inbound + outbound edges (hotspots only).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub connections: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl CodeGraph {
+    /// Summarize the project within the default token budget.
+    pub fn project_summary(&self) -> ProjectSummary {
+        self.project_summary_with_budget(DEFAULT_SUMMARY_TOKENS)
+    }
+
+    /// Summarize the project, trimming lists to fit roughly `max_tokens`.
+    pub fn project_summary_with_budget(&self, max_tokens: usize) -> ProjectSummary {
+        let files = self.all_files();
+        let root = common_root(&files);
+
+        let mut summary = ProjectSummary {
+            languages: language_breakdown(&files),
+            entry_points: self.entry_points(),
+            hotspots: self.hotspots(),
+            endpoints: self.endpoint_labels(),
+            external_dependencies: self.external_dependencies(&files),
+            directories: directory_tree(&files, &root),
+            ..Default::default()
+        };
+
+        summary.estimated_tokens = estimate_tokens(&summary);
+        while summary.estimated_tokens > max_tokens && shrink(&mut summary) {
+            summary.truncated = true;
+            summary.estimated_tokens = estimate_tokens(&summary);
+        }
+
+        summary
+    }
+
+    /// `main` first, then functions nobody calls that call something.
+    fn entry_points(&self) -> Vec<SummarySymbol> {
+        let graph = self.inner_graph();
+        let mut entries: Vec<SummarySymbol> = Vec::new();
+
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            if node.removed || !matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+                continue;
+            }
+            if node.name.starts_with("test_") || node.name == "new" {
+                continue;
+            }
+
+            let has_callers = graph
+                .edges_directed(idx, Direction::Incoming)
+                .any(|e| e.weight().kind == EdgeKind::Calls);
+            let has_callees = graph
+                .edges_directed(idx, Direction::Outgoing)
+                .any(|e| e.weight().kind == EdgeKind::Calls);
+
+            if node.name == "main" || (!has_callers && has_callees) {
+                entries.push(summary_symbol(node, 0));
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            (a.name != "main", &a.file, a.line).cmp(&(b.name != "main", &b.file, b.line))
+        });
+        entries.truncate(MAX_ENTRY_POINTS);
+        entries
+    }
+
+    /// Symbols with the most edges (excluding file/import bookkeeping).
+    fn hotspots(&self) -> Vec<SummarySymbol> {
+        let graph = self.inner_graph();
+        let mut scored: Vec<SummarySymbol> = graph
+            .node_indices()
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !node.removed && !matches!(node.kind, NodeKind::File | NodeKind::Import)
+            })
+            .map(|idx| {
+                let degree = graph
+                    .edges_directed(idx, Direction::Incoming)
+                    .chain(graph.edges_directed(idx, Direction::Outgoing))
+                    .filter(|e| e.weight().kind != EdgeKind::Defines)
+                    .count();
+                summary_symbol(&graph[idx], degree)
+            })
+            .filter(|s| s.connections > 0)
+            .collect();
+
+        scored.sort_by(|a, b| {
+            b.connections
+                .cmp(&a.connections)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut seen = HashSet::new();
+        scored.retain(|s| seen.insert(s.name.clone()));
+        scored.truncate(MAX_HOTSPOTS);
+        scored
+    }
+
+    /// Route definitions as `METHOD /path`.
+    fn endpoint_labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .endpoints()
+            .filter(|ep| ep.kind == ApiEndpointKind::Defines)
+            .map(|ep| format!("{} {}", ep.method.as_deref().unwrap_or("*"), ep.url))
+            .collect();
+        labels.sort();
+        labels.dedup();
+        labels.truncate(MAX_ENDPOINTS);
+        labels
+    }
+
+    /// Top-level import names that don't resolve to a project file/module.
+    fn external_dependencies(&self, files: &[PathBuf]) -> Vec<String> {
+        let local: HashSet<String> = files
+            .iter()
+            .flat_map(|f| {
+                let stem = f.file_stem().map(|s| s.to_string_lossy().to_string());
+                let dir = f
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|s| s.to_string_lossy().to_string());
+                stem.into_iter().chain(dir)
+            })
+            .collect();
+
+        let graph = self.inner_graph();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for node in graph.node_weights() {
+            if node.removed || node.kind != NodeKind::Import {
+                continue;
+            }
+            if let Some(package) = import_package(&node.name) {
+                if !local.contains(&package) && !LOCAL_PREFIXES.contains(&package.as_str()) {
+                    *counts.entry(package).or_default() += 1;
+                }
+            }
+        }
+
+        let mut deps: Vec<(String, usize)> = counts.into_iter().collect();
+        deps.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        deps.into_iter()
+            .take(MAX_DEPENDENCIES)
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
+fn summary_symbol(node: &NodeData, connections: usize) -> SummarySymbol {
+    SummarySymbol {
+        name: node.name.clone(),
+        kind: node.kind,
+        file: node.file_path.clone(),
+        line: node.line_start,
+        connections,
+    }
+}
+
+/// Files per language, most common first.
+pub(crate) fn language_breakdown(files: &[PathBuf]) -> Vec<LanguageCount> {
+    let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+    for file in files {
+        if let Some(lang) = SupportedLanguage::from_path(file) {
+            *counts.entry(lang.name()).or_default() += 1;
+        }
+    }
+
+    let mut langs: Vec<LanguageCount> = counts
+        .into_iter()
+        .map(|(language, files)| LanguageCount {
+            language: language.to_string(),
+            files,
+        })
+        .collect();
+    langs.sort_by_key(|l| std::cmp::Reverse(l.files));
+    langs
+}
+
+/// Directories relative to `root`, with file counts.
+fn directory_tree(files: &[PathBuf], root: &Path) -> Vec<String> {
+    let mut dirs: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        let dir = file
+            .parent()
+            .map(|p| p.strip_prefix(root).unwrap_or(p))
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let dir = if dir.is_empty() { ".".to_string() } else { dir };
+        *dirs.entry(dir).or_default() += 1;
+    }
+
+    dirs.into_iter()
+        .take(MAX_DIRECTORIES)
+        .map(|(dir, count)| format!("{}/ ({})", dir, count))
+        .collect()
+}
+
+/// Longest directory prefix shared by all files.
+fn common_root(files: &[PathBuf]) -> PathBuf {
+    let mut iter = files.iter().filter_map(|f| f.parent());
+    let Some(first) = iter.next() else {
+        return PathBuf::new();
+    };
+
+    let mut root = first.to_path_buf();
+    for dir in iter {
+        while !dir.starts_with(&root) {
+            if !root.pop() {
+                return PathBuf::new();
+            }
+        }
+    }
+    root
+}
+
+/// The package an import refers to: `serde::Serialize` -> `serde`,
+/// `'express'` -> `express`, `os.path` -> `os`. Relative imports are local.
+fn import_package(import: &str) -> Option<String> {
+    let text = import
+        .trim()
+        .trim_start_matches("from ")
+        .trim_start_matches("import ")
+        .trim_start_matches("pub ")
+        .trim_start_matches("use ")
+        .trim_start_matches("::");
+    // `{ a, b } from 'x'` style: take the module part
+    let text = text.rsplit(" from ").next().unwrap_or(text).trim();
+    let text = text.trim_matches(['"', '\'', '`', ';', ' ', '(', ')']);
+
+    if text.is_empty() || text.starts_with('.') || text.starts_with('/') {
+        return None;
+    }
+
+    let package = text
+        .split(['/', ':', '.', ' ', '{', ','])
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('@');
+
+    if package.is_empty() || !package.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        None
+    } else {
+        Some(package.to_string())
+    }
+}
+
+fn estimate_tokens(summary: &ProjectSummary) -> usize {
+    serde_json::to_string(summary)
+        .map(|s| s.len() / CHARS_PER_TOKEN)
+        .unwrap_or(0)
+}
+
+/// Drop one item from the longest trimmable list. Returns false if
+/// nothing is left to trim.
+fn shrink(summary: &mut ProjectSummary) -> bool {
+    let lens = [
+        summary.directories.len(),
+        summary.external_dependencies.len(),
+        summary.endpoints.len(),
+        summary.hotspots.len(),
+        summary.entry_points.len(),
+    ];
+    let Some((longest, &len)) = lens.iter().enumerate().max_by_key(|(_, &len)| len) else {
+        return false;
+    };
+    if len <= 1 {
+        return false;
+    }
+
+    match longest {
+        0 => summary.directories.truncate(len - 1),
+        1 => summary.external_dependencies.truncate(len - 1),
+        2 => summary.endpoints.truncate(len - 1),
+        3 => summary.hotspots.truncate(len - 1),
+        _ => summary.entry_points.truncate(len - 1),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+
+    #[test]
+    fn test_import_package() {
+        assert_eq!(import_package("serde::{Deserialize, Serialize}").as_deref(), Some("serde"));
+        assert_eq!(import_package("'express'").as_deref(), Some("express"));
+        assert_eq!(import_package("os.path").as_deref(), Some("os"));
+        assert_eq!(import_package("./utils"), None);
+    }
+
+    #[test]
+    fn test_project_summary_self() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+        let graph = build_graph(&root);
+        let summary = graph.project_summary();
+
+        assert_eq!(summary.languages[0].language, "Rust");
+        assert!(summary.entry_points.iter().any(|e| e.name == "main"));
+        assert!(summary.external_dependencies.contains(&"serde".to_string()));
+        assert!(summary.estimated_tokens <= DEFAULT_SUMMARY_TOKENS);
+    }
+
+    #[test]
+    fn test_project_summary_budget() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+        let graph = build_graph(&root);
+        let summary = graph.project_summary_with_budget(300);
+
+        assert!(summary.truncated);
+        assert!(!summary.languages.is_empty());
+        assert!(summary.entry_points.iter().any(|e| e.name == "main"));
+    }
+}
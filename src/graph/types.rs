@@ -39,6 +39,12 @@ pub enum NodeKind {
     Impl,
     /// A variable or field.
     Variable,
+    /// A UI component (a JSX-returning function in JS/TSX).
+    Component,
+    /// A macro definition (Rust `macro_rules!`).
+    Macro,
+    /// A field of a class or struct (Java fields, C# fields and properties).
+    Field,
 }
 
 impl fmt::Display for NodeKind {
@@ -58,10 +64,117 @@ impl fmt::Display for NodeKind {
             NodeKind::Trait => write!(f, "trait"),
             NodeKind::Impl => write!(f, "impl"),
             NodeKind::Variable => write!(f, "variable"),
+            NodeKind::Component => write!(f, "component"),
+            NodeKind::Macro => write!(f, "macro"),
+            NodeKind::Field => write!(f, "field"),
         }
     }
 }
 
+/// Access level of a symbol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Visible to everyone (`pub`, `export`, no leading underscore).
+    #[default]
+    Public,
+    /// Visible within the crate/package (`pub(crate)`, `internal`).
+    Crate,
+    /// Visible to subclasses (`protected`).
+    Protected,
+    /// Visible only to the defining module/class.
+    Private,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Visibility::Public => write!(f, "public"),
+            Visibility::Crate => write!(f, "crate"),
+            Visibility::Protected => write!(f, "protected"),
+            Visibility::Private => write!(f, "private"),
+        }
+    }
+}
+
+/// Which symbols a query should return, by test flag, visibility,
+/// whether they are generated and whether they are `async`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolFilter {
+    /// Include test functions and symbols in test files/modules.
+    pub include_tests: bool,
+    /// Include non-public symbols (private, crate, protected).
+    pub include_private: bool,
+    /// Include generated and vendored code.
+    #[serde(default = "default_true")]
+    pub include_generated: bool,
+    /// Only `async` functions and methods.
+    #[serde(default)]
+    pub async_only: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SymbolFilter {
+    /// A filter that lets every symbol through.
+    pub fn all() -> Self {
+        Self {
+            include_tests: true,
+            include_private: true,
+            include_generated: true,
+            async_only: false,
+        }
+    }
+
+    /// Check whether a node passes the filter.
+    pub fn allows(&self, node: &NodeData) -> bool {
+        (self.include_tests || !node.is_test)
+            && (self.include_private || node.visibility == Visibility::Public)
+            && (self.include_generated || !node.generated)
+            && (!self.async_only || node.is_async)
+    }
+}
+
+impl Default for SymbolFilter {
+    /// Hide tests, show private and generated symbols.
+    fn default() -> Self {
+        Self {
+            include_tests: false,
+            include_private: true,
+            include_generated: true,
+            async_only: false,
+        }
+    }
+}
+
+/// Which way to follow edges from a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraversalDirection {
+    /// Edges leaving the node (what it calls, uses, contains).
+    Outbound,
+    /// Edges arriving at the node (who calls, uses, contains it).
+    Inbound,
+    /// Both directions.
+    Both,
+}
+
+/// How far from a symbol's definition its dependents may live.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyScope {
+    /// Anywhere in the graph.
+    #[default]
+    Workspace,
+    /// The same package: under the nearest directory with a `Cargo.toml`,
+    /// `package.json` or `go.mod`.
+    Package,
+    /// The same file.
+    File,
+}
+
 /// The kind of an edge (relationship) in the code graph.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -88,6 +201,11 @@ pub enum EdgeKind {
     Parameter,
     /// Return type relationship (Function -> Type).
     Returns,
+    /// Component renders another component (Component -> Component).
+    Renders,
+    /// Symbol calls another through reflection or a string-named lookup
+    /// (`getattr(obj, "save")`). The target is a guess by name.
+    DynamicCall,
 }
 
 impl fmt::Display for EdgeKind {
@@ -104,6 +222,21 @@ impl fmt::Display for EdgeKind {
             EdgeKind::References => write!(f, "references"),
             EdgeKind::Parameter => write!(f, "parameter"),
             EdgeKind::Returns => write!(f, "returns"),
+            EdgeKind::Renders => write!(f, "renders"),
+            EdgeKind::DynamicCall => write!(f, "dynamic_call"),
+        }
+    }
+}
+
+impl EdgeKind {
+    /// How sure the extractor is that the edge exists, from 0.0 to 1.0.
+    ///
+    /// Edges read off the syntax tree are certain; `DynamicCall` edges are
+    /// matched by a string's contents and may point at the wrong symbol.
+    pub fn confidence(&self) -> f32 {
+        match self {
+            EdgeKind::DynamicCall => 0.3,
+            _ => 1.0,
         }
     }
 }
@@ -127,6 +260,39 @@ pub struct NodeData {
     /// and cleaned up during compaction.
     #[serde(default)]
     pub removed: bool,
+    /// Access level of the symbol.
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Whether this is test code (a test function or inside a test module/file).
+    #[serde(default)]
+    pub is_test: bool,
+    /// Whether `code_snippet` was cut short of the full definition.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Initializer of a constant, bounded in length.
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Whether the symbol is marked deprecated.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Python decorators on the definition, without their arguments.
+    #[serde(default)]
+    pub decorators: Vec<String>,
+    /// Whether the function is `async`.
+    #[serde(default)]
+    pub is_async: bool,
+    /// Whether the file is generated or vendored code, not to be edited by
+    /// hand.
+    #[serde(default)]
+    pub generated: bool,
+    /// Call-graph centrality, set by `CodeGraph::compute_centrality`; the
+    /// most depended-on symbol scores 1.0.
+    #[serde(default)]
+    pub importance: f64,
+    /// Name qualified by module and enclosing types: `config::Config::new`
+    /// in Rust, `models::User.save` elsewhere. Empty for files and imports.
+    #[serde(default)]
+    pub qualified_name: String,
 }
 
 impl NodeData {
@@ -143,6 +309,16 @@ impl NodeData {
             line_end: 0,
             code_snippet: String::new(),
             removed: false,
+            visibility: Visibility::Public,
+            is_test: false,
+            truncated: false,
+            value: None,
+            deprecated: false,
+            decorators: Vec::new(),
+            is_async: false,
+            generated: false,
+            importance: 0.0,
+            qualified_name: String::new(),
         }
     }
 
@@ -162,6 +338,16 @@ impl NodeData {
             line_end,
             code_snippet,
             removed: false,
+            visibility: Visibility::Public,
+            is_test: false,
+            truncated: false,
+            value: None,
+            deprecated: false,
+            decorators: Vec::new(),
+            is_async: false,
+            generated: false,
+            importance: 0.0,
+            qualified_name: String::new(),
         }
     }
 }
@@ -171,14 +357,22 @@ impl NodeData {
 pub struct EdgeData {
     /// The kind of relationship.
     pub kind: EdgeKind,
+    /// How many times the relationship occurs: a `Calls` edge stands for
+    /// every call from the caller to the callee.
+    #[serde(default = "default_count")]
+    pub count: u32,
 }
 
 impl EdgeData {
     pub fn new(kind: EdgeKind) -> Self {
-        Self { kind }
+        Self { kind, count: 1 }
     }
 }
 
+fn default_count() -> u32 {
+    1
+}
+
 /// A symbol extracted from parsing a source file.
 /// This is an intermediate representation before being added to the graph.
 #[derive(Debug, Clone)]
@@ -195,6 +389,22 @@ pub struct ExtractedSymbol {
     pub code_snippet: String,
     /// Parent symbol name (for methods inside classes/impls).
     pub parent: Option<String>,
+    /// Access level of the symbol.
+    pub visibility: Visibility,
+    /// Whether this is test code.
+    pub is_test: bool,
+    /// Whether `code_snippet` was cut short of the full definition.
+    pub truncated: bool,
+    /// Initializer of a constant (`200` for `const X: u64 = 200;`).
+    pub value: Option<String>,
+    /// Marked deprecated (`#[deprecated]`, `@Deprecated`, `[Obsolete]`,
+    /// `@deprecated` in a doc comment, `Deprecated:` in Go).
+    pub deprecated: bool,
+    /// Python decorators, without their arguments (`staticmethod`,
+    /// `pytest.fixture`, `app.route`).
+    pub decorators: Vec<String>,
+    /// Declared `async` (`async fn`, `async def`, `async function`).
+    pub is_async: bool,
 }
 
 /// An import extracted from a source file.
@@ -215,10 +425,105 @@ pub struct ExtractedCall {
     pub callee: String,
     /// The name of the function making the call.
     pub caller: String,
+    /// Path the callee was reached through: `config` in
+    /// `config::validate()`, `self.db` in `self.db.find()`. `None` for bare
+    /// calls and for chained calls, whose receiver is another call's result.
+    pub receiver: Option<String>,
     /// Line number of the call.
     pub line: usize,
 }
 
+impl ExtractedCall {
+    /// Is this a call to `stopword`? A bare name (`len`) matches every call
+    /// of that name; a qualified one (`console.log`) only calls through
+    /// that receiver, so a project's own `log` keeps its edges.
+    pub fn is_stopword(&self, stopword: &str) -> bool {
+        match stopword.rsplit_once('.') {
+            Some((receiver, name)) => self.callee == name && self.receiver.as_deref() == Some(receiver),
+            None => self.callee == stopword,
+        }
+    }
+}
+
+/// A type declaring the trait it implements or the class it extends
+/// (`impl Display for Config`).
+#[derive(Debug, Clone)]
+pub struct ExtractedSupertype {
+    /// The implementing or extending symbol, as extracted (`Config`).
+    pub name: String,
+    /// The trait, interface or base class, without path or generic
+    /// arguments (`fmt::Display` -> `Display`).
+    pub supertype: String,
+    /// `Implements` or `Extends`.
+    pub kind: EdgeKind,
+    /// Line where the implementing symbol starts.
+    pub line: usize,
+}
+
+/// Whether a file defines an API route or calls one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiEndpointKind {
+    /// Backend route definition (`@app.get("/api/users")`).
+    Defines,
+    /// Client-side call (`fetch("/api/users")`).
+    Consumes,
+}
+
+impl fmt::Display for ApiEndpointKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiEndpointKind::Defines => write!(f, "defines"),
+            ApiEndpointKind::Consumes => write!(f, "consumes"),
+        }
+    }
+}
+
+/// An API endpoint extracted from a source file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractedApiEndpoint {
+    /// Normalized URL (path params become `:param`).
+    pub url: String,
+    /// HTTP method, if known (uppercase).
+    pub method: Option<String>,
+    /// Definition or consumption.
+    pub kind: ApiEndpointKind,
+    /// Enclosing function/handler name.
+    pub scope: Option<String>,
+    /// Line number of the route or call.
+    pub line: usize,
+}
+
+/// An API endpoint stored in the graph, tagged with its file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiEndpoint {
+    /// Normalized URL (path params become `:param`).
+    pub url: String,
+    /// HTTP method, if known (uppercase).
+    pub method: Option<String>,
+    /// Definition or consumption.
+    pub kind: ApiEndpointKind,
+    /// Enclosing function/handler name.
+    pub scope: Option<String>,
+    /// File the endpoint appears in.
+    pub file: PathBuf,
+    /// Line number of the route or call.
+    pub line: usize,
+}
+
+impl ApiEndpoint {
+    pub fn from_extracted(ep: &ExtractedApiEndpoint, file: PathBuf) -> Self {
+        Self {
+            url: ep.url.clone(),
+            method: ep.method.clone(),
+            kind: ep.kind,
+            scope: ep.scope.clone(),
+            file,
+            line: ep.line,
+        }
+    }
+}
+
 /// All extracted information from a single source file.
 #[derive(Debug, Clone)]
 pub struct FileExtractions {
@@ -230,6 +535,20 @@ pub struct FileExtractions {
     pub imports: Vec<ExtractedImport>,
     /// Function/method calls.
     pub calls: Vec<ExtractedCall>,
+    /// JSX elements rendered by components (`caller` renders `callee`).
+    pub renders: Vec<ExtractedCall>,
+    /// Calls whose target is named by a string at runtime (`getattr`,
+    /// `obj["name"]()`), resolved into low-confidence `DynamicCall` edges.
+    pub dynamic_calls: Vec<ExtractedCall>,
+    /// Traits implemented and classes extended by the file's types.
+    pub supertypes: Vec<ExtractedSupertype>,
+    /// Names the module exports explicitly (Python `__all__`).
+    pub exports: Vec<String>,
+    /// Generated or vendored code: a `@generated` marker in the header or
+    /// a path matching the builder's generated patterns.
+    pub generated: bool,
+    /// API routes defined or called.
+    pub endpoints: Vec<ExtractedApiEndpoint>,
 }
 
 // ─── Graph Search Results ─────────────────────────────────────────────────────
@@ -249,6 +568,9 @@ pub struct GraphSearchResult {
     /// True if results were truncated due to limits
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub truncated: bool,
+    /// Symbols found, before any paging of `symbols`
+    #[serde(default)]
+    pub total: usize,
 }
 
 /// Information about a symbol in search results.
@@ -0,0 +1,229 @@
+//! Uncommitted changes — which symbols the working tree has touched.
+//!
+//! Maps the hunks of `git diff HEAD` (staged and unstaged edits) onto the
+//! graph so a pre-commit hook can list the changed symbols and what
+//! depends on them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::engine::{CodeGraph, DependencyInfo};
+use super::meta::run_git;
+use super::types::NodeKind;
+
+/// A symbol whose lines overlap an uncommitted edit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedSymbol {
+    pub symbol: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub line_start: usize,
+    pub line_end: usize,
+}
+
+/// Changed symbols and the symbols that depend on them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UncommittedChanges {
+    /// Symbols edited in the working tree or index.
+    pub changed: Vec<ChangedSymbol>,
+    /// Symbols outside the change that call or reference a changed symbol.
+    pub impacted: Vec<DependencyInfo>,
+}
+
+impl CodeGraph {
+    /// Symbols touched by uncommitted changes (staged and unstaged) in the
+    /// git repository containing `root`, plus their dependents.
+    ///
+    /// Line numbers come from the working tree, so the graph should be
+    /// fresh. Returns an empty result outside git or before the first commit.
+    pub fn uncommitted_symbols(&self, root: &Path) -> UncommittedChanges {
+        let mut result = UncommittedChanges::default();
+
+        let Some(repo_root) = run_git(root, &["rev-parse", "--show-toplevel"])
+            .map(|out| PathBuf::from(out.trim()))
+        else {
+            return result;
+        };
+        let Some(diff) = run_git(&repo_root, &["diff", "HEAD", "--unified=0", "--no-color"]) else {
+            return result;
+        };
+
+        // Graph files by canonical path, so git's repo-relative paths map back
+        let graph_files: HashMap<PathBuf, PathBuf> = self
+            .all_files()
+            .into_iter()
+            .filter_map(|f| std::fs::canonicalize(&f).ok().map(|c| (c, f)))
+            .collect();
+
+        let mut seen: HashSet<(PathBuf, String, usize)> = HashSet::new();
+        for (path, ranges) in parse_diff(&diff) {
+            let Some(file) = std::fs::canonicalize(repo_root.join(&path))
+                .ok()
+                .and_then(|c| graph_files.get(&c))
+            else {
+                continue;
+            };
+
+            let mut symbols = self.symbols_in_file(file);
+            symbols.retain(|n| n.kind != NodeKind::Import);
+            symbols.sort_by_key(|n| n.line_start);
+
+            for (start, end) in ranges {
+                let hits: Vec<_> = symbols
+                    .iter()
+                    .filter(|n| n.line_start <= end && start <= n.line_end)
+                    .collect();
+                // Keep the innermost symbols: a method, not its impl too
+                for n in &hits {
+                    let contains_other = hits.iter().any(|o| {
+                        !std::ptr::eq(**o, **n)
+                            && n.line_start <= o.line_start
+                            && o.line_end <= n.line_end
+                    });
+                    if contains_other {
+                        continue;
+                    }
+                    if seen.insert((n.file_path.clone(), n.name.clone(), n.line_start)) {
+                        result.changed.push(ChangedSymbol {
+                            symbol: n.name.clone(),
+                            kind: n.kind,
+                            file: n.file_path.clone(),
+                            line_start: n.line_start,
+                            line_end: n.line_end,
+                        });
+                    }
+                }
+            }
+        }
+
+        let changed_names: HashSet<&str> = result.changed.iter().map(|c| c.symbol.as_str()).collect();
+        let mut impacted_seen: HashSet<(PathBuf, String)> = HashSet::new();
+        for changed in &result.changed {
+            for dep in self.dependents(&changed.symbol) {
+                if dep.kind == NodeKind::File || changed_names.contains(dep.symbol.as_str()) {
+                    continue;
+                }
+                if impacted_seen.insert((dep.file.clone(), dep.symbol.clone())) {
+                    result.impacted.push(dep);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Parse `git diff --unified=0` output into changed new-side line ranges
+/// per repo-relative path.
+///
+/// Pure deletions have no new-side lines; they are attributed to the line
+/// after the deletion point so the enclosing symbol still shows up.
+fn parse_diff(diff: &str) -> Vec<(PathBuf, Vec<(usize, usize)>)> {
+    let mut files: Vec<(PathBuf, Vec<(usize, usize)>)> = Vec::new();
+
+    let mut prev = "";
+    for line in diff.lines() {
+        let is_header = prev.starts_with("--- ");
+        prev = line;
+        // An added line reading "++ x" also starts with "+++ "; headers follow "--- "
+        if let Some(path) = line.strip_prefix("+++ ").filter(|_| is_header) {
+            // Deleted files diff against /dev/null: nothing left to map
+            if let Some(path) = path.strip_prefix("b/") {
+                files.push((PathBuf::from(path), Vec::new()));
+            }
+            continue;
+        }
+        let Some(hunk) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let Some((_, ranges)) = files.last_mut() else {
+            continue;
+        };
+        // "@@ -12,3 +12,4 @@ fn context" → "+12,4"
+        let Some(new_side) = hunk.split_whitespace().find(|part| part.starts_with('+')) else {
+            continue;
+        };
+        let mut parts = new_side[1..].splitn(2, ',');
+        let start: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let count: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+
+        if count == 0 {
+            ranges.push((start + 1, start + 1));
+        } else {
+            ranges.push((start, start + count - 1));
+        }
+    }
+
+    files.retain(|(_, ranges)| !ranges.is_empty());
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::build_graph;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -2 +2,2 @@ fn a() {\n-x\n+y\n+z\n@@ -9,2 +9,0 @@\n-gone\n-gone\n";
+        assert_eq!(
+            parse_diff(diff),
+            vec![(PathBuf::from("src/a.rs"), vec![(2, 3), (10, 10)])]
+        );
+    }
+
+    #[test]
+    fn test_uncommitted_edit_reports_function() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+
+        fs::write(
+            root.join("lib.rs"),
+            "fn helper() -> u32 {\n    1\n}\n\nfn caller() -> u32 {\n    helper()\n}\n\nfn untouched() {}\n",
+        )
+        .unwrap();
+        git(root, &["add", "lib.rs"]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+
+        // Edit the body of helper without committing
+        fs::write(
+            root.join("lib.rs"),
+            "fn helper() -> u32 {\n    2\n}\n\nfn caller() -> u32 {\n    helper()\n}\n\nfn untouched() {}\n",
+        )
+        .unwrap();
+
+        let graph = build_graph(root);
+        let changes = graph.uncommitted_symbols(root);
+
+        let changed: Vec<&str> = changes.changed.iter().map(|c| c.symbol.as_str()).collect();
+        assert_eq!(changed, vec!["helper"]);
+        let impacted: Vec<&str> = changes.impacted.iter().map(|d| d.symbol.as_str()).collect();
+        assert_eq!(impacted, vec!["caller"]);
+    }
+
+    #[test]
+    fn test_uncommitted_outside_git() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn alpha() {}\n").unwrap();
+
+        let graph = build_graph(dir.path());
+
+        assert!(graph.uncommitted_symbols(dir.path()).changed.is_empty());
+    }
+}
@@ -18,29 +18,85 @@
 pub mod mutation;
 pub mod query;
 pub mod schema;
+pub mod timeout;
 
-use async_graphql::{EmptySubscription, Schema};
-use std::sync::Arc;
+use async_graphql::{Context, EmptySubscription, Schema};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
 
 use crate::graph::CodeGraph;
 use mutation::Mutation;
 use query::Query;
+use timeout::{within_budget, Deadline, QueryTimeout};
 
 /// The Anchor GraphQL schema type
 pub type AnchorSchema = Schema<Query, Mutation, EmptySubscription>;
 
-/// Build the GraphQL schema with the code graph as context
+/// The graph as seen by resolvers: queries take the read lock,
+/// `rebuild`/`reindexFile` take the write lock.
+pub type SharedGraph = Arc<RwLock<CodeGraph>>;
+
+/// Project root used by the `rebuild` and `reindexFile` mutations.
+pub struct ProjectRoot(pub PathBuf);
+
+/// Build the GraphQL schema with the code graph as context.
+///
+/// The schema has no project root, so `rebuild`/`reindexFile` fail;
+/// use [`build_live_schema`] for a graph that can be refreshed.
 pub fn build_schema(graph: Arc<CodeGraph>) -> AnchorSchema {
+    build_schema_with_timeout(graph, timeout::DEFAULT_QUERY_TIMEOUT)
+}
+
+/// Build the GraphQL schema with a time budget per query.
+///
+/// A query that runs past `timeout` returns the fields resolved so far
+/// and an error.
+pub fn build_schema_with_timeout(graph: Arc<CodeGraph>, timeout: Duration) -> AnchorSchema {
+    let graph = Arc::try_unwrap(graph).unwrap_or_else(|shared| (*shared).clone());
+    schema_builder(Arc::new(RwLock::new(graph)), timeout).finish()
+}
+
+/// Build a schema over a shared graph whose mutations can rebuild it from `root`.
+pub fn build_live_schema(graph: SharedGraph, root: PathBuf) -> AnchorSchema {
+    schema_builder(graph, timeout::DEFAULT_QUERY_TIMEOUT)
+        .data(ProjectRoot(root))
+        .finish()
+}
+
+fn schema_builder(
+    graph: SharedGraph,
+    timeout: Duration,
+) -> async_graphql::SchemaBuilder<Query, Mutation, EmptySubscription> {
     Schema::build(Query, Mutation, EmptySubscription)
         .data(graph)
+        .data(QueryTimeout(timeout))
+        .extension(Deadline::new(timeout))
         .limit_depth(5) // Prevent infinite nesting
         .limit_complexity(100) // Prevent overly complex queries
-        .finish()
 }
 
-/// Execute a GraphQL query and return JSON result
+/// Take the read lock on the schema's graph.
+pub(crate) fn read_graph<'a>(ctx: &Context<'a>) -> async_graphql::Result<RwLockReadGuard<'a, CodeGraph>> {
+    ctx.data::<SharedGraph>()?
+        .read()
+        .map_err(|e| async_graphql::Error::new(format!("lock error: {}", e)))
+}
+
+/// Take the write lock on the schema's graph.
+pub(crate) fn write_graph<'a>(ctx: &Context<'a>) -> async_graphql::Result<RwLockWriteGuard<'a, CodeGraph>> {
+    ctx.data::<SharedGraph>()?
+        .write()
+        .map_err(|e| async_graphql::Error::new(format!("lock error: {}", e)))
+}
+
+/// Execute a GraphQL query and return JSON result.
+///
+/// The query is abandoned with an error once the schema's time budget
+/// is spent.
 pub async fn execute(schema: &AnchorSchema, query: &str) -> String {
-    let result = schema.execute(query).await;
+    let budget = schema.data::<QueryTimeout>().copied().unwrap_or_default().0;
+    let result = within_budget(budget, schema.execute(query)).await;
     serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
 }
 
@@ -72,4 +128,83 @@ mod tests {
         assert!(result.contains("symbol"));
         assert!(!result.contains("error"));
     }
+
+    #[tokio::test]
+    async fn test_search_async_only() {
+        let source = "async fn fetch_user() {}\n\nfn fetch_config() {}\n";
+        let extraction = crate::parser::extract_file(std::path::Path::new("lib.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        let schema = build_schema(Arc::new(graph));
+
+        let result = execute(&schema, r#"{ search(pattern: "fetch.*", asyncOnly: true) { name isAsync } }"#).await;
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(
+            json["data"]["search"],
+            serde_json::json!([{ "name": "fetch_user", "isAsync": true }]),
+            "{}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reindex_file_updates_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn one() {}\n").unwrap();
+
+        let graph: SharedGraph = Arc::new(RwLock::new(crate::graph::build_graph(dir.path())));
+        let schema = build_live_schema(graph.clone(), dir.path().to_path_buf());
+        let before = graph.read().unwrap().stats().symbol_count;
+
+        std::fs::write(&file, "fn one() {}\nfn two() {}\nfn three() {}\n").unwrap();
+        let result = execute(&schema, r#"mutation { reindexFile(path: "lib.rs") { symbols } }"#).await;
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        let symbols = json["data"]["reindexFile"]["symbols"].as_u64().unwrap() as usize;
+        assert_eq!(symbols, before + 2, "{}", result);
+        assert_eq!(graph.read().unwrap().stats().symbol_count, symbols);
+        assert_eq!(graph.read().unwrap().search("three", 1).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reindex_file_stays_in_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(root.join("lib.rs"), "fn one() {}\n").unwrap();
+        let outside = dir.path().join("secret.rs");
+        std::fs::write(&outside, "fn secret() {}\n").unwrap();
+
+        let graph: SharedGraph = Arc::new(RwLock::new(crate::graph::build_graph(&root)));
+        let schema = build_live_schema(graph.clone(), root.clone());
+
+        let escapes = [
+            "../secret.rs".to_string(),
+            outside.display().to_string(),
+            "missing/../../secret.rs".to_string(),
+        ];
+        for path in escapes {
+            let query = format!(r#"mutation {{ reindexFile(path: "{}") {{ symbols }} }}"#, path);
+            let result = execute(&schema, &query).await;
+            assert!(result.contains("outside the project root"), "{}: {}", path, result);
+        }
+        assert!(graph.read().unwrap().search("secret", 1).is_empty());
+
+        // A deleted file inside the root is still reindexed (and dropped)
+        std::fs::remove_file(root.join("lib.rs")).unwrap();
+        let result = execute(&schema, r#"mutation { reindexFile(path: "lib.rs") { symbols } }"#).await;
+        assert!(!result.contains("outside the project root"), "{}", result);
+        assert!(graph.read().unwrap().search("one", 1).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_requires_project_root() {
+        let schema = build_schema(Arc::new(CodeGraph::new()));
+
+        let result = execute(&schema, "mutation { rebuild { files } }").await;
+
+        assert!(result.contains("read-only"), "{}", result);
+    }
 }
@@ -1,17 +1,47 @@
 //! GraphQL Mutation resolvers.
 //!
-//! Write operations for code modification.
+//! Index refresh (`rebuild`, `reindexFile`) and write operations for
+//! code modification.
 //! TODO: Write operations not finalized yet.
 
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, Error, Object, Result};
+use std::path::{Path, PathBuf};
 
-use super::schema::WriteResult;
+use super::schema::{Stats, WriteResult};
+use super::{write_graph, ProjectRoot};
+use crate::config::AnchorConfig;
+use crate::graph::rebuild_file;
 
 /// Root mutation type
 pub struct Mutation;
 
 #[Object]
 impl Mutation {
+    /// Rebuild the whole graph from the project root. Returns the new stats.
+    async fn rebuild(&self, ctx: &Context<'_>) -> Result<Stats> {
+        let root = project_root(ctx)?.to_path_buf();
+        // Parse outside the lock so readers aren't blocked during the scan,
+        // and off the async workers
+        let new_graph = tokio::task::spawn_blocking(move || {
+            AnchorConfig::for_project(&root).graph_builder(&root).build()
+        })
+        .await
+        .map_err(|e| Error::new(format!("rebuild failed: {}", e)))?;
+        let mut graph = write_graph(ctx)?;
+        *graph = new_graph;
+        Ok(graph.stats().into())
+    }
+
+    /// Re-parse a single file (relative to the project root). Returns the new stats.
+    async fn reindex_file(&self, ctx: &Context<'_>, path: String) -> Result<Stats> {
+        let path = within_root(project_root(ctx)?, &path)?;
+        let mut graph = write_graph(ctx)?;
+        let result = rebuild_file(&mut graph, &path);
+        graph.refresh_centrality();
+        result.map_err(|e| Error::new(format!("reindex {} failed: {}", path.display(), e)))?;
+        Ok(graph.stats().into())
+    }
+
     /// Create a new file with content (not yet finalized)
     async fn create_file(&self, path: String, _content: String) -> Result<WriteResult> {
         Ok(WriteResult::err(&format!(
@@ -72,3 +102,32 @@ impl Mutation {
         )))
     }
 }
+
+/// `path` joined to `root`, or an error if it resolves outside the root:
+/// `..` segments, an absolute path, or a symlink out of the tree. A file
+/// that no longer exists is resolved through its directory.
+fn within_root(root: &Path, path: &str) -> Result<PathBuf> {
+    let outside = || Error::new(format!("{} is outside the project root", path));
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| Error::new(format!("project root {}: {}", root.display(), e)))?;
+    let joined = canonical_root.join(path);
+    let resolved = match joined.canonicalize() {
+        Ok(resolved) => resolved,
+        Err(_) => {
+            let name = joined.file_name().ok_or_else(outside)?;
+            let parent = joined.parent().and_then(|p| p.canonicalize().ok()).ok_or_else(outside)?;
+            parent.join(name)
+        }
+    };
+    // Back under `root` as given, which is how the graph names its files
+    let relative = resolved.strip_prefix(&canonical_root).map_err(|_| outside())?;
+    Ok(root.join(relative))
+}
+
+/// The project root, if this schema was built with one.
+fn project_root<'a>(ctx: &Context<'a>) -> Result<&'a Path> {
+    ctx.data::<ProjectRoot>()
+        .map(|root| root.0.as_path())
+        .map_err(|_| Error::new("graph is read-only: schema has no project root"))
+}
@@ -3,11 +3,12 @@
 //! Read operations for the code graph.
 
 use async_graphql::{Context, Object, Result};
-use std::sync::Arc;
 
-use super::schema::{File, Stats, Symbol};
-use crate::graph::CodeGraph;
-use crate::regex::{parse, Matcher};
+use super::read_graph;
+use super::schema::{Endpoint, File, Stats, Symbol};
+use crate::graph::SymbolFilter;
+use crate::query::Page;
+use crate::regex::{matcher_cached, Matcher};
 
 /// Root query type
 pub struct Query;
@@ -25,29 +26,68 @@ impl Query {
     /// - `Config.*Manager` - starts with Config, ends with Manager
     /// - `.*Service` - ends with Service
     /// - `get.*&.*User` - contains "get" AND "User"
+    ///
+    /// `asyncOnly: true` keeps only `async` functions and methods.
+    /// `fuzzy: true` also matches near-miss names (`UserServ`,
+    /// `UserSevrice`), ranked after exact and prefix matches.
+    /// `ignoreCase: true` compares names without regard to case, in every
+    /// mode.
+    ///
+    /// Returns up to `limit` symbols after skipping `offset`.
+    #[allow(clippy::too_many_arguments)]
     async fn symbol(
         &self,
         ctx: &Context<'_>,
         name: String,
         #[graphql(default = false)] exact: bool,
         #[graphql(default)] pattern: Option<String>,
+        #[graphql(default = false)] async_only: bool,
+        #[graphql(default = false)] fuzzy: bool,
+        #[graphql(default = false)] ignore_case: bool,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 10)] limit: i32,
     ) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
-        let results = graph.search(&name, 50); // Get more for pattern filtering
+        let graph = read_graph(ctx)?;
+        let page = page(offset, limit);
+        // Get more for pattern filtering
+        let wanted = page.end().max(50);
+        let mut results = if fuzzy {
+            graph.fuzzy_search(&name, wanted)
+        } else if ignore_case {
+            graph.search_ignore_case(&name, wanted, &SymbolFilter::all())
+        } else {
+            graph.search(&name, wanted)
+        };
+        if async_only {
+            results.retain(|r| r.is_async);
+        }
 
         let filtered: Vec<_> = if let Some(ref pat) = pattern {
             // Use Brzozowski derivatives regex - ReDoS-safe
-            let regex = parse(pat).map_err(|e| async_graphql::Error::new(e.to_string()))?;
-            let mut matcher = Matcher::new(regex);
+            let mut matcher = cached_matcher(pat, ignore_case)?;
             results
                 .into_iter()
                 .filter(|r| matcher.is_match(&r.symbol))
                 .collect()
+        } else if exact && ignore_case {
+            results
+                .into_iter()
+                .filter(|r| r.symbol.to_lowercase() == name.to_lowercase())
+                .collect()
         } else if exact {
             results
                 .into_iter()
                 .filter(|r| r.symbol == name)
                 .collect()
+        } else if fuzzy {
+            // Already ranked: exact, prefix, then near misses
+            results
+        } else if ignore_case {
+            let name_lower = name.to_lowercase();
+            results
+                .into_iter()
+                .filter(|r| r.symbol.to_lowercase().starts_with(&name_lower))
+                .collect()
         } else {
             results
                 .into_iter()
@@ -55,9 +95,9 @@ impl Query {
                 .collect()
         };
 
-        Ok(filtered
+        Ok(page
+            .apply(filtered)
             .into_iter()
-            .take(10)
             .map(|r| Symbol {
                 name: r.symbol,
                 kind: r.kind.to_string(),
@@ -70,7 +110,7 @@ impl Query {
 
     /// Get a file and its symbols
     async fn file(&self, ctx: &Context<'_>, path: String) -> Result<File> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+        let graph = read_graph(ctx)?;
         let symbols = graph.symbols_in_file(std::path::Path::new(&path));
         Ok(File {
             path,
@@ -78,13 +118,20 @@ impl Query {
         })
     }
 
-    /// Get symbols that depend on the given symbol (callers)
-    async fn dependents(&self, ctx: &Context<'_>, symbol: String) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+    /// Get symbols that depend on the given symbol (callers), up to
+    /// `limit` after skipping `offset`
+    async fn dependents(
+        &self,
+        ctx: &Context<'_>,
+        symbol: String,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<Symbol>> {
+        let graph = read_graph(ctx)?;
         let deps = graph.dependents(&symbol);
-        Ok(deps
+        Ok(page(offset, limit)
+            .apply(deps)
             .into_iter()
-            .take(50)
             .map(|d| Symbol {
                 name: d.symbol,
                 kind: d.kind.to_string(),
@@ -95,13 +142,20 @@ impl Query {
             .collect())
     }
 
-    /// Get symbols that this symbol depends on (callees)
-    async fn dependencies(&self, ctx: &Context<'_>, symbol: String) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+    /// Get symbols that this symbol depends on (callees), up to `limit`
+    /// after skipping `offset`
+    async fn dependencies(
+        &self,
+        ctx: &Context<'_>,
+        symbol: String,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<Symbol>> {
+        let graph = read_graph(ctx)?;
         let deps = graph.dependencies(&symbol);
-        Ok(deps
+        Ok(page(offset, limit)
+            .apply(deps)
             .into_iter()
-            .take(50)
             .map(|d| Symbol {
                 name: d.symbol,
                 kind: d.kind.to_string(),
@@ -114,13 +168,62 @@ impl Query {
 
     /// Get graph statistics
     async fn stats(&self, ctx: &Context<'_>) -> Result<Stats> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
-        let s = graph.stats();
-        Ok(Stats {
-            files: s.file_count as i32,
-            symbols: s.symbol_count as i32,
-            edges: s.total_edges as i32,
-        })
+        let graph = read_graph(ctx)?;
+        Ok(graph.stats().into())
+    }
+
+    /// Circular dependencies: groups of symbols and files that reach each
+    /// other through calls or imports, each listed in source order.
+    async fn cycles(&self, ctx: &Context<'_>) -> Result<Vec<Vec<String>>> {
+        let graph = read_graph(ctx)?;
+        Ok(graph.find_cycles())
+    }
+
+    /// API routes and client calls found in the code, in file order.
+    ///
+    /// `method` keeps one HTTP method; `urlPrefix` keeps URLs whose path
+    /// (host and query stripped) starts with it.
+    async fn endpoints(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] method: Option<String>,
+        #[graphql(default)] url_prefix: Option<String>,
+    ) -> Result<Vec<Endpoint>> {
+        let graph = read_graph(ctx)?;
+        Ok(graph
+            .endpoints_matching(method.as_deref(), url_prefix.as_deref())
+            .into_iter()
+            .map(Endpoint::from)
+            .collect())
+    }
+
+    /// Find functions by signature shape (parameter and return types).
+    ///
+    /// Example: `bySignature(params: ["&str"], returns: "bool")` finds
+    /// `fn validate(s: &str) -> bool`. References, lifetimes and path
+    /// qualifiers are ignored; `_` matches any type.
+    async fn by_signature(
+        &self,
+        ctx: &Context<'_>,
+        params: Vec<String>,
+        #[graphql(default)] returns: Option<String>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<Symbol>> {
+        let graph = read_graph(ctx)?;
+        let params: Vec<&str> = params.iter().map(String::as_str).collect();
+
+        Ok(graph
+            .find_by_signature(&params, returns.as_deref())
+            .into_iter()
+            .take(limit as usize)
+            .map(|r| Symbol {
+                name: r.symbol,
+                kind: r.kind.to_string(),
+                file: r.file.to_string_lossy().to_string(),
+                line: r.line_start as i32,
+                code_internal: Some(r.code),
+            })
+            .collect())
     }
 
     /// Search all symbols with a regex pattern.
@@ -136,22 +239,30 @@ impl Query {
     /// - `.*Manager` - symbols ending with "Manager"
     /// - `Config.*&.*Manager` - starts with "Config" AND ends with "Manager"
     /// - `[A-Z][a-z]+` - CamelCase words
+    ///
+    /// `asyncOnly: true` keeps only `async` functions and methods.
+    /// `ignoreCase: true` lets `config.*manager` match `ConfigManager`.
     async fn search(
         &self,
         ctx: &Context<'_>,
         pattern: String,
+        #[graphql(default = 0)] offset: i32,
         #[graphql(default = 20)] limit: i32,
+        #[graphql(default = false)] async_only: bool,
+        #[graphql(default = false)] ignore_case: bool,
     ) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
-        let regex = parse(&pattern).map_err(|e| async_graphql::Error::new(e.to_string()))?;
-        let mut matcher = Matcher::new(regex);
+        let graph = read_graph(ctx)?;
+        // Runs against every symbol, so compile it (once per pattern)
+        let mut matcher = cached_matcher(&pattern, ignore_case)?;
 
         // Get all symbols from the graph and filter with regex
         let all_symbols = graph.all_symbols();
         let matched: Vec<_> = all_symbols
             .into_iter()
+            .filter(|r| !async_only || r.is_async)
             .filter(|r| matcher.is_match(&r.symbol))
-            .take(limit as usize)
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
             .map(|r| Symbol {
                 name: r.symbol,
                 kind: r.kind.to_string(),
@@ -164,3 +275,13 @@ impl Query {
         Ok(matched)
     }
 }
+
+/// A compiled matcher for a regex argument, optionally ignoring case.
+fn cached_matcher(pattern: &str, ignore_case: bool) -> Result<Matcher> {
+    matcher_cached(pattern, ignore_case).map_err(|e| async_graphql::Error::new(e.to_string()))
+}
+
+/// A page from GraphQL arguments; negative values count as zero.
+fn page(offset: i32, limit: i32) -> Page {
+    Page::new(offset.max(0) as usize, limit.max(0) as usize)
+}
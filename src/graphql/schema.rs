@@ -3,9 +3,9 @@
 //! These types are returned by queries and define the shape of responses.
 
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
-use std::sync::Arc;
 
-use crate::graph::CodeGraph;
+use super::read_graph;
+use crate::graph::{ApiEndpoint, GraphStats};
 
 /// A code symbol (function, class, struct, etc.)
 #[derive(SimpleObject)]
@@ -31,9 +31,18 @@ impl Symbol {
         self.code_internal.as_deref()
     }
 
+    /// Whether the function is declared `async`
+    async fn is_async(&self, ctx: &Context<'_>) -> Result<bool> {
+        let graph = read_graph(ctx)?;
+        Ok(graph
+            .symbols_in_file(std::path::Path::new(&self.file))
+            .into_iter()
+            .any(|s| s.name == self.name && s.line_start as i32 == self.line && s.is_async))
+    }
+
     /// Symbols that call/use this symbol
     async fn callers(&self, ctx: &Context<'_>) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+        let graph = read_graph(ctx)?;
         let deps = graph.dependents(&self.name);
         Ok(deps
             .into_iter()
@@ -50,7 +59,7 @@ impl Symbol {
 
     /// Symbols this symbol calls/uses
     async fn callees(&self, ctx: &Context<'_>) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+        let graph = read_graph(ctx)?;
         let deps = graph.dependencies(&self.name);
         Ok(deps
             .into_iter()
@@ -80,7 +89,7 @@ pub struct File {
 impl File {
     /// Symbols defined in this file
     async fn symbols(&self, ctx: &Context<'_>) -> Result<Vec<Symbol>> {
-        let graph = ctx.data::<Arc<CodeGraph>>()?;
+        let graph = read_graph(ctx)?;
         let symbols = graph.symbols_in_file(std::path::Path::new(&self.path));
         Ok(symbols
             .into_iter()
@@ -106,6 +115,46 @@ pub struct Stats {
     pub edges: i32,
 }
 
+impl From<GraphStats> for Stats {
+    fn from(s: GraphStats) -> Self {
+        Self {
+            files: s.file_count as i32,
+            symbols: s.symbol_count as i32,
+            edges: s.total_edges as i32,
+        }
+    }
+}
+
+/// An API route definition or client call
+#[derive(SimpleObject)]
+pub struct Endpoint {
+    /// Normalized URL, path params as `:param`
+    pub url: String,
+    /// HTTP method, uppercase, if known
+    pub method: Option<String>,
+    /// `defines` for a route, `consumes` for a call
+    pub kind: String,
+    /// File the endpoint appears in
+    pub file: String,
+    /// Line of the route or call
+    pub line: i32,
+    /// Enclosing handler or function
+    pub symbol: Option<String>,
+}
+
+impl From<&ApiEndpoint> for Endpoint {
+    fn from(ep: &ApiEndpoint) -> Self {
+        Self {
+            url: ep.url.clone(),
+            method: ep.method.clone(),
+            kind: ep.kind.to_string(),
+            file: ep.file.to_string_lossy().to_string(),
+            line: ep.line as i32,
+            symbol: ep.scope.clone(),
+        }
+    }
+}
+
 /// Result of a write operation
 #[derive(SimpleObject)]
 pub struct WriteResult {
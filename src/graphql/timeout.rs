@@ -0,0 +1,216 @@
+//! Query time budget.
+//!
+//! Depth and complexity limits bound the shape of a query, not how long it
+//! takes: `callers`/`callees` five levels deep on a dense graph can still
+//! run for a long time. Every query gets a time budget. `execute` abandons
+//! a query that overruns it, and the [`Deadline`] extension stops resolving
+//! fields once it has passed, so a runaway nested resolution returns what it
+//! has so far plus an error instead of hanging.
+
+use async_graphql::async_trait::async_trait;
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{Response, ServerError, ServerResult, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// tokio's clock, so tests can pause and advance it
+use tokio::time::Instant;
+
+/// Time budget of a query when the schema doesn't set one.
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A schema's time budget per query, stored as schema data.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryTimeout(pub Duration);
+
+impl Default for QueryTimeout {
+    fn default() -> Self {
+        Self(DEFAULT_QUERY_TIMEOUT)
+    }
+}
+
+/// Extension that stops resolving once the query's budget is spent.
+///
+/// After the deadline, list fields (`callers`, `callees`, `symbols`) come
+/// back empty and nullable fields null, so the traversal stops where it is
+/// without nulling the fields already resolved. The response gets one
+/// error naming the skipped fields.
+pub struct Deadline {
+    budget: Duration,
+}
+
+impl Deadline {
+    pub fn new(budget: Duration) -> Self {
+        Self { budget }
+    }
+}
+
+impl ExtensionFactory for Deadline {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(DeadlineExtension {
+            budget: self.budget,
+            deadline: Mutex::new(None),
+            skipped: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+/// Fields named in the deadline error, at most.
+const MAX_SKIPPED_LISTED: usize = 5;
+
+/// Per-query state: the deadline is set when execution starts.
+struct DeadlineExtension {
+    budget: Duration,
+    deadline: Mutex<Option<Instant>>,
+    /// Paths of the fields skipped after the deadline.
+    skipped: Mutex<Vec<String>>,
+}
+
+impl DeadlineExtension {
+    fn expired(&self) -> bool {
+        self.deadline
+            .lock()
+            .ok()
+            .and_then(|deadline| *deadline)
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[async_trait]
+impl Extension for DeadlineExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if let Ok(mut deadline) = self.deadline.lock() {
+            *deadline = Some(Instant::now() + self.budget);
+        }
+        let mut response = next.run(ctx, operation_name).await;
+
+        let skipped = self.skipped.lock().map(|s| s.clone()).unwrap_or_default();
+        if !skipped.is_empty() {
+            let mut listed = skipped[..skipped.len().min(MAX_SKIPPED_LISTED)].join(", ");
+            if skipped.len() > MAX_SKIPPED_LISTED {
+                listed.push_str(&format!(" and {} more", skipped.len() - MAX_SKIPPED_LISTED));
+            }
+            response.errors.push(ServerError::new(
+                format!(
+                    "query exceeded its {}ms budget; skipped {}",
+                    self.budget.as_millis(),
+                    listed
+                ),
+                None,
+            ));
+        }
+        response
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        // Non-null scalars can't be skipped without nulling their parent
+        let skip_as = if info.return_type.starts_with('[') {
+            Some(Value::List(Vec::new()))
+        } else if !info.return_type.ends_with('!') {
+            Some(Value::Null)
+        } else {
+            None
+        };
+
+        if let Some(value) = skip_as.filter(|_| !info.is_for_introspection && self.expired()) {
+            if let Ok(mut skipped) = self.skipped.lock() {
+                skipped.push(info.path_node.to_string());
+            }
+            return Ok(Some(value));
+        }
+        next.run(ctx, info).await
+    }
+}
+
+/// Run `execute` within `budget`, answering with a timeout error if it
+/// doesn't finish in time.
+pub(crate) async fn within_budget(
+    budget: Duration,
+    execute: impl std::future::Future<Output = Response>,
+) -> Response {
+    match tokio::time::timeout(budget, execute).await {
+        Ok(response) => response,
+        Err(_) => Response::from_errors(vec![ServerError::new(
+            format!("query timed out after {}ms", budget.as_millis()),
+            None,
+        )]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct SlowQuery;
+
+    // The tests run on paused time: sleeps and `advance` move the clock
+    // without waiting, so the outcome doesn't depend on machine speed.
+    #[Object]
+    impl SlowQuery {
+        /// Never finishes within a test's budget.
+        async fn stalled(&self) -> i32 {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            1
+        }
+
+        async fn items(&self) -> Vec<Item> {
+            (0..3).map(Item).collect()
+        }
+    }
+
+    struct Item(i32);
+
+    #[Object]
+    impl Item {
+        /// Takes 60ms of the budget, like a resolver walking a large graph.
+        async fn value(&self) -> Option<i32> {
+            tokio::time::advance(Duration::from_millis(60)).await;
+            Some(self.0)
+        }
+    }
+
+    fn schema(budget: Duration) -> Schema<SlowQuery, EmptyMutation, EmptySubscription> {
+        Schema::build(SlowQuery, EmptyMutation, EmptySubscription)
+            .extension(Deadline::new(budget))
+            .finish()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_query_times_out() {
+        let budget = Duration::from_millis(50);
+        let schema = schema(budget);
+
+        let response = within_budget(budget, schema.execute("{ stalled }")).await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("timed out"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deadline_returns_partial_result() {
+        let budget = Duration::from_millis(100);
+        let schema = schema(budget);
+
+        let response = within_budget(budget * 10, schema.execute("{ items { value } }")).await;
+        let json = serde_json::to_value(&response).unwrap();
+
+        // Items resolve at 0ms and 60ms; by the third (120ms) the budget is spent
+        assert_eq!(json["data"]["items"][0]["value"], 0);
+        assert_eq!(json["data"]["items"][1]["value"], 1);
+        assert!(json["data"]["items"][2]["value"].is_null());
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("items.2.value"), "{:?}", response.errors);
+    }
+}
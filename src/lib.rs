@@ -28,28 +28,30 @@
 
 pub mod cli;
 pub mod config;
-// pub mod daemon;  // TODO: Daemon uses write/lock, not finalized yet
+pub mod daemon;
 pub mod error;
 pub mod graph;
 pub mod graphql;
-// pub mod lock;  // TODO: Write operations not finalized yet
+pub mod lock;
 pub mod parser;
 pub mod query;
 pub mod regex;
 pub mod storage;
 pub mod updater;
 pub mod watcher;
-// pub mod write;  // TODO: Write operations not finalized yet
+pub mod write;
 
 // Re-exports for convenience
 pub use error::{AnchorError, Result};
 
 // Graph re-exports
-pub use graph::{build_graph, CodeGraph, EdgeKind, GraphStats, NodeKind, SearchResult};
+pub use graph::{
+    build_graph, CodeGraph, CodeGraphBuilder, EdgeKind, GraphHealth, GraphStats, NodeKind, SearchResult,
+};
 pub use parser::SupportedLanguage;
 pub use query::{
-    anchor_dependencies, anchor_file_symbols, anchor_search, anchor_stats, get_context,
-    get_context_for_change, graph_search, ContextResponse, Edit, Query, Reference, SearchResponse,
+    anchor_dependencies, anchor_file_symbols, anchor_search, anchor_search_batch, anchor_stats, get_context,
+    get_context_for_change, get_file_contexts, graph_search, graph_search_regex, graph_search_with_fanout, ContextResponse, Edit, Query, Reference, SearchResponse,
     Signature, StatsResponse, Symbol,
 };
 
@@ -58,8 +60,10 @@ pub use query::{
 //     create_file, insert_after, insert_before, replace_all, replace_first, WriteError, WriteResult,
 // };
 
-// GraphQL (query only - mutations not finalized)
-pub use graphql::{build_schema, execute, AnchorSchema};
+// GraphQL (queries and index refresh - write mutations not finalized)
+pub use graphql::{
+    build_live_schema, build_schema, build_schema_with_timeout, execute, AnchorSchema, SharedGraph,
+};
 
 // Regex engine (Brzozowski derivatives - ReDoS-safe)
 pub use regex::{parse as parse_regex, Matcher as RegexMatcher, Regex};
@@ -132,6 +136,86 @@ fn main() {
         assert!(!results.is_empty());
     }
 
+    #[test]
+    fn test_rust_macro_definitions() {
+        use crate::graph::Visibility;
+        use std::path::PathBuf;
+        let source = r#"
+#[macro_export]
+macro_rules! my_macro {
+    ($x:expr) => {
+        $x + 1
+    };
+}
+
+macro_rules! local_helper {
+    () => {};
+}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/macros.rs"), source).unwrap();
+        let macros: Vec<(&str, Visibility)> = extraction
+            .symbols
+            .iter()
+            .filter(|s| s.kind == NodeKind::Macro)
+            .map(|s| (s.name.as_str(), s.visibility))
+            .collect();
+        assert_eq!(
+            macros,
+            vec![("my_macro", Visibility::Public), ("local_helper", Visibility::Private)]
+        );
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        let results = graph.search("my_macro", 3);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].kind, NodeKind::Macro);
+        assert!(results[0].code.starts_with("macro_rules! my_macro"));
+    }
+
+    #[test]
+    fn test_rust_web_endpoints() {
+        use std::path::PathBuf;
+        let source = r#"
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/users/:id", get(handlers::show).delete(remove))
+        .route("/api/users", routing::post(|| async { "ok" }))
+}
+
+/// Lists items.
+#[get("/api/items/{id}")]
+#[tracing::instrument]
+async fn item() -> impl Responder { "" }
+
+#[route("/api/items", method = "PUT")]
+async fn replace_items() -> impl Responder { "" }
+
+fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/api/orders").route(web::get().to(list_orders)).to(fallback));
+    App::new().route("/static/app.js", web::get().to(assets));
+}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/server.rs"), source).unwrap();
+        let mut endpoints: Vec<(Option<&str>, &str, Option<&str>)> = extraction
+            .endpoints
+            .iter()
+            .map(|e| (e.method.as_deref(), e.url.as_str(), e.scope.as_deref()))
+            .collect();
+        endpoints.sort();
+        assert_eq!(
+            endpoints,
+            vec![
+                (None, "/api/orders", Some("fallback")),
+                (Some("DELETE"), "/api/users/:param", Some("remove")),
+                (Some("GET"), "/api/items/:param", Some("item")),
+                (Some("GET"), "/api/orders", Some("list_orders")),
+                (Some("GET"), "/api/users/:param", Some("show")),
+                (Some("POST"), "/api/users", Some("router")),
+                (Some("PUT"), "/api/items", Some("replace_items")),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_python_code() {
         let source = r#"
@@ -265,6 +349,13 @@ fn check_password(pw: &str) -> bool {
                 symbol: "validate".to_string(),
                 kind: Some("function".to_string()),
                 file: None,
+                include_tests: None,
+                include_private: None,
+                include_generated: None,
+                async_only: None,
+                dedup: None,
+                fuzzy: None,
+                ignore_case: None,
             },
         );
         assert!(response.found);
@@ -320,6 +411,998 @@ fn check_password(pw: &str) -> bool {
         ));
     }
 
+    #[test]
+    fn test_constant_values() {
+        use std::path::PathBuf;
+        let value_of = |file: &str, source: &str, name: &str| {
+            let extraction = parser::extract_file(&PathBuf::from(file), source).unwrap();
+            let symbol = extraction.symbols.into_iter().find(|s| s.name == name);
+            symbol.map(|s| (s.kind, s.value))
+        };
+
+        let rust = "const X: u64 = 200;\nstatic GREETING: &str = \"hi\";\nfn f() {}\n";
+        assert_eq!(value_of("lib.rs", rust, "X"), Some((NodeKind::Constant, Some("200".to_string()))));
+        assert_eq!(value_of("lib.rs", rust, "GREETING").unwrap().1.as_deref(), Some("\"hi\""));
+        assert_eq!(value_of("lib.rs", rust, "f").unwrap().1, None);
+
+        let js = "const FOO = \"bar\", LIMIT = 10;\n";
+        assert_eq!(value_of("app.js", js, "FOO").unwrap().1.as_deref(), Some("\"bar\""));
+        assert_eq!(value_of("app.js", js, "LIMIT").unwrap().1.as_deref(), Some("10"));
+
+        let py = "MAX_RETRIES = 3\nlowercase = 1\n\ndef f():\n    LOCAL = 2\n";
+        assert_eq!(
+            value_of("conf.py", py, "MAX_RETRIES"),
+            Some((NodeKind::Constant, Some("3".to_string())))
+        );
+        assert!(value_of("conf.py", py, "lowercase").is_none());
+        assert!(value_of("conf.py", py, "LOCAL").is_none());
+    }
+
+    #[test]
+    fn test_deprecated_symbols() {
+        use std::path::PathBuf;
+        let deprecated = |file: &str, source: &str| {
+            let extraction = parser::extract_file(&PathBuf::from(file), source).unwrap();
+            let mut names: Vec<String> = extraction
+                .symbols
+                .into_iter()
+                .filter(|s| s.deprecated)
+                .map(|s| s.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        let rust = "/// Old entry point.\n#[deprecated(since = \"0.2\")]\npub fn old() {}\n\n#[inline]\npub fn new() {}\n";
+        assert_eq!(deprecated("lib.rs", rust), vec!["old"]);
+
+        let py = "import warnings\n\n@deprecated(\"use new\")\ndef old():\n    pass\n\n@cache\ndef new():\n    pass\n";
+        assert_eq!(deprecated("api.py", py), vec!["old"]);
+
+        let js = "/**\n * @deprecated use newer()\n */\nexport function older() {}\n\n/** Current. */\nfunction newer() {}\n";
+        assert_eq!(deprecated("api.js", js), vec!["older"]);
+
+        let go = "package api\n\n// Old does it.\n//\n// Deprecated: use New.\nfunc Old() {}\n\nfunc New() {}\n";
+        assert_eq!(deprecated("api.go", go), vec!["Old"]);
+
+        let java = "class Api {\n    @Deprecated\n    public void old() {}\n\n    public void current() {}\n}\n";
+        assert_eq!(deprecated("Api.java", java), vec!["old"]);
+
+        let kotlin = "class Api {\n    @Deprecated(\"use current\")\n    fun old() {}\n\n    fun current() {}\n}\n";
+        assert_eq!(deprecated("Api.kt", kotlin), vec!["old"]);
+    }
+
+    #[test]
+    fn test_async_symbols() {
+        use std::path::PathBuf;
+        let async_names = |file: &str, source: &str| {
+            let extraction = parser::extract_file(&PathBuf::from(file), source).unwrap();
+            let mut names: Vec<String> = extraction
+                .symbols
+                .into_iter()
+                .filter(|s| s.is_async)
+                .map(|s| s.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        let rust = "pub async fn fetch() {}\n\npub fn parse() {}\n\nimpl Client {\n    pub async fn send(&self) {}\n    fn close(&self) {}\n}\n";
+        assert_eq!(async_names("lib.rs", rust), vec!["fetch", "send"]);
+
+        let py = "async def fetch():\n    pass\n\ndef parse():\n    pass\n\nclass Client:\n    @retry\n    async def send(self):\n        pass\n";
+        assert_eq!(async_names("api.py", py), vec!["fetch", "send"]);
+
+        let js = "export async function fetchUser() {}\n\nfunction parse() {}\n\nconst load = async () => {};\nconst sync = () => {};\n\nclass Client {\n    async send() {}\n    close() {}\n}\n";
+        assert_eq!(async_names("api.js", js), vec!["fetchUser", "load", "send"]);
+
+        let ts = "export async function fetchUser(id: string): Promise<User> {}\n\nfunction parse(s: string): number { return 1; }\n";
+        assert_eq!(async_names("api.ts", ts), vec!["fetchUser"]);
+
+        let extraction = parser::extract_file(&PathBuf::from("lib.rs"), rust).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        let filter = graph::SymbolFilter {
+            async_only: true,
+            ..graph::SymbolFilter::all()
+        };
+        assert_eq!(graph.search_filtered("fetch", 10, &filter).len(), 1);
+        assert!(graph.search_filtered("parse", 10, &filter).is_empty());
+        assert!(graph.search("fetch", 1)[0].is_async);
+    }
+
+    #[test]
+    fn test_jsx_components_and_renders() {
+        use crate::graph::EdgeKind;
+        use std::path::PathBuf;
+
+        let source = r#"
+function Header({ title }) {
+    return <h1>{title}</h1>;
+}
+
+export const App = () => {
+    return (
+        <div className="app">
+            <Header title="Home" />
+        </div>
+    );
+};
+
+function formatTitle(title) {
+    return title.toUpperCase();
+}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/App.tsx"), source).unwrap();
+        let kind_of = |name: &str| extraction.symbols.iter().find(|s| s.name == name).unwrap().kind;
+        assert_eq!(kind_of("App"), NodeKind::Component);
+        assert_eq!(kind_of("Header"), NodeKind::Component);
+        assert_eq!(kind_of("formatTitle"), NodeKind::Function);
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let deps = anchor_dependencies(&graph, "App");
+        assert!(deps
+            .dependencies
+            .iter()
+            .any(|d| d.symbol == "Header" && d.relationship == EdgeKind::Renders));
+        // <div> is an HTML element, not a component
+        assert_eq!(deps.dependencies.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_files_batch() {
+        use std::fs;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let paths = vec![
+            dir.path().join("lib.rs"),
+            dir.path().join("script.lua"),
+            dir.path().join("tool.py"),
+        ];
+        fs::write(&paths[0], "fn alpha() {}\n").unwrap();
+        fs::write(&paths[1], "print('hi')\n").unwrap();
+        fs::write(&paths[2], "def beta():\n    pass\n").unwrap();
+
+        let results = parser::extract_files(&paths);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+        assert_eq!(results[0].as_ref().unwrap().symbols[0].name, "alpha");
+        assert!(matches!(
+            results[1],
+            Err(AnchorError::UnsupportedLanguage(ref p)) if *p == paths[1]
+        ));
+        assert_eq!(results[2].as_ref().unwrap().symbols[0].name, "beta");
+    }
+
+    #[test]
+    fn test_symbol_at_prefers_innermost() {
+        use std::path::PathBuf;
+        let source = r#"
+pub struct Cache {
+    size: usize,
+}
+
+impl Cache {
+    pub fn get(&self, key: &str) -> usize {
+        let hit = key.len();
+        hit + self.size
+    }
+}
+"#;
+        let path = PathBuf::from("src/cache.rs");
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        // Line 8 is inside `get`, which is inside `impl Cache`
+        let symbol = graph.symbol_at(&path, 8).unwrap();
+        assert_eq!(symbol.symbol, "get");
+        assert_eq!(symbol.kind, NodeKind::Method);
+
+        // The impl line itself resolves to the impl
+        assert_eq!(graph.symbol_at(&path, 6).unwrap().kind, NodeKind::Impl);
+
+        // Blank line between definitions, unknown file
+        assert!(graph.symbol_at(&path, 5).is_none());
+        assert!(graph.symbol_at(&PathBuf::from("src/other.rs"), 8).is_none());
+    }
+
+    #[test]
+    fn test_truncated_snippet_flag_and_marker() {
+        use crate::parser::{extract_file_with, ExtractOptions, DEFAULT_TRUNCATION_MARKER};
+        use std::path::PathBuf;
+
+        let body: String = (0..80).map(|i| format!("    let x{} = {};\n", i, i)).collect();
+        let source = format!("fn long() {{\n{}}}\n\nfn short() {{}}\n", body);
+        let path = PathBuf::from("long.rs");
+
+        let extraction = parser::extract_file(&path, &source).unwrap();
+        let long = extraction.symbols.iter().find(|s| s.name == "long").unwrap();
+        let short = extraction.symbols.iter().find(|s| s.name == "short").unwrap();
+        assert!(long.truncated);
+        assert!(long.code_snippet.ends_with(DEFAULT_TRUNCATION_MARKER));
+        assert!(!short.truncated);
+
+        // Without a marker the snippet is a clean prefix of the source
+        let options = ExtractOptions {
+            truncation_marker: None,
+            ..ExtractOptions::default()
+        };
+        let extraction = extract_file_with(&path, &source, &options).unwrap();
+        let long = extraction.symbols.iter().find(|s| s.name == "long").unwrap();
+        assert!(long.truncated);
+        assert!(!long.code_snippet.contains("//"));
+        assert!(source.starts_with(&long.code_snippet));
+
+        // The flag reaches search results
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        assert!(graph.search("long", 1)[0].truncated);
+    }
+
+    #[test]
+    fn test_truncated_snippet_keeps_signature() {
+        use std::path::PathBuf;
+
+        let cases: String = (0..60).map(|i| format!("    ({}, {}),\n", i, i * 2)).collect();
+        let source = format!(
+            "@pytest.mark.parametrize(\"n, doubled\", [\n{}])\ndef test_double(n, doubled):\n    assert double(n) == doubled\n",
+            cases
+        );
+        let extraction = parser::extract_file(&PathBuf::from("tests/test_math.py"), &source).unwrap();
+        let test = extraction.symbols.iter().find(|s| s.name == "test_double").unwrap();
+        assert!(test.truncated);
+
+        let lines: Vec<&str> = test.code_snippet.lines().collect();
+        assert_eq!(lines[0], "@pytest.mark.parametrize(\"n, doubled\", [");
+        assert_eq!(lines[3], "# ...");
+        assert_eq!(lines[4], "def test_double(n, doubled):");
+        assert_eq!(lines[5], "    assert double(n) == doubled");
+
+        // With markers off the cut stays clean: no elision comment
+        let options = parser::ExtractOptions { truncation_marker: None, ..Default::default() };
+        let extraction = parser::extract_file_with(&PathBuf::from("tests/test_math.py"), &source, &options).unwrap();
+        let test = extraction.symbols.iter().find(|s| s.name == "test_double").unwrap();
+        let lines: Vec<&str> = test.code_snippet.lines().collect();
+        assert_eq!(lines[3], "def test_double(n, doubled):");
+        assert!(!test.code_snippet.contains("# ..."));
+
+        // A long body still keeps its head
+        let body: String = (0..80).map(|i| format!("    x{} = {}\n", i, i)).collect();
+        let source = format!("def long():\n{}", body);
+        let extraction = parser::extract_file(&PathBuf::from("long.py"), &source).unwrap();
+        let long = &extraction.symbols[0];
+        assert!(long.code_snippet.starts_with("def long():\n    x0 = 0\n"));
+        assert!(!long.code_snippet.contains("# ...\n"));
+    }
+
+    #[test]
+    fn test_truncation_marker_uses_language_comment() {
+        use std::path::PathBuf;
+
+        let body: String = (0..80).map(|i| format!("    x{} = {}\n", i, i)).collect();
+        let python = format!("def long():\n{}", body);
+        let ruby = format!("def long\n{}end\n", body);
+        let js = format!("function long() {{\n{}}}\n", body);
+        for (path, source, marker) in [
+            ("long.py", &python, "# ... (truncated)"),
+            ("long.rb", &ruby, "# ... (truncated)"),
+            ("long.js", &js, "// ... (truncated)"),
+        ] {
+            let extraction = parser::extract_file(&PathBuf::from(path), source).unwrap();
+            let long = extraction.symbols.iter().find(|s| s.name == "long").unwrap();
+            assert!(long.code_snippet.ends_with(marker), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_neighbors_query_json() {
+        use crate::graph::TraversalDirection;
+        use crate::query::{anchor_neighbors, NeighborsQuery};
+
+        let source = "fn login() { validate(); }\nfn validate() {}\n";
+        let extraction = parser::extract_file(&std::path::PathBuf::from("auth.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let query: NeighborsQuery =
+            serde_json::from_str(r#"{"symbol": "login", "edge_kinds": ["calls"]}"#).unwrap();
+        assert_eq!(query.direction, TraversalDirection::Outbound);
+
+        let response = anchor_neighbors(&graph, &query);
+        let names: Vec<&str> = response.neighbors.iter().map(|n| n.symbol.as_str()).collect();
+        assert_eq!(names, vec!["validate"]);
+    }
+
+    #[test]
+    fn test_search_dedups_alias_and_origin() {
+        use std::path::PathBuf;
+
+        // `export const authenticateUser = function authenticate(user) {...}`
+        let mut graph = CodeGraph::new();
+        let file = PathBuf::from("auth.js");
+        let code = "export const authenticateUser = function authenticate(user) {\n  return user;\n};";
+        graph.add_file(file.clone());
+        for (name, kind) in [("authenticate", NodeKind::Function), ("authenticateUser", NodeKind::Variable)] {
+            graph.add_symbol(name.to_string(), kind, file.clone(), 1, 3, code.to_string());
+        }
+
+        let response = anchor_search(&graph, Query::Simple("authent".to_string()));
+        assert_eq!(response.count, 1);
+        let mut names = vec![response.results[0].symbol.clone()];
+        names.extend(response.results[0].aliases.clone());
+        names.sort();
+        assert_eq!(names, vec!["authenticate", "authenticateUser"]);
+
+        let query: Query =
+            serde_json::from_str(r#"{"symbol": "authent", "kind": null, "file": null, "dedup": false}"#)
+                .unwrap();
+        assert_eq!(anchor_search(&graph, query).count, 2);
+    }
+
+    #[test]
+    fn test_search_keeps_distinct_symbols_on_one_line() {
+        use crate::query::dedup_results;
+        use std::path::PathBuf;
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("Point.cs"), "public record struct Point(int X, int Y);\n").unwrap(),
+            parser::extract_file(&PathBuf::from("min.js"), "const f = () => 1, g = () => 2;\n").unwrap(),
+        ]);
+
+        for names in [["X", "Y"], ["f", "g"]] {
+            let results: Vec<_> = names.iter().flat_map(|name| graph.search(name, 1)).collect();
+            let merged = dedup_results(results);
+            let kept: Vec<&str> = merged.iter().map(|r| r.symbol.as_str()).collect();
+            assert_eq!(kept, names, "{:?}", merged.iter().map(|r| &r.aliases).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_dynamic_call_is_low_confidence() {
+        use crate::graph::TraversalDirection;
+        use crate::query::{anchor_neighbors, NeighborsQuery};
+
+        let source = "class Repo:\n    def save(self):\n        pass\n\ndef persist(obj):\n    getattr(obj, 'save')()\n    getattr(obj, 'missing')()\n";
+        let extraction = parser::extract_file(&std::path::PathBuf::from("repo.py"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let dynamic = graph.neighbors(
+            "persist",
+            TraversalDirection::Outbound,
+            &[],
+            &[EdgeKind::DynamicCall],
+        );
+        let names: Vec<&str> = dynamic.iter().map(|d| d.symbol.as_str()).collect();
+        assert_eq!(names, vec!["save"]);
+        assert!(EdgeKind::DynamicCall.confidence() < EdgeKind::Calls.confidence());
+
+        let query: NeighborsQuery =
+            serde_json::from_str(r#"{"symbol": "persist", "min_confidence": 0.5}"#).unwrap();
+        assert!(anchor_neighbors(&graph, &query)
+            .neighbors
+            .iter()
+            .all(|n| n.relationship != EdgeKind::DynamicCall));
+    }
+
+    #[test]
+    fn test_qualified_names_disambiguate() {
+        use std::path::PathBuf;
+        let config = "pub struct Config;\n\nimpl Config {\n    pub fn new() -> Self {\n        Config\n    }\n}\n";
+        let builder = "pub struct Builder;\n\nimpl Builder {\n    pub fn new() -> Self {\n        Builder\n    }\n}\n\npub fn new() {}\n";
+        let models = "class User:\n    def new(self):\n        pass\n";
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/config.rs"), config).unwrap(),
+            parser::extract_file(&PathBuf::from("src/builder/mod.rs"), builder).unwrap(),
+            parser::extract_file(&PathBuf::from("app/models.py"), models).unwrap(),
+        ]);
+
+        let mut names: Vec<String> = graph.search("new", 10).into_iter().map(|r| r.qualified_name).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["builder::Builder::new", "builder::new", "config::Config::new", "models::User.new"]
+        );
+
+        let exact = |query: &str| -> Vec<String> {
+            anchor_search(&graph, Query::Simple(query.to_string()))
+                .results
+                .into_iter()
+                .map(|r| r.qualified_name)
+                .collect()
+        };
+        assert_eq!(exact("Config::new"), vec!["config::Config::new"]);
+        assert_eq!(exact("builder::new"), vec!["builder::new"]);
+        assert_eq!(exact("User.new"), vec!["models::User.new"]);
+        assert!(exact("Missing::new").is_empty());
+    }
+
+    #[test]
+    fn test_search_batch_keeps_order() {
+        use std::path::PathBuf;
+        let source = "pub fn login(user: &str) -> bool {\n    validate(user)\n}\n\nfn validate(input: &str) -> bool {\n    !input.is_empty()\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("auth.rs"), source).unwrap(),
+        ]);
+        let responses = anchor_search_batch(
+            &graph,
+            vec![
+                Query::Simple("validate".to_string()),
+                Query::Simple("missing_symbol".to_string()),
+                Query::Simple("login".to_string()),
+            ],
+        );
+
+        let queries: Vec<&str> = responses.iter().map(|r| r.query.as_str()).collect();
+        assert_eq!(queries, vec!["validate", "missing_symbol", "login"]);
+        assert!(responses[0].found && responses[2].found);
+        assert!(!responses[1].found);
+        assert_eq!(responses[2].results[0].symbol, "login");
+    }
+
+    #[test]
+    fn test_paged_search_and_dependencies() {
+        use std::path::PathBuf;
+        let source = "pub fn log() {}\n\npub fn load_a() {\n    log();\n}\n\npub fn load_b() {\n    log();\n}\n\npub fn load_c() {\n    log();\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/load.rs"), source).unwrap(),
+        ]);
+
+        let first = query::anchor_search_paged(&graph, Query::Simple("load_".to_string()), query::Page::new(0, 2));
+        assert_eq!((first.count, first.total), (2, 3));
+        let rest = query::anchor_search_paged(&graph, Query::Simple("load_".to_string()), query::Page::new(2, 2));
+        assert_eq!((rest.count, rest.total), (1, 3));
+        assert!(first.results.iter().all(|r| r.symbol != rest.results[0].symbol));
+
+        let deps = query::anchor_dependencies_paged(
+            &graph,
+            "log",
+            graph::DependencyScope::Workspace,
+            query::Page::new(1, 1),
+        );
+        let all = anchor_dependencies(&graph, "log");
+        assert_eq!(deps.dependents.len(), 1);
+        assert_eq!(deps.dependents[0].symbol, all.dependents[1].symbol);
+        assert_eq!(deps.total_dependents, all.dependents.len());
+        assert!(all.dependents.len() >= 3);
+
+        let result = query::graph_search_paged(&graph, "log", 1, None, query::Page::new(0, 1));
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.total, 4);
+    }
+
+    #[test]
+    fn test_graph_search_regex() {
+        use std::path::PathBuf;
+        let source = "pub fn handler_1() {\n    respond();\n}\n\npub fn handler_22() {}\n\npub fn handler_x() {}\n\nfn respond() {}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/handlers.rs"), source).unwrap(),
+        ]);
+
+        let result = graph_search_regex(&graph, "handler_\\d+", 0, None).unwrap();
+        assert_eq!(result.match_type, "symbol");
+        let mut names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["handler_1", "handler_22"]);
+
+        // Traverses from the matches like a name search
+        let result = graph_search_regex(&graph, "handler_1", 1, None).unwrap();
+        assert!(result.symbols.iter().any(|s| s.name == "respond"));
+
+        assert_eq!(graph_search_regex(&graph, "nothing.*", 1, None).unwrap().match_type, "none");
+        assert!(graph_search_regex(&graph, "(unclosed", 1, None).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_by_similarity() {
+        use std::path::PathBuf;
+        let source = "pub struct UserService;\n\npub struct UserServ;\n\npub struct UserServer;\n\npub fn user_stats() {}\n\npub fn render() {}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/users.rs"), source).unwrap(),
+        ]);
+
+        // Exact, then prefix matches, then near misses
+        let results = graph.fuzzy_search("UserServ", 10);
+        let names: Vec<&str> = results.iter().map(|r| r.symbol.as_str()).collect();
+        assert_eq!(names[0], "UserServ");
+        assert_eq!(results[0].similarity, Some(1.0));
+        assert_eq!(names[1..3], ["UserServer", "UserService"]);
+        assert!(!names.contains(&"render"));
+
+        // A misspelling only fuzzy search finds
+        assert!(graph.search("UserSevrice", 5).is_empty());
+        let results = graph.fuzzy_search("UserSevrice", 5);
+        assert_eq!(results[0].symbol, "UserService");
+        assert!(results[0].similarity.unwrap() > 0.9);
+
+        let response = anchor_search(
+            &graph,
+            Query::Structured {
+                symbol: "usersevice".to_string(),
+                kind: None,
+                file: None,
+                include_tests: None,
+                include_private: None,
+                include_generated: None,
+                async_only: None,
+                dedup: None,
+                fuzzy: Some(true),
+                ignore_case: None,
+            },
+        );
+        assert_eq!(response.results[0].symbol, "UserService");
+    }
+
+    #[test]
+    fn test_search_ignore_case() {
+        use std::path::PathBuf;
+        let source = "pub struct CodeGraph;\n\nimpl CodeGraph {\n    pub fn new() -> Self {\n        CodeGraph\n    }\n}\n\npub fn codegraph() {}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/graph.rs"), source).unwrap(),
+        ]);
+
+        // An exact hit hides the struct from a case-sensitive search
+        let names = |results: Vec<SearchResult>| -> Vec<String> {
+            let mut names: Vec<String> = results.into_iter().map(|r| r.symbol).collect();
+            names.sort();
+            names.dedup();
+            names
+        };
+        assert_eq!(names(graph.search("codegraph", 5)), vec!["codegraph"]);
+        assert_eq!(
+            names(graph.search_ignore_case("codegraph", 5, &graph::SymbolFilter::all())),
+            vec!["CodeGraph", "codegraph"]
+        );
+        assert_eq!(
+            graph.search_ignore_case("codegraph::NEW", 5, &graph::SymbolFilter::all())[0].qualified_name,
+            "graph::CodeGraph::new"
+        );
+
+        let response = anchor_search(
+            &graph,
+            Query::Structured {
+                symbol: "CODEGRAPH".to_string(),
+                kind: Some("struct".to_string()),
+                file: None,
+                include_tests: None,
+                include_private: None,
+                include_generated: None,
+                async_only: None,
+                dedup: None,
+                fuzzy: None,
+                ignore_case: Some(true),
+            },
+        );
+        assert_eq!(response.results[0].symbol, "CodeGraph");
+    }
+
+    #[test]
+    fn test_repeated_calls_counted_on_one_edge() {
+        use std::path::PathBuf;
+        let source = "fn validate(x: u32) -> bool {\n    x > 0\n}\n\nfn log() {}\n\nfn process() {\n    validate(1);\n    validate(2);\n    log();\n    validate(3);\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/process.rs"), source).unwrap(),
+        ]);
+
+        let mut calls: Vec<(String, u32)> = anchor_dependencies(&graph, "process")
+            .dependencies
+            .into_iter()
+            .filter(|d| d.relationship == EdgeKind::Calls)
+            .map(|d| (d.symbol, d.count))
+            .collect();
+        calls.sort();
+        assert_eq!(calls, vec![("log".to_string(), 1), ("validate".to_string(), 3)]);
+        assert_eq!(graph.search("validate", 1)[0].called_by.len(), 1);
+    }
+
+    #[test]
+    fn test_builtin_calls_dont_create_dependencies() {
+        use std::path::PathBuf;
+        // Project symbols that share names with builtins
+        let source = r#"
+struct Stack;
+
+impl Stack {
+    pub fn len(&self) -> usize { 0 }
+}
+
+fn println() {}
+
+fn report(items: &[u8]) {
+    println!("{}", items.len());
+    summarize(items);
+}
+
+fn summarize(_items: &[u8]) {}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/report.rs"), source).unwrap();
+        assert!(extraction.calls.iter().all(|c| c.callee != "len"));
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let deps = anchor_dependencies(&graph, "report");
+        let names: Vec<&str> = deps.dependencies.iter().map(|d| d.symbol.as_str()).collect();
+        assert_eq!(names, vec!["summarize"]);
+    }
+
+    #[test]
+    fn test_project_methods_named_like_builtins_keep_edges() {
+        use std::path::PathBuf;
+        let js = r#"
+function parse(text) { return text; }
+function log(line) { return line; }
+
+function load(raw) {
+    console.log("loading");
+    log(parse(JSON.parse(raw)));
+}
+"#;
+        let rust = "struct Out;\n\nfn write(out: &mut Out) {}\n\nfn flush(out: &mut Out) {\n    write(out);\n    println!(\"done\");\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/load.js"), js).unwrap(),
+            parser::extract_file(&PathBuf::from("src/out.rs"), rust).unwrap(),
+        ]);
+
+        let calls = |symbol: &str| -> Vec<(String, u32)> {
+            let mut deps: Vec<(String, u32)> = anchor_dependencies(&graph, symbol)
+                .dependencies
+                .iter()
+                .map(|d| (d.symbol.clone(), d.count))
+                .collect();
+            deps.sort();
+            deps
+        };
+        // The project's own log and parse, once each: console.log and
+        // JSON.parse aren't theirs
+        assert_eq!(calls("load"), vec![("log".to_string(), 1), ("parse".to_string(), 1)]);
+        assert_eq!(calls("flush"), vec![("write".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_qualified_calls_resolve_by_receiver() {
+        use std::path::PathBuf;
+        let cache = "pub struct Cache;\n\nimpl Cache {\n    pub fn new() -> Self { Cache }\n    pub fn reset(&self) {}\n}\n\npub fn validate() {}\n";
+        let config = "pub fn validate() {}\n";
+        let main = r#"
+struct Store;
+
+impl Store {
+    fn new() -> Self { Store }
+    fn reset(&self) {}
+    fn clear(&self) {
+        self.reset();
+    }
+}
+
+fn main() {
+    config::validate();
+    let cache = Cache::new();
+    Store::new().clear();
+}
+"#;
+        let extractions: Vec<_> = [("src/cache.rs", cache), ("src/config.rs", config), ("src/main.rs", main)]
+            .into_iter()
+            .map(|(path, source)| parser::extract_file(&PathBuf::from(path), source).unwrap())
+            .collect();
+        let calls = &extractions[2].calls;
+        let receiver = |callee: &str| {
+            calls
+                .iter()
+                .find(|c| c.callee == callee && c.caller == "main")
+                .map(|c| c.receiver.clone())
+        };
+        assert_eq!(receiver("validate"), Some(Some("config".to_string())));
+        // A chained call keeps only its last method
+        assert_eq!(receiver("clear"), Some(None));
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(extractions);
+
+        let deps = anchor_dependencies(&graph, "main");
+        let targets: Vec<(&str, &str)> = deps
+            .dependencies
+            .iter()
+            .map(|d| (d.symbol.as_str(), d.file.to_str().unwrap()))
+            .collect();
+        // Name-only matching would have picked cache.rs's validate
+        assert!(targets.contains(&("validate", "src/config.rs")), "{:?}", targets);
+        assert!(targets.contains(&("new", "src/cache.rs")), "{:?}", targets);
+        assert!(targets.contains(&("new", "src/main.rs")), "{:?}", targets);
+
+        // `self` resolves to the caller's own impl
+        let deps = anchor_dependencies(&graph, "clear");
+        let files: Vec<&str> = deps.dependencies.iter().map(|d| d.file.to_str().unwrap()).collect();
+        assert_eq!(files, vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_trait_impls_link_to_trait() {
+        use std::path::PathBuf;
+        let shapes = "pub trait Shape {\n    fn area(&self) -> f64;\n}\n\npub struct Circle;\n\nimpl Shape for Circle {\n    fn area(&self) -> f64 { 3.14 }\n}\n\nimpl std::fmt::Display for Circle {\n    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }\n}\n";
+        let square = "pub struct Square<T>(T);\n\nimpl<T> crate::geometry::Shape for Square<T> {\n    fn area(&self) -> f64 { 1.0 }\n}\n";
+        let extraction = parser::extract_file(&PathBuf::from("src/geometry.rs"), shapes).unwrap();
+        let supertypes: Vec<(&str, &str)> = extraction
+            .supertypes
+            .iter()
+            .map(|s| (s.name.as_str(), s.supertype.as_str()))
+            .collect();
+        assert_eq!(supertypes, vec![("Circle", "Shape"), ("Circle", "Display")]);
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            extraction,
+            parser::extract_file(&PathBuf::from("src/square.rs"), square).unwrap(),
+        ]);
+
+        let deps = anchor_dependencies(&graph, "Shape");
+        let mut implementors: Vec<(&str, NodeKind)> = deps
+            .dependents
+            .iter()
+            .filter(|d| d.relationship == EdgeKind::Implements)
+            .map(|d| (d.symbol.as_str(), d.kind))
+            .collect();
+        implementors.sort_by_key(|&(name, _)| name);
+        assert_eq!(implementors, vec![("Circle", NodeKind::Impl), ("Square<T>", NodeKind::Impl)]);
+
+        let result = graph_search(&graph, "Shape", 1);
+        let connection = result
+            .connections
+            .iter()
+            .find(|c| c.from == "Circle" && c.to == "Shape")
+            .expect("graph search shows the impl");
+        assert_eq!(
+            serde_json::to_value(connection.relationship).unwrap(),
+            serde_json::json!("implements")
+        );
+    }
+
+    #[test]
+    fn test_python_class_bases_link_with_extends() {
+        use std::path::PathBuf;
+        let base = "class BaseService:\n    def start(self):\n        pass\n\nclass Auditable(object):\n    pass\n";
+        let services = "from base import BaseService\nimport mixins\n\n@dataclass\nclass UserService(BaseService, mixins.Auditable, metaclass=ABCMeta):\n    pass\n\nclass Plain:\n    pass\n";
+        let extraction = parser::extract_file(&PathBuf::from("app/services.py"), services).unwrap();
+        let bases: Vec<(&str, &str)> = extraction
+            .supertypes
+            .iter()
+            .map(|s| (s.name.as_str(), s.supertype.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![("UserService", "BaseService"), ("UserService", "Auditable")]
+        );
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("app/base.py"), base).unwrap(),
+            extraction,
+        ]);
+
+        for base in ["BaseService", "Auditable"] {
+            let subclasses: Vec<String> = anchor_dependencies(&graph, base)
+                .dependents
+                .into_iter()
+                .filter(|d| d.relationship == EdgeKind::Extends)
+                .map(|d| d.symbol)
+                .collect();
+            assert_eq!(subclasses, vec!["UserService"], "subclasses of {}", base);
+        }
+        assert!(anchor_dependencies(&graph, "Plain")
+            .dependencies
+            .iter()
+            .all(|d| d.relationship != EdgeKind::Extends));
+    }
+
+    #[test]
+    fn test_imports_link_files() {
+        use std::path::PathBuf;
+        let utils = "export function format(x: number) {\n  return `${x}`;\n}\n";
+        let app = "import { format } from './utils';\nimport axios from 'axios';\n\nexport function show() {\n  return format(1);\n}\n";
+        let view = "import { format } from './utils.js';\n\nexport function render() {\n  return format(2);\n}\n";
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("web/utils.ts"), utils).unwrap(),
+            parser::extract_file(&PathBuf::from("web/app.ts"), app).unwrap(),
+            parser::extract_file(&PathBuf::from("web/view.ts"), view).unwrap(),
+        ]);
+
+        let importers = |graph: &CodeGraph| -> Vec<String> {
+            let mut importers: Vec<String> = graph_search(graph, "utils.ts", 1)
+                .connections
+                .into_iter()
+                .filter(|c| c.relationship == EdgeKind::Imports && c.to == "utils.ts")
+                .map(|c| c.from)
+                .collect();
+            importers.sort();
+            importers
+        };
+        assert_eq!(importers(&graph), vec!["app.ts", "view.ts"]);
+
+        // `axios` stays an import node, with no file behind it
+        let result = graph_search(&graph, "app.ts", 1);
+        assert!(result.connections.iter().all(|c| c.to != "axios"));
+        assert!(graph.search("axios", 5).iter().any(|r| r.kind == NodeKind::Import));
+
+        // Re-parsing either side keeps the link and both files
+        graph.replace_file(parser::extract_file(&PathBuf::from("web/utils.ts"), utils).unwrap());
+        graph.replace_file(parser::extract_file(&PathBuf::from("web/app.ts"), app).unwrap());
+        assert_eq!(importers(&graph), vec!["app.ts", "view.ts"]);
+        assert_eq!(graph.symbols_in_file(&PathBuf::from("web/utils.ts")).len(), 1);
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        use std::path::PathBuf;
+        let parity = "fn is_even(n: u32) -> bool {\n    if n == 0 { true } else { is_odd(n - 1) }\n}\n\nfn is_odd(n: u32) -> bool {\n    if n == 0 { false } else { is_even(n - 1) }\n}\n\nfn check() -> bool {\n    is_even(4)\n}\n";
+        let path = PathBuf::from("src/parity.rs");
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![parser::extract_file(&path, parity).unwrap()]);
+        assert_eq!(graph.find_cycles(), vec![vec!["is_even", "is_odd"]]);
+
+        // Removed symbols no longer form a cycle
+        graph.remove_file(&path);
+        assert!(graph.find_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_call_path() {
+        use std::path::PathBuf;
+        let api = "fn handle() {\n    route();\n}\n\nfn route() {\n    authorize();\n    save();\n}\n\nfn authorize() {\n    save();\n}\n\nfn save() {}\n\nfn unused() {}\n";
+        let cli = "fn handle() {\n    save();\n}\n";
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/api.rs"), api).unwrap(),
+        ]);
+        let names = |path: Vec<SearchResult>| -> Vec<String> {
+            path.into_iter().map(|s| s.symbol).collect()
+        };
+        assert_eq!(names(graph.call_path("handle", "save").unwrap()), vec!["handle", "route", "save"]);
+        assert!(graph.call_path("save", "handle").is_none());
+        assert!(graph.call_path("handle", "unused").is_none());
+        assert!(graph.call_path("handle", "missing").is_none());
+
+        // A second `handle` that calls `save` directly gives the shorter path
+        graph.replace_file(parser::extract_file(&PathBuf::from("src/cli.rs"), cli).unwrap());
+        let path = graph.call_path("handle", "save").unwrap();
+        assert_eq!(path[0].file, PathBuf::from("src/cli.rs"));
+        assert_eq!(names(path), vec!["handle", "save"]);
+
+        let response = query::anchor_path(&graph, "route", "save");
+        assert!(response.found);
+        assert_eq!(response.path.len(), 2);
+    }
+
+    #[test]
+    fn test_python_decorators() {
+        use std::path::PathBuf;
+        let source = "import pytest\n\n@pytest.fixture(scope=\"module\")\ndef client():\n    return None\n\nclass Repo:\n    @staticmethod\n    @cache\n    def build():\n        pass\n\n    def plain(self):\n        pass\n";
+        let extraction = parser::extract_file(&PathBuf::from("tests/conftest.py"), source).unwrap();
+        let symbol = |name: &str| extraction.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let client = symbol("client");
+        assert_eq!(client.decorators, vec!["pytest.fixture"]);
+        assert_eq!(client.line_start, 3, "starts at the decorator, not the def");
+        assert!(client.code_snippet.starts_with("@pytest.fixture"));
+
+        let build = symbol("build");
+        assert_eq!(build.decorators, vec!["staticmethod", "cache"]);
+        assert_eq!((build.line_start, build.line_end), (8, 11));
+        assert_eq!(build.parent.as_deref(), Some("Repo"));
+
+        assert!(symbol("plain").decorators.is_empty());
+        assert_eq!(symbol("plain").line_start, 13);
+    }
+
+    #[test]
+    fn test_python_all_sets_visibility() {
+        use crate::graph::{SymbolFilter, Visibility};
+        use std::path::PathBuf;
+        let module = "__all__ = [\"login\"]\n\ndef login():\n    pass\n\ndef logout():\n    pass\n\ndef _token():\n    pass\n";
+        let extraction = parser::extract_file(&PathBuf::from("auth/session.py"), module).unwrap();
+        let visibility = |name: &str| {
+            extraction
+                .symbols
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.visibility)
+        };
+        assert_eq!(visibility("login"), Some(Visibility::Public));
+        assert_eq!(visibility("logout"), Some(Visibility::Private));
+
+        // The package re-exports a name its module keeps private
+        let init = "from .session import login, _token\n\n__all__ = (\"login\", \"_token\")\n";
+        let package = parser::extract_file(&PathBuf::from("auth/__init__.py"), init).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction, package]);
+
+        let filter = SymbolFilter {
+            include_tests: false,
+            include_private: false,
+            include_generated: true,
+            async_only: false,
+        };
+        let public: Vec<String> = query::anchor_file_symbols_filtered(&graph, "auth/session.py", &filter)
+            .symbols
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(public, vec!["_token", "login"]);
+    }
+
+    #[test]
+    fn test_python_reexports_survive_replace_file() {
+        use crate::graph::SymbolFilter;
+        use std::path::PathBuf;
+        let session = |body: &str| {
+            let module = format!("__all__ = [\"login\"]\n\ndef login():\n    {}\n\ndef _token():\n    pass\n", body);
+            parser::extract_file(&PathBuf::from("auth/session.py"), &module).unwrap()
+        };
+        let init = "from .session import _token\n\n__all__ = [\"_token\"]\n";
+        let package = parser::extract_file(&PathBuf::from("auth/__init__.py"), init).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![session("pass"), package]);
+
+        let public = |graph: &CodeGraph| -> Vec<String> {
+            let filter = SymbolFilter {
+                include_private: false,
+                ..SymbolFilter::default()
+            };
+            query::anchor_file_symbols_filtered(graph, "auth/session.py", &filter)
+                .symbols
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        };
+        assert_eq!(public(&graph), vec!["_token", "login"]);
+
+        // Editing the module re-ingests `_token` as private; the package still exports it
+        graph.replace_file(session("return 1"));
+        assert_eq!(public(&graph), vec!["_token", "login"]);
+
+        // Also after a save and load
+        let mut loaded = CodeGraph::from_snapshot(graph.snapshot());
+        loaded.replace_file(session("return 2"));
+        assert_eq!(public(&loaded), vec!["_token", "login"]);
+    }
+
+    #[test]
+    fn test_extract_python_shebang_script() {
+        use std::path::PathBuf;
+        let source = "#!/usr/bin/env python3\n\ndef deploy():\n    pass\n";
+        let path = PathBuf::from("bin/deploy");
+        let extraction = parser::extract_file(&path, source).unwrap();
+        assert_eq!(extraction.symbols[0].name, "deploy");
+
+        assert_eq!(
+            SupportedLanguage::from_shebang("#!/usr/bin/python3.11"),
+            Some(SupportedLanguage::Python)
+        );
+        assert_eq!(
+            SupportedLanguage::from_shebang("#!/usr/bin/env -S node --experimental"),
+            Some(SupportedLanguage::JavaScript)
+        );
+        assert_eq!(SupportedLanguage::from_shebang("#!/bin/bash"), None);
+    }
+
     #[test]
     fn test_file_symbols_query() {
         let source = r#"
@@ -352,6 +1435,181 @@ struct Gamma {}
         assert!(response.symbols.is_empty());
     }
 
+    #[test]
+    fn test_test_symbols_hidden_by_default() {
+        let source = r#"
+pub fn parse_config() {}
+
+fn helper() {}
+
+#[test]
+fn test_parse_config() {
+    parse_config();
+}
+
+#[cfg(test)]
+mod tests {
+    fn fixture() {}
+}
+"#;
+        use std::path::PathBuf;
+        let extraction = parser::extract_file(&PathBuf::from("src/config.rs"), source).unwrap();
+
+        let flags = |name: &str| {
+            let sym = extraction.symbols.iter().find(|s| s.name == name).unwrap();
+            (sym.visibility, sym.is_test)
+        };
+        assert_eq!(flags("parse_config"), (graph::Visibility::Public, false));
+        assert_eq!(flags("helper"), (graph::Visibility::Private, false));
+        assert!(flags("test_parse_config").1);
+        assert!(flags("fixture").1);
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        // Excluded by default
+        let response = anchor_search(&graph, Query::Simple("test_parse_config".to_string()));
+        assert!(!response.results.iter().any(|r| r.symbol == "test_parse_config"));
+        let response = get_context(&graph, "test_parse_config", "explore");
+        assert!(!response.symbols.iter().any(|s| s.name == "test_parse_config"));
+        let response = anchor_file_symbols(&graph, "src/config.rs");
+        let names: Vec<&str> = response.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert!(!names.contains(&"test_parse_config"));
+        assert!(names.contains(&"helper"));
+
+        // Included on request
+        let response = anchor_search(
+            &graph,
+            Query::Structured {
+                symbol: "test_parse_config".to_string(),
+                kind: None,
+                file: None,
+                include_tests: Some(true),
+                include_private: None,
+                include_generated: None,
+                async_only: None,
+                dedup: None,
+                fuzzy: None,
+                ignore_case: None,
+            },
+        );
+        assert_eq!(response.results[0].symbol, "test_parse_config");
+        assert!(response.results[0].is_test);
+
+        let filter = graph::SymbolFilter {
+            include_tests: true,
+            include_private: true,
+            include_generated: true,
+            async_only: false,
+        };
+        let response =
+            query::get_context_filtered(&graph, "test_parse_config", "explore", None, &filter);
+        assert_eq!(response.symbols[0].name, "test_parse_config");
+
+        // Private helpers can be hidden too
+        let filter = graph::SymbolFilter {
+            include_tests: false,
+            include_private: false,
+            include_generated: true,
+            async_only: false,
+        };
+        let response = query::anchor_file_symbols_filtered(&graph, "src/config.rs", &filter);
+        let names: Vec<&str> = response.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["parse_config"]);
+    }
+
+    #[test]
+    fn test_access_modifiers_per_language() {
+        use crate::graph::Visibility;
+        use std::path::PathBuf;
+        let visibility = |path: &str, source: &str| -> Vec<(String, Visibility)> {
+            parser::extract_file(&PathBuf::from(path), source)
+                .unwrap()
+                .symbols
+                .into_iter()
+                .map(|s| (s.name, s.visibility))
+                .collect()
+        };
+        let of = |symbols: &[(String, Visibility)], name: &str| {
+            symbols.iter().find(|(n, _)| n == name).map(|&(_, v)| v)
+        };
+
+        let ts = visibility(
+            "src/api.ts",
+            "export class Client {\n  private token: string;\n  protected retry() {}\n  private sign() {}\n  #nonce() {}\n  send() {}\n}\n\nfunction helper() {}\n\nexport const VERSION = 1;\n",
+        );
+        assert_eq!(of(&ts, "Client"), Some(Visibility::Public));
+        assert_eq!(of(&ts, "retry"), Some(Visibility::Protected));
+        assert_eq!(of(&ts, "sign"), Some(Visibility::Private));
+        assert_eq!(of(&ts, "#nonce"), Some(Visibility::Private));
+        assert_eq!(of(&ts, "send"), Some(Visibility::Public));
+        assert_eq!(of(&ts, "helper"), Some(Visibility::Private));
+        assert_eq!(of(&ts, "VERSION"), Some(Visibility::Public));
+
+        // No `export` anywhere: a script, nothing to hide
+        let script = visibility("scripts/run.js", "function main() {}\n");
+        assert_eq!(of(&script, "main"), Some(Visibility::Public));
+
+        let java = visibility(
+            "src/Repo.java",
+            "public class Repo {\n    private int size;\n    protected void flush() {}\n    void reset() {}\n    public void save() {}\n}\n",
+        );
+        assert_eq!(of(&java, "Repo"), Some(Visibility::Public));
+        assert_eq!(of(&java, "flush"), Some(Visibility::Protected));
+        assert_eq!(of(&java, "reset"), Some(Visibility::Crate));
+        assert_eq!(of(&java, "save"), Some(Visibility::Public));
+
+        let csharp = visibility(
+            "src/Repo.cs",
+            "class Repo {\n    void Reset() {}\n    internal void Flush() {}\n    public void Save() {}\n}\n",
+        );
+        assert_eq!(of(&csharp, "Repo"), Some(Visibility::Crate));
+        assert_eq!(of(&csharp, "Reset"), Some(Visibility::Private));
+        assert_eq!(of(&csharp, "Flush"), Some(Visibility::Crate));
+        assert_eq!(of(&csharp, "Save"), Some(Visibility::Public));
+
+        // Public-only search leaves the internals out
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![parser::extract_file(
+            &PathBuf::from("src/Repo.java"),
+            "public class Repo {\n    void saveDraft() {}\n    public void save() {}\n}\n",
+        )
+        .unwrap()]);
+        let response = anchor_search(
+            &graph,
+            Query::Structured {
+                symbol: "save".to_string(),
+                kind: None,
+                file: None,
+                include_tests: None,
+                include_private: Some(false),
+                include_generated: None,
+                async_only: None,
+                dedup: None,
+                fuzzy: None,
+                ignore_case: None,
+            },
+        );
+        let found: Vec<(&str, Visibility)> = response
+            .results
+            .iter()
+            .map(|r| (r.symbol.as_str(), r.visibility))
+            .collect();
+        assert_eq!(found, vec![("save", Visibility::Public)]);
+    }
+
+    #[test]
+    fn test_is_test_path() {
+        use parser::extractor::is_test_path;
+        use std::path::Path;
+
+        assert!(is_test_path(Path::new("tests/integration.rs")));
+        assert!(is_test_path(Path::new("pkg/server_test.go")));
+        assert!(is_test_path(Path::new("app/test_views.py")));
+        assert!(is_test_path(Path::new("web/src/Button.test.tsx")));
+        assert!(!is_test_path(Path::new("src/testing.rs")));
+    }
+
     #[test]
     fn test_get_context_intents() {
         // Build a graph with test scenarios
@@ -400,6 +1658,46 @@ fn test_process() {
         // Should find similar functions like transform
     }
 
+    #[test]
+    fn test_get_context_intent_aliases() {
+        let source = r#"
+pub fn process(input: &str) -> bool {
+    validate(input)
+}
+
+fn validate(s: &str) -> bool {
+    !s.is_empty()
+}
+"#;
+        use std::path::PathBuf;
+        let extraction = parser::extract_file(&PathBuf::from("src/lib.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        // `modify` runs the change intent
+        let change = get_context(&graph, "validate", "change");
+        let modify = get_context(&graph, "validate", "modify");
+        assert_eq!(modify.intent, "change");
+        assert_eq!(modify.edits.len(), change.edits.len());
+        assert!(!modify.edits.is_empty());
+        assert_eq!(modify.used_by.len(), change.used_by.len());
+
+        assert_eq!(get_context(&graph, "validate", "understand").intent, "explore");
+
+        // `overview` returns graph stats
+        let overview = get_context(&graph, "validate", "overview");
+        assert_eq!(overview.intent, "overview");
+        let stats = overview.stats.expect("overview should include stats");
+        assert_eq!(stats.file_count, 1);
+        assert!(get_context(&graph, "validate", "explore").stats.is_none());
+
+        // A missing symbol still gets stats, but isn't reported as found
+        let missing = get_context(&graph, "nonexistent", "overview");
+        assert!(!missing.found);
+        assert!(missing.stats.is_some());
+        assert!(overview.found);
+    }
+
     #[test]
     fn test_parse_unicode_identifiers_python() {
         // Python supports unicode identifiers
@@ -482,6 +1780,383 @@ function createApp(): void {
         assert!(!results.is_empty());
         assert_eq!(results[0].kind, NodeKind::Interface);
     }
+
+    #[test]
+    fn test_parse_ruby_code() {
+        let source = r#"
+require 'json'
+require_relative 'lib/helpers'
+load 'tasks.rb'
+
+module Billing
+  class Invoice
+    def total
+      compute_sum(items)
+    end
+
+    def audit
+      Logger.new.record(total)
+    end
+
+    def compute_sum(list)
+      list.sum
+    end
+  end
+end
+"#;
+        use std::path::PathBuf;
+        let path = PathBuf::from("invoice.rb");
+        let extraction = parser::extract_file(&path, source).unwrap();
+
+        let import_paths: Vec<&str> = extraction.imports.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(import_paths, vec!["json", "lib/helpers", "tasks.rb"]);
+
+        let invoice = extraction.symbols.iter().find(|s| s.name == "Invoice").unwrap();
+        assert_eq!(invoice.kind, NodeKind::Class);
+        let total = extraction.symbols.iter().find(|s| s.name == "total").unwrap();
+        assert_eq!(total.kind, NodeKind::Method);
+        assert_eq!(total.parent.as_deref(), Some("Invoice"));
+
+        // `require` is not a call; `compute_sum(items)` is
+        assert!(extraction.calls.iter().all(|c| c.callee != "require" && c.callee != "load"));
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "compute_sum" && c.caller == "total"));
+
+        // Each invocation is recorded once, chained ones included (a bare
+        // `total` is indistinguishable from a local, so it isn't a call)
+        let mut audit_calls: Vec<&str> = extraction
+            .calls
+            .iter()
+            .filter(|c| c.caller == "audit")
+            .map(|c| c.callee.as_str())
+            .collect();
+        audit_calls.sort();
+        assert_eq!(audit_calls, vec!["new", "record"]);
+    }
+
+    #[test]
+    fn test_parse_kotlin_code() {
+        let source = r#"
+package com.acme.users
+
+import com.acme.db.Repo
+
+@RestController
+class UserController(private val repo: Repo) {
+    @GetMapping("/api/users/{id}")
+    fun getUser(id: Long): User {
+        return repo.find(id)
+    }
+}
+
+object Routes {
+    fun install(app: Application) {
+        app.routing {
+            post("/api/users") { createUser(call) }
+        }
+    }
+}
+
+fun createUser(call: ApplicationCall) {
+    println("created")
+}
+"#;
+        use std::path::PathBuf;
+        let path = PathBuf::from("UserController.kt");
+        assert_eq!(parser::SupportedLanguage::from_path(&path), Some(parser::SupportedLanguage::Kotlin));
+        assert_eq!(
+            parser::SupportedLanguage::from_path(&PathBuf::from("build.gradle.kts")),
+            Some(parser::SupportedLanguage::Kotlin)
+        );
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let symbol = |name: &str| extraction.symbols.iter().find(|s| s.name == name).unwrap();
+
+        assert_eq!(extraction.imports.len(), 1);
+        assert_eq!(symbol("UserController").kind, NodeKind::Class);
+        assert_eq!(symbol("Routes").kind, NodeKind::Class);
+        let get_user = symbol("getUser");
+        assert_eq!(get_user.kind, NodeKind::Method);
+        assert_eq!(get_user.parent.as_deref(), Some("UserController"));
+        assert_eq!(symbol("createUser").kind, NodeKind::Function);
+
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "find" && c.caller == "getUser" && c.receiver.as_deref() == Some("repo")));
+        assert!(extraction.calls.iter().all(|c| c.callee != "println"));
+
+        // Spring and Ktor routes
+        let mut endpoints: Vec<(Option<&str>, &str)> = extraction
+            .endpoints
+            .iter()
+            .map(|e| (e.method.as_deref(), e.url.as_str()))
+            .collect();
+        endpoints.sort();
+        assert_eq!(endpoints, vec![(Some("GET"), "/api/users/:param"), (Some("POST"), "/api/users")]);
+    }
+
+    #[test]
+    fn test_parse_swift_code() {
+        let source = r#"
+import Foundation
+
+protocol Store {
+    func save(_ user: User)
+}
+
+struct User {
+    let name: String
+}
+
+enum Role {
+    case admin, member
+}
+
+class UserService {
+    let store: Store
+
+    init(store: Store) {
+        self.store = store
+    }
+
+    func register(name: String) async -> User {
+        let user = User(name: name)
+        store.save(user)
+        return user
+    }
+}
+
+extension UserService {
+    func greet() {
+        print("hi")
+    }
+}
+
+func makeService() -> UserService {
+    return UserService(store: MemoryStore())
+}
+"#;
+        use std::path::PathBuf;
+        let path = PathBuf::from("UserService.swift");
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let symbol = |name: &str, kind: NodeKind| {
+            extraction
+                .symbols
+                .iter()
+                .find(|s| s.name == name && s.kind == kind)
+                .unwrap_or_else(|| panic!("no {} {:?}", name, kind))
+        };
+
+        assert_eq!(extraction.imports.len(), 1);
+        symbol("Store", NodeKind::Interface);
+        symbol("User", NodeKind::Struct);
+        symbol("Role", NodeKind::Enum);
+        symbol("UserService", NodeKind::Class);
+        symbol("makeService", NodeKind::Function);
+
+        let register = symbol("register", NodeKind::Method);
+        assert_eq!(register.parent.as_deref(), Some("UserService"));
+        assert!(register.is_async);
+        assert_eq!(symbol("init", NodeKind::Method).parent.as_deref(), Some("UserService"));
+        assert_eq!(symbol("save", NodeKind::Method).parent.as_deref(), Some("Store"));
+
+        // Extension methods belong to the extended type
+        symbol("UserService", NodeKind::Impl);
+        assert_eq!(symbol("greet", NodeKind::Method).parent.as_deref(), Some("UserService"));
+
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "save" && c.caller == "register" && c.receiver.as_deref() == Some("store")));
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "UserService" && c.caller == "makeService"));
+        assert!(extraction.calls.iter().all(|c| c.callee != "print"));
+    }
+
+    #[test]
+    fn test_parse_csharp_records_and_properties() {
+        let source = r#"
+using System;
+
+namespace Acme.Billing;
+
+public record Invoice(string Id, decimal Total)
+{
+    public string Currency { get; init; } = "EUR";
+
+    public Invoice WithDiscount(decimal pct)
+    {
+        return Apply(pct);
+    }
+}
+
+public record struct Point(int X, int Y);
+"#;
+        use std::path::PathBuf;
+        let path = PathBuf::from("Invoice.cs");
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let symbol = |name: &str| extraction.symbols.iter().find(|s| s.name == name).unwrap();
+
+        assert_eq!(extraction.imports.len(), 1);
+        assert_eq!(symbol("Acme.Billing").kind, NodeKind::Module);
+
+        let invoice = symbol("Invoice");
+        assert_eq!(invoice.kind, NodeKind::Class);
+        assert_eq!(invoice.parent.as_deref(), Some("Acme.Billing"));
+        assert_eq!(symbol("Point").kind, NodeKind::Struct);
+
+        // Positional and declared properties belong to the record
+        for property in ["Id", "Total", "Currency"] {
+            assert_eq!(symbol(property).kind, NodeKind::Field, "{}", property);
+            assert_eq!(symbol(property).parent.as_deref(), Some("Invoice"), "{}", property);
+        }
+        assert_eq!(symbol("X").parent.as_deref(), Some("Point"));
+        // Method parameters aren't properties
+        assert!(extraction.symbols.iter().all(|s| s.name != "pct"));
+
+        let method = symbol("WithDiscount");
+        assert_eq!(method.kind, NodeKind::Method);
+        assert_eq!(method.parent.as_deref(), Some("Invoice"));
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "Apply" && c.caller == "WithDiscount"));
+
+        // Block-scoped namespaces nest too
+        let source = "namespace Acme.Core {\n    public struct Money { public decimal Amount { get; set; } }\n}\n";
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let money = extraction.symbols.iter().find(|s| s.name == "Money").unwrap();
+        assert_eq!(money.kind, NodeKind::Struct);
+        assert_eq!(money.parent.as_deref(), Some("Acme.Core"));
+    }
+
+    #[test]
+    fn test_java_and_csharp_fields() {
+        use crate::graph::Visibility;
+        use std::path::PathBuf;
+        let java = r#"
+public class Order {
+    private final String id;
+    protected int width, height;
+
+    public String getId() {
+        return id;
+    }
+}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/Order.java"), java).unwrap();
+        let fields: Vec<(&str, Option<&str>, Visibility)> = extraction
+            .symbols
+            .iter()
+            .filter(|s| s.kind == NodeKind::Field)
+            .map(|s| (s.name.as_str(), s.parent.as_deref(), s.visibility))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", Some("Order"), Visibility::Private),
+                ("width", Some("Order"), Visibility::Protected),
+                ("height", Some("Order"), Visibility::Protected),
+            ]
+        );
+        assert!(extraction.symbols.iter().any(|s| s.name == "getId" && s.kind == NodeKind::Method));
+
+        let csharp = "class Cart {\n    private readonly List<Item> items, saved;\n    public int Count { get; }\n}\n";
+        let extraction = parser::extract_file(&PathBuf::from("src/Cart.cs"), csharp).unwrap();
+        let fields: Vec<(&str, Option<&str>)> = extraction
+            .symbols
+            .iter()
+            .filter(|s| s.kind == NodeKind::Field)
+            .map(|s| (s.name.as_str(), s.parent.as_deref()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("items", Some("Cart")), ("saved", Some("Cart")), ("Count", Some("Cart"))]
+        );
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        let results = graph.search("Count", 3);
+        assert_eq!(results[0].kind.to_string(), "field");
+    }
+
+    #[test]
+    fn test_parse_go_types_and_methods() {
+        let source = r#"
+package store
+
+import "fmt"
+
+// User is a stored account.
+type User struct {
+    Name string
+}
+
+type Repo interface {
+    Find(id int) (*User, error)
+}
+
+type (
+    ID    = int
+    Users []User
+)
+
+type memRepo struct {
+    users map[int]*User
+}
+
+func NewUser(name string) *User {
+    return &User{Name: name}
+}
+
+func (r *memRepo) Find(id int) (*User, error) {
+    return r.lookup(id), nil
+}
+
+func (s Set[T]) Len() int { return len(s) }
+"#;
+        use std::path::PathBuf;
+        let path = PathBuf::from("store/user.go");
+        let extraction = parser::extract_file(&path, source).unwrap();
+        let symbol = |name: &str| extraction.symbols.iter().find(|s| s.name == name).unwrap();
+
+        assert_eq!(extraction.imports.len(), 1);
+
+        let user = symbol("User");
+        assert_eq!(user.kind, NodeKind::Struct);
+        assert!(user.code_snippet.starts_with("type User struct"));
+        assert_eq!(user.visibility, graph::Visibility::Public);
+        assert_eq!(symbol("Repo").kind, NodeKind::Interface);
+        assert_eq!(symbol("ID").kind, NodeKind::Type);
+        assert_eq!(symbol("Users").kind, NodeKind::Type);
+
+        assert_eq!(symbol("NewUser").kind, NodeKind::Function);
+        assert_eq!(symbol("NewUser").parent, None);
+
+        // Methods belong to their receiver type
+        let find = symbol("Find");
+        assert_eq!(find.kind, NodeKind::Method);
+        assert_eq!(find.parent.as_deref(), Some("memRepo"));
+        assert_eq!(symbol("Len").parent.as_deref(), Some("Set"));
+        assert!(extraction
+            .calls
+            .iter()
+            .any(|c| c.callee == "lookup" && c.caller == "Find"));
+
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        let owners: Vec<String> = anchor_dependencies(&graph, "Find")
+            .dependents
+            .into_iter()
+            .filter(|d| d.relationship == EdgeKind::Contains)
+            .map(|d| d.symbol)
+            .collect();
+        assert_eq!(owners, vec!["memRepo"]);
+    }
 }
 
 #[cfg(test)]
@@ -501,4 +2176,58 @@ mod benchmarks {
         println!("Search benchmark: {}ms", elapsed.as_millis());
         assert!(elapsed.as_millis() < 100);
     }
+
+    /// Files to re-parse: those changed since `ANCHOR_BENCH_SINCE_COMMIT`
+    /// if set, otherwise the first few indexed files.
+    fn incremental_sample(graph: &CodeGraph, root: &std::path::Path) -> Vec<PathBuf> {
+        const SAMPLE_SIZE: usize = 5;
+
+        let files = graph.all_files();
+        if let Ok(commit) = std::env::var("ANCHOR_BENCH_SINCE_COMMIT") {
+            let output = std::process::Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(["diff", "--name-only", "--relative", &commit])
+                .output()
+                .expect("git diff failed");
+            let changed: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| root.join(line))
+                .filter(|path| files.contains(path))
+                .collect();
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+        files.into_iter().take(SAMPLE_SIZE).collect()
+    }
+
+    #[test]
+    fn benchmark_incremental_rebuild() {
+        let repo_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        let start = std::time::Instant::now();
+        let mut graph = build_graph(&repo_path);
+        let full = start.elapsed();
+
+        let sample = incremental_sample(&graph, &repo_path);
+        assert!(!sample.is_empty());
+
+        let start = std::time::Instant::now();
+        for file in &sample {
+            graph::rebuild_file(&mut graph, file).unwrap();
+        }
+        let incremental = start.elapsed() / sample.len() as u32;
+
+        let results = serde_json::json!({
+            "full_build_ms": full.as_secs_f64() * 1000.0,
+            "incremental_files": sample.len(),
+            "incremental_per_file_ms": incremental.as_secs_f64() * 1000.0,
+            "incremental_speedup": full.as_secs_f64() / incremental.as_secs_f64().max(1e-9),
+        });
+        println!("Incremental benchmark: {}", results);
+
+        // Re-parsing one file must beat re-indexing the whole tree
+        assert!(incremental < full, "{}", results);
+    }
 }
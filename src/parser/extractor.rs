@@ -5,29 +5,125 @@
 //! - Import statements
 //! - Function calls (for building call graphs)
 
-use std::path::Path;
-use tree_sitter::{Node, Parser};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Node, Parser, Tree};
 
-use super::language::SupportedLanguage;
+use super::language::{LanguageSpec, SupportedLanguage};
+use super::queries::api::extract_api_endpoints;
 use crate::error::AnchorError;
 use crate::graph::types::*;
 
+/// Marker appended to snippets cut short by the size limits. A leading
+/// `//` is swapped for the file's comment token (`# ... (truncated)` in
+/// Python).
+pub const DEFAULT_TRUNCATION_MARKER: &str = "// ... (truncated)";
+
+/// Options for symbol extraction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ExtractOptions {
+    /// Line appended to truncated snippets, or `None` to leave the cut
+    /// clean so the snippet can be fed back through the parser. Whether a
+    /// snippet was cut is always recorded in `ExtractedSymbol::truncated`.
+    pub truncation_marker: Option<String>,
+    /// Tag JSX-returning JS/TSX functions as `Component`s and record the
+    /// components they render. When off they stay plain functions.
+    pub jsx_components: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            truncation_marker: Some(DEFAULT_TRUNCATION_MARKER.to_string()),
+            jsx_components: true,
+        }
+    }
+}
+
+thread_local! {
+    /// Parsers already set up for each language, reused by every file this
+    /// thread parses. Parsers aren't `Send`, so each rayon worker keeps its
+    /// own.
+    static PARSERS: RefCell<HashMap<SupportedLanguage, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Parse `source` with this thread's parser for `lang`, creating it on
+/// first use.
+fn parse_tree(path: &Path, source: &str, lang: SupportedLanguage) -> crate::error::Result<Tree> {
+    #[cfg(test)]
+    broken_grammars::check(path, lang)?;
+
+    PARSERS.with(|parsers| {
+        let mut parsers = parsers.borrow_mut();
+        let parser = match parsers.entry(lang) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(new_parser(lang)?),
+        };
+        parser.parse(source, None).ok_or_else(|| {
+            // Don't let a failed parse resume into the next file
+            parser.reset();
+            AnchorError::TreeSitterParseFailed(path.to_path_buf())
+        })
+    })
+}
+
+/// A parser for `lang`. `set_language` only fails when the grammar's ABI
+/// version is outside what the runtime supports.
+fn new_parser(lang: SupportedLanguage) -> crate::error::Result<Parser> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&lang.tree_sitter_language())
+        .map_err(|e| AnchorError::GrammarAbiMismatch(lang, e.to_string()))?;
+    Ok(parser)
+}
+
+/// Test seam: files under a registered root fail to parse as if their
+/// language's grammar had an ABI version the runtime rejects.
+#[cfg(test)]
+pub(crate) mod broken_grammars {
+    use super::*;
+    use std::sync::Mutex;
+
+    static BROKEN: Mutex<Vec<(PathBuf, SupportedLanguage)>> = Mutex::new(Vec::new());
+
+    /// Break `lang` for files under `root`; other tests' files still parse.
+    pub(crate) fn break_under(root: &Path, lang: SupportedLanguage) {
+        BROKEN.lock().unwrap().push((root.to_path_buf(), lang));
+    }
+
+    /// The error `new_parser` gives when `set_language` fails.
+    pub(super) fn check(path: &Path, lang: SupportedLanguage) -> crate::error::Result<()> {
+        let broken = BROKEN.lock().unwrap().iter().any(|(root, l)| *l == lang && path.starts_with(root));
+        if broken {
+            let error = "Incompatible language version 99. Expected minimum 13, maximum 15";
+            return Err(AnchorError::GrammarAbiMismatch(lang, error.to_string()));
+        }
+        Ok(())
+    }
+}
+
 /// Extract all symbols, imports, and calls from a source file.
 ///
 /// Returns an error if the file's language is unsupported, the parser
 /// fails to initialize, or tree-sitter returns no parse tree.
 pub fn extract_file(path: &Path, source: &str) -> crate::error::Result<FileExtractions> {
-    let lang = SupportedLanguage::from_path(path)
-        .ok_or_else(|| AnchorError::UnsupportedLanguage(path.to_path_buf()))?;
+    extract_file_with(path, source, &ExtractOptions::default())
+}
 
-    let mut parser = Parser::new();
-    parser
-        .set_language(&lang.tree_sitter_language())
-        .map_err(|e| AnchorError::ParserInitError(path.to_path_buf(), e.to_string()))?;
+/// Like [`extract_file`], with explicit options.
+pub fn extract_file_with(
+    path: &Path,
+    source: &str,
+    options: &ExtractOptions,
+) -> crate::error::Result<FileExtractions> {
+    let lang = SupportedLanguage::detect(path, source)
+        .ok_or_else(|| AnchorError::UnsupportedLanguage(path.to_path_buf()))?;
 
-    let tree = parser
-        .parse(source, None)
-        .ok_or_else(|| AnchorError::TreeSitterParseFailed(path.to_path_buf()))?;
+    let tree = parse_tree(path, source, lang)?;
     let root = tree.root_node();
 
     let mut symbols = Vec::new();
@@ -42,17 +138,94 @@ pub fn extract_file(path: &Path, source: &str) -> crate::error::Result<FileExtra
         &mut symbols,
         &mut imports,
         &mut calls,
+        options,
     );
 
+    let renders = if options.jsx_components
+        && matches!(lang, SupportedLanguage::JavaScript | SupportedLanguage::Tsx)
+    {
+        extract_jsx_components(&root, source.as_bytes(), &mut symbols)
+    } else {
+        Vec::new()
+    };
+
+    let dynamic_calls = extract_dynamic_calls(&root, source.as_bytes(), lang, &symbols);
+    let supertypes = extract_supertypes(&root, source.as_bytes(), lang);
+
+    // Builtins like `len()` would otherwise bind to any project symbol of that name
+    let stopwords = lang.call_stopwords();
+    calls.retain(|call| !stopwords.iter().any(|stopword| call.is_stopword(stopword)));
+
+    if let Some(marker) = &options.truncation_marker {
+        let marker = match marker.strip_prefix("//") {
+            Some(rest) => format!("{}{}", lang.line_comment(), rest),
+            None => marker.clone(),
+        };
+        for symbol in symbols.iter_mut().filter(|s| s.truncated) {
+            symbol.code_snippet.push_str("\n    ");
+            symbol.code_snippet.push_str(&marker);
+        }
+    }
+
+    // `__all__` is the module's declared API and overrides the underscore rule
+    let exports = match lang {
+        SupportedLanguage::Python => python_exports(&root, source.as_bytes()),
+        _ => None,
+    };
+    if let Some(exports) = &exports {
+        for symbol in symbols.iter_mut().filter(|s| s.parent.is_none()) {
+            symbol.visibility = if exports.contains(&symbol.name) {
+                Visibility::Public
+            } else {
+                Visibility::Private
+            };
+        }
+    }
+
+    let generated = has_generated_marker(source);
+
+    // Everything in a test file is test code
+    if is_test_path(path) {
+        for symbol in &mut symbols {
+            symbol.is_test = true;
+        }
+    }
+
+    let endpoints = extract_api_endpoints(&root, source.as_bytes(), lang, path);
+
     Ok(FileExtractions {
         file_path: path.to_path_buf(),
         symbols,
         imports,
         calls,
+        renders,
+        dynamic_calls,
+        supertypes,
+        exports: exports.unwrap_or_default(),
+        generated,
+        endpoints,
     })
 }
 
+/// Extract several files in parallel without building a graph.
+///
+/// Results are in the same order as `paths`, one per file. Files in an
+/// unsupported language fail with `UnsupportedLanguage` without being read.
+pub fn extract_files(paths: &[PathBuf]) -> Vec<crate::error::Result<FileExtractions>> {
+    paths
+        .par_iter()
+        .map(|path| {
+            if SupportedLanguage::from_file(path).is_none() {
+                return Err(AnchorError::UnsupportedLanguage(path.clone()));
+            }
+            let source = std::fs::read_to_string(path)?;
+            extract_file(path, &source)
+        })
+        .collect()
+}
+
 /// Recursively extract information from a tree-sitter node.
+#[allow(clippy::too_many_arguments)]
 fn extract_node(
     node: &Node,
     source: &[u8],
@@ -61,99 +234,62 @@ fn extract_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
 ) {
     let kind = node.kind();
+    let before = symbols.len();
 
     match lang {
         SupportedLanguage::Rust => {
-            extract_rust_node(node, source, kind, current_scope, symbols, imports, calls);
+            extract_rust_node(node, source, kind, current_scope, symbols, imports, calls, options);
         }
         SupportedLanguage::Python => {
-            extract_python_node(node, source, kind, current_scope, symbols, imports, calls);
+            extract_python_node(node, source, kind, current_scope, symbols, imports, calls, options);
         }
         SupportedLanguage::JavaScript | SupportedLanguage::Tsx => {
-            extract_js_node(node, source, kind, current_scope, symbols, imports, calls);
+            extract_js_node(node, source, kind, current_scope, symbols, imports, calls, options);
         }
         SupportedLanguage::TypeScript => {
-            extract_ts_node(node, source, kind, current_scope, symbols, imports, calls);
+            extract_ts_node(node, source, kind, current_scope, symbols, imports, calls, options);
+        }
+        SupportedLanguage::CSharp => {
+            extract_csharp_node(node, source, kind, current_scope, symbols, imports, calls, options);
         }
         SupportedLanguage::Go => {
-            extract_generic_node(
-                node,
-                source,
-                kind,
-                current_scope,
-                symbols,
-                imports,
-                calls,
-                &["function_declaration", "method_declaration"],
-                &["import_declaration"],
-                &["call_expression"],
-            );
-        }
-        SupportedLanguage::Java => {
-            extract_generic_node(
-                node,
-                source,
-                kind,
-                current_scope,
-                symbols,
-                imports,
-                calls,
-                &[
-                    "method_declaration",
-                    "class_declaration",
-                    "interface_declaration",
-                ],
-                &["import_declaration"],
-                &["method_invocation"],
-            );
+            extract_go_node(node, source, kind, current_scope, symbols, imports, calls, options);
         }
-        SupportedLanguage::CSharp => {
-            extract_generic_node(
-                node,
-                source,
-                kind,
-                current_scope,
-                symbols,
-                imports,
-                calls,
-                &[
-                    "method_declaration",
-                    "class_declaration",
-                    "interface_declaration",
-                ],
-                &["using_directive"],
-                &["invocation_expression"],
-            );
-        }
-        SupportedLanguage::Ruby => {
-            extract_generic_node(
-                node,
-                source,
-                kind,
-                current_scope,
-                symbols,
-                imports,
-                calls,
-                &["method", "class", "module"],
-                &["call"],
-                &["call", "method_call"],
-            );
-        }
-        SupportedLanguage::Cpp | SupportedLanguage::Swift => {
-            extract_generic_node(
-                node,
-                source,
-                kind,
-                current_scope,
-                symbols,
-                imports,
-                calls,
-                &["function_definition", "class_specifier"],
-                &["preproc_include"],
-                &["call_expression"],
-            );
+        SupportedLanguage::Swift => {
+            extract_swift_node(node, source, kind, current_scope, symbols, imports, calls, options);
+        }
+        SupportedLanguage::Java
+        | SupportedLanguage::Ruby
+        | SupportedLanguage::Kotlin
+        | SupportedLanguage::Cpp => {
+            if let Some(spec) = lang.generic_spec() {
+                extract_generic_node(node, source, lang, kind, current_scope, symbols, imports, calls, spec, options);
+            }
+        }
+    }
+
+    // A Python definition's decorators are part of it
+    let decorated = node
+        .parent()
+        .filter(|p| lang == SupportedLanguage::Python && p.kind() == "decorated_definition");
+    for symbol in &mut symbols[before..] {
+        symbol.visibility = symbol_visibility(node, source, lang, &symbol.name);
+        symbol.is_test = is_test_symbol(node, source, lang, symbol);
+        symbol.truncated = snippet_truncated(node, source);
+        symbol.deprecated = is_deprecated(node, source, lang);
+        symbol.is_async = matches!(symbol.kind, NodeKind::Function | NodeKind::Method)
+            && is_async_definition(node, source, &symbol.name);
+        if symbol.kind == NodeKind::Constant {
+            symbol.value = constant_value(node, source, &symbol.name);
+        }
+        if let Some(decorated) = decorated {
+            symbol.line_start = decorated.start_position().row + 1;
+            symbol.code_snippet = bounded_snippet(&decorated, source, lang, options);
+            symbol.truncated = snippet_truncated(&decorated, source);
+            symbol.decorators = python_decorators(&decorated, source);
         }
     }
 
@@ -175,37 +311,70 @@ fn extract_node(
                 _ => None,
             }
         }
-        SupportedLanguage::Go => match kind {
-            "function_declaration" | "method_declaration" => node_name(node, source),
+        SupportedLanguage::CSharp => match kind {
+            "namespace_declaration"
+            | "class_declaration"
+            | "struct_declaration"
+            | "record_declaration"
+            | "interface_declaration"
+            | "method_declaration"
+            | "property_declaration" => node_name(node, source),
             _ => None,
         },
-        SupportedLanguage::Java | SupportedLanguage::CSharp => match kind {
-            "method_declaration" | "class_declaration" => node_name(node, source),
-            _ => None,
-        },
-        SupportedLanguage::Ruby => match kind {
-            "method" | "class" | "module" => node_name(node, source),
+        SupportedLanguage::Go => match kind {
+            "function_declaration" | "method_declaration" => node_name(node, source),
             _ => None,
         },
-        SupportedLanguage::Cpp | SupportedLanguage::Swift => match kind {
-            "function_definition" | "class_specifier" => node_name(node, source),
+        SupportedLanguage::Swift => match kind {
+            "class_declaration" | "protocol_declaration" => swift_type_name(node, source),
+            "function_declaration" | "protocol_function_declaration" | "init_declaration" => {
+                node_name(node, source)
+            }
             _ => None,
         },
+        SupportedLanguage::Java
+        | SupportedLanguage::Ruby
+        | SupportedLanguage::Kotlin
+        | SupportedLanguage::Cpp => lang.generic_spec().and_then(|spec| {
+            spec.symbol_kind(kind)
+                .and_then(|_| spec_node_name(node, source, spec))
+        }),
     };
 
     let scope = new_scope.as_deref().or(current_scope);
 
+    // C# `namespace Acme.Billing;` scopes the declarations that follow it
+    let file_namespace = (lang == SupportedLanguage::CSharp)
+        .then(|| {
+            let mut cursor = node.walk();
+            let found = node
+                .children(&mut cursor)
+                .find(|c| c.kind() == "file_scoped_namespace_declaration")
+                .and_then(|ns| Some((ns.id(), node_name(&ns, source)?)));
+            found
+        })
+        .flatten();
+    let mut in_file_namespace = false;
+
     // Recurse into children
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
-            extract_node(&child, source, lang, scope, symbols, imports, calls);
+            let child_scope = match &file_namespace {
+                Some((_, name)) if in_file_namespace => Some(name.as_str()),
+                _ => scope,
+            };
+            extract_node(&child, source, lang, child_scope, symbols, imports, calls, options);
+            if file_namespace.as_ref().is_some_and(|(id, _)| *id == child.id()) {
+                in_file_namespace = true;
+            }
         }
     }
 }
 
 // ─── Rust Extraction ────────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn extract_rust_node(
     node: &Node,
     source: &[u8],
@@ -214,6 +383,7 @@ fn extract_rust_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
 ) {
     match kind {
         "function_item" => {
@@ -230,8 +400,15 @@ fn extract_rust_node(
                     kind: sym_kind,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: parent_scope,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -242,8 +419,15 @@ fn extract_rust_node(
                     kind: NodeKind::Struct,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -254,8 +438,15 @@ fn extract_rust_node(
                     kind: NodeKind::Enum,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -266,8 +457,15 @@ fn extract_rust_node(
                     kind: NodeKind::Trait,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -278,8 +476,15 @@ fn extract_rust_node(
                     kind: NodeKind::Impl,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -290,8 +495,15 @@ fn extract_rust_node(
                     kind: NodeKind::Constant,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: current_scope.map(|s| s.to_string()),
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -302,8 +514,15 @@ fn extract_rust_node(
                     kind: NodeKind::Type,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -314,8 +533,34 @@ fn extract_rust_node(
                     kind: NodeKind::Module,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
+                    parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
+                });
+            }
+        }
+        "macro_definition" => {
+            if let Some(name) = node_name(node, source) {
+                symbols.push(ExtractedSymbol {
+                    name,
+                    kind: NodeKind::Macro,
+                    line_start: node.start_position().row + 1,
+                    line_end: node.end_position().row + 1,
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Rust, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -335,11 +580,12 @@ fn extract_rust_node(
             });
         }
         "call_expression" => {
-            if let Some(callee_name) = get_call_name(node, source) {
+            if let Some((callee_name, receiver)) = get_call_name(node, source) {
                 if let Some(caller) = current_scope {
                     calls.push(ExtractedCall {
                         callee: callee_name,
                         caller: caller.to_string(),
+                        receiver,
                         line: node.start_position().row + 1,
                     });
                 }
@@ -351,6 +597,7 @@ fn extract_rust_node(
 
 // ─── Python Extraction ──────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn extract_python_node(
     node: &Node,
     source: &[u8],
@@ -359,6 +606,7 @@ fn extract_python_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
 ) {
     match kind {
         "function_definition" => {
@@ -375,8 +623,43 @@ fn extract_python_node(
                     kind: sym_kind,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Python, options),
                     parent: parent_scope,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
+                });
+            }
+        }
+        "assignment" if current_scope.is_none() => {
+            // Module-level `MAX_RETRIES = 3` (ALL_CAPS by convention)
+            let at_module_level = node
+                .parent()
+                .and_then(|stmt| stmt.parent())
+                .is_some_and(|p| p.kind() == "module");
+            let name = node
+                .child_by_field_name("left")
+                .filter(|left| left.kind() == "identifier")
+                .map(|left| node_text(&left, source));
+            if let Some(name) = name.filter(|n| at_module_level && is_constant_name(n)) {
+                symbols.push(ExtractedSymbol {
+                    name,
+                    kind: NodeKind::Constant,
+                    line_start: node.start_position().row + 1,
+                    line_end: node.end_position().row + 1,
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Python, options),
+                    parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -387,8 +670,15 @@ fn extract_python_node(
                     kind: NodeKind::Class,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::Python, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -427,11 +717,12 @@ fn extract_python_node(
             });
         }
         "call" => {
-            if let Some(callee_name) = get_python_call_name(node, source) {
+            if let Some((callee_name, receiver)) = get_python_call_name(node, source) {
                 if let Some(caller) = current_scope {
                     calls.push(ExtractedCall {
                         callee: callee_name,
                         caller: caller.to_string(),
+                        receiver,
                         line: node.start_position().row + 1,
                     });
                 }
@@ -441,8 +732,47 @@ fn extract_python_node(
     }
 }
 
+/// Names listed in a module-level `__all__ = [...]` (or tuple), including
+/// later `__all__ += [...]` additions. `None` when the module has no
+/// `__all__`.
+fn python_exports(root: &Node, source: &[u8]) -> Option<Vec<String>> {
+    let mut exports: Option<Vec<String>> = None;
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        let Some(assignment) = statement.named_child(0) else {
+            continue;
+        };
+        if !matches!(assignment.kind(), "assignment" | "augmented_assignment")
+            || assignment
+                .child_by_field_name("left")
+                .is_none_or(|left| node_text(&left, source) != "__all__")
+        {
+            continue;
+        }
+        let Some(names) = assignment
+            .child_by_field_name("right")
+            .filter(|right| matches!(right.kind(), "list" | "tuple"))
+        else {
+            continue;
+        };
+
+        let mut names_cursor = names.walk();
+        let listed = names
+            .named_children(&mut names_cursor)
+            .filter(|item| item.kind() == "string")
+            .filter_map(|item| string_literal(&node_text(&item, source)));
+        let exports = exports.get_or_insert_with(Vec::new);
+        if assignment.kind() == "assignment" {
+            exports.clear();
+        }
+        exports.extend(listed);
+    }
+    exports
+}
+
 // ─── JavaScript Extraction ──────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn extract_js_node(
     node: &Node,
     source: &[u8],
@@ -451,6 +781,7 @@ fn extract_js_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
 ) {
     match kind {
         "function_declaration" => {
@@ -460,8 +791,15 @@ fn extract_js_node(
                     kind: NodeKind::Function,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::JavaScript, options),
                     parent: current_scope.map(|s| s.to_string()),
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -472,8 +810,15 @@ fn extract_js_node(
                     kind: NodeKind::Class,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::JavaScript, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -484,14 +829,21 @@ fn extract_js_node(
                     kind: NodeKind::Method,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::JavaScript, options),
                     parent: current_scope.map(|s| s.to_string()),
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
         "lexical_declaration" | "variable_declaration" => {
             // Handle: const foo = () => {} or const FOO = "bar"
-            extract_js_variable_declaration(node, source, current_scope, symbols);
+            extract_js_variable_declaration(node, source, current_scope, symbols, options);
         }
         "import_statement" => {
             extract_js_import(node, source, imports);
@@ -500,11 +852,12 @@ fn extract_js_node(
             // Exports may contain declarations — let children handle extraction
         }
         "call_expression" => {
-            if let Some(callee_name) = get_call_name(node, source) {
+            if let Some((callee_name, receiver)) = get_call_name(node, source) {
                 if let Some(caller) = current_scope {
                     calls.push(ExtractedCall {
                         callee: callee_name,
                         caller: caller.to_string(),
+                        receiver,
                         line: node.start_position().row + 1,
                     });
                 }
@@ -514,8 +867,309 @@ fn extract_js_node(
     }
 }
 
+/// Tag JSX-returning functions as components and collect what they render.
+///
+/// A function (declaration or `const X = () => ...`) whose body contains a
+/// JSX element becomes a `Component`. Capitalized elements it contains
+/// (`<Header />`, `<UI.Button>`) are returned as renders; lowercase ones are
+/// HTML intrinsics and are skipped.
+fn extract_jsx_components(
+    root: &Node,
+    source: &[u8],
+    symbols: &mut [ExtractedSymbol],
+) -> Vec<ExtractedCall> {
+    let mut renders = Vec::new();
+    let mut stack = vec![*root];
+
+    while let Some(node) = stack.pop() {
+        for i in (0..node.child_count()).rev() {
+            if let Some(child) = node.child(i) {
+                stack.push(child);
+            }
+        }
+
+        let body = match node.kind() {
+            "function_declaration" => Some(node),
+            "variable_declarator" => node.child_by_field_name("value").filter(|v| {
+                matches!(v.kind(), "arrow_function" | "function" | "function_expression")
+            }),
+            _ => None,
+        };
+        let (Some(body), Some(name)) = (body, node_name(&node, source)) else {
+            continue;
+        };
+
+        let mut has_jsx = false;
+        let mut rendered: Vec<String> = Vec::new();
+        collect_jsx_elements(&body, source, &mut has_jsx, &mut rendered);
+        if !has_jsx {
+            continue;
+        }
+
+        let line = node.start_position().row + 1;
+        let Some(symbol) = symbols.iter_mut().find(|s| {
+            s.name == name && s.kind == NodeKind::Function && (s.line_start..=s.line_end).contains(&line)
+        }) else {
+            continue;
+        };
+        symbol.kind = NodeKind::Component;
+
+        for element in rendered {
+            if element != name {
+                renders.push(ExtractedCall {
+                    callee: element,
+                    caller: name.clone(),
+                    receiver: None,
+                    line,
+                });
+            }
+        }
+    }
+
+    renders
+}
+
+/// Find JSX elements under `node`, recording capitalized element names once.
+fn collect_jsx_elements(node: &Node, source: &[u8], has_jsx: &mut bool, rendered: &mut Vec<String>) {
+    let element_name = match node.kind() {
+        "jsx_opening_element" | "jsx_self_closing_element" => {
+            *has_jsx = true;
+            node.child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source).ok())
+                .map(|text| text.rsplit('.').next().unwrap_or(text).to_string())
+        }
+        "jsx_element" | "jsx_fragment" => {
+            *has_jsx = true;
+            None
+        }
+        _ => None,
+    };
+    if let Some(name) = element_name {
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) && !rendered.contains(&name) {
+            rendered.push(name);
+        }
+    }
+
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect_jsx_elements(&child, source, has_jsx, rendered);
+        }
+    }
+}
+
+// ─── Dynamic Calls ──────────────────────────────────────────────
+
+/// Collect the traits each type implements (`impl Display for Config`)
+/// and the classes each Python class extends (`class Admin(User, Auditable)`).
+fn extract_supertypes(root: &Node, source: &[u8], lang: SupportedLanguage) -> Vec<ExtractedSupertype> {
+    let mut supertypes = Vec::new();
+    if !matches!(lang, SupportedLanguage::Rust | SupportedLanguage::Python) {
+        return supertypes;
+    }
+    let mut stack = vec![*root];
+
+    while let Some(node) = stack.pop() {
+        for i in (0..node.child_count()).rev() {
+            if let Some(child) = node.child(i) {
+                stack.push(child);
+            }
+        }
+        let line = node.start_position().row + 1;
+
+        match (lang, node.kind()) {
+            (SupportedLanguage::Rust, "impl_item") => {
+                let (Some(trait_node), Some(name)) =
+                    (node.child_by_field_name("trait"), get_rust_impl_name(&node, source))
+                else {
+                    continue;
+                };
+                if let Some(supertype) = trait_node.utf8_text(source).ok().and_then(type_base_name) {
+                    supertypes.push(ExtractedSupertype {
+                        name,
+                        supertype,
+                        kind: EdgeKind::Implements,
+                        line,
+                    });
+                }
+            }
+            (SupportedLanguage::Python, "class_definition") => {
+                // `class Foo:` has no superclasses field at all
+                let (Some(name), Some(bases)) =
+                    (node_name(&node, source), node.child_by_field_name("superclasses"))
+                else {
+                    continue;
+                };
+                // The class symbol starts at its decorators
+                let line = node
+                    .parent()
+                    .filter(|p| p.kind() == "decorated_definition")
+                    .map_or(line, |p| p.start_position().row + 1);
+                // Keyword arguments (`metaclass=ABCMeta`) aren't bases
+                for i in 0..bases.named_child_count() {
+                    let Some(base) = bases.named_child(i) else {
+                        continue;
+                    };
+                    if !matches!(base.kind(), "identifier" | "attribute" | "subscript") {
+                        continue;
+                    }
+                    if let Some(supertype) = base.utf8_text(source).ok().and_then(type_base_name) {
+                        supertypes.push(ExtractedSupertype {
+                            name: name.clone(),
+                            supertype,
+                            kind: EdgeKind::Extends,
+                            line,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    supertypes
+}
+
+/// A type's own name without its path or generic arguments:
+/// `std::fmt::Display` -> `Display`, `From<String>` -> `From`,
+/// `typing.Generic[T]` -> `Generic`.
+fn type_base_name(text: &str) -> Option<String> {
+    let path = text.split(['<', '[']).next().unwrap_or(text);
+    let name = path.rsplit(['.', ':']).next().unwrap_or(path).trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Collect calls whose target is named by a string at runtime:
+/// `getattr(obj, "save")` in Python, `obj["save"]()` and `save.call(...)`
+/// in JS/TS, `send(:save)` in Ruby, `getMethod("save")` in Java and
+/// `MethodByName("Save")` in Go.
+///
+/// The caller is the innermost function or method around the call site;
+/// calls at module level are skipped, like ordinary calls.
+fn extract_dynamic_calls(
+    root: &Node,
+    source: &[u8],
+    lang: SupportedLanguage,
+    symbols: &[ExtractedSymbol],
+) -> Vec<ExtractedCall> {
+    let mut calls = Vec::new();
+    let mut stack = vec![*root];
+
+    while let Some(node) = stack.pop() {
+        for i in (0..node.child_count()).rev() {
+            if let Some(child) = node.child(i) {
+                stack.push(child);
+            }
+        }
+
+        let Some(callee) = dynamic_call_target(&node, source, lang) else {
+            continue;
+        };
+        let line = node.start_position().row + 1;
+        let caller = symbols
+            .iter()
+            .filter(|s| matches!(s.kind, NodeKind::Function | NodeKind::Method | NodeKind::Component))
+            .filter(|s| (s.line_start..=s.line_end).contains(&line))
+            .min_by_key(|s| s.line_end - s.line_start);
+        if let Some(caller) = caller {
+            calls.push(ExtractedCall {
+                callee,
+                caller: caller.name.clone(),
+                receiver: None,
+                line,
+            });
+        }
+    }
+
+    calls
+}
+
+/// The symbol name a reflective call site targets, if `node` is one.
+fn dynamic_call_target(node: &Node, source: &[u8], lang: SupportedLanguage) -> Option<String> {
+    let text = |n: Node| n.utf8_text(source).ok().map(str::trim);
+
+    match (lang, node.kind()) {
+        (SupportedLanguage::Python, "call") => {
+            let function = node.child_by_field_name("function")?;
+            if text(function)? != "getattr" {
+                return None;
+            }
+            string_literal(text(argument(node, 1)?)?)
+        }
+        (
+            SupportedLanguage::JavaScript | SupportedLanguage::TypeScript | SupportedLanguage::Tsx,
+            "call_expression",
+        ) => {
+            let function = node.child_by_field_name("function")?;
+            match function.kind() {
+                // obj["save"]()
+                "subscript_expression" => {
+                    string_literal(text(function.child_by_field_name("index")?)?)
+                }
+                // save.call(ctx, ...), save.apply(ctx, args)
+                "member_expression" => {
+                    let property = text(function.child_by_field_name("property")?)?;
+                    if !matches!(property, "call" | "apply") {
+                        return None;
+                    }
+                    let object = text(function.child_by_field_name("object")?)?;
+                    let name = object.rsplit('.').next().unwrap_or(object);
+                    is_identifier(name).then(|| name.to_string())
+                }
+                _ => None,
+            }
+        }
+        (SupportedLanguage::Ruby, "call") => {
+            let method = text(node.child_by_field_name("method")?)?;
+            if !matches!(method, "send" | "public_send" | "__send__") {
+                return None;
+            }
+            let target = text(argument(node, 0)?)?;
+            string_literal(target.strip_prefix(':').unwrap_or(target))
+        }
+        (SupportedLanguage::Java, "method_invocation") => {
+            let method = text(node.child_by_field_name("name")?)?;
+            if !matches!(method, "getMethod" | "getDeclaredMethod") {
+                return None;
+            }
+            string_literal(text(argument(node, 0)?)?)
+        }
+        (SupportedLanguage::Go, "call_expression") => {
+            let function = node.child_by_field_name("function")?;
+            if function.kind() != "selector_expression"
+                || text(function.child_by_field_name("field")?)? != "MethodByName"
+            {
+                return None;
+            }
+            string_literal(text(argument(node, 0)?)?)
+        }
+        _ => None,
+    }
+}
+
+/// The `index`th argument of a call node.
+fn argument<'tree>(call: &Node<'tree>, index: usize) -> Option<Node<'tree>> {
+    let args = call.child_by_field_name("arguments")?;
+    let mut cursor = args.walk();
+    let arg = args.named_children(&mut cursor).nth(index);
+    arg
+}
+
+/// The contents of a quoted string literal, if they form an identifier.
+/// Interpolated or computed strings don't name a single symbol.
+fn string_literal(text: &str) -> Option<String> {
+    let unquoted = text.trim_matches(|c| matches!(c, '"' | '\'' | '`'));
+    is_identifier(unquoted).then(|| unquoted.to_string())
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
 // ─── TypeScript Extraction ──────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn extract_ts_node(
     node: &Node,
     source: &[u8],
@@ -524,9 +1178,10 @@ fn extract_ts_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
 ) {
     // TypeScript shares most node kinds with JavaScript
-    extract_js_node(node, source, kind, current_scope, symbols, imports, calls);
+    extract_js_node(node, source, kind, current_scope, symbols, imports, calls, options);
 
     // TypeScript-specific nodes
     match kind {
@@ -537,8 +1192,15 @@ fn extract_ts_node(
                     kind: NodeKind::Interface,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::TypeScript, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -549,8 +1211,15 @@ fn extract_ts_node(
                     kind: NodeKind::Type,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::TypeScript, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -561,8 +1230,15 @@ fn extract_ts_node(
                     kind: NodeKind::Enum,
                     line_start: node.start_position().row + 1,
                     line_end: node.end_position().row + 1,
-                    code_snippet: bounded_snippet(node, source),
+                    code_snippet: bounded_snippet(node, source, SupportedLanguage::TypeScript, options),
                     parent: None,
+                    visibility: Visibility::Public,
+                    is_test: false,
+                    truncated: false,
+                    value: None,
+                    deprecated: false,
+                    decorators: Vec::new(),
+                    is_async: false,
                 });
             }
         }
@@ -570,11 +1246,10 @@ fn extract_ts_node(
     }
 }
 
-// ─── Generic Extraction (for new languages) ─────────────────────
+// ─── C# Extraction ──────────────────────────────────────────────
 
-/// Generic node extraction for languages without dedicated extractors.
 #[allow(clippy::too_many_arguments)]
-fn extract_generic_node(
+fn extract_csharp_node(
     node: &Node,
     source: &[u8],
     kind: &str,
@@ -582,101 +1257,966 @@ fn extract_generic_node(
     symbols: &mut Vec<ExtractedSymbol>,
     imports: &mut Vec<ExtractedImport>,
     calls: &mut Vec<ExtractedCall>,
-    func_kinds: &[&str],
-    import_kinds: &[&str],
-    call_kinds: &[&str],
+    options: &ExtractOptions,
 ) {
-    // Extract functions/methods
-    if func_kinds.contains(&kind) {
-        if let Some(name) = node_name(node, source) {
-            let sym_kind = if current_scope.is_some() {
-                NodeKind::Method
-            } else {
-                NodeKind::Function
-            };
+    let symbol_kind = match kind {
+        "namespace_declaration" | "file_scoped_namespace_declaration" => Some(NodeKind::Module),
+        "class_declaration" => Some(NodeKind::Class),
+        "struct_declaration" => Some(NodeKind::Struct),
+        // `record struct` is a value type; `record` and `record class` aren't
+        "record_declaration" => {
+            let mut cursor = node.walk();
+            let is_struct = node.children(&mut cursor).any(|c| c.kind() == "struct");
+            Some(if is_struct { NodeKind::Struct } else { NodeKind::Class })
+        }
+        "interface_declaration" => Some(NodeKind::Interface),
+        "enum_declaration" => Some(NodeKind::Enum),
+        "method_declaration" => Some(if current_scope.is_some() {
+            NodeKind::Method
+        } else {
+            NodeKind::Function
+        }),
+        "property_declaration" => Some(NodeKind::Field),
+        // Positional record parameters are properties: `record Point(int X, int Y)`
+        "parameter" => node
+            .parent()
+            .and_then(|list| list.parent())
+            .filter(|owner| owner.kind() == "record_declaration")
+            .map(|_| NodeKind::Field),
+        "field_declaration" => Some(NodeKind::Field),
+        _ => None,
+    };
+
+    if let Some(symbol_kind) = symbol_kind {
+        let names: Vec<String> = if kind == "field_declaration" {
+            declarator_names(node, source)
+        } else {
+            node_name(node, source).into_iter().collect()
+        };
+        for name in names {
             symbols.push(ExtractedSymbol {
                 name,
-                kind: sym_kind,
+                kind: symbol_kind,
                 line_start: node.start_position().row + 1,
                 line_end: node.end_position().row + 1,
-                code_snippet: bounded_snippet(node, source),
+                code_snippet: bounded_snippet(node, source, SupportedLanguage::CSharp, options),
                 parent: current_scope.map(|s| s.to_string()),
+                visibility: Visibility::Public,
+                is_test: false,
+                truncated: false,
+                value: None,
+                deprecated: false,
+                decorators: Vec::new(),
+                is_async: false,
             });
         }
+        return;
     }
 
-    // Extract imports
-    if import_kinds.contains(&kind) {
-        let text = node_text(node, source);
-        imports.push(ExtractedImport {
-            path: text.trim().to_string(),
-            symbols: Vec::new(),
-            line: node.start_position().row + 1,
-        });
-    }
-
-    // Extract calls
-    if call_kinds.contains(&kind) {
-        if let Some(callee_name) = get_call_name(node, source) {
-            if let Some(caller) = current_scope {
+    match kind {
+        "using_directive" => {
+            imports.push(ExtractedImport {
+                path: node_text(node, source).trim().to_string(),
+                symbols: Vec::new(),
+                line: node.start_position().row + 1,
+            });
+        }
+        "invocation_expression" => {
+            if let (Some((callee, receiver)), Some(caller)) = (get_call_name(node, source), current_scope) {
                 calls.push(ExtractedCall {
-                    callee: callee_name,
+                    callee,
                     caller: caller.to_string(),
+                    receiver,
                     line: node.start_position().row + 1,
                 });
             }
         }
+        _ => {}
     }
 }
 
-// ─── Helper Functions ───────────────────────────────────────────
-
-/// Get the name of a node from its "name" field.
-fn node_name(node: &Node, source: &[u8]) -> Option<String> {
-    node.child_by_field_name("name")
-        .and_then(|n| n.utf8_text(source).ok())
-        .map(|s| s.to_string())
-}
-
-/// Get the full text of a node.
-fn node_text(node: &Node, source: &[u8]) -> String {
-    node.utf8_text(source).unwrap_or("").to_string()
-}
-
-/// Maximum lines kept in a code snippet.
-const MAX_SNIPPET_LINES: usize = 50;
-/// Maximum bytes kept in a code snippet.
-const MAX_SNIPPET_BYTES: usize = 8192;
+// ─── Go Extraction ──────────────────────────────────────────────
 
-/// Truncate a code snippet to bounded size (lines and bytes).
-fn bounded_snippet(node: &Node, source: &[u8]) -> String {
-    let raw = node.utf8_text(source).unwrap_or("").to_string();
+#[allow(clippy::too_many_arguments)]
+fn extract_go_node(
+    node: &Node,
+    source: &[u8],
+    kind: &str,
+    current_scope: Option<&str>,
+    symbols: &mut Vec<ExtractedSymbol>,
+    imports: &mut Vec<ExtractedImport>,
+    calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
+) {
+    let symbol = |name: String, kind: NodeKind, def: &Node, parent: Option<String>| ExtractedSymbol {
+        name,
+        kind,
+        line_start: def.start_position().row + 1,
+        line_end: def.end_position().row + 1,
+        code_snippet: bounded_snippet(def, source, SupportedLanguage::Go, options),
+        parent,
+        visibility: Visibility::Public,
+        is_test: false,
+        truncated: false,
+        value: None,
+        deprecated: false,
+        decorators: Vec::new(),
+        is_async: false,
+    };
 
-    // Apply byte limit first
-    let byte_bounded = if raw.len() > MAX_SNIPPET_BYTES {
-        // Find a clean UTF-8 boundary
-        let mut end = MAX_SNIPPET_BYTES;
-        while end > 0 && !raw.is_char_boundary(end) {
-            end -= 1;
-        }
-        let mut s = raw[..end].to_string();
-        s.push_str("\n    // ... (truncated)");
-        s
-    } else {
+    match kind {
+        "function_declaration" => {
+            if let Some(name) = node_name(node, source) {
+                symbols.push(symbol(name, NodeKind::Function, node, None));
+            }
+        }
+        // `func (r *Repo) Save()` belongs to `Repo`
+        "method_declaration" => {
+            if let Some(name) = node_name(node, source) {
+                let receiver = go_receiver_type(node, source);
+                symbols.push(symbol(name, NodeKind::Method, node, receiver));
+            }
+        }
+        // `type User struct {}`, or several specs in `type ( ... )`
+        "type_declaration" => {
+            let mut cursor = node.walk();
+            let specs: Vec<Node> = node
+                .named_children(&mut cursor)
+                .filter(|c| matches!(c.kind(), "type_spec" | "type_alias"))
+                .collect();
+            for spec in &specs {
+                let Some(name) = node_name(spec, source) else {
+                    continue;
+                };
+                let symbol_kind = match spec.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") if spec.kind() == "type_spec" => NodeKind::Struct,
+                    Some("interface_type") if spec.kind() == "type_spec" => NodeKind::Interface,
+                    _ => NodeKind::Type,
+                };
+                // A lone spec keeps its `type` keyword in the snippet
+                let def = if specs.len() == 1 { node } else { spec };
+                symbols.push(symbol(name, symbol_kind, def, current_scope.map(|s| s.to_string())));
+            }
+        }
+        "import_declaration" => {
+            imports.push(ExtractedImport {
+                path: node_text(node, source).trim().to_string(),
+                symbols: Vec::new(),
+                line: node.start_position().row + 1,
+            });
+        }
+        "call_expression" => {
+            if let (Some((callee, receiver)), Some(caller)) = (get_call_name(node, source), current_scope) {
+                calls.push(ExtractedCall {
+                    callee,
+                    caller: caller.to_string(),
+                    receiver,
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The type a Go method is declared on, without pointer or type
+/// parameters: `Repo` for `func (r *Repo[T]) Save()`.
+fn go_receiver_type(method: &Node, source: &[u8]) -> Option<String> {
+    let receiver = method.child_by_field_name("receiver")?;
+    let mut cursor = receiver.walk();
+    let param = receiver
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "parameter_declaration")?;
+    let text = node_text(&param.child_by_field_name("type")?, source);
+    type_base_name(text.trim_start_matches(['*', ' ']))
+}
+
+// ─── Swift Extraction ───────────────────────────────────────────
+
+#[allow(clippy::too_many_arguments)]
+fn extract_swift_node(
+    node: &Node,
+    source: &[u8],
+    kind: &str,
+    current_scope: Option<&str>,
+    symbols: &mut Vec<ExtractedSymbol>,
+    imports: &mut Vec<ExtractedImport>,
+    calls: &mut Vec<ExtractedCall>,
+    options: &ExtractOptions,
+) {
+    // Classes, structs, enums, actors and extensions are all
+    // `class_declaration`, told apart by their keyword
+    let symbol_kind = match kind {
+        "class_declaration" => match node
+            .child_by_field_name("declaration_kind")
+            .map(|k| k.kind())
+        {
+            Some("struct") => Some(NodeKind::Struct),
+            Some("enum") => Some(NodeKind::Enum),
+            Some("extension") => Some(NodeKind::Impl),
+            _ => Some(NodeKind::Class),
+        },
+        "protocol_declaration" => Some(NodeKind::Interface),
+        "typealias_declaration" => Some(NodeKind::Type),
+        "function_declaration" | "protocol_function_declaration" | "init_declaration" => {
+            Some(if current_scope.is_some() {
+                NodeKind::Method
+            } else {
+                NodeKind::Function
+            })
+        }
+        _ => None,
+    };
+
+    if let Some(symbol_kind) = symbol_kind {
+        let name = match kind {
+            "class_declaration" | "protocol_declaration" | "typealias_declaration" => {
+                swift_type_name(node, source)
+            }
+            _ => node_name(node, source),
+        };
+        if let Some(name) = name {
+            symbols.push(ExtractedSymbol {
+                name,
+                kind: symbol_kind,
+                line_start: node.start_position().row + 1,
+                line_end: node.end_position().row + 1,
+                code_snippet: bounded_snippet(node, source, SupportedLanguage::Swift, options),
+                parent: current_scope.map(|s| s.to_string()),
+                visibility: Visibility::Public,
+                is_test: false,
+                truncated: false,
+                value: None,
+                deprecated: false,
+                decorators: Vec::new(),
+                is_async: false,
+            });
+        }
+        return;
+    }
+
+    match kind {
+        "import_declaration" => {
+            imports.push(ExtractedImport {
+                path: node_text(node, source).trim().to_string(),
+                symbols: Vec::new(),
+                line: node.start_position().row + 1,
+            });
+        }
+        // The callee is the first child: `save(user)`, `repo.find(id)`
+        "call_expression" => {
+            let callee = node
+                .named_child(0)
+                .and_then(|callee| split_call_target(callee.utf8_text(source).ok()?, &['.']));
+            if let (Some((callee, receiver)), Some(caller)) = (callee, current_scope) {
+                calls.push(ExtractedCall {
+                    callee,
+                    caller: caller.to_string(),
+                    receiver,
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Name of a Swift type declaration, without generic parameters; an
+/// extension is named after the type it extends.
+fn swift_type_name(node: &Node, source: &[u8]) -> Option<String> {
+    type_base_name(&node_text(&node.child_by_field_name("name")?, source))
+}
+
+// ─── Generic Extraction (for new languages) ─────────────────────
+
+/// Generic node extraction for languages without dedicated extractors.
+#[allow(clippy::too_many_arguments)]
+fn extract_generic_node(
+    node: &Node,
+    source: &[u8],
+    lang: SupportedLanguage,
+    kind: &str,
+    current_scope: Option<&str>,
+    symbols: &mut Vec<ExtractedSymbol>,
+    imports: &mut Vec<ExtractedImport>,
+    calls: &mut Vec<ExtractedCall>,
+    spec: &LanguageSpec,
+    options: &ExtractOptions,
+) {
+    // Extract definitions; a field declaration may declare several
+    // (`int width, height;`)
+    if let Some(def_kind) = spec.symbol_kind(kind) {
+        let names: Vec<String> = if def_kind == NodeKind::Field {
+            declarator_names(node, source)
+        } else {
+            spec_node_name(node, source, spec).into_iter().collect()
+        };
+        for name in names {
+            // Functions nested in a class/module are methods
+            let sym_kind = if def_kind == NodeKind::Function && current_scope.is_some() {
+                NodeKind::Method
+            } else {
+                def_kind
+            };
+            symbols.push(ExtractedSymbol {
+                name,
+                kind: sym_kind,
+                line_start: node.start_position().row + 1,
+                line_end: node.end_position().row + 1,
+                code_snippet: bounded_snippet(node, source, lang, options),
+                parent: current_scope.map(|s| s.to_string()),
+                visibility: Visibility::Public,
+                is_test: false,
+                truncated: false,
+                value: None,
+                deprecated: false,
+                decorators: Vec::new(),
+                is_async: false,
+            });
+        }
+    }
+
+    // Extract imports (Kotlin's `import` keyword shares its statement's kind)
+    if node.is_named() && spec.import_kinds.contains(&kind) {
+        let text = node_text(node, source);
+        imports.push(ExtractedImport {
+            path: text.trim().to_string(),
+            symbols: Vec::new(),
+            line: node.start_position().row + 1,
+        });
+    }
+
+    // Extract calls (some calls, like Ruby's `require`, are really imports)
+    if spec.call_kinds.contains(&kind) {
+        if let Some((callee_name, receiver)) = get_spec_call_name(node, source, spec) {
+            if spec.import_calls.contains(&callee_name.as_str()) {
+                if let Some(path) = get_spec_import_path(node, source) {
+                    imports.push(ExtractedImport {
+                        path,
+                        symbols: Vec::new(),
+                        line: node.start_position().row + 1,
+                    });
+                }
+            } else if let Some(caller) = current_scope {
+                calls.push(ExtractedCall {
+                    callee: callee_name,
+                    caller: caller.to_string(),
+                    receiver,
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+    }
+}
+
+/// Get a definition's name using the language's name field.
+///
+/// C/C++ declarators nest (`function_declarator` -> `identifier`), so we
+/// descend through `declarator` fields; falls back to the `name` field.
+fn spec_node_name(node: &Node, source: &[u8], spec: &LanguageSpec) -> Option<String> {
+    let Some(mut named) = node.child_by_field_name(spec.name_field) else {
+        return node_name(node, source);
+    };
+    while let Some(inner) = named.child_by_field_name("declarator") {
+        named = inner;
+    }
+    named
+        .utf8_text(source)
+        .ok()
+        .map(|s| s.rsplit("::").next().unwrap_or(s).to_string())
+}
+
+/// Names declared by a field declaration: each `declarator` child's name
+/// (Java `int width, height;`), or those of its `variable_declaration`
+/// (C#).
+fn declarator_names(node: &Node, source: &[u8]) -> Vec<String> {
+    let declaration = {
+        let mut cursor = node.walk();
+        let found = node
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "variable_declaration");
+        found.unwrap_or(*node)
+    };
+    let mut cursor = declaration.walk();
+    let names = declaration
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "variable_declarator")
+        .filter_map(|declarator| node_name(&declarator, source))
+        .collect();
+    names
+}
+
+/// Get the callee name from a call node using the language's call field.
+fn get_spec_call_name(
+    node: &Node,
+    source: &[u8],
+    spec: &LanguageSpec,
+) -> Option<(String, Option<String>)> {
+    let func_node = match spec.call_field {
+        "" => node.named_child(0)?,
+        field => node.child_by_field_name(field)?,
+    };
+    let text = func_node.utf8_text(source).ok()?;
+
+    split_call_target(text, &['.', ':'])
+}
+
+/// Get the imported path from an import-like call (`require 'json'`).
+fn get_spec_import_path(node: &Node, source: &[u8]) -> Option<String> {
+    let args = node.child_by_field_name("arguments")?;
+    let text = args.utf8_text(source).ok()?;
+    let path = text
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim()
+        .trim_matches(['"', '\''])
+        .to_string();
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+// ─── Visibility & Test Detection ────────────────────────────────
+
+/// Determine the access level of a definition node.
+fn symbol_visibility(node: &Node, source: &[u8], lang: SupportedLanguage, name: &str) -> Visibility {
+    match lang {
+        SupportedLanguage::Rust => rust_visibility(node, source),
+        SupportedLanguage::Python => {
+            let dunder = name.starts_with("__") && name.ends_with("__");
+            if name.starts_with('_') && !dunder {
+                Visibility::Private
+            } else {
+                Visibility::Public
+            }
+        }
+        SupportedLanguage::Go => {
+            if name.starts_with(|c: char| c.is_uppercase()) {
+                Visibility::Public
+            } else {
+                Visibility::Private
+            }
+        }
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript | SupportedLanguage::Tsx => {
+            js_visibility(node, source)
+        }
+        SupportedLanguage::Java | SupportedLanguage::CSharp | SupportedLanguage::Kotlin => {
+            modifier_visibility(node, source, lang)
+        }
+        _ => Visibility::Public,
+    }
+}
+
+/// JS/TS: class members by their `private`/`protected` modifier or a
+/// `#private` name; everything else by whether it is `export`ed. A file
+/// with no `export` at all (a script, CommonJS) keeps everything public.
+fn js_visibility(node: &Node, source: &[u8]) -> Visibility {
+    let in_class = node.parent().is_some_and(|p| p.kind() == "class_body");
+    if in_class {
+        let keywords = modifier_keywords(node, source);
+        let private_name = node
+            .child_by_field_name("name")
+            .is_some_and(|n| n.kind() == "private_property_identifier");
+        return if private_name || keywords.contains(&"private") {
+            Visibility::Private
+        } else if keywords.contains(&"protected") {
+            Visibility::Protected
+        } else {
+            Visibility::Public
+        };
+    }
+
+    let mut current = Some(*node);
+    let mut root = *node;
+    while let Some(n) = current {
+        match n.kind() {
+            "export_statement" => return Visibility::Public,
+            // Declared inside a function body
+            "statement_block" => return Visibility::Private,
+            _ => {}
+        }
+        root = n;
+        current = n.parent();
+    }
+    let mut cursor = root.walk();
+    let has_exports = root
+        .children(&mut cursor)
+        .any(|c| c.kind() == "export_statement");
+    if has_exports {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    }
+}
+
+/// Java, C# and Kotlin: the access keyword among the declaration's
+/// modifiers, or the language's default — package-private in Java,
+/// `private` for C# members and `internal` for C# types, `public` in
+/// Kotlin. Interface members are public.
+fn modifier_visibility(node: &Node, source: &[u8], lang: SupportedLanguage) -> Visibility {
+    let keywords = modifier_keywords(node, source);
+    if keywords.contains(&"public") {
+        return Visibility::Public;
+    }
+    if keywords.contains(&"private") {
+        return Visibility::Private;
+    }
+    if keywords.contains(&"protected") {
+        return Visibility::Protected;
+    }
+    if keywords.contains(&"internal") {
+        return Visibility::Crate;
+    }
+
+    let in_interface = std::iter::successors(node.parent(), |p| p.parent())
+        .take(2)
+        .any(|p| matches!(p.kind(), "interface_body" | "interface_declaration"));
+    match lang {
+        _ if in_interface => Visibility::Public,
+        SupportedLanguage::Kotlin => Visibility::Public,
+        SupportedLanguage::CSharp => {
+            let is_member = std::iter::successors(node.parent(), |p| p.parent())
+                .take(2)
+                .any(|p| {
+                    matches!(
+                        p.kind(),
+                        "class_declaration" | "struct_declaration" | "record_declaration"
+                    )
+                });
+            if is_member {
+                Visibility::Private
+            } else {
+                Visibility::Crate
+            }
+        }
+        _ => Visibility::Crate,
+    }
+}
+
+/// Words of a declaration's modifier children (`public static`,
+/// `private readonly`), across the grammars' names for them.
+fn modifier_keywords<'a>(node: &Node, source: &'a [u8]) -> Vec<&'a str> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|c| {
+            matches!(
+                c.kind(),
+                "modifiers" | "modifier" | "accessibility_modifier" | "visibility_modifier"
+            )
+        })
+        .flat_map(|c| {
+            std::str::from_utf8(&source[c.byte_range()])
+                .unwrap_or("")
+                .split_whitespace()
+        })
+        .collect()
+}
+
+/// Rust: `pub` is public, `pub(crate)`/`pub(super)`/`pub(in ..)` is crate,
+/// no modifier is private — except trait items and trait impl methods,
+/// which are as visible as the trait, and `#[no_mangle]`/`#[export_name]`
+/// functions, which are exported from the binary.
+fn rust_visibility(node: &Node, source: &[u8]) -> Visibility {
+    let has_attribute = |names: &[&str]| {
+        let mut prev = node.prev_sibling();
+        while let Some(attr) = prev.filter(|p| p.kind() == "attribute_item") {
+            let text = attr.utf8_text(source).unwrap_or("");
+            if names.iter().any(|name| text.contains(name)) {
+                return true;
+            }
+            prev = attr.prev_sibling();
+        }
+        false
+    };
+
+    // `macro_rules!` has no `pub`; `#[macro_export]` exports it from the crate
+    if node.kind() == "macro_definition" {
+        return if has_attribute(&["macro_export"]) {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+    }
+    if node.kind() == "function_item" && has_attribute(&["no_mangle", "export_name"]) {
+        return Visibility::Public;
+    }
+
+    let mut cursor = node.walk();
+    let modifier = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "visibility_modifier");
+
+    if let Some(modifier) = modifier {
+        return if modifier.utf8_text(source).unwrap_or("").trim() == "pub" {
+            Visibility::Public
+        } else {
+            Visibility::Crate
+        };
+    }
+
+    let container = node
+        .parent()
+        .filter(|p| p.kind() == "declaration_list")
+        .and_then(|p| p.parent());
+    match container {
+        Some(c) if c.kind() == "trait_item" => Visibility::Public,
+        Some(c) if c.kind() == "impl_item" && c.child_by_field_name("trait").is_some() => {
+            Visibility::Public
+        }
+        _ => Visibility::Private,
+    }
+}
+
+/// Is this definition test code?
+fn is_test_symbol(node: &Node, source: &[u8], lang: SupportedLanguage, symbol: &ExtractedSymbol) -> bool {
+    match lang {
+        // `#[test]` on the item, or anywhere inside a `#[cfg(test)]` item
+        SupportedLanguage::Rust => {
+            let mut current = Some(*node);
+            while let Some(n) = current {
+                if has_test_attribute(&n, source) {
+                    return true;
+                }
+                current = n.parent();
+            }
+            false
+        }
+        SupportedLanguage::Python => {
+            symbol.name.starts_with("test_")
+                || (symbol.kind == NodeKind::Class && symbol.name.starts_with("Test"))
+                || symbol.parent.as_deref().is_some_and(|p| p.starts_with("Test"))
+        }
+        _ => false,
+    }
+}
+
+/// Check the attributes directly preceding a Rust item for `test`
+/// (`#[test]`, `#[tokio::test]`, `#[cfg(test)]`).
+fn has_test_attribute(node: &Node, source: &[u8]) -> bool {
+    let mut prev = node.prev_sibling();
+    while let Some(attr) = prev {
+        if attr.kind() != "attribute_item" {
+            break;
+        }
+        let text = attr.utf8_text(source).unwrap_or("");
+        let mentions_test = text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == "test");
+        if mentions_test && !text.contains("not(test") {
+            return true;
+        }
+        prev = attr.prev_sibling();
+    }
+    false
+}
+
+/// Lines at the top of a file searched for a `@generated` marker.
+const GENERATED_MARKER_LINES: usize = 5;
+
+/// Does the file header carry the `@generated` marker code generators
+/// write (`// @generated by protoc`, `# @generated`)?
+fn has_generated_marker(source: &str) -> bool {
+    source
+        .lines()
+        .take(GENERATED_MARKER_LINES)
+        .any(|line| line.contains("@generated"))
+}
+
+/// Names of the decorators on a Python `decorated_definition`, without
+/// their arguments: `@app.route("/")` -> `app.route`.
+fn python_decorators(decorated: &Node, source: &[u8]) -> Vec<String> {
+    let mut cursor = decorated.walk();
+    let decorators = decorated
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "decorator")
+        .filter_map(|decorator| {
+            let expr = decorator.named_child(0)?;
+            let target = match expr.kind() {
+                "call" => expr.child_by_field_name("function")?,
+                _ => expr,
+            };
+            let name: String = node_text(&target, source).split_whitespace().collect();
+            (!name.is_empty()).then_some(name)
+        })
+        .collect();
+    decorators
+}
+
+/// Is this definition marked deprecated?
+///
+/// Rust `#[deprecated]`, Python `@deprecated` decorators, Java/Kotlin
+/// `@Deprecated`, C# `[Obsolete]`, a Go doc comment paragraph starting
+/// with `Deprecated:`, and `@deprecated` in a doc comment (JSDoc, Javadoc).
+fn is_deprecated(node: &Node, source: &[u8], lang: SupportedLanguage) -> bool {
+    let mentions = |text: &str, word: &str| {
+        text.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|w| w == word)
+    };
+
+    let comments = leading_comments(node, source);
+    if comments.contains("@deprecated") {
+        return true;
+    }
+
+    match lang {
+        SupportedLanguage::Rust => {
+            let mut prev = node.prev_sibling();
+            while let Some(sibling) = prev {
+                match sibling.kind() {
+                    "attribute_item" => {
+                        if mentions(sibling.utf8_text(source).unwrap_or(""), "deprecated") {
+                            return true;
+                        }
+                    }
+                    "line_comment" | "block_comment" => {}
+                    _ => break,
+                }
+                prev = sibling.prev_sibling();
+            }
+            false
+        }
+        SupportedLanguage::Python => node
+            .parent()
+            .filter(|p| p.kind() == "decorated_definition")
+            .is_some_and(|p| {
+                let mut cursor = p.walk();
+                let found = p
+                    .children(&mut cursor)
+                    .filter(|c| c.kind() == "decorator")
+                    .any(|d| mentions(d.utf8_text(source).unwrap_or(""), "deprecated"));
+                found
+            }),
+        SupportedLanguage::Go => comments
+            .lines()
+            .any(|l| l.trim_start_matches(['/', ' ']).starts_with("Deprecated:")),
+        SupportedLanguage::Java | SupportedLanguage::Kotlin | SupportedLanguage::CSharp => {
+            let mut cursor = node.walk();
+            let found = node
+                .children(&mut cursor)
+                .filter(|c| matches!(c.kind(), "modifiers" | "attribute_list"))
+                .any(|c| {
+                    let text = c.utf8_text(source).unwrap_or("");
+                    mentions(text, "Deprecated") || mentions(text, "Obsolete")
+                });
+            found
+        }
+        _ => false,
+    }
+}
+
+/// Is this function declared `async`?
+///
+/// Rust `async fn`, Python `async def`, JS/TS `async function`, async
+/// methods and arrow functions (`const load = async () => ...`), and C#
+/// `async` methods.
+fn is_async_definition(node: &Node, source: &[u8], name: &str) -> bool {
+    let has_async = |node: Node| {
+        let mut cursor = node.walk();
+        let found = node.children(&mut cursor).any(|child| match child.kind() {
+            "async" => true,
+            "function_modifiers" | "modifier" => child
+                .utf8_text(source)
+                .is_ok_and(|text| text.split_whitespace().any(|word| word == "async")),
+            _ => false,
+        });
+        found
+    };
+    // An arrow function or function expression assigned to a name
+    let value = match node.kind() {
+        "lexical_declaration" | "variable_declaration" => (0..node.named_child_count())
+            .filter_map(|i| node.named_child(i))
+            .find(|d| d.kind() == "variable_declarator" && node_name(d, source).as_deref() == Some(name))
+            .and_then(|d| d.child_by_field_name("value")),
+        _ => None,
+    };
+    has_async(*node) || value.is_some_and(has_async)
+}
+
+/// Text of the comments directly above a definition (or above the
+/// `export` statement wrapping it), nearest last.
+fn leading_comments(node: &Node, source: &[u8]) -> String {
+    let anchor = node
+        .parent()
+        .filter(|p| p.kind() == "export_statement")
+        .unwrap_or(*node);
+
+    let mut comments = Vec::new();
+    let mut prev = anchor.prev_sibling();
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            k if k.contains("comment") => comments.push(sibling.utf8_text(source).unwrap_or("")),
+            // Rust attributes sit between a doc comment and its item
+            "attribute_item" => {}
+            _ => break,
+        }
+        prev = sibling.prev_sibling();
+    }
+    comments.reverse();
+    comments.join("\n")
+}
+
+/// Is this a test file by naming convention?
+///
+/// Covers `tests/` and `__tests__/` directories, `test_*.py`, `*_test.go`,
+/// `*_test.py`, and `*.test.*` / `*.spec.*` (JS/TS).
+pub fn is_test_path(path: &Path) -> bool {
+    let in_test_dir = path.components().any(|c| {
+        matches!(c.as_os_str().to_str(), Some("tests" | "test" | "__tests__"))
+    });
+    if in_test_dir {
+        return true;
+    }
+
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let stem = name.split('.').next().unwrap_or(name);
+    stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+// ─── Helper Functions ───────────────────────────────────────────
+
+/// Get the name of a node from its "name" field.
+fn node_name(node: &Node, source: &[u8]) -> Option<String> {
+    node.child_by_field_name("name")
+        .and_then(|n| n.utf8_text(source).ok())
+        .map(|s| s.to_string())
+}
+
+/// `ALL_CAPS` names (letters, digits, underscores, at least one letter).
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Get the full text of a node.
+fn node_text(node: &Node, source: &[u8]) -> String {
+    node.utf8_text(source).unwrap_or("").to_string()
+}
+
+/// Maximum lines kept in a code snippet.
+const MAX_SNIPPET_LINES: usize = 50;
+/// Maximum bytes kept in a code snippet.
+const MAX_SNIPPET_BYTES: usize = 8192;
+
+/// Lines kept from the top of a snippet whose signature falls past the
+/// line limit (the first decorators or attributes).
+const SNIPPET_HEAD_LINES: usize = 3;
+
+/// Truncate a code snippet to bounded size (lines and bytes).
+///
+/// The cut is clean: no marker is added here. `extract_file_with` appends
+/// the configured marker to symbols flagged as truncated. The exception is
+/// a signature past the line limit (behind a long run of decorators or
+/// attributes): it is kept, and what came before is dropped, marked by a
+/// `// ...` comment in the language's syntax unless markers are off.
+fn bounded_snippet(node: &Node, source: &[u8], lang: SupportedLanguage, options: &ExtractOptions) -> String {
+    let raw = node.utf8_text(source).unwrap_or("").to_string();
+
+    // Apply line limit, keeping the signature
+    let lines: Vec<&str> = raw.lines().collect();
+    let line_bounded = if lines.len() <= MAX_SNIPPET_LINES {
         raw
+    } else {
+        match signature_rows(node, source) {
+            Some((start, end)) if end >= MAX_SNIPPET_LINES && start > SNIPPET_HEAD_LINES => {
+                let indent = &lines[start][..lines[start].len() - lines[start].trim_start().len()];
+                let marker = options
+                    .truncation_marker
+                    .is_some()
+                    .then(|| format!("{}{} ...", indent, lang.line_comment()));
+                let tail = MAX_SNIPPET_LINES - SNIPPET_HEAD_LINES - 1;
+                lines[..SNIPPET_HEAD_LINES]
+                    .iter()
+                    .copied()
+                    .chain(marker.as_deref())
+                    .chain(lines[start..].iter().take(tail).copied())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            _ => lines[..MAX_SNIPPET_LINES].join("\n"),
+        }
     };
 
-    // Apply line limit
-    let lines: Vec<&str> = byte_bounded.lines().collect();
-    if lines.len() <= MAX_SNIPPET_LINES {
-        byte_bounded
+    // Apply byte limit
+    if line_bounded.len() > MAX_SNIPPET_BYTES {
+        // Find a clean UTF-8 boundary
+        let mut end = MAX_SNIPPET_BYTES;
+        while end > 0 && !line_bounded.is_char_boundary(end) {
+            end -= 1;
+        }
+        line_bounded[..end].to_string()
     } else {
-        let mut truncated: String = lines[..MAX_SNIPPET_LINES].join("\n");
-        truncated.push_str("\n    // ...");
-        truncated
+        line_bounded
     }
 }
 
+/// First and last line of a definition's signature, relative to the node:
+/// from its name to the line opening the body (`{`, `:`, `=>`).
+fn signature_rows(node: &Node, source: &[u8]) -> Option<(usize, usize)> {
+    // A Python `decorated_definition` wraps the function
+    let def = node.child_by_field_name("definition").unwrap_or(*node);
+    let start = def.child_by_field_name("name")?.start_position().row;
+    let body = def.child_by_field_name("body")?;
+
+    // A `{` body opens on the signature's last line; anything else (a
+    // Python block, an arrow's expression) follows the last code before it
+    let end = if source.get(body.start_byte()) == Some(&b'{') {
+        body.start_position().row
+    } else {
+        let before = &source[def.start_byte()..body.start_byte()];
+        let code = before
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .unwrap_or(0);
+        def.start_position().row + before[..code].iter().filter(|&&b| b == b'\n').count()
+    };
+    let base = node.start_position().row;
+    Some((start - base, end - base))
+}
+
+/// Maximum bytes kept of a constant's initializer.
+const MAX_VALUE_BYTES: usize = 120;
+
+/// The initializer expression of a constant definition, bounded in size.
+///
+/// Handles Rust `const`/`static` items, JS `const X = ...` declarators and
+/// Python module-level assignments.
+fn constant_value(node: &Node, source: &[u8], name: &str) -> Option<String> {
+    let value = match node.kind() {
+        "const_item" | "static_item" => node.child_by_field_name("value"),
+        "assignment" => node.child_by_field_name("right"),
+        "lexical_declaration" | "variable_declaration" => (0..node.named_child_count())
+            .filter_map(|i| node.named_child(i))
+            .find(|d| d.kind() == "variable_declarator" && node_name(d, source).as_deref() == Some(name))
+            .and_then(|d| d.child_by_field_name("value")),
+        _ => None,
+    }?;
+
+    let text = value.utf8_text(source).ok()?.trim();
+    if text.len() <= MAX_VALUE_BYTES {
+        return Some(text.to_string());
+    }
+    let mut end = MAX_VALUE_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(format!("{}...", &text[..end]))
+}
+
+/// Whether `bounded_snippet` cuts this node short.
+fn snippet_truncated(node: &Node, source: &[u8]) -> bool {
+    let raw = node.utf8_text(source).unwrap_or("");
+    raw.len() > MAX_SNIPPET_BYTES || raw.lines().count() > MAX_SNIPPET_LINES
+}
+
 /// Get the type name from a Rust impl block.
 /// Handles `impl Foo` and `impl Trait for Foo`.
 fn get_rust_impl_name(node: &Node, source: &[u8]) -> Option<String> {
@@ -710,37 +2250,69 @@ fn get_rust_impl_name(node: &Node, source: &[u8]) -> Option<String> {
     }
 }
 
-/// Get the function name from a call_expression node (Rust/JS/TS).
-fn get_call_name(node: &Node, source: &[u8]) -> Option<String> {
+/// Get the function name and receiver from a call_expression node
+/// (Rust/JS/TS).
+fn get_call_name(node: &Node, source: &[u8]) -> Option<(String, Option<String>)> {
     let func_node = node.child_by_field_name("function")?;
     let text = func_node.utf8_text(source).ok()?;
 
-    // Handle method calls: obj.method() -> "method"
+    // Handle method calls: obj.method() -> "method" on "obj"
     // Handle simple calls: func() -> "func"
-    // Handle namespaced: mod::func() -> "func"
-    let name = text.rsplit(['.', ':']).next().unwrap_or(text).trim();
-
-    if name.is_empty() {
-        None
-    } else {
-        Some(name.to_string())
-    }
+    // Handle namespaced: mod::func() -> "func" on "mod"
+    split_call_target(text, &['.', ':'])
 }
 
-/// Get the function name from a Python call node.
-fn get_python_call_name(node: &Node, source: &[u8]) -> Option<String> {
+/// Get the function name and receiver from a Python call node.
+fn get_python_call_name(node: &Node, source: &[u8]) -> Option<(String, Option<String>)> {
     let func_node = node.child_by_field_name("function")?;
     let text = func_node.utf8_text(source).ok()?;
 
-    // Handle: obj.method() -> "method"
+    // Handle: obj.method() -> "method" on "obj"
     // Handle: func() -> "func"
-    let name = text.rsplit('.').next().unwrap_or(text).trim();
+    split_call_target(text, &['.'])
+}
 
+/// Split a call target like `config::validate`, `self.db.find` or
+/// `Vec::<u8>::new` into the called name and the path before it.
+///
+/// A chained call (`load().validate`) only names its last method; its
+/// receiver is another call's result, not a path, so it is dropped.
+fn split_call_target(text: &str, separators: &[char]) -> Option<(String, Option<String>)> {
+    let path = strip_type_arguments(text);
+    let (receiver, name) = match path.rfind(separators) {
+        Some(i) => (&path[..i], &path[i + 1..]),
+        None => ("", path.as_str()),
+    };
+    let name = name.trim();
     if name.is_empty() {
-        None
-    } else {
-        Some(name.to_string())
+        return None;
+    }
+
+    let receiver = receiver.trim_end_matches(separators).trim_end_matches('?').trim();
+    let receiver = (!receiver.is_empty() && !receiver.contains(['(', '[']))
+        .then(|| receiver.split_whitespace().collect::<String>());
+    Some((name.to_string(), receiver))
+}
+
+/// Drop generic arguments from a call target: `Vec::<u8>::new` ->
+/// `Vec::new`, `parse::<u32>` -> `parse`.
+fn strip_type_arguments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '<' => {
+                if depth == 0 && out.ends_with("::") {
+                    out.truncate(out.len() - 2);
+                }
+                depth += 1;
+            }
+            '>' if depth > 0 => depth -= 1,
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
     }
+    out
 }
 
 /// Extract variable declarations that define functions or constants (JS/TS).
@@ -749,6 +2321,7 @@ fn extract_js_variable_declaration(
     source: &[u8],
     current_scope: Option<&str>,
     symbols: &mut Vec<ExtractedSymbol>,
+    options: &ExtractOptions,
 ) {
     let child_count = node.child_count();
     for i in 0..child_count {
@@ -775,8 +2348,15 @@ fn extract_js_variable_declaration(
                         kind,
                         line_start: node.start_position().row + 1,
                         line_end: node.end_position().row + 1,
-                        code_snippet: bounded_snippet(node, source),
+                        code_snippet: bounded_snippet(node, source, SupportedLanguage::JavaScript, options),
                         parent: current_scope.map(|s| s.to_string()),
+                        visibility: Visibility::Public,
+                        is_test: false,
+                        truncated: false,
+                        value: None,
+                        deprecated: false,
+                        decorators: Vec::new(),
+                        is_async: false,
                     });
                 }
             }
@@ -825,3 +2405,76 @@ fn extract_js_import(node: &Node, source: &[u8], imports: &mut Vec<ExtractedImpo
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const SOURCES: &[(&str, &str)] = &[
+        ("src/lib.rs", "pub struct Config;\n\nimpl Config {\n    pub fn load() -> Self {\n        Config\n    }\n}\n\nfn main() {\n    Config::load();\n}\n"),
+        ("app/views.py", "import os\n\nclass View:\n    def render(self):\n        return os.getcwd()\n"),
+        ("web/app.ts", "export function greet(name: string): string {\n  return format(name);\n}\n"),
+        ("src/broken.rs", "fn broken( {\n"),
+        ("src/util.rs", "pub fn helper() -> u32 {\n    1\n}\n"),
+    ];
+
+    fn clear_parsers() {
+        PARSERS.with(|parsers| parsers.borrow_mut().clear());
+    }
+
+    fn extract(path: &str, source: &str) -> String {
+        format!("{:?}", extract_file(Path::new(path), source).unwrap())
+    }
+
+    #[test]
+    fn test_reused_parser_matches_fresh_parser() {
+        let fresh: Vec<String> = SOURCES
+            .iter()
+            .map(|(path, source)| {
+                clear_parsers();
+                extract(path, source)
+            })
+            .collect();
+
+        // Languages interleave and a malformed file sits in between
+        clear_parsers();
+        for _ in 0..3 {
+            let reused: Vec<String> = SOURCES.iter().map(|(path, source)| extract(path, source)).collect();
+            assert_eq!(reused, fresh);
+        }
+        PARSERS.with(|parsers| assert_eq!(parsers.borrow().len(), 3));
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with --ignored --nocapture"]
+    fn bench_parser_reuse() {
+        const ROUNDS: usize = 500;
+        let files = ROUNDS * SOURCES.len();
+
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            for (path, source) in SOURCES {
+                clear_parsers();
+                extract(path, source);
+            }
+        }
+        let fresh = start.elapsed();
+
+        clear_parsers();
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            for (path, source) in SOURCES {
+                extract(path, source);
+            }
+        }
+        let reused = start.elapsed();
+
+        println!(
+            "fresh parser: {:?}/file, reused parser: {:?}/file",
+            fresh / files as u32,
+            reused / files as u32
+        );
+        assert!(reused < fresh);
+    }
+}
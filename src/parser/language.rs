@@ -1,9 +1,12 @@
 //! Language detection and tree-sitter grammar loading.
 
 use serde::{Deserialize, Serialize};
+use std::io::Read;
 use std::path::Path;
 use tree_sitter::Language;
 
+use crate::graph::types::NodeKind;
+
 /// Supported programming languages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SupportedLanguage {
@@ -16,6 +19,7 @@ pub enum SupportedLanguage {
     Java,
     CSharp,
     Ruby,
+    Kotlin,
     Cpp,
     Swift,
 }
@@ -34,13 +38,75 @@ impl SupportedLanguage {
             "java" => Some(SupportedLanguage::Java),
             "cs" => Some(SupportedLanguage::CSharp),
             "rb" => Some(SupportedLanguage::Ruby),
-            // "kt" | "kts" => Some(SupportedLanguage::Kotlin),  // Disabled: tree-sitter version conflict
+            "kt" | "kts" => Some(SupportedLanguage::Kotlin),
             "cpp" | "cc" | "cxx" | "hpp" | "h" => Some(SupportedLanguage::Cpp),
             "swift" => Some(SupportedLanguage::Swift),
             _ => None,
         }
     }
 
+    /// Detect language from a shebang line (`#!/usr/bin/env python3`).
+    ///
+    /// Handles both direct interpreter paths and `env` (with or without
+    /// `-S`). Version suffixes like `python3.11` are ignored.
+    pub fn from_shebang(first_line: &str) -> Option<Self> {
+        let command = first_line.strip_prefix("#!")?.trim();
+        let mut words = command.split_whitespace();
+        let mut interpreter = words.next()?.rsplit('/').next()?;
+        if interpreter == "env" {
+            interpreter = words.find(|w| !w.starts_with('-'))?;
+        }
+
+        let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        match name {
+            "python" | "pypy" => Some(SupportedLanguage::Python),
+            "node" | "nodejs" => Some(SupportedLanguage::JavaScript),
+            "deno" | "bun" | "ts-node" | "tsx" => Some(SupportedLanguage::TypeScript),
+            "ruby" => Some(SupportedLanguage::Ruby),
+            "rust-script" => Some(SupportedLanguage::Rust),
+            "swift" => Some(SupportedLanguage::Swift),
+            _ => None,
+        }
+    }
+
+    /// Detect language from the extension, falling back to the shebang
+    /// for extensionless files.
+    pub fn detect(path: &Path, source: &str) -> Option<Self> {
+        Self::from_path(path).or_else(|| {
+            if path.extension().is_some() {
+                return None;
+            }
+            Self::from_shebang(source.lines().next()?)
+        })
+    }
+
+    /// Detect language for a file on disk.
+    ///
+    /// Like [`detect`](Self::detect), but only reads the first bytes of
+    /// extensionless files, and skips files too large to be a script.
+    pub fn from_file(path: &Path) -> Option<Self> {
+        /// Extensionless files larger than this aren't sniffed.
+        const MAX_SCRIPT_BYTES: u64 = 1024 * 1024;
+
+        if let Some(lang) = Self::from_path(path) {
+            return Some(lang);
+        }
+        if path.extension().is_some() {
+            return None;
+        }
+
+        let file = std::fs::File::open(path).ok()?;
+        if file.metadata().ok()?.len() > MAX_SCRIPT_BYTES {
+            return None;
+        }
+        let mut head = Vec::with_capacity(256);
+        file.take(256).read_to_end(&mut head).ok()?;
+
+        // Binary files won't have a UTF-8 first line
+        let first_line = head.split(|&b| b == b'\n').next()?;
+        Self::from_shebang(std::str::from_utf8(first_line).ok()?)
+    }
+
     /// Get the tree-sitter Language for this language.
     pub fn tree_sitter_language(&self) -> Language {
         match self {
@@ -53,6 +119,7 @@ impl SupportedLanguage {
             SupportedLanguage::Java => tree_sitter_java::LANGUAGE.into(),
             SupportedLanguage::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
             SupportedLanguage::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            SupportedLanguage::Kotlin => tree_sitter_kotlin_ng::LANGUAGE.into(),
             SupportedLanguage::Cpp => tree_sitter_cpp::LANGUAGE.into(),
             SupportedLanguage::Swift => tree_sitter_swift::LANGUAGE.into(),
         }
@@ -70,11 +137,59 @@ impl SupportedLanguage {
             SupportedLanguage::Java => "Java",
             SupportedLanguage::CSharp => "C#",
             SupportedLanguage::Ruby => "Ruby",
+            SupportedLanguage::Kotlin => "Kotlin",
             SupportedLanguage::Cpp => "C++",
             SupportedLanguage::Swift => "Swift",
         }
     }
 
+    /// The token starting a line comment (`//`, or `#` for Python and Ruby).
+    pub fn line_comment(&self) -> &'static str {
+        match self {
+            SupportedLanguage::Python | SupportedLanguage::Ruby => "#",
+            _ => "//",
+        }
+    }
+
+    /// Node-kind table for languages handled by the generic extractor.
+    ///
+    /// Returns `None` for languages with a dedicated extractor
+    /// (Rust, Python, JavaScript, TypeScript, C#, Go, Swift).
+    pub fn generic_spec(&self) -> Option<&'static LanguageSpec> {
+        match self {
+            SupportedLanguage::Java => Some(&JAVA_SPEC),
+            SupportedLanguage::Ruby => Some(&RUBY_SPEC),
+            SupportedLanguage::Kotlin => Some(&KOTLIN_SPEC),
+            SupportedLanguage::Cpp => Some(&CPP_SPEC),
+            _ => None,
+        }
+    }
+
+    /// Builtin and standard-library names that parse as calls but never
+    /// refer to project code (`len()`, `Some(..)`, `print()`).
+    ///
+    /// Calls to these names are dropped during extraction so they don't
+    /// attach to an unrelated project symbol that happens to share the name.
+    /// Methods of builtin objects are listed with their receiver
+    /// (`console.log`), so only those calls are dropped; names projects
+    /// commonly define themselves (`parse`, `push`, `size`) aren't listed.
+    pub fn call_stopwords(&self) -> &'static [&'static str] {
+        match self {
+            SupportedLanguage::Rust => RUST_CALL_STOPWORDS,
+            SupportedLanguage::Python => PYTHON_CALL_STOPWORDS,
+            SupportedLanguage::JavaScript | SupportedLanguage::TypeScript | SupportedLanguage::Tsx => {
+                JS_CALL_STOPWORDS
+            }
+            SupportedLanguage::Go => GO_CALL_STOPWORDS,
+            SupportedLanguage::Java => JAVA_CALL_STOPWORDS,
+            SupportedLanguage::CSharp => CSHARP_CALL_STOPWORDS,
+            SupportedLanguage::Ruby => RUBY_CALL_STOPWORDS,
+            SupportedLanguage::Kotlin => KOTLIN_CALL_STOPWORDS,
+            SupportedLanguage::Cpp => CPP_CALL_STOPWORDS,
+            SupportedLanguage::Swift => SWIFT_CALL_STOPWORDS,
+        }
+    }
+
     /// Check if two languages are in the same ecosystem (can call each other).
     pub fn same_ecosystem(&self, other: &Self) -> bool {
         match (self, other) {
@@ -89,8 +204,11 @@ impl SupportedLanguage {
             (SupportedLanguage::Tsx, SupportedLanguage::TypeScript) => true,
             (SupportedLanguage::Tsx, SupportedLanguage::Tsx) => true,
 
-            // JVM ecosystem
+            // JVM ecosystem (Kotlin and Java call each other)
             (SupportedLanguage::Java, SupportedLanguage::Java) => true,
+            (SupportedLanguage::Java, SupportedLanguage::Kotlin) => true,
+            (SupportedLanguage::Kotlin, SupportedLanguage::Java) => true,
+            (SupportedLanguage::Kotlin, SupportedLanguage::Kotlin) => true,
 
             // Each other language is its own ecosystem
             (SupportedLanguage::Python, SupportedLanguage::Python) => true,
@@ -106,3 +224,139 @@ impl SupportedLanguage {
         }
     }
 }
+
+// ─── Call Stopwords ─────────────────────────────────────────────
+
+const RUST_CALL_STOPWORDS: &[&str] = &[
+    "Some", "Ok", "Err", "Box", "Vec", "String", "Rc", "Arc", "len", "is_empty",
+    "to_string", "to_owned", "into", "unwrap", "expect", "println", "print", "eprintln",
+    "format", "vec", "panic", "assert", "assert_eq", "writeln",
+];
+
+const PYTHON_CALL_STOPWORDS: &[&str] = &[
+    "print", "len", "range", "str", "int", "float", "bool", "list", "dict", "set", "tuple",
+    "isinstance", "super", "open", "enumerate", "zip", "map", "filter", "sorted", "type",
+    "getattr", "setattr", "hasattr", "repr", "min", "max", "sum", "any", "all",
+];
+
+const JS_CALL_STOPWORDS: &[&str] = &[
+    "parseInt", "parseFloat", "String", "Number", "Boolean", "Array", "Object", "Promise",
+    "setTimeout", "console.log", "console.error", "console.warn", "JSON.parse",
+    "JSON.stringify",
+];
+
+const GO_CALL_STOPWORDS: &[&str] = &[
+    "len", "cap", "append", "make", "new", "panic", "print", "println", "copy", "delete",
+    "fmt.Println", "fmt.Printf", "fmt.Sprintf", "fmt.Errorf",
+];
+
+const JAVA_CALL_STOPWORDS: &[&str] = &[
+    "System.out.println", "System.out.print", "equals", "toString", "hashCode",
+    "String.valueOf",
+];
+
+const CSHARP_CALL_STOPWORDS: &[&str] = &[
+    "Console.WriteLine", "Console.Write", "ToString", "Equals", "GetHashCode", "nameof",
+];
+
+const RUBY_CALL_STOPWORDS: &[&str] = &[
+    "puts", "print", "p", "raise", "attr_accessor", "attr_reader", "attr_writer",
+];
+
+const KOTLIN_CALL_STOPWORDS: &[&str] = &[
+    "println", "print", "listOf", "mapOf", "setOf", "mutableListOf", "mutableMapOf", "require",
+    "check", "error", "TODO", "let", "apply", "also", "run", "with", "lazy",
+];
+
+const CPP_CALL_STOPWORDS: &[&str] = &[
+    "printf", "move", "forward", "make_shared", "make_unique",
+];
+
+const SWIFT_CALL_STOPWORDS: &[&str] = &["print", "fatalError", "precondition"];
+
+// ─── Generic Extractor Specs ────────────────────────────────────
+
+/// Which tree-sitter node kinds mean what, for the generic extractor.
+///
+/// Adding or correcting a language is a matter of editing its table.
+#[derive(Debug)]
+pub struct LanguageSpec {
+    /// Definition node kinds and the symbol kind each produces.
+    /// `Function` becomes `Method` when nested in another definition.
+    pub symbol_kinds: &'static [(&'static str, NodeKind)],
+    /// Node kinds that are import statements.
+    pub import_kinds: &'static [&'static str],
+    /// Callee names that are really imports (Ruby `require`).
+    pub import_calls: &'static [&'static str],
+    /// Node kinds that are calls.
+    pub call_kinds: &'static [&'static str],
+    /// Field on a call node holding the callee, or `""` when the callee
+    /// is the call's first child (Kotlin).
+    pub call_field: &'static str,
+    /// Field on a definition node holding its name.
+    pub name_field: &'static str,
+}
+
+impl LanguageSpec {
+    /// The symbol kind produced by a definition node, if it is one.
+    pub fn symbol_kind(&self, node_kind: &str) -> Option<NodeKind> {
+        self.symbol_kinds
+            .iter()
+            .find(|(k, _)| *k == node_kind)
+            .map(|(_, kind)| *kind)
+    }
+}
+
+static JAVA_SPEC: LanguageSpec = LanguageSpec {
+    symbol_kinds: &[
+        ("method_declaration", NodeKind::Function),
+        ("class_declaration", NodeKind::Class),
+        ("interface_declaration", NodeKind::Interface),
+        ("field_declaration", NodeKind::Field),
+    ],
+    import_kinds: &["import_declaration"],
+    import_calls: &[],
+    call_kinds: &["method_invocation"],
+    call_field: "name",
+    name_field: "name",
+};
+
+static RUBY_SPEC: LanguageSpec = LanguageSpec {
+    symbol_kinds: &[
+        ("method", NodeKind::Function),
+        ("singleton_method", NodeKind::Function),
+        ("class", NodeKind::Class),
+        ("module", NodeKind::Module),
+    ],
+    // Ruby has no import statement; `require` is an ordinary call
+    import_kinds: &[],
+    import_calls: &["require", "require_relative", "load"],
+    call_kinds: &["call"],
+    call_field: "method",
+    name_field: "name",
+};
+
+static KOTLIN_SPEC: LanguageSpec = LanguageSpec {
+    symbol_kinds: &[
+        ("function_declaration", NodeKind::Function),
+        ("class_declaration", NodeKind::Class),
+        ("object_declaration", NodeKind::Class),
+    ],
+    import_kinds: &["import"],
+    import_calls: &[],
+    call_kinds: &["call_expression"],
+    call_field: "",
+    name_field: "name",
+};
+
+static CPP_SPEC: LanguageSpec = LanguageSpec {
+    symbol_kinds: &[
+        ("function_definition", NodeKind::Function),
+        ("class_specifier", NodeKind::Class),
+    ],
+    import_kinds: &["preproc_include"],
+    import_calls: &[],
+    call_kinds: &["call_expression"],
+    call_field: "function",
+    name_field: "declarator",
+};
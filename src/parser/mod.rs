@@ -5,6 +5,9 @@
 
 pub mod extractor;
 pub mod language;
+pub mod queries;
 
-pub use extractor::extract_file;
+pub use extractor::{
+    extract_file, extract_file_with, extract_files, ExtractOptions, DEFAULT_TRUNCATION_MARKER,
+};
 pub use language::SupportedLanguage;
@@ -15,6 +15,8 @@ use super::go::extract_go_apis;
 use super::java::extract_java_apis;
 use super::csharp::extract_csharp_apis;
 use super::ruby::extract_ruby_apis;
+use super::kotlin::extract_kotlin_apis;
+use super::rust::extract_rust_apis;
 
 /// Extract API endpoints from a parsed AST.
 ///
@@ -45,8 +47,14 @@ pub fn extract_api_endpoints(
         SupportedLanguage::Ruby => {
             extract_ruby_apis(root, source)
         }
+        SupportedLanguage::Kotlin => {
+            extract_kotlin_apis(root, source)
+        }
+        SupportedLanguage::Rust => {
+            extract_rust_apis(root, source)
+        }
         // Languages without API detection yet
-        SupportedLanguage::Rust | SupportedLanguage::Cpp | SupportedLanguage::Swift => {
+        SupportedLanguage::Cpp | SupportedLanguage::Swift => {
             Vec::new()
         }
     }
@@ -140,14 +140,14 @@ fn normalize_url(url: &str) -> String {
             // Go path params: :id or *filepath
             ':' | '*' => {
                 result.push(':');
-                while chars.peek().map_or(false, |c| c.is_alphanumeric() || *c == '_') {
+                while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
                     chars.next();
                 }
                 result.push_str("param");
             }
             // Curly brace style: {id}
             '{' => {
-                while let Some(c2) = chars.next() {
+                for c2 in chars.by_ref() {
                     if c2 == '}' {
                         break;
                     }
@@ -224,7 +224,7 @@ fn normalize_url(url: &str) -> String {
     while let Some(c) = chars.next() {
         if c == '{' {
             // Spring path variable: {id}
-            while let Some(c2) = chars.next() {
+            for c2 in chars.by_ref() {
                 if c2 == '}' {
                     break;
                 }
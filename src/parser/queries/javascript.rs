@@ -244,7 +244,7 @@ fn normalize_url(url: &str) -> String {
             '$' if chars.peek() == Some(&'{') => {
                 chars.next(); // consume '{'
                 let mut depth = 1;
-                while let Some(c2) = chars.next() {
+                for c2 in chars.by_ref() {
                     if c2 == '{' {
                         depth += 1;
                     } else if c2 == '}' {
@@ -257,16 +257,16 @@ fn normalize_url(url: &str) -> String {
                 result.push_str(":param");
             }
             // Express/path style: :id (but not ::)
-            ':' if chars.peek().map_or(false, |c| c.is_alphabetic()) => {
+            ':' if chars.peek().is_some_and(|c| c.is_alphabetic()) => {
                 result.push(':');
-                while chars.peek().map_or(false, |c| c.is_alphanumeric() || *c == '_') {
+                while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
                     chars.next();
                 }
                 result.push_str("param");
             }
             // Curly brace style: {id}
             '{' => {
-                while let Some(c2) = chars.next() {
+                for c2 in chars.by_ref() {
                     if c2 == '}' {
                         break;
                     }
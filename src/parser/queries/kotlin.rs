@@ -26,7 +26,7 @@ fn extract_from_node(
 
     // Track function scope
     let new_scope = if kind == "function_declaration" {
-        node.child_by_field_name("simple_identifier")
+        node.child_by_field_name("name")
             .and_then(|n| n.utf8_text(source).ok())
             .map(|s| s.to_string())
     } else {
@@ -178,7 +178,7 @@ fn normalize_url(url: &str) -> String {
     while let Some(c) = chars.next() {
         if c == '{' {
             // Ktor/Spring path parameter: {id}
-            while let Some(c2) = chars.next() {
+            for c2 in chars.by_ref() {
                 if c2 == '}' {
                     break;
                 }
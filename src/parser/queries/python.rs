@@ -271,7 +271,7 @@ fn normalize_url(url: &str) -> String {
         match c {
             // Python f-string or path param: {id} or {user_id}
             '{' => {
-                while let Some(c2) = chars.next() {
+                for c2 in chars.by_ref() {
                     if c2 == '}' {
                         break;
                     }
@@ -280,7 +280,7 @@ fn normalize_url(url: &str) -> String {
             }
             // Flask/Werkzeug style: <id> or <int:id>
             '<' => {
-                while let Some(c2) = chars.next() {
+                for c2 in chars.by_ref() {
                     if c2 == '>' {
                         break;
                     }
@@ -127,7 +127,7 @@ fn normalize_url(url: &str) -> String {
         if c == ':' {
             // Rails route parameter: :id
             result.push(':');
-            while chars.peek().map_or(false, |c| c.is_alphanumeric() || *c == '_') {
+            while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
                 chars.next();
             }
             result.push_str("param");
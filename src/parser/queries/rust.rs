@@ -0,0 +1,364 @@
+//! Rust API endpoint detection via AST traversal.
+//!
+//! Detects axum and actix-web routes:
+//!   - axum: Router::new().route("/api/users/:id", get(show).delete(remove))
+//!   - actix: #[get("/api/users/{id}")] async fn show() {}
+//!   - actix: web::resource("/api/users").route(web::get().to(list))
+//!   - actix: App::new().route("/api/users", web::post().to(create))
+
+use tree_sitter::Node;
+use crate::graph::types::{ExtractedApiEndpoint, ApiEndpointKind};
+
+/// A method and handler found in a route expression; either may be unknown.
+type Route = (Option<&'static str>, Option<String>);
+
+/// Extract API endpoints from Rust AST.
+pub fn extract_rust_apis(root: &Node, source: &[u8]) -> Vec<ExtractedApiEndpoint> {
+    let mut endpoints = Vec::new();
+    extract_from_node(root, source, &mut endpoints, None);
+    endpoints
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &[u8],
+    endpoints: &mut Vec<ExtractedApiEndpoint>,
+    current_scope: Option<&str>,
+) {
+    let kind = node.kind();
+
+    // Track function scope
+    let new_scope = if kind == "function_item" {
+        node.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+            .map(|s| s.to_string())
+    } else {
+        None
+    };
+    let scope = new_scope.as_deref().or(current_scope);
+
+    match kind {
+        "function_item" => {
+            if let Some(endpoint) = extract_route_from_attributes(node, source, scope) {
+                endpoints.push(endpoint);
+            }
+        }
+        "call_expression" => endpoints.extend(extract_routes_from_call(node, source, scope)),
+        _ => {}
+    }
+
+    // Recurse
+    let count = node.child_count();
+    for i in 0..count {
+        if let Some(child) = node.child(i) {
+            extract_from_node(&child, source, endpoints, scope);
+        }
+    }
+}
+
+/// Extract an actix route attribute on a handler: `#[get("/api/users")]`,
+/// or `#[route("/api/users", method = "POST")]`.
+fn extract_route_from_attributes(
+    node: &Node,
+    source: &[u8],
+    scope: Option<&str>,
+) -> Option<ExtractedApiEndpoint> {
+    // Attributes are the function's preceding siblings
+    let mut sibling = node.prev_sibling();
+    while let Some(attr_item) = sibling {
+        match attr_item.kind() {
+            "attribute_item" => {}
+            "line_comment" | "block_comment" => {
+                sibling = attr_item.prev_sibling();
+                continue;
+            }
+            _ => break,
+        }
+        sibling = attr_item.prev_sibling();
+
+        let Some(attribute) = attr_item.named_child(0) else { continue };
+        let Some(path) = attribute.named_child(0) else { continue };
+        let Some(args) = attribute.child_by_field_name("arguments") else { continue };
+        let Some(name) = last_segment(&path, source) else { continue };
+
+        // Skip #[instrument], #[allow(...)] and the like
+        let method = match name {
+            "route" => route_attribute_method(&args, source),
+            _ => match http_method(name) {
+                Some(method) => Some(method),
+                None => continue,
+            },
+        };
+        let Some(url) = get_first_string_arg(&args, source) else { continue };
+        if !is_api_url(&url) {
+            return None;
+        }
+
+        return Some(ExtractedApiEndpoint {
+            url: normalize_url(&url),
+            method: method.map(|s| s.to_string()),
+            kind: ApiEndpointKind::Defines,
+            scope: scope.map(|s| s.to_string()),
+            line: attr_item.start_position().row + 1,
+        });
+    }
+    None
+}
+
+/// Extract routes from `.route("/path", ...)` on an axum `Router` or actix
+/// `App`/`Scope`, or from an actix `web::resource("/path")` chain.
+fn extract_routes_from_call(
+    node: &Node,
+    source: &[u8],
+    scope: Option<&str>,
+) -> Vec<ExtractedApiEndpoint> {
+    let Some(func) = node.child_by_field_name("function") else { return Vec::new() };
+    let Some(args) = node.child_by_field_name("arguments") else { return Vec::new() };
+    let Some(url_node) = args.named_child(0).filter(|n| is_string(n)) else { return Vec::new() };
+
+    let mut routes = match func.kind() {
+        "field_expression" if field_name(&func, source) == Some("route") => match args.named_child(1) {
+            Some(expr) => route_methods(&expr, source),
+            None => return Vec::new(),
+        },
+        "identifier" | "scoped_identifier" if last_segment(&func, source) == Some("resource") => {
+            resource_methods(node, source)
+        }
+        _ => return Vec::new(),
+    };
+
+    let Some(url) = url_node.utf8_text(source).ok().map(strip_quotes) else { return Vec::new() };
+    if !is_api_url(&url) {
+        return Vec::new();
+    }
+    if routes.is_empty() {
+        routes.push((None, None));
+    }
+
+    routes
+        .into_iter()
+        .map(|(method, handler)| ExtractedApiEndpoint {
+            url: normalize_url(&url),
+            method: method.map(|s| s.to_string()),
+            kind: ApiEndpointKind::Defines,
+            scope: handler.or_else(|| scope.map(|s| s.to_string())),
+            line: url_node.start_position().row + 1,
+        })
+        .collect()
+}
+
+/// Methods and handlers of a route expression: axum's
+/// `get(show).post(create)` or actix's `web::get().to(list)`.
+fn route_methods(node: &Node, source: &[u8]) -> Vec<Route> {
+    if node.kind() != "call_expression" {
+        return Vec::new();
+    }
+    let Some(func) = node.child_by_field_name("function") else { return Vec::new() };
+    let handler = node
+        .child_by_field_name("arguments")
+        .and_then(|args| args.named_child(0))
+        .and_then(|arg| handler_name(&arg, source));
+
+    match func.kind() {
+        // get(show).post(create), web::get().to(list)
+        "field_expression" => {
+            let mut routes = func
+                .child_by_field_name("value")
+                .map(|value| route_methods(&value, source))
+                .unwrap_or_default();
+            match field_name(&func, source) {
+                Some("to") if routes.is_empty() => routes.push((None, handler)),
+                Some("to") => {
+                    for route in &mut routes {
+                        route.1 = handler.clone();
+                    }
+                }
+                Some(name) => {
+                    if let Some(method) = http_method(name) {
+                        routes.push((Some(method), handler));
+                    }
+                }
+                None => {}
+            }
+            routes
+        }
+        // get(show), routing::post(create), web::get(), any(fallback), web::to(list)
+        "identifier" | "scoped_identifier" => match last_segment(&func, source) {
+            Some("any" | "to") => vec![(None, handler)],
+            Some(name) => http_method(name).map(|m| vec![(Some(m), handler)]).unwrap_or_default(),
+            None => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Methods and handlers chained onto `web::resource("/path")`:
+/// `.route(web::get().to(list))`, `.to(handler)`, `.get(handler)`.
+fn resource_methods(node: &Node, source: &[u8]) -> Vec<Route> {
+    let mut routes = Vec::new();
+    let mut current = *node;
+
+    while let Some(field) = current.parent().filter(|p| p.kind() == "field_expression") {
+        let Some(call) = field.parent().filter(|p| p.kind() == "call_expression") else { break };
+        let arg = call
+            .child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0));
+
+        match field_name(&field, source) {
+            Some("route") => routes.extend(arg.map(|a| route_methods(&a, source)).unwrap_or_default()),
+            Some("to") => routes.push((None, arg.and_then(|a| handler_name(&a, source)))),
+            Some(name) => {
+                if let Some(method) = http_method(name) {
+                    routes.push((Some(method), arg.and_then(|a| handler_name(&a, source))));
+                }
+            }
+            None => {}
+        }
+        current = call;
+    }
+    routes
+}
+
+/// The method from `method = "POST"` in a `#[route(...)]` attribute.
+fn route_attribute_method(args: &Node, source: &[u8]) -> Option<&'static str> {
+    let mut after_method = false;
+    let count = args.child_count();
+    for i in 0..count {
+        if let Some(child) = args.child(i) {
+            let text = child.utf8_text(source).ok()?;
+            match child.kind() {
+                "identifier" => after_method = text == "method",
+                "string_literal" if after_method => {
+                    return http_method(&strip_quotes(text).to_lowercase());
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// HTTP method for a method helper name (`get`, `post`, ...).
+fn http_method(name: &str) -> Option<&'static str> {
+    match name {
+        "get" => Some("GET"),
+        "post" => Some("POST"),
+        "put" => Some("PUT"),
+        "delete" => Some("DELETE"),
+        "patch" => Some("PATCH"),
+        "head" => Some("HEAD"),
+        "options" => Some("OPTIONS"),
+        "trace" => Some("TRACE"),
+        _ => None,
+    }
+}
+
+/// Handler function name: `show`, or `show` from `handlers::show`.
+fn handler_name(node: &Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "identifier" | "scoped_identifier" => last_segment(node, source).map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+/// Last segment of a path: `get` in `web::get`.
+fn last_segment<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    match node.kind() {
+        "identifier" => node.utf8_text(source).ok(),
+        "scoped_identifier" => node.child_by_field_name("name")?.utf8_text(source).ok(),
+        _ => None,
+    }
+}
+
+/// Field name of a field expression: `route` in `router.route`.
+fn field_name<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    node.child_by_field_name("field")?.utf8_text(source).ok()
+}
+
+fn is_string(node: &Node) -> bool {
+    node.kind() == "string_literal" || node.kind() == "raw_string_literal"
+}
+
+fn get_first_string_arg(args: &Node, source: &[u8]) -> Option<String> {
+    let count = args.child_count();
+    for i in 0..count {
+        if let Some(child) = args.child(i) {
+            if is_string(&child) {
+                let text = child.utf8_text(source).ok()?;
+                return Some(strip_quotes(text));
+            }
+        }
+    }
+    None
+}
+
+fn strip_quotes(s: &str) -> String {
+    let s = s.trim();
+
+    // Handle r"raw" and r#"raw"#
+    let s = s.strip_prefix('r').map(|r| r.trim_matches('#')).unwrap_or(s);
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        s[1..s.len()-1].to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+fn normalize_url(url: &str) -> String {
+    let mut result = String::new();
+    let mut chars = url.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // axum 0.7 path params: :id or *rest
+            ':' | '*' => {
+                result.push(':');
+                while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+                    chars.next();
+                }
+                result.push_str("param");
+            }
+            // actix and axum 0.8: {id}, {id:\d+} or {*rest}
+            '{' => {
+                for c2 in chars.by_ref() {
+                    if c2 == '}' {
+                        break;
+                    }
+                }
+                result.push_str(":param");
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn is_api_url(url: &str) -> bool {
+    let url = url.to_lowercase();
+    url.starts_with("/api/")
+        || url.starts_with("/v1/")
+        || url.starts_with("/v2/")
+        || url.starts_with("/v3/")
+        || url.contains("/api/")
+        || (url.starts_with('/') && url.len() > 1 && !url.contains('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("/api/users/:id"), "/api/users/:param");
+        assert_eq!(normalize_url("/api/users/{id}"), "/api/users/:param");
+        assert_eq!(normalize_url("/api/users/{id:\\d+}/files/{*rest}"), "/api/users/:param/files/:param");
+    }
+
+    #[test]
+    fn test_strip_quotes() {
+        assert_eq!(strip_quotes("\"/api/users\""), "/api/users");
+        assert_eq!(strip_quotes("r\"/api/users\""), "/api/users");
+        assert_eq!(strip_quotes("r#\"/api/users\"#"), "/api/users");
+    }
+}
@@ -1,23 +1,58 @@
 //! Context engine - the main query interface for AI agents.
 //!
-//! Three intents:
+//! Intents:
 //! - `explore`: "What is this? How does it work?"
 //! - `change`: "I'm modifying this - what breaks?"
 //! - `create`: "I'm adding something like this - show me patterns"
+//! - `overview`: symbol plus graph statistics
+//!
+//! Other documented names map onto these via [`INTENT_ALIASES`].
 
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::Path;
 
-use crate::graph::{CodeGraph, DependencyInfo, SearchResult};
+use crate::graph::engine::LookupCache;
+use crate::graph::signature::types_match;
+use crate::graph::{
+    BlameCache, CodeGraph, DependencyInfo, EdgeKind, SearchResult, SignatureShape, SymbolFilter,
+    TraversalDirection, Visibility,
+};
 
+use super::search::dedup_results;
 use super::types::{ContextResponse, Edit, Reference, Signature, Symbol};
 
+/// Alternate intent names and the intent each one runs.
+///
+/// Unknown intents still fall back to `explore`.
+pub const INTENT_ALIASES: &[(&str, &str)] = &[
+    ("understand", "explore"),
+    ("find", "explore"),
+    ("modify", "change"),
+    ("refactor", "change"),
+];
+
+/// Minimum similarity score (0.0-1.0) for a symbol to be offered as a
+/// `create` pattern.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.2;
+
+/// Resolve an intent alias to the intent it runs (`modify` → `change`).
+pub fn canonical_intent(intent: &str) -> &str {
+    INTENT_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == intent)
+        .map_or(intent, |(_, target)| target)
+}
+
 /// Get context for a symbol based on intent.
 ///
 /// Intents:
 /// - `explore` (default): Symbol + what it uses + what uses it
 /// - `change`: Symbol + dependents + suggested edits + tests to update
-/// - `create`: Symbol + similar patterns in codebase
+/// - `create`: Symbol + similar patterns in codebase (scored against
+///   [`DEFAULT_SIMILARITY_THRESHOLD`])
+/// - `tests`: Symbol + the tests covering it and the files they live in
+/// - `overview`: Symbol (if any) + graph statistics
 pub fn get_context(graph: &CodeGraph, query: &str, intent: &str) -> ContextResponse {
     get_context_for_change(graph, query, intent, None)
 }
@@ -42,14 +77,105 @@ pub fn get_context_for_change(
     intent: &str,
     new_signature: Option<&str>,
 ) -> ContextResponse {
+    get_context_filtered(graph, query, intent, new_signature, &SymbolFilter::default())
+}
+
+/// Get context, choosing which symbols to match with `filter`.
+///
+/// The default filter hides test functions; pass `include_tests: true`
+/// when debugging a test itself.
+pub fn get_context_filtered(
+    graph: &CodeGraph,
+    query: &str,
+    intent: &str,
+    new_signature: Option<&str>,
+    filter: &SymbolFilter,
+) -> ContextResponse {
+    get_context_with_threshold(
+        graph,
+        query,
+        intent,
+        new_signature,
+        filter,
+        DEFAULT_SIMILARITY_THRESHOLD,
+    )
+}
+
+/// Get context with an explicit similarity threshold for `create` patterns.
+///
+/// Raise the threshold to only see close structural matches; `0.0` offers
+/// every symbol of the same kind, best match first.
+pub fn get_context_with_threshold(
+    graph: &CodeGraph,
+    query: &str,
+    intent: &str,
+    new_signature: Option<&str>,
+    filter: &SymbolFilter,
+    similarity_threshold: f64,
+) -> ContextResponse {
+    let mut cache = LookupCache::default();
+    context_with(graph, query, intent, new_signature, filter, similarity_threshold, &mut cache)
+}
+
+/// Explore contexts for every symbol defined in `path`, keyed by name.
+///
+/// Each entry equals `get_context(graph, name, "explore")`, but name and
+/// import lookups are shared across the file's symbols instead of being
+/// recomputed per symbol. Test symbols are left out, as `get_context`
+/// hides them. The backend for "explain this file".
+pub fn get_file_contexts(graph: &CodeGraph, path: &str) -> BTreeMap<String, ContextResponse> {
+    get_file_contexts_with(graph, path, &mut LookupCache::default())
+}
+
+fn get_file_contexts_with(
+    graph: &CodeGraph,
+    path: &str,
+    cache: &mut LookupCache,
+) -> BTreeMap<String, ContextResponse> {
+    let filter = SymbolFilter::default();
+    let mut contexts = BTreeMap::new();
+    for node in graph.symbols_in_file(Path::new(path)) {
+        if !filter.allows(node) || contexts.contains_key(&node.name) {
+            continue;
+        }
+        let context = context_with(
+            graph,
+            &node.name,
+            "explore",
+            None,
+            &filter,
+            DEFAULT_SIMILARITY_THRESHOLD,
+            cache,
+        );
+        contexts.insert(node.name.clone(), context);
+    }
+    contexts
+}
+
+/// [`get_context_with_threshold`], reusing graph lookups from `cache`.
+fn context_with(
+    graph: &CodeGraph,
+    query: &str,
+    intent: &str,
+    new_signature: Option<&str>,
+    filter: &SymbolFilter,
+    similarity_threshold: f64,
+    cache: &mut LookupCache,
+) -> ContextResponse {
+    let intent = canonical_intent(intent);
     let mut response = ContextResponse {
         query: query.to_string(),
         intent: intent.to_string(),
         ..Default::default()
     };
 
+    // Overview has stats even when the symbol isn't found
+    if intent == "overview" {
+        response.stats = Some(graph.stats());
+    }
+
     // Find the symbol first
-    let results = graph.search(query, 5);
+    let results = dedup_results(graph.search_filtered_with(query, 5, filter, cache));
     if results.is_empty() {
         return response;
     }
@@ -60,13 +186,32 @@ pub fn get_context_for_change(
     match intent {
         "explore" => explore(graph, query, &results, &mut response),
         "change" => change(graph, query, &results, new_signature, &mut response),
-        "create" => create(graph, query, &results, &mut response),
+        "create" => create(graph, &results, similarity_threshold, &mut response),
+        "tests" => tests(graph, query, &mut response),
+        "overview" => {}
         _ => explore(graph, query, &results, &mut response), // default
     }
 
     response
 }
 
+/// Fill in `last_modified` for the symbols of a context response.
+///
+/// Runs `git blame` once per file, so it is opt-in rather than part of
+/// every context query. Symbols outside git are left without one.
+pub fn attach_blame(graph: &CodeGraph, response: &mut ContextResponse) {
+    let mut cache = BlameCache::new();
+    for symbol in &mut response.symbols {
+        let file = Path::new(&symbol.file);
+        let line_end = graph
+            .symbols_in_file(file)
+            .into_iter()
+            .find(|node| node.name == symbol.name && node.line_start == symbol.line)
+            .map_or(symbol.line, |node| node.line_end);
+        symbol.last_modified = cache.range(file, symbol.line, line_end);
+    }
+}
+
 /// Explore intent: understand what something is and how it connects.
 fn explore(
     graph: &CodeGraph,
@@ -75,11 +220,11 @@ fn explore(
     response: &mut ContextResponse,
 ) {
     // What uses this symbol (dependents)
-    let dependents = graph.dependents(query);
+    let dependents = graph.neighbors(query, TraversalDirection::Inbound, &[], &[]);
     response.used_by = dependents.iter().map(Reference::from_dep).collect();
 
     // What this symbol uses (dependencies)
-    let dependencies = graph.dependencies(query);
+    let dependencies = graph.neighbors(query, TraversalDirection::Outbound, &[], &[]);
     response.uses = dependencies.iter().map(Reference::from_dep).collect();
 }
 
@@ -87,7 +232,7 @@ fn explore(
 fn change(
     graph: &CodeGraph,
     query: &str,
-    _results: &[SearchResult],
+    results: &[SearchResult],
     new_signature: Option<&str>,
     response: &mut ContextResponse,
 ) {
@@ -119,6 +264,85 @@ fn change(
 
     // Find related tests
     response.tests = find_tests(graph, query);
+
+    if let Some(target) = results.first() {
+        response.warnings = change_warnings(graph, target, &dependents);
+    }
+}
+
+/// Cautions about changing `target` itself: it is generated or deprecated,
+/// it overrides or implements a method of a base type, or it is public API
+/// called from other files.
+fn change_warnings(
+    graph: &CodeGraph,
+    target: &SearchResult,
+    dependents: &[DependencyInfo],
+) -> Vec<String> {
+    let name = &target.symbol;
+    let mut warnings = Vec::new();
+
+    if target.generated {
+        warnings.push(format!(
+            "{} is generated code in {}; change its generator or source instead, edits will be overwritten",
+            name,
+            target.file.display()
+        ));
+    }
+
+    if target.deprecated {
+        warnings.push(format!(
+            "{} is deprecated; consider moving callers to its replacement instead",
+            name
+        ));
+    }
+
+    // Container (class, impl) -> base it extends or implements -> same-named method
+    let containers = graph.neighbors(name, TraversalDirection::Inbound, &[], &[EdgeKind::Contains]);
+    for container in containers.iter().filter(|c| c.file == target.file) {
+        let bases = graph.neighbors(
+            &container.symbol,
+            TraversalDirection::Outbound,
+            &[],
+            &[EdgeKind::Extends, EdgeKind::Implements],
+        );
+        for base in bases {
+            let base_defines = graph
+                .neighbors(&base.symbol, TraversalDirection::Outbound, &[], &[EdgeKind::Contains])
+                .iter()
+                .any(|member| member.symbol == *name);
+            if !base_defines {
+                continue;
+            }
+            let verb = match base.relationship {
+                EdgeKind::Implements => "implements",
+                _ => "overrides",
+            };
+            let warning = format!(
+                "{} {} {}::{}; editing may diverge from the base behavior",
+                name, verb, base.symbol, name
+            );
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    if target.visibility == Visibility::Public {
+        let external = dependents
+            .iter()
+            .filter(|d| d.relationship == EdgeKind::Calls && d.file != target.file)
+            .count();
+        if external > 0 {
+            warnings.push(format!(
+                "{} is public API with {} external caller{}",
+                name,
+                external,
+                if external == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    warnings
 }
 
 /// Extract function signature from code snippet.
@@ -181,6 +405,9 @@ fn build_edit(
             new_args: vec![],
             removed_args: vec![],
             context: vec![],
+            actual_args: 0,
+            expected_args: None,
+            arity_ok: true,
         });
     }
 
@@ -191,6 +418,11 @@ fn build_edit(
     // Get context from the file if possible
     let context = get_context_lines(&dep.file, actual_line, 2);
 
+    // Check the call's argument count against the new signature
+    let actual_args = extract_call_args(usage_expr).len();
+    let expected_args = new_sig.as_ref().map(|sig| sig.required_args());
+    let arity_ok = new_sig.as_ref().is_none_or(|sig| sig.accepts_arity(actual_args));
+
     // Generate suggested fix if we have signature diff
     let (suggested, new_args, removed_args) = match (new_sig, sig_diff) {
         (Some(new_sig), Some((added, removed))) => {
@@ -215,6 +447,9 @@ fn build_edit(
         new_args,
         removed_args,
         context,
+        actual_args,
+        expected_args,
+        arity_ok,
     })
 }
 
@@ -342,7 +577,7 @@ fn extract_call_expression(code: &str) -> Option<String> {
 }
 
 /// Read context lines from a file around a specific line.
-fn get_context_lines(file_path: &Path, line: usize, context_size: usize) -> Vec<String> {
+pub(crate) fn get_context_lines(file_path: &Path, line: usize, context_size: usize) -> Vec<String> {
     let Ok(content) = fs::read_to_string(file_path) else {
         return vec![];
     };
@@ -372,15 +607,26 @@ fn get_context_lines(file_path: &Path, line: usize, context_size: usize) -> Vec<
 /// Create intent: find similar patterns to follow.
 fn create(
     graph: &CodeGraph,
-    _query: &str,
     results: &[SearchResult],
+    threshold: f64,
     response: &mut ContextResponse,
 ) {
     if let Some(reference) = results.first() {
-        response.patterns = find_similar(graph, reference);
+        response.patterns = find_similar(graph, reference, threshold);
     }
 }
 
+/// Tests intent: which tests cover this, and where they live. Only the
+/// tests are looked up, so the response stays small.
+fn tests(graph: &CodeGraph, query: &str, response: &mut ContextResponse) {
+    response.tests = find_tests(graph, query);
+
+    let mut files: Vec<String> = response.tests.iter().map(|t| t.file.clone()).collect();
+    files.sort();
+    files.dedup();
+    response.test_files = files;
+}
+
 /// Find test functions related to a symbol.
 fn find_tests(graph: &CodeGraph, symbol: &str) -> Vec<Symbol> {
     let mut tests = Vec::new();
@@ -415,32 +661,122 @@ fn find_tests(graph: &CodeGraph, symbol: &str) -> Vec<Symbol> {
     tests
 }
 
-/// Find similar symbols (same kind, nearby files).
-fn find_similar(graph: &CodeGraph, reference: &SearchResult) -> Vec<Symbol> {
-    let mut similar = Vec::new();
+/// Find symbols to use as patterns for a new one like `reference`.
+///
+/// Candidates of the same kind are scored by code similarity (Jaccard over
+/// token shingles) blended with signature shape (parameter count and types,
+/// return type). Candidates scoring below `threshold` are dropped. The rest
+/// are grouped by file, best file first, so examples that live together
+/// are shown together.
+fn find_similar(graph: &CodeGraph, reference: &SearchResult, threshold: f64) -> Vec<Symbol> {
+    let reference_shingles = shingles(&reference.code, &reference.symbol);
+    let reference_shape = SignatureShape::from_code(&reference.code, &reference.file);
+
+    let mut scored: Vec<(f64, SearchResult)> = graph
+        .all_symbols()
+        .into_iter()
+        .filter(|r| r.kind == reference.kind && r.symbol != reference.symbol && !r.is_test)
+        .filter_map(|candidate| {
+            let candidate_shingles = shingles(&candidate.code, &candidate.symbol);
+            let code_score = jaccard(&reference_shingles, &candidate_shingles);
+            let shape = SignatureShape::from_code(&candidate.code, &candidate.file);
+            let score = match (&reference_shape, &shape) {
+                (Some(a), Some(b)) => 0.6 * code_score + 0.4 * shape_similarity(a, b),
+                _ => code_score,
+            };
+            (score >= threshold).then_some((score, candidate))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| a.1.file.cmp(&b.1.file))
+            .then_with(|| a.1.line_start.cmp(&b.1.line_start))
+    });
+
+    // Group by file, files ordered by their best candidate
+    let mut files: Vec<&Path> = Vec::new();
+    for (_, candidate) in &scored {
+        if !files.contains(&candidate.file.as_path()) {
+            files.push(&candidate.file);
+        }
+    }
 
-    // Get symbols of the same kind
-    let all = graph.search("", 100);
-    for result in all {
-        if result.kind == reference.kind && result.symbol != reference.symbol {
-            // Prefer same directory
-            let same_dir = result.file.parent() == reference.file.parent();
-            if same_dir || similar.len() < 3 {
-                similar.push(Symbol::from_search_result(&result));
-            }
-            if similar.len() >= 5 {
-                break;
-            }
+    files
+        .iter()
+        .flat_map(|file| scored.iter().filter(move |(_, c)| c.file == *file))
+        .take(5)
+        .map(|(_, candidate)| Symbol::from_search_result(candidate))
+        .collect()
+}
+
+/// Size of the token windows compared by `find_similar`.
+const SHINGLE_SIZE: usize = 3;
+
+/// Overlapping `SHINGLE_SIZE`-token windows of `code`.
+///
+/// Tokens are identifiers, numbers and single punctuation characters. The
+/// symbol's own name is replaced by a placeholder so it doesn't count
+/// against otherwise identical code.
+fn shingles(code: &str, name: &str) -> HashSet<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in code.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
         }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    for token in tokens.iter_mut().filter(|t| *t == name) {
+        *token = "$name".to_string();
     }
 
-    similar
+    tokens.windows(SHINGLE_SIZE).map(<[String]>::to_vec).collect()
+}
+
+/// Jaccard similarity of two shingle sets (0.0 when both are empty).
+fn jaccard(a: &HashSet<Vec<String>>, b: &HashSet<Vec<String>>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Fraction of signature positions (each parameter plus the return type)
+/// whose types agree. Different parameter counts score zero.
+fn shape_similarity(a: &SignatureShape, b: &SignatureShape) -> f64 {
+    if a.params.len() != b.params.len() {
+        return 0.0;
+    }
+    let params = a
+        .params
+        .iter()
+        .zip(&b.params)
+        .filter(|(x, y)| types_match(x, y))
+        .count();
+    let ret = match (&a.return_type, &b.return_type) {
+        (Some(x), Some(y)) => types_match(x, y),
+        (None, None) => true,
+        _ => false,
+    };
+
+    (params + usize::from(ret)) as f64 / (a.params.len() + 1) as f64
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use super::super::types::Signature;
+    use crate::graph::NodeKind;
     use crate::parser;
     use std::path::PathBuf;
 
@@ -481,6 +817,59 @@ fn test_process() {
         assert_eq!(response.intent, "explore");
     }
 
+    #[test]
+    fn test_file_contexts_match_per_symbol() {
+        let graph = build_test_graph();
+
+        let mut bulk = LookupCache::default();
+        let contexts = get_file_contexts_with(&graph, "src/lib.rs", &mut bulk);
+
+        let names: Vec<&str> = contexts.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["process", "transform", "validate"]);
+
+        let mut per_symbol = 0;
+        for (name, context) in &contexts {
+            let mut cache = LookupCache::default();
+            let single = context_with(
+                &graph,
+                name,
+                "explore",
+                None,
+                &SymbolFilter::default(),
+                DEFAULT_SIMILARITY_THRESHOLD,
+                &mut cache,
+            );
+            per_symbol += cache.computed;
+            assert_eq!(
+                serde_json::to_value(context).unwrap(),
+                serde_json::to_value(&single).unwrap()
+            );
+            assert_eq!(
+                serde_json::to_value(&single).unwrap(),
+                serde_json::to_value(get_context(&graph, name, "explore")).unwrap()
+            );
+        }
+
+        // One import list, instead of one per symbol
+        assert_eq!(bulk.computed, 1);
+        assert_eq!(per_symbol, 3);
+    }
+
+    #[test]
+    fn test_tests_intent() {
+        let graph = build_test_graph();
+        let response = get_context(&graph, "process", "tests");
+
+        assert!(response.found);
+        assert_eq!(response.intent, "tests");
+        let tests: Vec<&str> = response.tests.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tests, vec!["test_process"]);
+        assert_eq!(response.test_files, vec!["src/lib.rs"]);
+        // No explore/change payload
+        assert!(response.used_by.is_empty() && response.uses.is_empty());
+        assert!(response.edits.is_empty());
+    }
+
     #[test]
     fn test_change_intent() {
         let graph = build_test_graph();
@@ -491,6 +880,109 @@ fn test_process() {
         // Should have edits for dependents
     }
 
+    #[test]
+    fn test_change_warns_about_deprecated_target() {
+        let source = "#[deprecated(note = \"use parse_v2\")]\npub fn parse_v1(s: &str) -> u32 {\n    s.len() as u32\n}\n\npub fn parse_v2(s: &str) -> u32 {\n    parse_v1(s)\n}\n";
+        let extraction = parser::extract_file(&PathBuf::from("src/parse.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let response = get_context(&graph, "parse_v1", "change");
+        assert_eq!(response.warnings.len(), 1, "{:?}", response.warnings);
+        assert!(response.warnings[0].contains("parse_v1 is deprecated"));
+
+        // Same-file callers aren't external
+        assert!(get_context(&graph, "parse_v2", "change").warnings.is_empty());
+    }
+
+    #[test]
+    fn test_change_warns_about_generated_code() {
+        use crate::graph::CodeGraphBuilder;
+        use std::fs;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("generated")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("generated/api.rs"), "pub fn fetch_user() -> u32 {\n    1\n}\n").unwrap();
+        fs::write(
+            root.join("src/schema.rs"),
+            "// @generated by schema-gen. Do not edit.\n\npub fn schema_version() -> u32 {\n    2\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub fn load() -> u32 {\n    fetch_user() + schema_version()\n}\n",
+        )
+        .unwrap();
+
+        let graph = CodeGraphBuilder::new(root).generated("generated/").build();
+
+        let response = get_context(&graph, "fetch_user", "change");
+        assert!(response.symbols[0].generated);
+        assert!(
+            response.warnings.iter().any(|w| w.contains("fetch_user is generated code")),
+            "{:?}",
+            response.warnings
+        );
+        // The header marks a file without any configured pattern
+        let response = get_context(&graph, "schema_version", "change");
+        assert!(response.warnings.iter().any(|w| w.contains("schema_version is generated code")));
+        assert!(!get_context(&graph, "load", "explore").symbols[0].generated);
+
+        let filter = SymbolFilter {
+            include_generated: false,
+            ..SymbolFilter::all()
+        };
+        assert!(graph.search_filtered("fetch_user", 10, &filter).is_empty());
+        assert_eq!(graph.search_filtered("load", 10, &filter).len(), 1);
+    }
+
+    #[test]
+    fn test_change_warns_about_override() {
+        let mut graph = CodeGraph::new();
+        let child_file = PathBuf::from("src/worker.py");
+        let base_file = PathBuf::from("src/base.py");
+        let child_run = graph.add_symbol("run".to_string(), NodeKind::Method, child_file.clone(), 2, 3, String::new());
+        let child = graph.add_symbol("Worker".to_string(), NodeKind::Class, child_file, 1, 3, String::new());
+        let base_run = graph.add_symbol("run".to_string(), NodeKind::Method, base_file.clone(), 2, 3, String::new());
+        let base = graph.add_symbol("Task".to_string(), NodeKind::Class, base_file, 1, 3, String::new());
+        graph.add_edge(child, child_run, EdgeKind::Contains);
+        graph.add_edge(base, base_run, EdgeKind::Contains);
+        graph.add_edge(child, base, EdgeKind::Extends);
+
+        let response = get_context(&graph, "run", "change");
+
+        assert_eq!(response.symbols[0].file, "src/worker.py");
+        assert_eq!(
+            response.warnings,
+            vec!["run overrides Task::run; editing may diverge from the base behavior"]
+        );
+    }
+
+    #[test]
+    fn test_change_warns_about_public_api_callers() {
+        let lib = "pub fn connect(url: &str) -> bool {\n    !url.is_empty()\n}\n";
+        let app = "fn main() {\n    connect(\"db\");\n}\n\nfn retry() {\n    connect(\"db\");\n}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/net.rs"), lib).unwrap(),
+            parser::extract_file(&PathBuf::from("src/main.rs"), app).unwrap(),
+        ]);
+
+        let response = get_context(&graph, "connect", "change");
+        assert_eq!(response.warnings, vec!["connect is public API with 2 external callers"]);
+
+        // Not public: no warning even with callers
+        let private = lib.replace("pub fn", "fn");
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/net.rs"), &private).unwrap(),
+            parser::extract_file(&PathBuf::from("src/main.rs"), app).unwrap(),
+        ]);
+        assert!(get_context(&graph, "connect", "change").warnings.is_empty());
+    }
+
     #[test]
     fn test_create_intent() {
         let graph = build_test_graph();
@@ -501,6 +993,60 @@ fn test_process() {
         // Should find similar functions like transform
     }
 
+    #[test]
+    fn test_create_ranks_structurally_similar_first() {
+        let handlers = r#"
+fn render_page(items: Vec<String>, width: usize) -> String {
+    let mut out = String::new();
+    for item in items {
+        out.push_str(&format!("{:>w$}", item, w = width));
+    }
+    out
+}
+
+fn log_request(id: u64) {
+    println!("request {}", id);
+}
+"#;
+        let rules = r#"
+fn validate_email(s: &str) -> bool {
+    !s.is_empty() && s.contains('@')
+}
+
+fn is_blank(s: &str) -> bool {
+    s.trim().is_empty()
+}
+"#;
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/handlers.rs"), handlers).unwrap(),
+            parser::extract_file(&PathBuf::from("src/rules.rs"), rules).unwrap(),
+        ]);
+
+        let patterns = |threshold| {
+            get_context_with_threshold(
+                &graph,
+                "validate_email",
+                "create",
+                None,
+                &SymbolFilter::default(),
+                threshold,
+            )
+            .patterns
+        };
+
+        let similar = patterns(DEFAULT_SIMILARITY_THRESHOLD);
+        assert_eq!(similar[0].name, "is_blank");
+        assert!(similar.iter().all(|p| p.name != "log_request"), "{:?}", similar);
+
+        // A zero threshold keeps everything, grouped by file after the best match
+        let all: Vec<String> = patterns(0.0).into_iter().map(|p| p.name).collect();
+        assert_eq!(all[0], "is_blank");
+        assert_eq!(all.len(), 3);
+
+        assert!(patterns(1.0).is_empty());
+    }
+
     #[test]
     fn test_extract_call_expression() {
         assert_eq!(
@@ -594,6 +1140,51 @@ fn process(input: &str) {
         }
     }
 
+    #[test]
+    fn test_change_flags_arity_mismatch() {
+        let graph = build_test_graph();
+
+        let response = get_context_for_change(
+            &graph,
+            "validate",
+            "change",
+            Some("validate(s: &str, strict: bool) -> bool"),
+        );
+        let edit = response
+            .edits
+            .iter()
+            .find(|e| e.in_symbol == "process")
+            .expect("process calls validate");
+        assert_eq!(edit.actual_args, 1);
+        assert_eq!(edit.expected_args, Some(2));
+        assert!(!edit.arity_ok);
+
+        // A defaulted parameter doesn't break existing calls
+        let response = get_context_for_change(
+            &graph,
+            "validate",
+            "change",
+            Some("validate(s, strict=False)"),
+        );
+        assert!(response.edits.iter().all(|e| e.arity_ok));
+
+        // No new signature: nothing to check
+        let response = get_context_for_change(&graph, "validate", "change", None);
+        assert!(response.edits.iter().all(|e| e.arity_ok && e.expected_args.is_none()));
+    }
+
+    #[test]
+    fn test_signature_arity() {
+        let sig = Signature::parse("fn check(&self, a: i32, b: i32)").unwrap();
+        assert_eq!(sig.required_args(), 2);
+        assert!(sig.accepts_arity(2));
+        assert!(!sig.accepts_arity(1));
+
+        let sig = Signature::parse("log(msg, *args)").unwrap();
+        assert_eq!(sig.max_args(), None);
+        assert!(sig.accepts_arity(4));
+    }
+
     #[test]
     fn test_extract_call_args() {
         assert_eq!(extract_call_args("foo()"), Vec::<String>::new());
@@ -0,0 +1,230 @@
+//! Focus mode — boilerplate dependents moved behind the substantive ones.
+//!
+//! A `change` context lists every dependent, and trivial ones (accessors,
+//! tests, log lines) often crowd out the callers whose behavior a change
+//! actually affects. Focus mode keeps them all but lists the boilerplate
+//! last.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::graph::{CodeGraph, NodeData, NodeKind};
+
+use super::types::ContextResponse;
+
+/// Why a dependent counts as boilerplate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Boilerplate {
+    /// Test code.
+    Test,
+    /// A one-line getter (`fn total(&self) -> u32 { compute(self) }`).
+    Accessor,
+    /// Only uses the symbol inside log statements.
+    Logging,
+}
+
+/// Calls that write a log line rather than do work.
+const LOG_MARKERS: &[&str] = &[
+    "log::", "trace!(", "debug!(", "info!(", "warn!(", "error!(", "println!(", "eprintln!(",
+    "logger.", "logging.", "log.", "console.", "print(",
+];
+
+/// Name prefixes of getters.
+const ACCESSOR_PREFIXES: &[&str] = &["get", "is_", "has_"];
+
+/// Classify a dependent of `target`, or `None` if it is substantive.
+pub fn classify_dependent(dependent: &NodeData, target: &str) -> Option<Boilerplate> {
+    if !matches!(dependent.kind, NodeKind::Function | NodeKind::Method) {
+        return None;
+    }
+    if dependent.is_test {
+        return Some(Boilerplate::Test);
+    }
+    if is_accessor(dependent) {
+        return Some(Boilerplate::Accessor);
+    }
+
+    let mut mentions = dependent
+        .code_snippet
+        .lines()
+        .skip(1)
+        .filter(|line| mentions_call(line, target))
+        .peekable();
+    if mentions.peek().is_some() && mentions.all(has_log_marker) {
+        return Some(Boilerplate::Logging);
+    }
+    None
+}
+
+/// List boilerplate dependents and their edits after the substantive ones,
+/// keeping the order within each group.
+pub fn apply_focus(graph: &CodeGraph, response: &mut ContextResponse) {
+    let target = response.query.clone();
+    let is_boilerplate = |file: &str, name: &str, line: Option<usize>| {
+        graph
+            .symbols_in_file(Path::new(file))
+            .into_iter()
+            .filter(|node| node.name == name)
+            .find(|node| line.is_none_or(|line| node.line_start == line))
+            .is_some_and(|node| classify_dependent(node, &target).is_some())
+    };
+
+    response
+        .used_by
+        .sort_by_cached_key(|r| is_boilerplate(&r.file, &r.name, Some(r.line)));
+    response
+        .edits
+        .sort_by_cached_key(|e| is_boilerplate(&e.file, &e.in_symbol, None));
+}
+
+/// A body of a single line, in a function named like a getter, or taking
+/// no arguments besides `self` and reading its fields (`self.x`, `this.x`).
+fn is_accessor(node: &NodeData) -> bool {
+    let mut lines = node
+        .code_snippet
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !matches!(*line, "{" | "}" | "};"));
+    let Some(signature) = lines.next() else {
+        return false;
+    };
+    // A one-liner (`fn get(&self) -> u32 { self.x }`) has its body on the
+    // signature line
+    let body = match (lines.next(), lines.next()) {
+        (None, _) => signature.split_once('{').map_or("", |(_, body)| body),
+        (Some(body), None) => body,
+        (Some(_), Some(_)) => return false,
+    };
+
+    let getter_name = ACCESSOR_PREFIXES.iter().any(|p| node.name.starts_with(p));
+    let params = signature
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map_or("", |(params, _)| params);
+    let no_params = params
+        .split(',')
+        .map(str::trim)
+        .all(|p| p.is_empty() || matches!(p, "self" | "&self" | "&mut self" | "mut self" | "cls"));
+    let reads_fields = ["self.", "this.", "cls."].iter().any(|r| body.contains(r));
+    getter_name || (no_params && reads_fields)
+}
+
+/// Is `line` a log statement: a log marker not inside a longer name
+/// (`log.` but not `catalog.`, `print(` but not `fingerprint(`)?
+fn has_log_marker(line: &str) -> bool {
+    LOG_MARKERS.iter().any(|marker| {
+        line.match_indices(marker).any(|(i, _)| {
+            !line[..i].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    })
+}
+
+/// Does `line` call `name` (not just a longer name ending in it)?
+fn mentions_call(line: &str, name: &str) -> bool {
+    line.match_indices(name).any(|(i, _)| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + name.len()..].trim_start().chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_') && after == Some('(')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use crate::query::get_context;
+    use std::path::PathBuf;
+
+    fn node(kind: NodeKind, name: &str, code: &str) -> NodeData {
+        let lines = code.lines().count();
+        NodeData::new_symbol(name.to_string(), kind, PathBuf::from("src/lib.rs"), 1, lines, code.to_string())
+    }
+
+    #[test]
+    fn test_classify_dependent() {
+        let getter = node(NodeKind::Method, "get_total", "fn get_total(&self) -> u32 {\n    compute(self.items)\n}");
+        assert_eq!(classify_dependent(&getter, "compute"), Some(Boilerplate::Accessor));
+
+        let one_liner = node(NodeKind::Method, "total", "fn total(&self) -> u32 { compute(self.items) }");
+        assert_eq!(classify_dependent(&one_liner, "compute"), Some(Boilerplate::Accessor));
+
+        let logged = node(
+            NodeKind::Function,
+            "report",
+            "fn report(items: &[u32]) {\n    let n = items.len();\n    log::info!(\"total {}\", compute(items));\n}",
+        );
+        assert_eq!(classify_dependent(&logged, "compute"), Some(Boilerplate::Logging));
+
+        let mut test = node(NodeKind::Function, "test_compute", "fn test_compute() {\n    assert_eq!(compute(&[1]), 1);\n}");
+        test.is_test = true;
+        assert_eq!(classify_dependent(&test, "compute"), Some(Boilerplate::Test));
+
+        // Uses the result, and only logs a different call
+        let checkout = node(
+            NodeKind::Function,
+            "checkout",
+            "fn checkout(cart: &Cart) -> u32 {\n    let total = compute(&cart.items);\n    log::info!(\"precompute({})\", total);\n    charge(total)\n}",
+        );
+        assert_eq!(classify_dependent(&checkout, "compute"), None);
+
+        // Markers inside longer names aren't log calls
+        let stored = node(NodeKind::Function, "store", "fn store(x: u32) {\n    catalog.add(compute(x));\n}");
+        assert_eq!(classify_dependent(&stored, "compute"), None);
+        let hashed = node(NodeKind::Function, "hash", "fn hash(x: u32) -> u64 {\n    fingerprint(compute(x))\n}");
+        assert_eq!(classify_dependent(&hashed, "compute"), None);
+
+        // A one-statement function without parameters isn't a getter
+        let main = node(NodeKind::Function, "main", "fn main() { run(compute()) }");
+        assert_eq!(classify_dependent(&main, "compute"), None);
+        let run = node(NodeKind::Function, "main", "fn main() {\n    run(compute())\n}");
+        assert_eq!(classify_dependent(&run, "compute"), None);
+    }
+
+    #[test]
+    fn test_focus_lists_real_consumer_first() {
+        let source = r#"
+pub fn compute(items: &[u32]) -> u32 {
+    items.iter().sum()
+}
+
+fn checkout(cart: &Cart, card: &Card) -> Receipt {
+    let total = compute(&cart.items);
+    let charge = card.charge(total);
+    Receipt::new(charge)
+}
+
+impl Cart {
+    fn get_total(&self) -> u32 {
+        compute(&self.items)
+    }
+
+    fn get_subtotal(&self) -> u32 {
+        compute(&self.items[1..])
+    }
+
+    fn has_total(&self) -> bool {
+        compute(&self.items) > 0
+    }
+}
+"#;
+        let extraction = parser::extract_file(&PathBuf::from("src/cart.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+
+        let callers = |response: &ContextResponse| -> Vec<String> {
+            response
+                .used_by
+                .iter()
+                .filter(|r| r.relationship == "calls")
+                .map(|r| r.name.clone())
+                .collect()
+        };
+        let mut response = get_context(&graph, "compute", "change");
+        assert_eq!(callers(&response).last().unwrap(), "checkout");
+
+        apply_focus(&graph, &mut response);
+        assert_eq!(callers(&response), vec!["checkout", "has_total", "get_subtotal", "get_total"]);
+        assert_eq!(response.edits[0].in_symbol, "checkout");
+    }
+}
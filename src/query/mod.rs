@@ -12,17 +12,27 @@
 //! ```
 
 pub mod context;
+pub mod focus;
+pub mod projection;
 pub mod search;
 pub mod types;
 
 // Re-export the main API
-pub use context::{get_context, get_context_for_change};
+pub use context::{
+    attach_blame, canonical_intent, get_context, get_context_filtered, get_context_for_change,
+    get_context_with_threshold, get_file_contexts, DEFAULT_SIMILARITY_THRESHOLD, INTENT_ALIASES,
+};
+pub use focus::{apply_focus, classify_dependent, Boilerplate};
+pub use projection::{project_context, project_dependencies, project_search};
 pub use types::{
-    ContextResponse, DependencyResponse, Edit, FileSymbolEntry, FileSymbolsResponse, Param,
-    Query, Reference, SearchResponse, Signature, StatsResponse, Symbol,
+    ContextResponse, DependencyResponse, Edit, FileSymbolEntry, FileSymbolsResponse,
+    NeighborsQuery, NeighborsResponse, Page, Param, PathResponse, Query, Reference, SearchResponse, Signature,
+    StatsResponse, Symbol,
 };
 
 // Re-export search functions for backwards compatibility
 pub use search::{
-    anchor_dependencies, anchor_file_symbols, anchor_search, anchor_stats, graph_search,
+    anchor_dependencies, anchor_dependencies_paged, anchor_dependencies_scoped, anchor_file_symbols, anchor_file_symbols_filtered,
+    anchor_neighbors, anchor_path,
+    anchor_search, anchor_search_batch, anchor_search_batch_filtered, anchor_search_filtered, anchor_search_paged, anchor_stats, dedup_results, graph_search, graph_search_paged, graph_search_regex, graph_search_with_fanout,
 };
@@ -0,0 +1,239 @@
+//! Field projection — trim a response to the fields a caller asked for.
+//!
+//! Agents on a tight token budget often only need `name,file,line`.
+//! Projection applies to the symbol records inside a response (search
+//! results, context symbols/references, dependency entries); everything
+//! else in the response is kept as is.
+//!
+//! ```ignore
+//! let response = anchor_search(&graph, Query::Simple("login".into()));
+//! let json = project_search(&response, &["name", "file"])?;
+//! // {"found":true,"count":1,"results":[{"name":"login","file":"src/auth.rs"}]}
+//! ```
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::types::{ContextResponse, DependencyResponse, SearchResponse};
+use crate::error::{AnchorError, Result};
+
+/// The fields a kind of record can be projected to.
+struct RecordSchema {
+    /// Keys in the serialized record.
+    fields: &'static [&'static str],
+    /// Requested name → serialized key, for records with legacy key names.
+    aliases: &'static [(&'static str, &'static str)],
+}
+
+impl RecordSchema {
+    /// Resolve a requested field to its serialized key.
+    fn key<'a>(&self, field: &'a str) -> Option<&'a str> {
+        if self.fields.contains(&field) {
+            return Some(field);
+        }
+        self.aliases
+            .iter()
+            .find(|(alias, _)| *alias == field)
+            .map(|(_, key)| *key)
+    }
+
+    fn expected(&self) -> String {
+        let mut names: Vec<&str> = self.fields.to_vec();
+        names.extend(self.aliases.iter().map(|(alias, _)| *alias));
+        names.join(", ")
+    }
+}
+
+/// `SearchResult` records in `anchor_search` responses.
+const SEARCH_RESULT: RecordSchema = RecordSchema {
+    fields: &[
+        "symbol", "kind", "file", "line_start", "line_end", "code", "calls", "called_by",
+        "imports", "visibility", "is_test", "truncated", "value",
+        "deprecated", "is_async", "generated", "importance", "qualified_name", "similarity",
+        "aliases",
+    ],
+    aliases: &[("name", "symbol"), ("line", "line_start")],
+};
+
+/// `Symbol` and `Reference` records in `get_context` responses.
+const CONTEXT_SYMBOL: RecordSchema = RecordSchema {
+    fields: &[
+        "name", "kind", "file", "line", "code", "truncated", "generated", "relationship",
+        "last_modified",
+    ],
+    aliases: &[],
+};
+
+/// `DependencyInfo` records in `anchor_dependencies` responses.
+const DEPENDENCY: RecordSchema = RecordSchema {
+    fields: &["symbol", "kind", "file", "line", "relationship", "count"],
+    aliases: &[("name", "symbol")],
+};
+
+/// Project the `results` of a search response to `fields`.
+pub fn project_search(response: &SearchResponse, fields: &[&str]) -> Result<Value> {
+    project(response, &["results"], &SEARCH_RESULT, fields)
+}
+
+/// Project the symbol and reference lists of a context response to `fields`.
+pub fn project_context(response: &ContextResponse, fields: &[&str]) -> Result<Value> {
+    project(
+        response,
+        &["symbols", "used_by", "uses", "patterns", "tests"],
+        &CONTEXT_SYMBOL,
+        fields,
+    )
+}
+
+/// Project the dependents and dependencies of a response to `fields`.
+pub fn project_dependencies(response: &DependencyResponse, fields: &[&str]) -> Result<Value> {
+    project(response, &["dependents", "dependencies"], &DEPENDENCY, fields)
+}
+
+/// Serialize `response` and trim every record in the `lists` to `fields`.
+///
+/// Empty `fields` returns the full response. Unknown field names are an
+/// error so typos don't silently return empty records.
+fn project<T: Serialize>(
+    response: &T,
+    lists: &[&str],
+    schema: &RecordSchema,
+    fields: &[&str],
+) -> Result<Value> {
+    let mut value = serde_json::to_value(response)?;
+    if fields.is_empty() {
+        return Ok(value);
+    }
+
+    let keys = fields
+        .iter()
+        .map(|&field| {
+            schema.key(field).map(|key| (field, key)).ok_or_else(|| {
+                AnchorError::UnknownField(field.to_string(), schema.expected())
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for list in lists {
+        if let Some(Value::Array(records)) = value.get_mut(*list) {
+            for record in records.iter_mut() {
+                *record = project_record(record, &keys);
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+/// Keep only the requested keys of one record, named as requested.
+fn project_record(record: &Value, keys: &[(&str, &str)]) -> Value {
+    let mut out = Map::new();
+    for &(field, key) in keys {
+        if let Some(v) = record.get(key) {
+            out.insert(field.to_string(), v.clone());
+        }
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::CodeGraph;
+    use crate::parser;
+    use crate::query::{anchor_dependencies, anchor_search, get_context, Query};
+    use std::path::PathBuf;
+
+    fn build_test_graph() -> CodeGraph {
+        let source = "pub fn login(u: &str) -> bool {\n    validate(u)\n}\n\nfn validate(s: &str) -> bool {\n    !s.is_empty()\n}\n";
+        let extraction = parser::extract_file(&PathBuf::from("src/auth.rs"), source).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![extraction]);
+        graph
+    }
+
+    #[test]
+    fn test_project_search_fields() {
+        let graph = build_test_graph();
+        let response = anchor_search(&graph, Query::Simple("login".to_string()));
+
+        let json = project_search(&response, &["name", "file"]).unwrap();
+
+        let result = &json["results"][0];
+        assert_eq!(result["name"], "login");
+        assert_eq!(result["file"], "src/auth.rs");
+        assert!(result.get("code").is_none());
+        assert!(result.get("kind").is_none());
+        // Response-level fields are kept
+        assert_eq!(json["found"], true);
+    }
+
+    #[test]
+    fn test_project_context_and_dependencies() {
+        let graph = build_test_graph();
+
+        let json = project_context(&get_context(&graph, "validate", "explore"), &["name"]).unwrap();
+        assert_eq!(json["symbols"][0], serde_json::json!({ "name": "validate" }));
+
+        let deps = anchor_dependencies(&graph, "login");
+        let json = project_dependencies(&deps, &["name", "line"]).unwrap();
+        let first = json["dependencies"][0].as_object().unwrap();
+        assert_eq!(first.len(), 2);
+    }
+
+    #[test]
+    fn test_project_search_importance() {
+        let mut graph = build_test_graph();
+        graph.compute_centrality();
+        let response = anchor_search(&graph, Query::Simple("validate".to_string()));
+
+        let json = project_search(&response, &["name", "importance"]).unwrap();
+        let result = json["results"][0].as_object().unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result["importance"].as_f64().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_project_search_qualified_name() {
+        let graph = build_test_graph();
+        let response = anchor_search(&graph, Query::Simple("login".to_string()));
+
+        let json = project_search(&response, &["qualified_name"]).unwrap();
+        assert_eq!(json["results"][0], serde_json::json!({ "qualified_name": "auth::login" }));
+    }
+
+    #[test]
+    fn test_project_search_similarity() {
+        let graph = build_test_graph();
+        let query: Query =
+            serde_json::from_value(serde_json::json!({ "symbol": "validat", "fuzzy": true })).unwrap();
+        let response = anchor_search(&graph, query);
+
+        let json = project_search(&response, &["name", "similarity"]).unwrap();
+        let result = &json["results"][0];
+        assert_eq!(result["name"], "validate");
+        assert!(result["similarity"].as_f64().unwrap() > 0.5);
+    }
+
+    #[test]
+    fn test_project_dependencies_count() {
+        let source = "fn run() {\n    step();\n    step();\n}\n\nfn step() {}\n";
+        let mut graph = CodeGraph::new();
+        graph.build_from_extractions(vec![
+            parser::extract_file(&PathBuf::from("src/run.rs"), source).unwrap(),
+        ]);
+
+        let deps = anchor_dependencies(&graph, "run");
+        let json = project_dependencies(&deps, &["name", "count"]).unwrap();
+        assert_eq!(json["dependencies"][0], serde_json::json!({ "name": "step", "count": 2 }));
+    }
+
+    #[test]
+    fn test_project_unknown_field() {
+        let graph = build_test_graph();
+        let response = anchor_search(&graph, Query::Simple("login".to_string()));
+
+        let err = project_search(&response, &["nmae"]).unwrap_err();
+        assert!(matches!(err, AnchorError::UnknownField(ref f, _) if f == "nmae"));
+    }
+}
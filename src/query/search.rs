@@ -3,18 +3,49 @@
 //! These are the lower-level search operations. For high-level
 //! agent queries, use `get_context` from the context module.
 
-use crate::graph::{CodeGraph, GraphSearchResult};
+use std::collections::HashSet;
+
+use crate::graph::{CodeGraph, DependencyScope, GraphSearchResult, NodeKind, SearchResult, SymbolFilter};
+use crate::regex::{matcher_cached, ParseError};
 
 use super::types::{
-    DependencyResponse, FileSymbolEntry, FileSymbolsResponse, Query, SearchResponse, StatsResponse,
+    DependencyResponse, FileSymbolEntry, FileSymbolsResponse, NeighborsQuery, NeighborsResponse,
+    Page, PathResponse, Query, SearchResponse, StatsResponse, Symbol,
 };
 
-/// Search for symbols by name.
+/// Search for symbols by name, returning the first five results.
+///
+/// Test symbols are hidden unless the query sets `include_tests`. With
+/// `fuzzy`, near-miss names match too, ranked below exact and prefix hits;
+/// with `ignore_case`, `codegraph` finds `CodeGraph`.
 pub fn anchor_search(graph: &CodeGraph, query: Query) -> SearchResponse {
+    anchor_search_paged(graph, query, Page::new(0, 5))
+}
+
+/// [`anchor_search`], returning one `page` of the results and their total.
+pub fn anchor_search_paged(graph: &CodeGraph, query: Query, page: Page) -> SearchResponse {
+    anchor_search_filtered(graph, query, page, &SymbolFilter::default())
+}
+
+/// [`anchor_search_paged`] with project `defaults` (see
+/// [`QueryConfig::symbol_filter`](crate::config::QueryConfig::symbol_filter))
+/// for the filters the query doesn't set itself.
+pub fn anchor_search_filtered(
+    graph: &CodeGraph,
+    query: Query,
+    page: Page,
+    defaults: &SymbolFilter,
+) -> SearchResponse {
     let name = query.symbol_name();
-    let limit = 5;
+    let filter = query.symbol_filter(*defaults);
 
-    let mut results = graph.search(name, limit);
+    let mut results = if query.fuzzy() {
+        graph.fuzzy_search_filtered(name, usize::MAX, &filter)
+    } else if query.ignore_case() {
+        graph.search_ignore_case(name, usize::MAX, &filter)
+    } else {
+        graph.search_filtered(name, usize::MAX, &filter)
+    };
 
     // Apply optional filters for structured queries
     if let Query::Structured { kind, file, .. } = &query {
@@ -27,19 +58,135 @@ pub fn anchor_search(graph: &CodeGraph, query: Query) -> SearchResponse {
         }
     }
 
+    if query.dedup() {
+        results = dedup_results(results);
+    }
+
+    let total = results.len();
+    let results = page.apply(results);
     SearchResponse {
-        found: !results.is_empty(),
+        query: name.to_string(),
+        found: total > 0,
         count: results.len(),
+        total,
         results,
     }
 }
 
+/// Run several searches in one call, one response per query in input
+/// order. Each response names the query it answers.
+pub fn anchor_search_batch(graph: &CodeGraph, queries: Vec<Query>) -> Vec<SearchResponse> {
+    anchor_search_batch_filtered(graph, queries, &SymbolFilter::default())
+}
+
+/// [`anchor_search_batch`] with project `defaults` for unset filters.
+pub fn anchor_search_batch_filtered(
+    graph: &CodeGraph,
+    queries: Vec<Query>,
+    defaults: &SymbolFilter,
+) -> Vec<SearchResponse> {
+    queries
+        .into_iter()
+        .map(|query| anchor_search_filtered(graph, query, Page::new(0, 5), defaults))
+        .collect()
+}
+
+/// Collapse results that are the same definition, keeping the first,
+/// best-ranked one. The names of the dropped duplicates are recorded in
+/// its `aliases`.
+pub fn dedup_results(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut merged: Vec<SearchResult> = Vec::with_capacity(results.len());
+    for result in results {
+        let existing = merged.iter_mut().find(|m| same_definition(m, &result));
+        match existing {
+            Some(kept) => {
+                if result.symbol != kept.symbol && !kept.aliases.contains(&result.symbol) {
+                    kept.aliases.push(result.symbol);
+                }
+            }
+            None => merged.push(result),
+        }
+    }
+    merged
+}
+
+/// Are these one definition: the same node, or a variable binding and the
+/// definition it names (`const signIn = function login`)? Distinct symbols
+/// can share a span too, like `Point(int X, int Y)` or `const f = .., g = ..`.
+fn same_definition(a: &SearchResult, b: &SearchResult) -> bool {
+    let same_span = a.file == b.file && a.line_start == b.line_start && a.line_end == b.line_end;
+    let same_node = a.symbol == b.symbol && a.kind == b.kind;
+    let binding = (a.kind == NodeKind::Variable) != (b.kind == NodeKind::Variable);
+    same_span && (same_node || binding)
+}
+
 /// Get dependencies and dependents for a symbol.
 pub fn anchor_dependencies(graph: &CodeGraph, symbol: &str) -> DependencyResponse {
+    anchor_dependencies_scoped(graph, symbol, DependencyScope::Workspace)
+}
+
+/// Get dependencies for a symbol and its dependents within `scope`, e.g.
+/// only callers in the symbol's own package.
+pub fn anchor_dependencies_scoped(
+    graph: &CodeGraph,
+    symbol: &str,
+    scope: DependencyScope,
+) -> DependencyResponse {
+    anchor_dependencies_paged(graph, symbol, scope, Page::all())
+}
+
+/// [`anchor_dependencies_scoped`], returning one `page` each of the
+/// dependents and dependencies, with their totals.
+pub fn anchor_dependencies_paged(
+    graph: &CodeGraph,
+    symbol: &str,
+    scope: DependencyScope,
+    page: Page,
+) -> DependencyResponse {
+    let dependents = graph.dependents_in(symbol, scope);
+    let dependencies = graph.dependencies(symbol);
     DependencyResponse {
         symbol: symbol.to_string(),
-        dependents: graph.dependents(symbol),
-        dependencies: graph.dependencies(symbol),
+        total_dependents: dependents.len(),
+        total_dependencies: dependencies.len(),
+        dependents: page.apply(dependents),
+        dependencies: page.apply(dependencies),
+    }
+}
+
+/// Get the neighbors of a symbol that match the query's kind filters.
+pub fn anchor_neighbors(graph: &CodeGraph, query: &NeighborsQuery) -> NeighborsResponse {
+    let mut neighbors = graph.neighbors(
+        &query.symbol,
+        query.direction,
+        &query.node_kinds,
+        &query.edge_kinds,
+    );
+    if let Some(min) = query.min_confidence {
+        neighbors.retain(|n| n.relationship.confidence() >= min);
+    }
+
+    NeighborsResponse {
+        symbol: query.symbol.clone(),
+        direction: query.direction,
+        neighbors,
+    }
+}
+
+/// Find the shortest call chain from one symbol to another.
+pub fn anchor_path(graph: &CodeGraph, from: &str, to: &str) -> PathResponse {
+    let path: Vec<Symbol> = graph
+        .call_path(from, to)
+        .unwrap_or_default()
+        .iter()
+        .map(Symbol::from_search_result)
+        .collect();
+
+    PathResponse {
+        from: from.to_string(),
+        to: to.to_string(),
+        found: !path.is_empty(),
+        path,
     }
 }
 
@@ -47,11 +194,22 @@ pub fn anchor_dependencies(graph: &CodeGraph, symbol: &str) -> DependencyRespons
 pub fn anchor_stats(graph: &CodeGraph) -> StatsResponse {
     StatsResponse {
         stats: graph.stats(),
+        health: graph.health(),
+        grammar_failures: graph.meta().map(|meta| meta.grammar_failures.clone()).unwrap_or_default(),
     }
 }
 
-/// Get all symbols in a file.
+/// Get all symbols in a file, hiding test symbols.
 pub fn anchor_file_symbols(graph: &CodeGraph, file_path: &str) -> FileSymbolsResponse {
+    anchor_file_symbols_filtered(graph, file_path, &SymbolFilter::default())
+}
+
+/// Get the symbols in a file that pass `filter`.
+pub fn anchor_file_symbols_filtered(
+    graph: &CodeGraph,
+    file_path: &str,
+    filter: &SymbolFilter,
+) -> FileSymbolsResponse {
     use std::path::Path;
 
     let path = Path::new(file_path);
@@ -59,6 +217,7 @@ pub fn anchor_file_symbols(graph: &CodeGraph, file_path: &str) -> FileSymbolsRes
 
     let entries: Vec<FileSymbolEntry> = symbols
         .iter()
+        .filter(|node| filter.allows(node))
         .map(|node| FileSymbolEntry {
             name: node.name.clone(),
             kind: node.kind.to_string(),
@@ -68,10 +227,15 @@ pub fn anchor_file_symbols(graph: &CodeGraph, file_path: &str) -> FileSymbolsRes
         })
         .collect();
 
+    let found = !entries.is_empty();
     FileSymbolsResponse {
         file: file_path.to_string(),
-        found: !entries.is_empty(),
+        found,
         symbols: entries,
+        owners: match graph.resolve_file(path).filter(|_| found) {
+            Some(file) => graph.owners(&file.to_string_lossy()),
+            None => Vec::new(),
+        },
     }
 }
 
@@ -79,3 +243,45 @@ pub fn anchor_file_symbols(graph: &CodeGraph, file_path: &str) -> FileSymbolsRes
 pub fn graph_search(graph: &CodeGraph, query: &str, depth: usize) -> GraphSearchResult {
     graph.search_graph(query, depth)
 }
+
+/// Graph-aware search following at most `max_fanout` edges per node.
+pub fn graph_search_with_fanout(
+    graph: &CodeGraph,
+    query: &str,
+    depth: usize,
+    max_fanout: Option<usize>,
+) -> GraphSearchResult {
+    graph.search_graph_with_fanout(query, depth, max_fanout)
+}
+
+/// Graph-aware search from the symbols whose names match a regex
+/// `pattern` (the ReDoS-safe engine in [`crate::regex`], as in the GraphQL
+/// `search` query), e.g. `handler_\d+` or `Config.*&.*Manager`.
+pub fn graph_search_regex(
+    graph: &CodeGraph,
+    pattern: &str,
+    depth: usize,
+    max_fanout: Option<usize>,
+) -> Result<GraphSearchResult, ParseError> {
+    let mut matcher = matcher_cached(pattern, false)?;
+    Ok(graph.search_graph_matching(|name| matcher.is_match(name), depth, max_fanout))
+}
+
+/// [`graph_search_with_fanout`], returning one `page` of the symbols and
+/// only the connections that touch them. `total` counts every symbol.
+pub fn graph_search_paged(
+    graph: &CodeGraph,
+    query: &str,
+    depth: usize,
+    max_fanout: Option<usize>,
+    page: Page,
+) -> GraphSearchResult {
+    let mut result = graph.search_graph_with_fanout(query, depth, max_fanout);
+    result.symbols = page.apply(result.symbols);
+
+    let names: HashSet<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+    result
+        .connections
+        .retain(|c| names.contains(c.from.as_str()) || names.contains(c.to.as_str()));
+    result
+}
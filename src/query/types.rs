@@ -4,7 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::graph::{DependencyInfo, GraphStats, SearchResult};
+use crate::graph::{
+    BlameInfo, DependencyInfo, EdgeKind, GrammarFailure, GraphHealth, GraphStats, NodeKind,
+    SearchResult, SymbolFilter, TraversalDirection,
+};
 
 /// Query input — supports both simple string and structured queries.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +20,27 @@ pub enum Query {
         symbol: String,
         kind: Option<String>,
         file: Option<String>,
+        /// Override the default for test symbols.
+        #[serde(default)]
+        include_tests: Option<bool>,
+        /// Override the default for non-public symbols.
+        #[serde(default)]
+        include_private: Option<bool>,
+        /// Override the default for generated and vendored code.
+        #[serde(default)]
+        include_generated: Option<bool>,
+        /// Only return `async` functions and methods.
+        #[serde(default)]
+        async_only: Option<bool>,
+        /// Merge results for the same definition (default on).
+        #[serde(default)]
+        dedup: Option<bool>,
+        /// Also match names close to `symbol` (misspelled, abbreviated).
+        #[serde(default)]
+        fuzzy: Option<bool>,
+        /// Compare names without regard to case.
+        #[serde(default)]
+        ignore_case: Option<bool>,
     },
 }
 
@@ -27,14 +51,116 @@ impl Query {
             Query::Structured { symbol, .. } => symbol.as_str(),
         }
     }
+
+    /// Whether duplicate results should be merged; on unless the query
+    /// turns it off.
+    pub fn dedup(&self) -> bool {
+        match self {
+            Query::Simple(_) => true,
+            Query::Structured { dedup, .. } => dedup.unwrap_or(true),
+        }
+    }
+
+    /// Whether names close to the query should match too; off unless the
+    /// query turns it on.
+    pub fn fuzzy(&self) -> bool {
+        match self {
+            Query::Simple(_) => false,
+            Query::Structured { fuzzy, .. } => fuzzy.unwrap_or(false),
+        }
+    }
+
+    /// Whether names should match regardless of case; off unless the
+    /// query turns it on.
+    pub fn ignore_case(&self) -> bool {
+        match self {
+            Query::Simple(_) => false,
+            Query::Structured { ignore_case, .. } => ignore_case.unwrap_or(false),
+        }
+    }
+
+    /// The symbol filter for this query: `defaults` with any per-query overrides.
+    pub fn symbol_filter(&self, defaults: SymbolFilter) -> SymbolFilter {
+        match self {
+            Query::Simple(_) => defaults,
+            Query::Structured {
+                include_tests,
+                include_private,
+                include_generated,
+                async_only,
+                ..
+            } => SymbolFilter {
+                include_tests: include_tests.unwrap_or(defaults.include_tests),
+                include_private: include_private.unwrap_or(defaults.include_private),
+                include_generated: include_generated.unwrap_or(defaults.include_generated),
+                async_only: async_only.unwrap_or(defaults.async_only),
+            },
+        }
+    }
+}
+
+// ─── Pagination ────────────────────────────────────────────────────
+
+/// A window into a long result list: skip `offset` items and return at
+/// most `limit`. Paged responses report the total alongside the page so
+/// callers know whether to ask for the next one.
+///
+/// ```json
+/// {"offset": 50, "limit": 50}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+fn default_page_limit() -> usize {
+    50
+}
+
+impl Page {
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// A page holding every item.
+    pub fn all() -> Self {
+        Self::new(0, usize::MAX)
+    }
+
+    /// Number of items needed to fill this page: `offset + limit`.
+    pub fn end(&self) -> usize {
+        self.offset.saturating_add(self.limit)
+    }
+
+    /// The items on this page.
+    pub fn apply<T>(&self, items: Vec<T>) -> Vec<T> {
+        items.into_iter().skip(self.offset).take(self.limit).collect()
+    }
+}
+
+impl Default for Page {
+    /// The first 50 items.
+    fn default() -> Self {
+        Self::new(0, default_page_limit())
+    }
 }
 
 // ─── Basic Search Response ─────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResponse {
+    /// The symbol name that was searched for.
+    #[serde(default)]
+    pub query: String,
     pub found: bool,
+    /// Results on this page.
     pub count: usize,
+    /// Results across all pages.
+    #[serde(default)]
+    pub total: usize,
     pub results: Vec<SearchResult>,
 }
 
@@ -45,6 +171,58 @@ pub struct DependencyResponse {
     pub symbol: String,
     pub dependents: Vec<DependencyInfo>,
     pub dependencies: Vec<DependencyInfo>,
+    /// Dependents across all pages.
+    #[serde(default)]
+    pub total_dependents: usize,
+    /// Dependencies across all pages.
+    #[serde(default)]
+    pub total_dependencies: usize,
+}
+
+// ─── Neighbors Query ───────────────────────────────────────────────
+
+/// Adjacent nodes of a symbol, filtered by node and edge kind.
+///
+/// ```json
+/// {"symbol": "login", "direction": "outbound", "node_kinds": ["struct"], "edge_kinds": ["references"]}
+/// ```
+///
+/// Empty kind lists match every kind. `min_confidence` drops guessed
+/// edges such as `dynamic_call` (see `EdgeKind::confidence`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborsQuery {
+    pub symbol: String,
+    #[serde(default = "default_direction")]
+    pub direction: TraversalDirection,
+    #[serde(default)]
+    pub node_kinds: Vec<NodeKind>,
+    #[serde(default)]
+    pub edge_kinds: Vec<EdgeKind>,
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
+}
+
+fn default_direction() -> TraversalDirection {
+    TraversalDirection::Outbound
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborsResponse {
+    pub symbol: String,
+    pub direction: TraversalDirection,
+    pub neighbors: Vec<DependencyInfo>,
+}
+
+// ─── Path Response ─────────────────────────────────────────────────
+
+/// Shortest call chain between two symbols; `path` is empty when `to`
+/// is not reachable from `from`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathResponse {
+    pub from: String,
+    pub to: String,
+    pub found: bool,
+    pub path: Vec<Symbol>,
 }
 
 // ─── Stats Response ────────────────────────────────────────────────
@@ -52,6 +230,11 @@ pub struct DependencyResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsResponse {
     pub stats: GraphStats,
+    #[serde(default)]
+    pub health: GraphHealth,
+    /// Languages whose files were skipped because the grammar failed to load.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grammar_failures: Vec<GrammarFailure>,
 }
 
 // ─── File Symbols Response ─────────────────────────────────────────
@@ -61,6 +244,9 @@ pub struct FileSymbolsResponse {
     pub file: String,
     pub found: bool,
     pub symbols: Vec<FileSymbolEntry>,
+    /// Reviewers for the file, from `CODEOWNERS`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,7 +270,7 @@ pub struct FileSymbolEntry {
 pub struct ContextResponse {
     /// The query that was executed
     pub query: String,
-    /// The intent: explore, change, create
+    /// The intent: explore, change, create, tests, overview
     pub intent: String,
     /// Whether the query found results
     pub found: bool,
@@ -109,10 +295,19 @@ pub struct ContextResponse {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub patterns: Vec<Symbol>,
 
-    /// Related tests - for change (to know what to update)
+    /// Related tests - for change (to know what to update) and tests
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tests: Vec<Symbol>,
 
+    /// Files the related tests live in - for tests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_files: Vec<String>,
+
+    /// Cautions about the target itself - for change (deprecated,
+    /// overrides a base method, public API with external callers)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+
     /// Project/file overview stats
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stats: Option<GraphStats>,
@@ -130,6 +325,8 @@ impl Default for ContextResponse {
             edits: Vec::new(),
             patterns: Vec::new(),
             tests: Vec::new(),
+            test_files: Vec::new(),
+            warnings: Vec::new(),
             stats: None,
         }
     }
@@ -143,6 +340,15 @@ pub struct Symbol {
     pub file: String,
     pub line: usize,
     pub code: String,
+    /// Whether `code` was cut short of the full definition.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Whether the symbol is in generated or vendored code.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+    /// Last commit to change the symbol, filled by `attach_blame`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<BlameInfo>,
 }
 
 impl Symbol {
@@ -153,6 +359,9 @@ impl Symbol {
             file: r.file.to_string_lossy().to_string(),
             line: r.line_start,
             code: r.code.clone(),
+            truncated: r.truncated,
+            generated: r.generated,
+            last_modified: None,
         }
     }
 }
@@ -205,6 +414,19 @@ pub struct Edit {
     /// Context: lines before and after
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub context: Vec<String>,
+    /// Number of arguments at the call site
+    #[serde(default)]
+    pub actual_args: usize,
+    /// Required arguments in the new signature (if signature changed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_args: Option<usize>,
+    /// Whether the call's argument count fits the new signature
+    #[serde(default = "default_true")]
+    pub arity_ok: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Parsed function signature for comparison.
@@ -215,6 +437,11 @@ pub struct Signature {
     pub return_type: Option<String>,
 }
 
+/// Is this a rest parameter (`*args`, `**kwargs`, `...rest`)?
+fn is_variadic(name: &str) -> bool {
+    name.starts_with('*') || name.starts_with("...")
+}
+
 /// A function parameter.
 #[derive(Debug, Clone)]
 pub struct Param {
@@ -275,6 +502,35 @@ impl Signature {
         })
     }
 
+    /// Parameters a caller passes explicitly (receivers like `self` excluded).
+    fn call_params(&self) -> impl Iterator<Item = &Param> {
+        self.params.iter().filter(|p| {
+            let name = p.name.trim_start_matches('&').trim_start_matches("mut ").trim();
+            !matches!(name, "self" | "cls" | "this")
+        })
+    }
+
+    /// Number of arguments a call must pass (parameters without defaults).
+    pub fn required_args(&self) -> usize {
+        self.call_params()
+            .filter(|p| !p.name.contains('=') && !p.typ.contains('=') && !is_variadic(&p.name))
+            .count()
+    }
+
+    /// Maximum number of arguments a call may pass (`None` if variadic).
+    pub fn max_args(&self) -> Option<usize> {
+        if self.call_params().any(|p| is_variadic(&p.name)) {
+            None
+        } else {
+            Some(self.call_params().count())
+        }
+    }
+
+    /// Check whether a call with `count` arguments fits this signature.
+    pub fn accepts_arity(&self, count: usize) -> bool {
+        count >= self.required_args() && self.max_args().is_none_or(|max| count <= max)
+    }
+
     /// Compare with another signature and return (added_params, removed_params)
     pub fn diff(&self, new: &Signature) -> (Vec<Param>, Vec<Param>) {
         let old_names: std::collections::HashSet<_> =
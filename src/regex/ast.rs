@@ -19,6 +19,8 @@ pub enum Regex {
     Any,
     /// Matches a character class [a-z]
     Class(HashSet<char>),
+    /// Matches one character outside a class [^a-z]
+    NotClass(HashSet<char>),
     /// Concatenation: R1 followed by R2
     Concat(Arc<Regex>, Arc<Regex>),
     /// Union: R1 | R2
@@ -62,10 +64,13 @@ impl Regex {
 
     /// Union (alternation)
     pub fn union(r1: Arc<Regex>, r2: Arc<Regex>) -> Arc<Regex> {
-        // Normalize: Empty is identity
+        // Normalize: Empty is identity, R | R = R
         match (r1.as_ref(), r2.as_ref()) {
             (Regex::Empty, _) => r2,
             (_, Regex::Empty) => r1,
+            _ if r1 == r2 => r1,
+            (Regex::Union(a, b), _) if *a == r2 || *b == r2 => r1,
+            (_, Regex::Union(a, b)) if *a == r1 || *b == r1 => r2,
             _ => Arc::new(Regex::Union(r1, r2)),
         }
     }
@@ -107,6 +112,18 @@ impl Regex {
     pub fn plus(r: Arc<Regex>) -> Arc<Regex> {
         Regex::concat(r.clone(), Regex::star(r))
     }
+
+    /// Number of nodes, counting a shared subexpression at each use: how
+    /// much a derivative may have to walk.
+    pub fn size(&self) -> usize {
+        match self {
+            Regex::Concat(r1, r2) | Regex::Union(r1, r2) | Regex::Intersect(r1, r2) => {
+                1 + r1.size() + r2.size()
+            }
+            Regex::Negate(r) | Regex::Star(r) => 1 + r.size(),
+            _ => 1,
+        }
+    }
 }
 
 impl PartialEq for Regex {
@@ -117,6 +134,7 @@ impl PartialEq for Regex {
             (Regex::Literal(a), Regex::Literal(b)) => a == b,
             (Regex::Any, Regex::Any) => true,
             (Regex::Class(a), Regex::Class(b)) => a == b,
+            (Regex::NotClass(a), Regex::NotClass(b)) => a == b,
             (Regex::Concat(a1, a2), Regex::Concat(b1, b2)) => a1 == b1 && a2 == b2,
             (Regex::Union(a1, a2), Regex::Union(b1, b2)) => a1 == b1 && a2 == b2,
             (Regex::Intersect(a1, a2), Regex::Intersect(b1, b2)) => a1 == b1 && a2 == b2,
@@ -134,7 +152,7 @@ impl Hash for Regex {
         std::mem::discriminant(self).hash(state);
         match self {
             Regex::Literal(c) => c.hash(state),
-            Regex::Class(set) => {
+            Regex::Class(set) | Regex::NotClass(set) => {
                 for c in set.iter() {
                     c.hash(state);
                 }
@@ -0,0 +1,116 @@
+//! Compiled patterns kept across queries.
+//!
+//! The daemon answers many searches with the same few patterns. Parsing a
+//! pattern and building its DFA once, then handing out copies, saves
+//! repeating that work on every query.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::ast::Regex;
+use super::derivative::Matcher;
+use super::parser::{parse, parse_ci, ParseError};
+
+/// Patterns kept before the least recently used is dropped.
+pub const PATTERN_CACHE_CAPACITY: usize = 64;
+
+/// Least-recently-used map from (pattern, ignore case) to its matcher.
+struct PatternCache {
+    entries: HashMap<(String, bool), (Matcher, u64)>,
+    clock: u64,
+}
+
+impl PatternCache {
+    fn get(&mut self, key: &(String, bool)) -> Option<Matcher> {
+        self.clock += 1;
+        let (matcher, last_used) = self.entries.get_mut(key)?;
+        *last_used = self.clock;
+        Some(matcher.clone())
+    }
+
+    fn insert(&mut self, key: (String, bool), matcher: Matcher) {
+        if self.entries.len() >= PATTERN_CACHE_CAPACITY && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (matcher, self.clock));
+    }
+}
+
+fn cache() -> &'static Mutex<PatternCache> {
+    static CACHE: OnceLock<Mutex<PatternCache>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        Mutex::new(PatternCache {
+            entries: HashMap::new(),
+            clock: 0,
+        })
+    })
+}
+
+/// A compiled [`Matcher`] for `pattern`, built once and copied from the
+/// cache on later calls. The copy starts with the DFA, or the derivatives
+/// the cached matcher has learned, and can be used without locking.
+pub fn matcher_cached(pattern: &str, ignore_case: bool) -> Result<Matcher, ParseError> {
+    let key = (pattern.to_string(), ignore_case);
+    if let Some(matcher) = cache().lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return Ok(matcher);
+    }
+
+    // Parse and compile outside the lock; a racing thread just does it twice
+    let regex = if ignore_case { parse_ci(pattern)? } else { parse(pattern)? };
+    let matcher = Matcher::compiled(regex);
+    cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, matcher.clone());
+    Ok(matcher)
+}
+
+/// [`parse`] (or [`parse_ci`]) through the same cache as [`matcher_cached`].
+pub fn parse_cached(pattern: &str, ignore_case: bool) -> Result<Arc<Regex>, ParseError> {
+    matcher_cached(pattern, ignore_case).map(|matcher| matcher.regex().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cached_reuses_patterns() {
+        let first = parse_cached("cached.*Pattern", false).unwrap();
+        let second = parse_cached("cached.*Pattern", false).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // Case folding is part of the key
+        let folded = parse_cached("cached.*Pattern", true).unwrap();
+        assert!(!Arc::ptr_eq(&first, &folded));
+        assert!(matcher_cached("cached.*Pattern", true).unwrap().is_match("CACHEDPATTERN"));
+
+        assert!(parse_cached("(unclosed", false).is_err());
+    }
+
+    #[test]
+    fn test_pattern_cache_evicts_least_recently_used() {
+        let mut cache = PatternCache {
+            entries: HashMap::new(),
+            clock: 0,
+        };
+        let key = |i: usize| (format!("p{}", i), false);
+        for i in 0..PATTERN_CACHE_CAPACITY {
+            cache.insert(key(i), Matcher::new(parse("a").unwrap()));
+        }
+        assert!(cache.get(&key(0)).is_some());
+
+        cache.insert(key(PATTERN_CACHE_CAPACITY), Matcher::new(parse("a").unwrap()));
+        assert_eq!(cache.entries.len(), PATTERN_CACHE_CAPACITY);
+        assert!(cache.get(&key(0)).is_some());
+        assert!(cache.get(&key(1)).is_none());
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::ast::Regex;
+use super::dfa::Dfa;
 
 /// Check if regex accepts the empty string.
 pub fn nullable(r: &Regex) -> bool {
@@ -16,6 +17,7 @@ pub fn nullable(r: &Regex) -> bool {
         Regex::Literal(_) => false,
         Regex::Any => false,
         Regex::Class(_) => false,
+        Regex::NotClass(_) => false,
         Regex::Concat(r1, r2) => nullable(r1) && nullable(r2),
         Regex::Union(r1, r2) => nullable(r1) || nullable(r2),
         Regex::Intersect(r1, r2) => nullable(r1) && nullable(r2),
@@ -46,6 +48,13 @@ pub fn derivative(r: &Regex, c: char) -> Arc<Regex> {
                 Arc::new(Regex::Empty)
             }
         }
+        Regex::NotClass(set) => {
+            if set.contains(&c) {
+                Arc::new(Regex::Empty)
+            } else {
+                Arc::new(Regex::Epsilon)
+            }
+        }
         Regex::Concat(r1, r2) => {
             // D_c(R1 R2) = D_c(R1) R2 | ν(R1) D_c(R2)
             let d1 = Regex::concat(derivative(r1, c), r2.clone());
@@ -89,21 +98,48 @@ pub fn matches(r: &Regex, s: &str) -> bool {
 }
 
 /// Matcher with memoization for repeated queries.
+///
+/// Clones start with everything the original has compiled or learned, so
+/// one matcher can seed many (see [`matcher_cached`](super::matcher_cached)).
+#[derive(Clone)]
 pub struct Matcher {
     regex: Arc<Regex>,
     cache: HashMap<(Arc<Regex>, char), Arc<Regex>>,
+    /// Precomputed transitions, for patterns matched against many strings.
+    dfa: Option<Dfa>,
 }
 
 impl Matcher {
+    /// A matcher computing derivatives as it goes.
     pub fn new(regex: Arc<Regex>) -> Self {
         Self {
             regex,
             cache: HashMap::new(),
+            dfa: None,
         }
     }
 
+    /// A matcher for a hot pattern, run against many strings: compiles
+    /// the pattern to a [`Dfa`] up front, falling back to derivatives
+    /// when it has too many states or the pattern is too large.
+    pub fn compiled(regex: Arc<Regex>) -> Self {
+        let dfa = Dfa::build(&regex);
+        Self {
+            dfa,
+            ..Self::new(regex)
+        }
+    }
+
+    /// The pattern being matched.
+    pub fn regex(&self) -> &Arc<Regex> {
+        &self.regex
+    }
+
     /// Check if string matches the regex.
     pub fn is_match(&mut self, s: &str) -> bool {
+        if let Some(dfa) = &self.dfa {
+            return dfa.is_match(s);
+        }
         let mut current = self.regex.clone();
 
         for c in s.chars() {
@@ -119,6 +155,9 @@ impl Matcher {
 
     /// Check if string starts with a match (prefix match).
     pub fn is_prefix_match(&mut self, s: &str) -> bool {
+        if let Some(dfa) = &self.dfa {
+            return dfa.is_prefix_match(s);
+        }
         let mut current = self.regex.clone();
 
         // Check if current state is nullable at any point
@@ -0,0 +1,240 @@
+//! DFA - derivatives compiled into a transition table.
+//!
+//! Every derivative of a regex is a state; exploring them up front turns
+//! matching into one table lookup per character. Worth it for patterns run
+//! against every symbol in the graph. Patterns whose derivatives don't
+//! settle into a small set of states, or are too large to explore quickly,
+//! stay on the lazy `Matcher`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use super::ast::Regex;
+use super::derivative::{derivative, nullable};
+
+/// States explored before giving up on building a DFA.
+pub const MAX_DFA_STATES: usize = 512;
+
+/// Largest pattern, in [`Regex::size`] nodes, compiled to a DFA. Every
+/// state of a long counted repetition (`a{1000}`) is a derivative about
+/// as large as the pattern, so exploring them takes seconds; such patterns
+/// stay on the lazy `Matcher`, which only visits the states its inputs
+/// reach.
+pub const MAX_DFA_PATTERN_SIZE: usize = 256;
+
+/// A regex compiled to a deterministic automaton.
+///
+/// Characters the regex never names behave alike, so the alphabet is the
+/// named characters plus one class for everything else.
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    /// Class of each ASCII character.
+    ascii: [usize; 128],
+    /// Class of each named non-ASCII character.
+    other_chars: HashMap<char, usize>,
+    /// Number of character classes, including the catch-all.
+    classes: usize,
+    /// `transitions[state * classes + class]` is the next state.
+    transitions: Vec<usize>,
+    accepting: Vec<bool>,
+}
+
+impl Dfa {
+    /// Explore the derivatives of `regex`. Returns `None` if it has more
+    /// than [`MAX_DFA_STATES`] distinct states or is larger than
+    /// [`MAX_DFA_PATTERN_SIZE`].
+    pub fn build(regex: &Arc<Regex>) -> Option<Self> {
+        if regex.size() > MAX_DFA_PATTERN_SIZE {
+            return None;
+        }
+        let mut named: Vec<char> = {
+            let mut chars = HashSet::new();
+            named_chars(regex, &mut chars);
+            chars.into_iter().collect()
+        };
+        named.sort_unstable();
+
+        // Stands in for every character the regex doesn't name
+        let catch_all = ('\u{E000}'..='\u{F8FF}').find(|c| !named.contains(c))?;
+        let classes = named.len() + 1;
+        let mut representatives = named.clone();
+        representatives.push(catch_all);
+
+        let mut ids: HashMap<Arc<Regex>, usize> = HashMap::from([(regex.clone(), 0)]);
+        let mut states = vec![regex.clone()];
+        let mut queue = VecDeque::from([0]);
+        let mut transitions = Vec::new();
+
+        while let Some(state) = queue.pop_front() {
+            transitions.resize((state + 1) * classes, 0);
+            for (class, &c) in representatives.iter().enumerate() {
+                let next = derivative(&states[state], c);
+                let id = match ids.get(&next) {
+                    Some(&id) => id,
+                    None => {
+                        if states.len() == MAX_DFA_STATES {
+                            return None;
+                        }
+                        let id = states.len();
+                        ids.insert(next.clone(), id);
+                        states.push(next);
+                        queue.push_back(id);
+                        id
+                    }
+                };
+                transitions[state * classes + class] = id;
+            }
+        }
+
+        let mut ascii = [named.len(); 128];
+        let mut other_chars = HashMap::new();
+        for (class, &c) in named.iter().enumerate() {
+            if c.is_ascii() {
+                ascii[c as usize] = class;
+            } else {
+                other_chars.insert(c, class);
+            }
+        }
+
+        Some(Self {
+            ascii,
+            other_chars,
+            classes,
+            transitions,
+            accepting: states.iter().map(|state| nullable(state)).collect(),
+        })
+    }
+
+    /// Number of states.
+    pub fn len(&self) -> usize {
+        self.accepting.len()
+    }
+
+    /// A DFA always has its start state.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Check if the whole string matches.
+    pub fn is_match(&self, s: &str) -> bool {
+        let state = s.chars().fold(0, |state, c| self.step(state, c));
+        self.accepting[state]
+    }
+
+    /// Check if string starts with a match (prefix match).
+    pub fn is_prefix_match(&self, s: &str) -> bool {
+        let mut state = 0;
+        if self.accepting[state] {
+            return true;
+        }
+        for c in s.chars() {
+            state = self.step(state, c);
+            if self.accepting[state] {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn step(&self, state: usize, c: char) -> usize {
+        let class = if c.is_ascii() {
+            self.ascii[c as usize]
+        } else {
+            self.other_chars.get(&c).copied().unwrap_or(self.classes - 1)
+        };
+        self.transitions[state * self.classes + class]
+    }
+}
+
+/// Characters the regex names in literals and classes.
+fn named_chars(regex: &Regex, chars: &mut HashSet<char>) {
+    match regex {
+        Regex::Literal(c) => {
+            chars.insert(*c);
+        }
+        Regex::Class(set) | Regex::NotClass(set) => chars.extend(set),
+        Regex::Concat(r1, r2) | Regex::Union(r1, r2) | Regex::Intersect(r1, r2) => {
+            named_chars(r1, chars);
+            named_chars(r2, chars);
+        }
+        Regex::Negate(r) | Regex::Star(r) => named_chars(r, chars),
+        Regex::Empty | Regex::Epsilon | Regex::Any => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regex::{matches, parse};
+
+    #[test]
+    fn test_dfa_agrees_with_derivatives() {
+        let patterns = [
+            "Config.*Manager",
+            ".*Service",
+            "get.*&.*User",
+            "~(test.*)",
+            "[A-Z][a-z]+",
+            "(ab|a)*b",
+            ".*a.*b.*c.*",
+            "é+x?",
+            "[^a-z].*",
+        ];
+        let inputs = [
+            "",
+            "ConfigManager",
+            "ConfigFileManager",
+            "Config",
+            "UserService",
+            "Service",
+            "ServiceX",
+            "getUser",
+            "getActiveUsers",
+            "test_login",
+            "login",
+            "Manager",
+            "camelCase",
+            "aabab",
+            "ababb",
+            "xaybzc",
+            "cba",
+            "ééx",
+            "éy",
+            "日本Service",
+        ];
+
+        for pattern in patterns {
+            let regex = parse(pattern).unwrap();
+            let dfa = Dfa::build(&regex).unwrap_or_else(|| panic!("{} has too many states", pattern));
+            for input in inputs {
+                assert_eq!(
+                    dfa.is_match(input),
+                    matches(&regex, input),
+                    "{} on {:?}",
+                    pattern,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dfa_prefix_match() {
+        let dfa = Dfa::build(&parse("get[A-Z]").unwrap()).unwrap();
+        assert!(dfa.is_prefix_match("getUser"));
+        assert!(!dfa.is_prefix_match("get_user"));
+    }
+
+    #[test]
+    fn test_large_patterns_stay_lazy() {
+        let regex = parse("(a|b){1000}").unwrap();
+        assert!(regex.size() > MAX_DFA_PATTERN_SIZE);
+        assert!(Dfa::build(&regex).is_none());
+
+        // Still answered, one derivative per character
+        let mut matcher = crate::regex::matcher_cached("(a|b){200}", false).unwrap();
+        assert!(matcher.is_match(&"ab".repeat(100)));
+        assert!(!matcher.is_match(&"ab".repeat(99)));
+        assert!(!matcher.is_match("UserService"));
+    }
+}
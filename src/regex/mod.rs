@@ -29,12 +29,16 @@
 //! ```
 
 mod ast;
+mod cache;
 mod derivative;
+mod dfa;
 mod parser;
 
 pub use ast::Regex;
+pub use cache::{matcher_cached, parse_cached, PATTERN_CACHE_CAPACITY};
 pub use derivative::{derivative, matches, nullable, Matcher};
-pub use parser::{parse, ParseError};
+pub use dfa::{Dfa, MAX_DFA_PATTERN_SIZE, MAX_DFA_STATES};
+pub use parser::{parse, parse_ci, ParseError};
 
 #[cfg(test)]
 mod tests {
@@ -124,6 +128,16 @@ mod tests {
         assert!(!matches(&r, "CONFIG"));
     }
 
+    #[test]
+    fn test_compiled_matcher() {
+        let mut matcher = Matcher::compiled(parse("get.*&.*User").unwrap());
+
+        assert!(matcher.is_match("getUser"));
+        assert!(matcher.is_match("getActiveUser"));
+        assert!(!matcher.is_match("setUser"));
+        assert!(matcher.is_prefix_match("getUserById"));
+    }
+
     #[test]
     fn test_matcher_with_cache() {
         let pattern = parse("test.*").unwrap();
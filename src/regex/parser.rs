@@ -1,15 +1,40 @@
 //! Simple regex parser.
 //!
-//! Supports: literals, ., *, +, ?, |, &, ~, ^, $, (), []
+//! Supports: literals, ., *, +, ?, {n,m}, |, &, ~, ^, $, (), [], \d \w \s
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::ast::Regex;
 
+/// Placeholders for `^` and `$` while parsing. They never reach the
+/// matcher: [`resolve_anchors`] replaces them.
+const START_ANCHOR: char = '\u{E000}';
+const END_ANCHOR: char = '\u{E001}';
+
+/// Most copies counted repetitions may make of any part of a pattern.
+/// Each repetition copies its atom, so nested counts multiply:
+/// `(a{10}){100}` makes 1000 copies of `a`.
+const MAX_REPETITION: usize = 1000;
+
 /// Parse a regex pattern string into AST.
+///
+/// Patterns match whole strings. `^` and `$` assert the start and end of
+/// the input: `^Config$` matches only `Config`, and an anchor with
+/// something matched before `^` (or after `$`) never matches, as in `a^b`.
 pub fn parse(pattern: &str) -> Result<Arc<Regex>, ParseError> {
-    let mut parser = Parser::new(pattern);
-    parser.parse_expr()
+    let mut parser = Parser::new(pattern, false);
+    parser.parse_pattern()
+}
+
+/// Parse a regex pattern that matches regardless of case.
+///
+/// Each literal is folded into a class of its case variants while parsing
+/// (`c` becomes `[cC]`), and classes gain the variants of their members, so
+/// matching itself is unchanged.
+pub fn parse_ci(pattern: &str) -> Result<Arc<Regex>, ParseError> {
+    let mut parser = Parser::new(pattern, true);
+    parser.parse_pattern()
 }
 
 #[derive(Debug, Clone)]
@@ -31,14 +56,20 @@ struct Parser<'a> {
     #[allow(dead_code)]
     input: &'a str,
     pos: usize,
+    ignore_case: bool,
+    /// Copies made by the repetitions in the atom just parsed: the largest
+    /// product of nested counts.
+    copies: usize,
 }
 
 impl<'a> Parser<'a> {
-    fn new(input: &'a str) -> Self {
+    fn new(input: &'a str, ignore_case: bool) -> Self {
         Self {
             chars: input.char_indices().peekable(),
             input,
             pos: 0,
+            ignore_case,
+            copies: 1,
         }
     }
 
@@ -53,6 +84,20 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// A literal character, or the class of its case variants when
+    /// ignoring case.
+    fn literal(&self, c: char) -> Arc<Regex> {
+        if !self.ignore_case {
+            return Regex::lit(c);
+        }
+        let variants = case_variants(c);
+        if variants.len() == 1 {
+            Regex::lit(c)
+        } else {
+            Arc::new(Regex::Class(variants))
+        }
+    }
+
     fn error(&self, msg: &str) -> ParseError {
         ParseError {
             message: msg.to_string(),
@@ -60,6 +105,12 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parse a whole pattern, then resolve its anchors.
+    fn parse_pattern(&mut self) -> Result<Arc<Regex>, ParseError> {
+        let regex = self.parse_expr()?;
+        Ok(resolve_anchors(&regex, true, true))
+    }
+
     /// Parse full expression (handles |)
     fn parse_expr(&mut self) -> Result<Arc<Regex>, ParseError> {
         let mut left = self.parse_intersect()?;
@@ -111,7 +162,10 @@ impl<'a> Parser<'a> {
 
     /// Parse quantified atom (*, +, ?)
     fn parse_quantified(&mut self) -> Result<Arc<Regex>, ParseError> {
+        let outer = std::mem::replace(&mut self.copies, 1);
         let atom = self.parse_atom()?;
+        let inner = self.copies;
+        self.copies = outer.max(inner);
 
         match self.peek() {
             Some('*') => {
@@ -126,10 +180,61 @@ impl<'a> Parser<'a> {
                 self.next();
                 Ok(Regex::optional(atom))
             }
+            Some('{') => {
+                let (min, max) = self.parse_repetition()?;
+                let copies = inner.saturating_mul(max.unwrap_or(min).max(1));
+                if copies > MAX_REPETITION {
+                    return Err(self.error("Repetition count too large"));
+                }
+                self.copies = self.copies.max(copies);
+                Ok(repeat(atom, min, max))
+            }
             _ => Ok(atom),
         }
     }
 
+    /// Parse a counted repetition `{n}`, `{n,}` or `{n,m}`. `None` as the
+    /// maximum means unbounded.
+    fn parse_repetition(&mut self) -> Result<(usize, Option<usize>), ParseError> {
+        self.next(); // consume '{'
+
+        let min = self
+            .parse_count()?
+            .ok_or_else(|| self.error("Expected repetition count"))?;
+        let max = match self.next() {
+            Some('}') => return Ok((min, Some(min))),
+            Some(',') => {
+                let max = self.parse_count()?;
+                if self.next() != Some('}') {
+                    return Err(self.error("Expected '}'"));
+                }
+                max
+            }
+            _ => return Err(self.error("Expected ',' or '}'")),
+        };
+
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("Repetition maximum is less than minimum"));
+        }
+        Ok((min, max))
+    }
+
+    /// Parse a decimal repetition count, if there is one.
+    fn parse_count(&mut self) -> Result<Option<usize>, ParseError> {
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            self.next();
+            digits.push(c);
+        }
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        match digits.parse::<usize>() {
+            Ok(count) if count <= MAX_REPETITION => Ok(Some(count)),
+            _ => Err(self.error("Repetition count too large")),
+        }
+    }
+
     /// Parse atomic expression
     fn parse_atom(&mut self) -> Result<Arc<Regex>, ParseError> {
         match self.peek() {
@@ -153,20 +258,20 @@ impl<'a> Parser<'a> {
                 Ok(Arc::new(Regex::Any))
             }
             Some('^') => {
-                // Start anchor - for simplicity, treat as epsilon at start
+                // Start anchor, resolved once the whole pattern is parsed
                 self.next();
-                Ok(Arc::new(Regex::Epsilon))
+                Ok(Regex::lit(START_ANCHOR))
             }
             Some('$') => {
-                // End anchor - for simplicity, treat as epsilon at end
+                // End anchor, resolved once the whole pattern is parsed
                 self.next();
-                Ok(Arc::new(Regex::Epsilon))
+                Ok(Regex::lit(END_ANCHOR))
             }
             Some('[') => self.parse_class(),
             Some('\\') => {
                 self.next(); // consume '\'
                 match self.next() {
-                    Some(c) => Ok(Regex::lit(c)),
+                    Some(c) => Ok(escape_class(c).unwrap_or_else(|| self.literal(c))),
                     None => Err(self.error("Expected character after \\")),
                 }
             }
@@ -175,7 +280,7 @@ impl<'a> Parser<'a> {
             }
             Some(c) => {
                 self.next();
-                Ok(Regex::lit(c))
+                Ok(self.literal(c))
             }
         }
     }
@@ -191,7 +296,7 @@ impl<'a> Parser<'a> {
             false
         };
 
-        let mut chars = std::collections::HashSet::new();
+        let mut chars = HashSet::new();
 
         while let Some(c) = self.peek() {
             if c == ']' {
@@ -220,15 +325,104 @@ impl<'a> Parser<'a> {
         }
         self.next(); // consume ']'
 
-        let class = Arc::new(Regex::Class(chars));
+        if self.ignore_case {
+            chars = chars.into_iter().flat_map(case_variants).collect();
+        }
         if negated {
-            Ok(Regex::negate(class))
+            Ok(Arc::new(Regex::NotClass(chars)))
         } else {
-            Ok(class)
+            Ok(Arc::new(Regex::Class(chars)))
         }
     }
 }
 
+/// Replace anchor placeholders: an anchor holds (becomes epsilon) only
+/// where nothing can have been matched before a `^` or after a `$`, and
+/// can never match (becomes empty) anywhere else.
+fn resolve_anchors(regex: &Arc<Regex>, at_start: bool, at_end: bool) -> Arc<Regex> {
+    match regex.as_ref() {
+        Regex::Literal(START_ANCHOR) => anchor(at_start),
+        Regex::Literal(END_ANCHOR) => anchor(at_end),
+        Regex::Concat(first, second) => Regex::concat(
+            resolve_anchors(first, at_start, at_end && matches_only_empty(second)),
+            resolve_anchors(second, at_start && matches_only_empty(first), at_end),
+        ),
+        Regex::Union(a, b) => Regex::union(
+            resolve_anchors(a, at_start, at_end),
+            resolve_anchors(b, at_start, at_end),
+        ),
+        Regex::Intersect(a, b) => Regex::intersect(
+            resolve_anchors(a, at_start, at_end),
+            resolve_anchors(b, at_start, at_end),
+        ),
+        Regex::Negate(inner) => Regex::negate(resolve_anchors(inner, at_start, at_end)),
+        // A repeated anchor is only at the edge on one repetition
+        Regex::Star(inner) => Regex::star(resolve_anchors(inner, false, false)),
+        _ => regex.clone(),
+    }
+}
+
+fn anchor(holds: bool) -> Arc<Regex> {
+    Arc::new(if holds { Regex::Epsilon } else { Regex::Empty })
+}
+
+/// Can this (unresolved) regex only ever match the empty string?
+fn matches_only_empty(regex: &Regex) -> bool {
+    match regex {
+        Regex::Epsilon | Regex::Literal(START_ANCHOR) | Regex::Literal(END_ANCHOR) => true,
+        Regex::Concat(a, b) | Regex::Union(a, b) => matches_only_empty(a) && matches_only_empty(b),
+        Regex::Star(inner) => matches_only_empty(inner),
+        _ => false,
+    }
+}
+
+/// `atom` repeated `min` to `max` times, as plain concatenation so the
+/// derivative engine needs nothing new: `a{2,4}` is `aa(a(a)?)?` and
+/// `a{2,}` is `aaa*`.
+fn repeat(atom: Arc<Regex>, min: usize, max: Option<usize>) -> Arc<Regex> {
+    let required = (0..min).fold(Arc::new(Regex::Epsilon), |acc, _| {
+        Regex::concat(acc, atom.clone())
+    });
+    let rest = match max {
+        None => Regex::star(atom),
+        Some(max) => (min..max).fold(Arc::new(Regex::Epsilon), |acc, _| {
+            Regex::optional(Regex::concat(atom.clone(), acc))
+        }),
+    };
+    Regex::concat(required, rest)
+}
+
+/// The class a `\d`, `\w` or `\s` escape stands for (ASCII digits, word
+/// characters, whitespace). The uppercase forms match one character
+/// outside the class. Other escapes are literals.
+fn escape_class(c: char) -> Option<Arc<Regex>> {
+    let chars: HashSet<char> = match c.to_ascii_lowercase() {
+        'd' => ('0'..='9').collect(),
+        'w' => ('a'..='z').chain('A'..='Z').chain('0'..='9').chain(['_']).collect(),
+        's' => [' ', '\t', '\n', '\r', '\x0b', '\x0c'].into(),
+        _ => return None,
+    };
+    if c.is_ascii_uppercase() {
+        Some(Arc::new(Regex::NotClass(chars)))
+    } else {
+        Some(Arc::new(Regex::Class(chars)))
+    }
+}
+
+/// `c` with its single-character upper- and lowercase forms.
+fn case_variants(c: char) -> HashSet<char> {
+    let mut variants = HashSet::from([c]);
+    let mut lower = c.to_lowercase();
+    if let (Some(l), None) = (lower.next(), lower.next()) {
+        variants.insert(l);
+    }
+    let mut upper = c.to_uppercase();
+    if let (Some(u), None) = (upper.next(), upper.next()) {
+        variants.insert(u);
+    }
+    variants
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +443,119 @@ mod tests {
         assert!(matches(&r, "aaa"));
     }
 
+    #[test]
+    fn test_parse_repeat_exact() {
+        let r = parse("a{3}").unwrap();
+        assert!(matches(&r, "aaa"));
+        assert!(!matches(&r, "aa"));
+        assert!(!matches(&r, "aaaa"));
+        assert!(matches(&parse("a{0}").unwrap(), ""));
+    }
+
+    #[test]
+    fn test_parse_repeat_at_least() {
+        let r = parse("a{2,}").unwrap();
+        assert!(!matches(&r, "a"));
+        assert!(matches(&r, "aa"));
+        assert!(matches(&r, "aaaaa"));
+    }
+
+    #[test]
+    fn test_parse_repeat_range() {
+        let r = parse("(ab){2,4}").unwrap();
+        assert!(!matches(&r, "ab"));
+        assert!(matches(&r, "abab"));
+        assert!(matches(&r, "ababab"));
+        assert!(matches(&r, "abababab"));
+        assert!(!matches(&r, "ababababab"));
+
+        let r = parse("[0-9]{3}-x").unwrap();
+        assert!(matches(&r, "123-x"));
+        assert!(!matches(&r, "12-x"));
+    }
+
+    #[test]
+    fn test_parse_repeat_malformed() {
+        assert!(parse("a{2,1}").is_err());
+        assert!(parse("a{}").is_err());
+        assert!(parse("a{,3}").is_err());
+        assert!(parse("a{2").is_err());
+        assert!(parse("a{2,3").is_err());
+        assert!(parse("a{99999}").is_err());
+    }
+
+    #[test]
+    fn test_parse_nested_repeat_limit() {
+        // Nested counts multiply; side by side they don't
+        assert!(parse("(a{10}){100}").is_ok());
+        assert!(parse("a{1000}b{1000}").is_ok());
+        assert!(parse("(a{100}){100}").is_err());
+        assert!(parse("(a{1000}){1000}").is_err());
+        assert!(parse("((ab{10}|c){10}){11}").is_err());
+        assert!(parse("(x(a{10}){10}y){2,}").is_ok());
+        assert!(parse("~(a{500}){3}").is_err());
+    }
+
+    #[test]
+    fn test_parse_negated_class() {
+        let r = parse("[^a]").unwrap();
+        assert!(matches(&r, "b"));
+        assert!(!matches(&r, "a"));
+        assert!(!matches(&r, ""));
+        assert!(!matches(&r, "bc"));
+
+        let r = parse("get[^_]+").unwrap();
+        assert!(matches(&r, "getUser"));
+        assert!(!matches(&r, "get_user"));
+        assert!(!matches(&r, "get"));
+    }
+
+    #[test]
+    fn test_parse_escape_classes() {
+        let r = parse("handler_\\d+").unwrap();
+        assert!(matches(&r, "handler_1"));
+        assert!(matches(&r, "handler_42"));
+        assert!(!matches(&r, "handler_"));
+        assert!(!matches(&r, "handler_x"));
+
+        let r = parse("\\w+\\s\\w+").unwrap();
+        assert!(matches(&r, "get_user by_id"));
+        assert!(!matches(&r, "get-user by_id"));
+
+        let r = parse("a\\Db").unwrap();
+        assert!(matches(&r, "axb"));
+        assert!(!matches(&r, "a1b"));
+        assert!(!matches(&r, "ab"));
+        assert!(!matches(&r, "axxb"));
+
+        // Other escapes stay literal
+        let r = parse("a\\.b").unwrap();
+        assert!(matches(&r, "a.b"));
+        assert!(!matches(&r, "axb"));
+    }
+
+    #[test]
+    fn test_parse_anchors() {
+        let r = parse("^Config$").unwrap();
+        assert!(matches(&r, "Config"));
+        assert!(!matches(&r, "ConfigManager"));
+        assert!(!matches(&r, "MyConfig"));
+
+        let r = parse("^Config.*").unwrap();
+        assert!(matches(&r, "ConfigManager"));
+        assert!(!matches(&r, "MyConfig"));
+
+        // Anchors inside groups and alternatives
+        let r = parse("(^get)User|^set.*").unwrap();
+        assert!(matches(&r, "getUser"));
+        assert!(matches(&r, "setName"));
+
+        // Anchors away from the edges never match
+        assert!(!matches(&parse("a^b").unwrap(), "ab"));
+        assert!(!matches(&parse("a$b").unwrap(), "ab"));
+        assert!(!matches(&parse(".*$x").unwrap(), "x"));
+    }
+
     #[test]
     fn test_parse_union() {
         let r = parse("a|b").unwrap();
@@ -300,4 +607,17 @@ mod tests {
         assert!(matches(&r, "cba"));
         assert!(!matches(&r, "d"));
     }
+
+    #[test]
+    fn test_parse_ci() {
+        let r = parse_ci("config.*manager").unwrap();
+        assert!(matches(&r, "ConfigManager"));
+        assert!(matches(&r, "CONFIG_FILE_MANAGER"));
+        assert!(!matches(&r, "ConfigManagers"));
+        assert!(!matches(&parse("config.*manager").unwrap(), "ConfigManager"));
+
+        let r = parse_ci("[a-c]_[^x]").unwrap();
+        assert!(matches(&r, "B_y"));
+        assert!(!matches(&r, "b_X"));
+    }
 }
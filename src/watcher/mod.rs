@@ -3,9 +3,11 @@
 //! Watches the project directory for file changes and incrementally
 //! updates the code graph without requiring a full rebuild.
 
+use chrono::{DateTime, Utc};
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
@@ -48,13 +50,15 @@ pub fn start_watching(
     };
 
     let root_owned = root.to_path_buf();
+    let stats = Arc::new(WatcherStats::default());
+    let handler_stats = Arc::clone(&stats);
 
     let mut debouncer = new_debouncer(
         debounce,
         move |result: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
             match result {
                 Ok(events) => {
-                    handle_events(&events, &graph, &root_owned);
+                    handle_events(&events, &graph, &root_owned, &handler_stats);
                 }
                 Err(e) => {
                     warn!(error = %e, "file watcher error");
@@ -71,6 +75,7 @@ pub fn start_watching(
 
     Ok(WatcherHandle {
         _debouncer: debouncer,
+        stats,
     })
 }
 
@@ -79,6 +84,7 @@ fn handle_events(
     events: &[notify_debouncer_mini::DebouncedEvent],
     graph: &Arc<RwLock<CodeGraph>>,
     _root: &Path,
+    stats: &WatcherStats,
 ) {
     // Deduplicate: collect unique paths and their last event kind
     let mut paths: std::collections::HashMap<PathBuf, DebouncedEventKind> =
@@ -105,11 +111,13 @@ fn handle_events(
     }
 
     debug!(count = paths.len(), "processing file events");
+    stats.backlog.fetch_add(paths.len(), Ordering::Relaxed);
 
     let mut graph = match graph.write() {
         Ok(g) => g,
         Err(e) => {
             warn!(error = %e, "failed to acquire graph write lock");
+            stats.backlog.fetch_sub(paths.len(), Ordering::Relaxed);
             return;
         }
     };
@@ -128,6 +136,7 @@ fn handle_events(
                     debug!(file = %path.display(), "removing deleted file");
                     graph.remove_file(path);
                 }
+                stats.files_updated.fetch_add(1, Ordering::Relaxed);
             }
             DebouncedEventKind::AnyContinuous => {
                 // Ongoing writes — skip until settled
@@ -137,6 +146,12 @@ fn handle_events(
                 debug!(file = %path.display(), "unhandled event kind");
             }
         }
+        stats.backlog.fetch_sub(1, Ordering::Relaxed);
+    }
+    // Once per debounced batch rather than per file
+    graph.refresh_centrality();
+    if let Ok(mut last) = stats.last_update.lock() {
+        *last = Some(Utc::now());
     }
 }
 
@@ -157,4 +172,64 @@ fn should_ignore(path: &Path) -> bool {
 /// Drop this to stop watching.
 pub struct WatcherHandle {
     _debouncer: notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>,
+    stats: Arc<WatcherStats>,
+}
+
+impl WatcherHandle {
+    /// Counters for how far incremental updates have got.
+    pub fn stats(&self) -> Arc<WatcherStats> {
+        Arc::clone(&self.stats)
+    }
+}
+
+/// How the watcher is keeping up with file changes.
+#[derive(Debug, Default)]
+pub struct WatcherStats {
+    backlog: AtomicUsize,
+    files_updated: AtomicU64,
+    last_update: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl WatcherStats {
+    /// Changed files received but not yet applied to the graph, e.g.
+    /// while a long query holds the graph lock.
+    pub fn backlog(&self) -> usize {
+        self.backlog.load(Ordering::Relaxed)
+    }
+
+    /// Files re-parsed or removed since the watcher started.
+    pub fn files_updated(&self) -> u64 {
+        self.files_updated.load(Ordering::Relaxed)
+    }
+
+    /// When the last batch of changes was applied.
+    pub fn last_update(&self) -> Option<DateTime<Utc>> {
+        self.last_update.lock().ok().and_then(|last| *last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify_debouncer_mini::DebouncedEvent;
+
+    #[test]
+    fn test_handle_events_updates_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn hello() {}\n").unwrap();
+        let graph = Arc::new(RwLock::new(CodeGraph::new()));
+        let stats = WatcherStats::default();
+
+        let events = vec![
+            DebouncedEvent::new(file.clone(), DebouncedEventKind::Any),
+            DebouncedEvent::new(dir.path().join("notes.txt"), DebouncedEventKind::Any),
+        ];
+        handle_events(&events, &graph, dir.path(), &stats);
+
+        assert_eq!(stats.backlog(), 0);
+        assert_eq!(stats.files_updated(), 1);
+        assert!(stats.last_update().is_some());
+        assert_eq!(graph.read().unwrap().search("hello", 1).len(), 1);
+    }
 }
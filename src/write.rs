@@ -168,6 +168,107 @@ pub fn replace_first(
     })
 }
 
+/// Where [`replace_word`] rewrites a word.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub struct WordSite {
+    /// 1-based inclusive line span.
+    pub lines: (usize, usize),
+    /// Only the word after this receiver and a `.`, `::` or `->`
+    /// (`order.save`, `Order::save`). `None` for the word where it isn't
+    /// reached through anything: `save()`, `fn save`.
+    pub receiver: Option<String>,
+}
+
+/// Replace `word` with `replacement` where it stands as a whole
+/// identifier at one of `sites`: renaming `validate` leaves `validate2`
+/// and `prevalidate` alone.
+///
+/// `lines_written` counts the lines that changed.
+pub fn replace_word(
+    path: &Path,
+    word: &str,
+    replacement: &str,
+    sites: &[WordSite],
+) -> Result<WriteResult, WriteError> {
+    let start = std::time::Instant::now();
+
+    if word.is_empty() {
+        return Err(WriteError::InvalidInput("empty word".to_string()));
+    }
+    let original =
+        fs::read_to_string(path).map_err(|_| WriteError::FileNotFound(path.to_path_buf()))?;
+
+    let mut new_content = String::with_capacity(original.len());
+    let mut count = 0;
+    let mut lines_changed = 0;
+    for (i, line) in original.split_inclusive('\n').enumerate() {
+        let mut at: Vec<usize> = sites
+            .iter()
+            .filter(|site| (site.lines.0..=site.lines.1).contains(&(i + 1)))
+            .flat_map(|site| word_occurrences(line, word, site.receiver.as_deref()))
+            .collect();
+        at.sort_unstable();
+        at.dedup();
+
+        let mut last = 0;
+        for &pos in &at {
+            new_content.push_str(&line[last..pos]);
+            new_content.push_str(replacement);
+            last = pos + word.len();
+        }
+        new_content.push_str(&line[last..]);
+        if !at.is_empty() {
+            count += at.len();
+            lines_changed += 1;
+        }
+    }
+
+    if count == 0 {
+        return Err(WriteError::PatternNotFound(word.to_string()));
+    }
+    fs::write(path, &new_content)?;
+
+    let elapsed = start.elapsed();
+
+    Ok(WriteResult {
+        operation: "replace_word".to_string(),
+        path: path.display().to_string(),
+        success: true,
+        time_ms: elapsed.as_millis() as u64,
+        lines_written: lines_changed,
+        bytes_written: new_content.len(),
+        replacements: Some(count),
+    })
+}
+
+/// Byte offsets of `word` in `line` as a whole identifier, placed as
+/// [`WordSite::receiver`] describes.
+pub(crate) fn word_occurrences(line: &str, word: &str, receiver: Option<&str>) -> Vec<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word)
+        .map(|(at, _)| at)
+        .filter(|&at| {
+            let end = at + word.len();
+            let before = &line[..at];
+            if before.chars().next_back().is_some_and(is_ident)
+                || line[end..].chars().next().is_some_and(is_ident)
+            {
+                return false;
+            }
+            let member_of = ["?.", ".", "::", "->"]
+                .iter()
+                .find_map(|separator| before.strip_suffix(separator));
+            match (receiver, member_of) {
+                (None, member_of) => member_of.is_none(),
+                (Some(receiver), Some(rest)) => rest
+                    .strip_suffix(receiver)
+                    .is_some_and(|prefix| !prefix.chars().next_back().is_some_and(is_ident)),
+                (Some(_), None) => false,
+            }
+        })
+        .collect()
+}
+
 /// Result of a write operation.
 #[derive(Debug, serde::Serialize)]
 pub struct WriteResult {
@@ -307,4 +408,36 @@ mod tests {
         assert!(content.contains("qux"));
         assert_eq!(result.replacements, Some(3));
     }
+
+    #[test]
+    fn test_replace_word() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.rs");
+
+        fs::write(&path, "fn validate() {}\nfn validate2() { validate(); prevalidate(); }\nvalidate();\n").unwrap();
+
+        let bare = |lines| WordSite { lines, receiver: None };
+        let result = replace_word(&path, "validate", "check", &[bare((1, 2))]).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "fn check() {}\nfn validate2() { check(); prevalidate(); }\nvalidate();\n");
+        assert_eq!(result.replacements, Some(2));
+        assert_eq!(result.lines_written, 2);
+        assert!(replace_word(&path, "validate", "check", &[bare((1, 1))]).is_err());
+    }
+
+    #[test]
+    fn test_replace_word_after_receiver() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.rs");
+
+        fs::write(&path, "Order::save(o); User::save(u); self.save(); user.save(); save();\n").unwrap();
+
+        let member = |receiver: &str| WordSite { lines: (1, 1), receiver: Some(receiver.to_string()) };
+        replace_word(&path, "save", "persist", &[member("Order"), member("self")]).unwrap();
+        replace_word(&path, "save", "store", &[WordSite { lines: (1, 1), receiver: None }]).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "Order::persist(o); User::save(u); self.persist(); user.save(); store();\n");
+    }
 }